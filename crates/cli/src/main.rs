@@ -1,14 +1,14 @@
 //! RepoSync command-line management tool.
 //!
 //! Provides subcommands for inspecting sync status, managing conflicts,
-//! editing identity mappings, viewing the audit log, and generating /
-//! validating configuration files.
+//! editing identity mappings, viewing the audit log, importing SVN dump
+//! files, and generating / validating configuration files.
 //!
 //! Also provides the `personal` subcommand group for Personal Branch Mode.
 
 mod personal;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 use std::sync::Arc;
 
@@ -17,8 +17,14 @@ use clap::{Parser, Subcommand};
 use tracing_subscriber::EnvFilter;
 
 use reposync_core::config::AppConfig;
+use reposync_core::conflict::resolver::Resolution;
 use reposync_core::db::Database;
+use reposync_core::dump_import::{open_dump, run_dump_import};
+use reposync_core::file_policy::FilePolicy;
+use reposync_core::git::GitClient;
 use reposync_core::identity::IdentityMapper;
+use reposync_core::import::{ImportConfig, ImportProgress};
+use reposync_core::sync_engine::{repo_secret, svn_revprop_error_key_for, SyncEngine};
 
 // ---------------------------------------------------------------------------
 // CLI argument definitions
@@ -62,6 +68,23 @@ enum Commands {
         action: SyncAction,
     },
 
+    /// Import SVN history from a dump file instead of the live server.
+    Import {
+        /// `svnadmin dump` / `svnrdump dump` file (`-` for stdin).
+        #[arg(long, value_name = "FILE")]
+        dump: PathBuf,
+
+        /// Repository path in the dump to import (default: `svn.trunk_path`,
+        /// or the SVN branch of `--repo`).
+        #[arg(long, value_name = "PATH")]
+        root: Option<String>,
+
+        /// ID of the repository (from the repositories table) to import for;
+        /// its sync engine continues after the import.
+        #[arg(long, value_name = "ID")]
+        repo: Option<String>,
+    },
+
     /// Manage SVN-to-Git identity mappings.
     Identity {
         #[command(subcommand)]
@@ -78,6 +101,9 @@ enum Commands {
     /// Validate a configuration file.
     Validate,
 
+    /// Check the bridge for problems that do not stop syncing.
+    Doctor,
+
     /// Show recent audit log entries.
     Audit {
         /// Maximum number of entries to show.
@@ -113,14 +139,18 @@ enum ConflictsAction {
         /// Conflict ID.
         id: String,
     },
-    /// Resolve a conflict.
+    /// Resolve a conflict and commit the chosen content to the losing side.
     Resolve {
         /// Conflict ID.
         id: String,
 
-        /// Resolution: svn or git.
+        /// Resolution: svn, git, or merged.
         #[arg(long)]
         accept: String,
+
+        /// File with the merged content (required for `--accept merged`).
+        #[arg(long)]
+        file: Option<PathBuf>,
     },
 }
 
@@ -178,10 +208,14 @@ async fn run(cli: Cli) -> Result<()> {
 
             match cli.command {
                 Commands::Status => cmd_status(&db),
-                Commands::Conflicts { action } => cmd_conflicts(&db, action),
+                Commands::Conflicts { action } => cmd_conflicts(&db, &config, action).await,
                 Commands::Sync { action } => cmd_sync(&db, &config, action).await,
+                Commands::Import { dump, root, repo } => {
+                    cmd_import(&db, &config, &dump, root, repo).await
+                }
                 Commands::Identity { action } => cmd_identity(&config, action),
                 Commands::Audit { limit } => cmd_audit(&db, limit),
+                Commands::Doctor => cmd_doctor(&db, &config),
                 _ => unreachable!(),
             }
         }
@@ -251,11 +285,53 @@ admin_password_env = "ADMIN_PASSWORD"
 # email_smtp = "smtp.example.com:587"
 # email_from = "reposync@example.com"
 # email_recipients = ["admin@example.com"]
+# Consecutive failed cycles of a repository before an alert is sent; it is
+# repeated every as many further failures.
+# error_threshold = 3
+# Without routes, conflicts and error-threshold alerts go to every channel.
+# [[notifications.routes]]
+# events = ["conflict", "sync_failed", "error_threshold", "sync_complete"]
+# channels = ["slack", "email"]
+# repos = ["my-repo"]
 
 [sync]
 mode = "direct"
 auto_merge = true
+# sync_branches = ["develop", "release-*:release/*"]
 sync_tags = true
+# tag_mappings = ["*:v*"]
+# Record the Git author as svn:author (needs a pre-revprop-change hook).
+preserve_svn_author = true
+# preserve_svn_date = true
+# Commit signed commits with the committer's own SVN credential from their
+# RepoSync profile. Only commits with a signature verified by GitHub/Gitea
+# qualify; leave off unless signed commits are enforced.
+# commit_as_author = true
+# Mirror svn:ignore / svn:global-ignores as a block in .gitignore (both ways).
+sync_ignores = false
+# Mirror svn:externals as submodules (other synced repos) or vendored copies.
+# sync_externals = true
+# Map svn:executable to Git's executable file mode (both ways).
+sync_executable_bit = true
+# Mirror svn:eol-style in .gitattributes and ignore CRLF/LF churn.
+normalize_line_endings = true
+# Keep SVN keywords collapsed ($Id$) in Git.
+collapse_keywords = true
+# Turn svn:mergeinfo merges into Git merge commits and back.
+sync_merges = true
+# Skip symlinks that point outside the repository.
+# reject_external_symlinks = true
+
+# Used when mode = "pr": SVN revisions are committed to `branch` and a pull
+# request into the default branch is opened for them.
+# [sync.pr]
+# branch = "reposync/svn-sync"
+# title_prefix = "[svn-sync]"
+# labels = ["svn-sync"]
+# reviewers = ["alice"]
+# auto_merge = false
+# Also merge when the head reports no CI checks at all (repositories without CI).
+# merge_without_checks = false
 "#;
 
     if output.exists() {
@@ -323,6 +399,7 @@ fn cmd_validate(config_path: &PathBuf) -> Result<()> {
         }
     );
     println!("  GitHub repo   : {}", config.github.repo);
+    println!("  Git remote    : {}", config.github.clone_url());
     println!(
         "  GitHub token  : {}",
         if config.github.token.is_some() {
@@ -331,6 +408,18 @@ fn cmd_validate(config_path: &PathBuf) -> Result<()> {
             "NOT SET"
         }
     );
+    if let Some(ref app) = config.github.app {
+        println!(
+            "  GitHub App    : app {} / installation {} ({})",
+            app.app_id,
+            app.installation_id,
+            if config.github.app_auth.is_some() {
+                "key loaded"
+            } else {
+                "KEY NOT FOUND"
+            }
+        );
+    }
     println!(
         "  Webhook secret: {}",
         if config.github.webhook_secret.is_some() {
@@ -342,6 +431,15 @@ fn cmd_validate(config_path: &PathBuf) -> Result<()> {
     println!("  Web listen    : {}", config.web.listen);
     println!("  Poll interval : {}s", config.daemon.poll_interval_secs);
     println!("  Data directory: {}", config.daemon.data_dir.display());
+
+    let unavailable = config.github.provider.unavailable_features();
+    if !unavailable.is_empty() {
+        println!();
+        println!("Plain Git remote: these features need a Git host API and are unavailable:");
+        for feature in unavailable {
+            println!("  - {}", feature);
+        }
+    }
     println!();
     println!("Configuration is valid.");
 
@@ -406,7 +504,7 @@ fn cmd_status(db: &Database) -> Result<()> {
     Ok(())
 }
 
-fn cmd_conflicts(db: &Database, action: ConflictsAction) -> Result<()> {
+async fn cmd_conflicts(db: &Database, config: &AppConfig, action: ConflictsAction) -> Result<()> {
     match action {
         ConflictsAction::List { status, limit } => {
             let conflicts = db
@@ -517,19 +615,48 @@ fn cmd_conflicts(db: &Database, action: ConflictsAction) -> Result<()> {
             Ok(())
         }
 
-        ConflictsAction::Resolve { id, accept } => {
-            let resolution = match accept.as_str() {
-                "svn" => "accept_svn",
-                "git" => "accept_git",
-                other => {
-                    anyhow::bail!("invalid resolution '{}': use 'svn' or 'git'", other);
+        ConflictsAction::Resolve { id, accept, file } => {
+            let resolution = match (accept.as_str(), file) {
+                ("svn", _) => Resolution::AcceptSvn,
+                ("git", _) => Resolution::AcceptGit,
+                ("merged", Some(path)) => Resolution::AcceptMerged(
+                    std::fs::read_to_string(&path)
+                        .with_context(|| format!("failed to read {}", path.display()))?,
+                ),
+                ("merged", None) => anyhow::bail!("--accept merged requires --file"),
+                (other, _) => {
+                    anyhow::bail!("invalid resolution '{}': use 'svn', 'git' or 'merged'", other);
                 }
             };
 
-            db.resolve_conflict(&id, "resolved", resolution, "cli")
-                .context("failed to resolve conflict")?;
+            // A conflict recorded by a per-repo engine is resolved through
+            // an engine for that repository's SVN URL and working tree.
+            let conflict = db
+                .get_conflict(&id)
+                .context("failed to look up conflict")?
+                .ok_or_else(|| anyhow::anyhow!("conflict '{}' not found", id))?;
+            let engine = match conflict.repo_id {
+                Some(ref repo_id) => {
+                    let repo = db
+                        .get_repository(repo_id)
+                        .context("failed to look up repository")?
+                        .ok_or_else(|| anyhow::anyhow!("repository '{}' not found", repo_id))?;
+                    build_repo_sync_engine(config, &repo)?
+                }
+                None => build_sync_engine(config)?,
+            };
+            let outcome = engine
+                .apply_conflict_resolution(&id, &resolution, "cli")
+                .await
+                .map_err(|e| anyhow::anyhow!("failed to resolve conflict: {}", e))?;
 
             println!("Conflict {} resolved (accepted {})", id, accept);
+            if let Some(rev) = outcome.svn_revision {
+                println!("  SVN commit : r{}", rev);
+            }
+            if let Some(ref sha) = outcome.git_sha {
+                println!("  Git commit : {}", sha);
+            }
             Ok(())
         }
     }
@@ -541,32 +668,7 @@ async fn cmd_sync(_db: &Database, config: &AppConfig, action: SyncAction) -> Res
             println!("Triggering immediate sync cycle...");
             println!();
 
-            let svn_client = reposync_core::svn::SvnClient::new(
-                &config.svn.url,
-                &config.svn.username,
-                config.svn.password.as_deref().unwrap_or(""),
-            );
-
-            let git_repo_path = config.daemon.data_dir.join("git-repo");
-            let git_client = reposync_core::git::GitClient::new(&git_repo_path)
-                .context("failed to open Git repository")?;
-
-            let identity = IdentityMapper::new(&config.identity)
-                .context("failed to initialize identity mapper")?;
-
-            // Open a dedicated database connection for the sync engine
-            let engine_db = {
-                let db_path = config.daemon.data_dir.join("reposync.db");
-                Database::new(&db_path).context("failed to open engine database")?
-            };
-
-            let engine = reposync_core::sync_engine::SyncEngine::new(
-                config.clone(),
-                engine_db,
-                svn_client,
-                git_client,
-                Arc::new(identity),
-            );
+            let engine = build_sync_engine(config)?;
 
             let spinner = indicatif::ProgressBar::new_spinner();
             spinner.set_message("Running sync cycle...");
@@ -593,6 +695,160 @@ async fn cmd_sync(_db: &Database, config: &AppConfig, action: SyncAction) -> Res
     }
 }
 
+/// Build a sync engine over the daemon's Git working copy and database.
+fn build_sync_engine(config: &AppConfig) -> Result<SyncEngine> {
+    let svn_client = reposync_core::svn::SvnClient::new(
+        &config.svn.url,
+        &config.svn.username,
+        config.svn.password.as_deref().unwrap_or(""),
+    );
+
+    let git_repo_path = config.daemon.data_dir.join("git-repo");
+    let git_client = reposync_core::git::GitClient::new(&git_repo_path)
+        .context("failed to open Git repository")?;
+
+    let identity =
+        IdentityMapper::new(&config.identity).context("failed to initialize identity mapper")?;
+
+    // Open a dedicated database connection for the sync engine
+    let engine_db = {
+        let db_path = config.daemon.data_dir.join("reposync.db");
+        Database::new(&db_path).context("failed to open engine database")?
+    };
+
+    Ok(SyncEngine::new(
+        config.clone(),
+        engine_db,
+        svn_client,
+        git_client,
+        Arc::new(identity),
+    ))
+}
+
+/// Build the sync engine the daemon uses for `repo` from the repositories
+/// table.
+fn build_repo_sync_engine(
+    config: &AppConfig,
+    repo: &reposync_core::models::Repository,
+) -> Result<SyncEngine> {
+    let identity =
+        IdentityMapper::new(&config.identity).context("failed to initialize identity mapper")?;
+    let engine_db = {
+        let db_path = config.daemon.data_dir.join("reposync.db");
+        Database::new(&db_path).context("failed to open engine database")?
+    };
+    SyncEngine::for_repository(config, repo, engine_db, Arc::new(identity))
+        .with_context(|| format!("failed to set up sync engine for {}", repo.name))
+}
+
+/// Import the history in an SVN dump into the daemon's Git repository, or
+/// the working tree of repository `repo_id`, and push it, leaving the
+/// watermarks for incremental sync.
+async fn cmd_import(
+    db: &Database,
+    config: &AppConfig,
+    dump: &Path,
+    root: Option<String>,
+    repo_id: Option<String>,
+) -> Result<()> {
+    let repo = match repo_id {
+        Some(ref id) => Some(
+            db.get_repository(id)
+                .context("failed to look up repository")?
+                .ok_or_else(|| anyhow::anyhow!("repository '{}' not found", id))?,
+        ),
+        None => None,
+    };
+    let (root_default, git_repo_path, git_token, config) = match &repo {
+        Some(repo) => (
+            repo.svn_branch.clone(),
+            config.daemon.data_dir.join("repos").join(&repo.id).join("git-repo"),
+            repo_secret(db, repo, "secret_git_token").or_else(|| config.github.token.clone()),
+            config.for_repository(repo),
+        ),
+        None => (
+            config.svn.trunk_path.clone(),
+            config.daemon.data_dir.join("git-repo"),
+            config.github.token.clone(),
+            config.clone(),
+        ),
+    };
+    let config = &config;
+    let root = root.unwrap_or_else(|| root_default.trim_matches('/').to_string());
+    let mut git_client = if git_repo_path.join(".git").exists() {
+        GitClient::new(&git_repo_path).context("failed to open Git repository")?
+    } else {
+        std::fs::create_dir_all(&git_repo_path).context("failed to create git repo directory")?;
+        let branch = &config.github.default_branch;
+        let status = std::process::Command::new("git")
+            .args(["init", "--initial-branch", branch])
+            .current_dir(&git_repo_path)
+            .status()
+            .context("failed to run git init")?;
+        if !status.success() {
+            anyhow::bail!("git init failed in {}", git_repo_path.display());
+        }
+        let git_client = GitClient::new(&git_repo_path).context("failed to open Git repository")?;
+        git_client
+            .repo()
+            .remote("origin", &config.github.clone_url())
+            .context("failed to add the origin remote")?;
+        git_client
+    };
+    git_client
+        .ensure_remote_credentials("origin", config.github.git_auth(git_token.as_deref()))
+        .context("failed to set git credentials")?;
+    let git_client = Arc::new(std::sync::Mutex::new(git_client));
+
+    let identity =
+        IdentityMapper::new(&config.identity).context("failed to initialize identity mapper")?;
+    let import_config = ImportConfig {
+        committer_name: "RepoSync".into(),
+        committer_email: "reposync@localhost".into(),
+        remote_name: "origin".into(),
+        branch: config.github.default_branch.clone(),
+        push_token: git_token,
+        message_prefix: None,
+        sync_executable_bit: config.sync.sync_executable_bit,
+        normalize_line_endings: config.sync.normalize_line_endings,
+        collapse_keywords: config.sync.collapse_keywords,
+        resume: false,
+        layout: None,
+        filter: Default::default(),
+        repo_id: repo_id.clone(),
+        per_repo_progress: false,
+    };
+    let progress = Arc::new(tokio::sync::RwLock::new(ImportProgress::default()));
+
+    let spinner = indicatif::ProgressBar::new_spinner();
+    spinner.set_message(format!("Importing /{} from {}...", root, dump.display()));
+    spinner.enable_steady_tick(std::time::Duration::from_millis(100));
+
+    let count = run_dump_import(
+        open_dump(dump)?,
+        &root,
+        &git_client,
+        &identity,
+        db,
+        &FilePolicy::from(&config.sync),
+        &import_config,
+        progress.clone(),
+        None,
+    )
+    .await?;
+
+    spinner.finish_and_clear();
+    println!("Dump import completed: {} commits", count);
+    let watermark = match repo_id {
+        Some(ref id) => Some(db.get_repo_watermark(id)?.0.to_string()),
+        None => db.get_watermark("svn_rev")?,
+    };
+    if let Some(rev) = watermark {
+        println!("  SVN watermark : r{}", rev);
+    }
+    Ok(())
+}
+
 fn cmd_identity(config: &AppConfig, action: IdentityAction) -> Result<()> {
     let mapper =
         IdentityMapper::new(&config.identity).context("failed to initialize identity mapper")?;
@@ -642,6 +898,53 @@ fn cmd_audit(db: &Database, limit: u32) -> Result<()> {
     Ok(())
 }
 
+fn cmd_doctor(db: &Database, config: &AppConfig) -> Result<()> {
+    println!("RepoSync Doctor");
+    println!("=================");
+    println!();
+
+    let mut warnings = Vec::new();
+
+    match config.validate() {
+        Ok(()) => println!("  [OK] Configuration is valid"),
+        Err(e) => warnings.push(format!("Configuration: {}", e)),
+    }
+
+    // Revision properties the engine could not set on Git -> SVN commits.
+    let repos = db.list_repositories().context("failed to list repositories")?;
+    let mut scopes: Vec<(String, Option<String>)> = vec![("global".to_string(), None)];
+    scopes.extend(repos.into_iter().map(|r| (r.name, Some(r.id))));
+    let mut revprops_ok = true;
+    for (name, repo_id) in &scopes {
+        let key = svn_revprop_error_key_for(repo_id.as_deref());
+        if let Some(error) = db.get_state(&key)?.filter(|v| !v.is_empty()) {
+            revprops_ok = false;
+            warnings.push(format!(
+                "{}: Git authors are not recorded as svn:author ({}). Add a \
+                 pre-revprop-change hook on the SVN server that allows svn:author \
+                 and svn:date, or set sync.preserve_svn_author = false.",
+                name, error
+            ));
+        }
+    }
+    if revprops_ok && config.sync.preserve_svn_author {
+        println!("  [OK] No svn:author/svn:date failures recorded");
+    }
+
+    println!();
+    if warnings.is_empty() {
+        println!("No problems found.");
+    } else {
+        for warning in &warnings {
+            println!("  [WARN] {}", warning);
+        }
+        println!();
+        println!("{} warning(s)", warnings.len());
+    }
+
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Utilities
 // ---------------------------------------------------------------------------
@@ -5,7 +5,6 @@ use std::time::Duration;
 use anyhow::{Context, Result};
 
 use reposync_core::db::Database;
-use reposync_core::config::GitProvider;
 use reposync_core::git::github::GitHubClient;
 use reposync_core::git::GitClient;
 use reposync_core::personal_config::PersonalConfig;
@@ -42,10 +41,14 @@ pub async fn run_start(config: &PersonalConfig, foreground: bool) -> Result<()>
     );
 
     let git_repo_path = data_dir.join("git-repo");
-    let git_client = GitClient::new(&git_repo_path).context("failed to open git repository")?;
+    let mut git_client =
+        GitClient::new(&git_repo_path).context("failed to open git repository")?;
+    let _ = git_client.ensure_remote_credentials("origin", config.github.git_auth());
 
     let github_token = config.github.token.as_deref().unwrap_or("");
-    let github_client = GitHubClient::new(&config.github.api_url, github_token, GitProvider::default());
+    let github_client =
+        GitHubClient::new(&config.github.api_url, github_token, config.github.provider.clone())
+            .with_app_auth(config.github.app_auth.clone());
 
     let engine = reposync_personal::engine::PersonalSyncEngine::new(
         config.clone(),
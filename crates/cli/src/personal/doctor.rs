@@ -25,6 +25,13 @@ pub fn run_doctor(config: &PersonalConfig) -> Result<()> {
         }
     }
 
+    for feature in config.github.provider.unavailable_features() {
+        println!(
+            "  {}",
+            style::dim(&format!("  ○ Unavailable       {} (plain Git remote)", feature))
+        );
+    }
+
     // 2. Data directory
     let data_dir = &config.personal.data_dir;
     if data_dir.exists() {
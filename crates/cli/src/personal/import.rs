@@ -1,19 +1,29 @@
 //! SVN import command with progress visualization.
 
+use std::path::Path;
+
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 
 use reposync_core::db::Database;
-use reposync_core::config::GitProvider;
 use reposync_core::git::github::GitHubClient;
+use reposync_core::dump_import::open_dump;
 use reposync_core::git::GitClient;
+use reposync_core::import::ImportFilter;
 use reposync_core::personal_config::PersonalConfig;
 use reposync_core::svn::SvnClient;
 
 use super::style;
 
-/// Run the import command.
-pub async fn run_import(config: &PersonalConfig, mode: &str) -> Result<()> {
+/// Run the import command; `dump` is a dump file and the repository path it
+/// is rooted at, replacing the SVN server as the source.  `filter` narrows
+/// the revisions and paths imported.
+pub async fn run_import(
+    config: &PersonalConfig,
+    mode: &str,
+    dump: Option<(&Path, &str)>,
+    filter: ImportFilter,
+) -> Result<()> {
     let data_dir = &config.personal.data_dir;
 
     println!();
@@ -36,26 +46,32 @@ pub async fn run_import(config: &PersonalConfig, mode: &str) -> Result<()> {
 
     // GitHub client
     let github_token = config.github.token.as_deref().unwrap_or("");
-    let github_client = GitHubClient::new(&config.github.api_url, github_token, GitProvider::default());
+    let github_client =
+        GitHubClient::new(&config.github.api_url, github_token, config.github.provider.clone())
+            .with_app_auth(config.github.app_auth.clone());
 
     // Git repository
     let git_repo_path = data_dir.join("git-repo");
-    let git_client = if git_repo_path.exists() {
+    let git_auth = config.github.git_auth();
+    let mut git_client = if git_repo_path.exists() {
         GitClient::new(&git_repo_path).context("failed to open git repository")?
     } else {
         std::fs::create_dir_all(&git_repo_path).context("failed to create git repo directory")?;
         let remote_url = config.github.clone_url();
-        match GitClient::clone_repo(&remote_url, &git_repo_path, config.github.token.as_deref()) {
+        match GitClient::clone_repo(&remote_url, &git_repo_path, &git_auth) {
             Ok(client) => client,
             Err(_) => GitClient::init(&git_repo_path).context("failed to init git repository")?,
         }
     };
+    // A fresh repository has no origin yet; the credentials are kept anyway.
+    let _ = git_client.ensure_remote_credentials("origin", git_auth);
 
     let git_client = std::sync::Arc::new(std::sync::Mutex::new(git_client));
     let formatter = reposync_personal::commit_format::CommitFormatter::new(&config.commit_format);
 
     let import_mode = match mode {
         "snapshot" => reposync_personal::initial_import::ImportMode::Snapshot,
+        "branches" => reposync_personal::initial_import::ImportMode::Layout,
         _ => reposync_personal::initial_import::ImportMode::Full,
     };
 
@@ -66,6 +82,7 @@ pub async fn run_import(config: &PersonalConfig, mode: &str) -> Result<()> {
         db: &db,
         config,
         formatter: &formatter,
+        filter,
     };
 
     let spinner = ProgressBar::new_spinner();
@@ -79,7 +96,10 @@ pub async fn run_import(config: &PersonalConfig, mode: &str) -> Result<()> {
     spinner.set_message(format!("Importing ({} mode)...", mode));
     spinner.enable_steady_tick(std::time::Duration::from_millis(100));
 
-    let count = importer.import(import_mode).await?;
+    let count = match dump {
+        Some((path, root)) => importer.import_dump(open_dump(path)?, root).await?,
+        None => importer.import(import_mode).await?,
+    };
 
     spinner.finish_and_clear();
 
@@ -291,12 +291,23 @@ password_env = "{svn_password_env}"
 # GitHub API base URL (change for GitHub Enterprise).
 api_url = "{github_api_url}"
 
+# Git hosting provider: "github", "gitea", "gitlab" (api_url then ends in
+# /api/v4), "bitbucket-server" (api_url ends in /rest/api/1.0, repo is
+# PROJECT/slug) or "git" (plain remote without an API, repo is its URL).
+# provider = "github"
+
 # Target repository in owner/repo format.
 repo = "{github_repo}"
 
 # Environment variable holding a GitHub personal access token.
 token_env = "{github_token_env}"
 
+# Private key for SSH remotes, its passphrase variable and the known_hosts
+# file host keys are checked against (default ~/.ssh/known_hosts).
+# ssh_key_path = "/home/you/.ssh/id_ed25519"
+# ssh_key_passphrase_env = "SSH_KEY_PASSPHRASE"
+# ssh_known_hosts = "/home/you/.ssh/known_hosts"
+
 # Default branch name for the mirror repository.
 default_branch = "main"
 
@@ -327,9 +338,12 @@ svn_username = "{dev_svn_username}"
 # git_to_svn = "{{original_message}}\n\n[reposync] Git-SHA: {{git_sha}}"
 
 [options]
-# Normalize CRLF to LF during sync.
+# Normalize CRLF to LF during sync (svn:eol-style <-> .gitattributes).
 normalize_line_endings = true
 
+# Keep SVN keywords collapsed ($Id$) in Git.
+collapse_keywords = true
+
 # Preserve the executable bit from SVN svn:executable.
 sync_executable_bit = true
 
@@ -339,6 +353,12 @@ max_file_size = 0
 # Glob patterns for files/directories to ignore during sync.
 ignore_patterns = []
 
+# Skip symlinks pointing outside the repository.
+reject_external_symlinks = false
+
+# Mirror svn:ignore / svn:global-ignores as a block in .gitignore (both ways).
+sync_ignores = false
+
 # Whether to sync SVN externals (metadata only).
 sync_externals = false
 
@@ -16,6 +16,7 @@ pub mod style;
 use anyhow::{Context, Result};
 use clap::Subcommand;
 
+use reposync_core::import::ImportFilter;
 use reposync_core::personal_config::PersonalConfig;
 
 /// Personal branch mode subcommands.
@@ -37,6 +38,46 @@ pub enum PersonalCommands {
         /// Import full SVN history (one commit per revision).
         #[arg(long, conflicts_with = "snapshot")]
         full: bool,
+
+        /// Import full history of trunk, branches and tags as Git branches
+        /// and tags (standard layout: the SVN URL points at trunk).
+        #[arg(long, conflicts_with_all = ["snapshot", "dump"])]
+        branches: bool,
+
+        /// Import full history from an `svnadmin dump` / `svnrdump` file
+        /// (`-` for stdin) instead of the SVN server.
+        #[arg(long, value_name = "FILE", conflicts_with = "snapshot")]
+        dump: Option<std::path::PathBuf>,
+
+        /// Repository path in the dump that the SVN URL points at.
+        #[arg(long, value_name = "PATH", default_value = "trunk", requires = "dump")]
+        dump_root: String,
+
+        /// First SVN revision to import; the tree before it becomes a single
+        /// baseline commit.
+        #[arg(long, value_name = "REV", conflicts_with_all = ["snapshot", "branches", "dump"])]
+        from_rev: Option<i64>,
+
+        /// Import the revisions committed after this date (YYYY-MM-DD or
+        /// RFC 3339) instead of a start revision.
+        #[arg(
+            long,
+            value_name = "DATE",
+            conflicts_with_all = ["from_rev", "snapshot", "branches", "dump"]
+        )]
+        from_date: Option<String>,
+
+        /// Last SVN revision to import (default: HEAD).
+        #[arg(long, value_name = "REV", conflicts_with_all = ["snapshot", "branches", "dump"])]
+        to_rev: Option<i64>,
+
+        /// Only import paths matching this glob, e.g. `src/**` (repeatable).
+        #[arg(long, value_name = "GLOB")]
+        include: Vec<String>,
+
+        /// Leave out paths matching this glob (repeatable).
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
     },
 
     /// Start the sync daemon.
@@ -95,10 +136,41 @@ pub async fn run_personal(cmd: PersonalCommands, config_path: &str) -> Result<()
     match cmd {
         PersonalCommands::Init { output } => init::run_init(&output).await,
 
-        PersonalCommands::Import { snapshot, full: _ } => {
+        PersonalCommands::Import {
+            snapshot,
+            full: _,
+            branches,
+            dump,
+            dump_root,
+            from_rev,
+            from_date,
+            to_rev,
+            include,
+            exclude,
+        } => {
             let config = load_config(config_path)?;
-            let mode = if snapshot { "snapshot" } else { "full" };
-            import::run_import(&config, mode).await
+            let filter = ImportFilter {
+                from_rev,
+                from_date,
+                to_rev,
+                include_paths: include,
+                exclude_paths: exclude,
+            };
+            match dump {
+                Some(path) => {
+                    import::run_import(&config, "dump", Some((&path, &dump_root)), filter).await
+                }
+                None => {
+                    let mode = if snapshot {
+                        "snapshot"
+                    } else if branches {
+                        "branches"
+                    } else {
+                        "full"
+                    };
+                    import::run_import(&config, mode, None, filter).await
+                }
+            }
         }
 
         PersonalCommands::Start { foreground } => {
@@ -6,12 +6,15 @@
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 use crate::db::Database;
 use crate::errors::ConfigError;
+use crate::git::auth::{GitAuth, SshKey};
+use crate::git::GitHubAppAuth;
 
 // ---------------------------------------------------------------------------
 // Top-level config
@@ -126,11 +129,13 @@ pub struct SvnConfig {
     #[serde(default = "default_trunk")]
     pub trunk_path: String,
 
-    /// Path to branches (relative to repo root). Default `branches`.
+    /// Path to branches, relative to the project: the URL for the standard
+    /// layout, the parent of the synced path otherwise. Default `branches`.
     #[serde(default = "default_branches")]
     pub branches_path: String,
 
-    /// Path to tags (relative to repo root). Default `tags`.
+    /// Path to tags, relative to the project like `branches_path`. Default
+    /// `tags`.
     #[serde(default = "default_tags")]
     pub tags_path: String,
 
@@ -168,6 +173,50 @@ pub enum GitProvider {
     #[default]
     GitHub,
     Gitea,
+    /// GitLab (gitlab.com or self-managed), API v4.
+    GitLab,
+    /// Bitbucket Server / Data Center, REST API 1.0.
+    #[serde(rename = "bitbucket-server")]
+    BitbucketServer,
+    /// Any Git remote (SSH, HTTPS or a path) without a REST API.  `repo` is
+    /// the remote itself and only direct sync is available.
+    #[serde(rename = "git")]
+    Plain,
+}
+
+impl GitProvider {
+    /// Provider by its name in the config file and the repositories table.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "github" => Some(Self::GitHub),
+            "gitea" => Some(Self::Gitea),
+            "gitlab" => Some(Self::GitLab),
+            "bitbucket-server" => Some(Self::BitbucketServer),
+            "git" => Some(Self::Plain),
+            _ => None,
+        }
+    }
+
+    /// Whether the provider has a REST API for pull requests, commit
+    /// statuses and repository creation.
+    pub fn has_api(&self) -> bool {
+        *self != Self::Plain
+    }
+
+    /// Features that need a REST API and are therefore unavailable with this
+    /// provider.
+    pub fn unavailable_features(&self) -> &'static [&'static str] {
+        if self.has_api() {
+            return &[];
+        }
+        &[
+            "pull requests: PR sync mode and auto-merge; personal mode syncs the commits \
+             pushed to the default branch back to SVN instead of merged PRs",
+            "commit statuses",
+            "repository auto-create",
+            "webhooks: Git changes are picked up by polling the remote every poll interval",
+        ]
+    }
 }
 
 /// GitHub repository and API configuration.
@@ -184,7 +233,8 @@ pub struct GitHubConfig {
     #[serde(default)]
     pub git_base_url: Option<String>,
 
-    /// Repository in `owner/repo` format.
+    /// Repository in `owner/repo` format.  With the `git` provider this is
+    /// the remote URL or path instead.
     pub repo: String,
 
     /// Environment variable holding the GitHub personal access token.
@@ -195,21 +245,48 @@ pub struct GitHubConfig {
     #[serde(default)]
     pub webhook_secret_env: Option<String>,
 
+    /// Private key for SSH remotes (`ssh://…` or `git@host:path`).
+    #[serde(default)]
+    pub ssh_key_path: Option<PathBuf>,
+
+    /// Environment variable holding the passphrase of an encrypted SSH key.
+    /// The setup wizard stores it in the database instead.
+    #[serde(default)]
+    pub ssh_key_passphrase_env: Option<String>,
+
+    /// `known_hosts` file SSH host keys are verified against.  Default:
+    /// `~/.ssh/known_hosts` of the daemon's user.
+    #[serde(default)]
+    pub ssh_known_hosts: Option<PathBuf>,
+
     /// Default branch name (e.g. `main`).
     #[serde(default = "default_branch")]
     pub default_branch: String,
 
-    /// Git hosting provider (github or gitea). Default: github.
+    /// Git hosting provider (github, gitea, gitlab, bitbucket-server or
+    /// git for a plain remote). Default: github.
     #[serde(default)]
     pub provider: GitProvider,
 
+    /// Authenticate as a GitHub App installation instead of with a token.
+    #[serde(default)]
+    pub app: Option<GitHubAppConfig>,
+
     /// Resolved token (populated by `resolve_env_vars`).
     #[serde(skip)]
     pub token: Option<String>,
 
+    /// Installation token source of `app` (populated by `resolve_env_vars`).
+    #[serde(skip)]
+    pub app_auth: Option<Arc<GitHubAppAuth>>,
+
     /// Resolved webhook secret.
     #[serde(skip)]
     pub webhook_secret: Option<String>,
+
+    /// Resolved SSH key passphrase.
+    #[serde(skip)]
+    pub ssh_key_passphrase: Option<String>,
 }
 
 impl GitHubConfig {
@@ -224,6 +301,123 @@ impl GitHubConfig {
             &self.repo,
         )
     }
+
+    /// Credentials for Git clone, fetch and push: `token` (the resolved or a
+    /// per-repository token) plus the configured SSH key.  The GitHub App,
+    /// if it serves this repository, takes the place of the token.
+    pub fn git_auth(&self, token: Option<&str>) -> GitAuth {
+        GitAuth::from_token(token)
+            .with_ssh_key(self.ssh_key_path.as_ref().map(|path| SshKey {
+                private_key: path.clone(),
+                passphrase: self.ssh_key_passphrase.clone(),
+                known_hosts: self.ssh_known_hosts.clone(),
+            }))
+            .with_app(self.github_app_auth())
+    }
+
+    /// The GitHub App's token source if it serves this repository: the
+    /// provider is GitHub and the API is the one the app was set up for.
+    /// Repositories on other hosts keep using their own token.
+    pub fn github_app_auth(&self) -> Option<Arc<GitHubAppAuth>> {
+        self.app_auth.clone().filter(|app| {
+            self.provider == GitProvider::GitHub
+                && app.api_url() == self.api_url.trim_end_matches('/')
+        })
+    }
+
+    /// This configuration pointed at a repository from the repositories
+    /// table: its provider, API URL and Git repository replace the
+    /// configured ones where set.
+    pub fn for_repository(&self, repo: &crate::models::Repository) -> GitHubConfig {
+        let mut github = self.clone();
+        if !repo.git_repo.is_empty() {
+            github.repo = repo.git_repo.clone();
+        }
+        if !repo.git_api_url.is_empty() {
+            github.api_url = repo.git_api_url.clone();
+        }
+        if let Some(provider) = GitProvider::from_name(&repo.git_provider) {
+            github.provider = provider;
+        }
+        github
+    }
+}
+
+/// GitHub App credentials (`[github.app]`).  The app's installation tokens
+/// are used for the API and for Git instead of a personal access token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubAppConfig {
+    /// App ID shown on the app's settings page.
+    pub app_id: u64,
+
+    /// ID of the app's installation on the account owning the repository.
+    pub installation_id: u64,
+
+    /// PEM file holding the app's private key.
+    #[serde(default)]
+    pub private_key_path: Option<PathBuf>,
+
+    /// Environment variable holding the PEM private key itself.  Takes
+    /// precedence over `private_key_path`.
+    #[serde(default)]
+    pub private_key_env: Option<String>,
+}
+
+impl GitHubAppConfig {
+    /// Read the private key and set up the installation token source for the
+    /// API at `api_url`.
+    ///
+    /// A missing key logs a warning and yields `None`, like other unresolved
+    /// secrets; a key that is present but not a valid RSA key is an error.
+    pub fn load(&self, api_url: &str) -> Result<Option<Arc<GitHubAppAuth>>, ConfigError> {
+        let (pem, field) = if let Some(ref env_name) = self.private_key_env {
+            let field = "github.app.private_key_env";
+            (resolve_optional_env(env_name, field), field)
+        } else if let Some(ref path) = self.private_key_path {
+            let pem = match std::fs::read_to_string(path) {
+                Ok(pem) => Some(pem),
+                Err(e) => {
+                    warn!(path = %path.display(), error = %e, "cannot read GitHub App private key");
+                    None
+                }
+            };
+            (pem, "github.app.private_key_path")
+        } else {
+            return Ok(None);
+        };
+        let Some(pem) = pem else {
+            return Ok(None);
+        };
+        GitHubAppAuth::new(api_url, self.app_id, self.installation_id, &pem)
+            .map(|auth| Some(Arc::new(auth)))
+            .map_err(|e| ConfigError::InvalidValue {
+                field: field.into(),
+                detail: e.to_string(),
+            })
+    }
+
+    /// Check the IDs and that a private key source is set.
+    pub fn validate(&self, provider: &GitProvider) -> Result<(), ConfigError> {
+        if *provider != GitProvider::GitHub {
+            return Err(ConfigError::InvalidValue {
+                field: "github.app".into(),
+                detail: "GitHub App authentication requires provider 'github'".into(),
+            });
+        }
+        if self.app_id == 0 || self.installation_id == 0 {
+            return Err(ConfigError::InvalidValue {
+                field: "github.app".into(),
+                detail: "app_id and installation_id must be set".into(),
+            });
+        }
+        if self.private_key_path.is_none() && self.private_key_env.is_none() {
+            return Err(ConfigError::InvalidValue {
+                field: "github.app".into(),
+                detail: "set private_key_path or private_key_env".into(),
+            });
+        }
+        Ok(())
+    }
 }
 
 fn default_github_api_url() -> String {
@@ -354,7 +548,7 @@ impl Default for WebConfig {
 // ---------------------------------------------------------------------------
 
 /// Notification channel configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationConfig {
     /// Environment variable holding the Slack incoming-webhook URL.
     #[serde(default)]
@@ -372,11 +566,77 @@ pub struct NotificationConfig {
     #[serde(default)]
     pub email_recipients: Vec<String>,
 
+    /// Number of consecutive failed cycles of a repository after which an
+    /// `error_threshold` alert is sent, repeated every as many further
+    /// failures while the streak lasts (0 disables it).
+    #[serde(default = "default_error_threshold")]
+    pub error_threshold: u32,
+
+    /// Event routing rules. Without any, conflicts and error-threshold
+    /// alerts go to every configured channel.
+    #[serde(default)]
+    pub routes: Vec<NotificationRoute>,
+
     /// Resolved Slack webhook URL.
     #[serde(skip)]
     pub slack_webhook_url: Option<String>,
 }
 
+fn default_error_threshold() -> u32 {
+    3
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            slack_webhook_url_env: None,
+            email_smtp: None,
+            email_from: None,
+            email_recipients: Vec::new(),
+            error_threshold: default_error_threshold(),
+            routes: Vec::new(),
+            slack_webhook_url: None,
+        }
+    }
+}
+
+/// An event the notifier can report.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// A conflict needs manual resolution.
+    Conflict,
+    /// A sync cycle failed.
+    SyncFailed,
+    /// A repository failed `error_threshold` cycles in a row.
+    ErrorThreshold,
+    /// A sync cycle completed with changes.
+    SyncComplete,
+}
+
+/// A notification delivery channel.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationChannel {
+    Slack,
+    Email,
+}
+
+/// Routes a set of events to a set of channels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationRoute {
+    /// Events this route applies to.
+    pub events: Vec<NotificationEvent>,
+
+    /// Channels to deliver to (empty = every configured channel).
+    #[serde(default)]
+    pub channels: Vec<NotificationChannel>,
+
+    /// Repository names this route applies to (empty = all repositories).
+    #[serde(default)]
+    pub repos: Vec<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Sync behaviour
 // ---------------------------------------------------------------------------
@@ -388,17 +648,22 @@ pub enum SyncMode {
     /// Push directly to the target branch.
     #[default]
     Direct,
-    /// Create a pull request for each sync batch.
+    /// Commit SVN revisions to `sync.pr.branch` and open a pull request
+    /// into the target branch.
     Pr,
 }
 
 /// Sub-configuration for PR-based sync mode.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrConfig {
     /// Title prefix for auto-generated PRs.
     #[serde(default = "default_pr_prefix")]
     pub title_prefix: String,
 
+    /// Branch that SVN revisions accumulate on until the PR is merged.
+    #[serde(default = "default_pr_branch")]
+    pub branch: String,
+
     /// Labels to apply to the PR.
     #[serde(default)]
     pub labels: Vec<String>,
@@ -407,15 +672,39 @@ pub struct PrConfig {
     #[serde(default)]
     pub reviewers: Vec<String>,
 
-    /// Automatically merge the PR if CI passes.
+    /// Automatically merge the PR once CI passes. A head commit without any
+    /// status or check run stays pending unless `merge_without_checks` is set.
     #[serde(default)]
     pub auto_merge: bool,
+
+    /// With `auto_merge`, also merge a head commit that still has no status
+    /// or check run one sync cycle after it was first seen, for repositories
+    /// without CI. Default false.
+    #[serde(default)]
+    pub merge_without_checks: bool,
 }
 
 fn default_pr_prefix() -> String {
     "[svn-sync]".into()
 }
 
+fn default_pr_branch() -> String {
+    "reposync/svn-sync".into()
+}
+
+impl Default for PrConfig {
+    fn default() -> Self {
+        Self {
+            title_prefix: default_pr_prefix(),
+            branch: default_pr_branch(),
+            labels: Vec::new(),
+            reviewers: Vec::new(),
+            auto_merge: false,
+            merge_without_checks: false,
+        }
+    }
+}
+
 /// Sync behaviour configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncConfig {
@@ -427,18 +716,87 @@ pub struct SyncConfig {
     #[serde(default = "default_true")]
     pub auto_merge: bool,
 
-    /// Branch names to synchronize (empty = all).
+    /// SVN branches under `svn.branches_path` to synchronize with Git
+    /// branches (empty = trunk only, `"*"` = all). Entries are names or
+    /// `svn:git` patterns with one `*`, e.g. `"release-*:release/*"`.
     #[serde(default)]
     pub sync_branches: Vec<String>,
 
-    /// Whether to sync tags.
+    /// Make full imports replay every branch under `svn.branches_path` and
+    /// tag under `svn.tags_path` into Git branches and tags along with
+    /// trunk (standard layout). Names are mapped with `sync_branches` and
+    /// `tag_mappings`; unmatched names are kept.
+    #[serde(default)]
+    pub import_branches: bool,
+
+    /// Whether to sync tags between `svn.tags_path` and Git tags. Tags that
+    /// exist when tag sync first runs are not copied.
     #[serde(default = "default_true")]
     pub sync_tags: bool,
 
+    /// Tag name transformations as `svn:git` patterns with one `*`, e.g.
+    /// `"*:v*"`. Tags no entry matches keep their name on both sides.
+    #[serde(default)]
+    pub tag_mappings: Vec<String>,
+
     /// PR-specific settings (used when `mode` is `Pr`).
     #[serde(default)]
     pub pr: PrConfig,
 
+    /// Set `svn:author` on revisions committed from Git to the SVN user the
+    /// Git author maps to. The SVN server needs a `pre-revprop-change` hook
+    /// that allows it.
+    #[serde(default = "default_true")]
+    pub preserve_svn_author: bool,
+
+    /// Also set `svn:date` on those revisions to the Git author date.
+    #[serde(default)]
+    pub preserve_svn_date: bool,
+
+    /// Commit Git changes to SVN with the author's own SVN credential
+    /// stored in RepoSync, so the server's per-user authz applies.
+    ///
+    /// Commit emails can be set to anything, so only commits whose signature
+    /// the Git host verified (GitHub and Gitea) are committed as the
+    /// committer; all others go in as the service account.  Keep this off
+    /// unless the Git host requires signed commits.
+    #[serde(default)]
+    pub commit_as_author: bool,
+
+    /// Mirror `svn:ignore` / `svn:global-ignores` as a generated block in
+    /// `.gitignore` files, and turn edits of that block in Git back into
+    /// those properties. Other `.gitignore` lines are kept and synced as
+    /// file content.
+    #[serde(default)]
+    pub sync_ignores: bool,
+
+    /// Mirror `svn:externals`: each definition is kept in a `.svnexternals`
+    /// file, and externals become submodules when they point at another
+    /// synced repository, or vendored snapshots otherwise.
+    #[serde(default)]
+    pub sync_externals: bool,
+
+    /// Map `svn:executable` to Git mode `100755` and Git mode changes back
+    /// to the property.
+    #[serde(default = "default_true")]
+    pub sync_executable_bit: bool,
+
+    /// Mirror `svn:eol-style` in `.gitattributes`, compare text with CRLF
+    /// normalized to LF, and give files added from Git the `svn:eol-style`
+    /// their `.gitattributes` rules call for.
+    #[serde(default = "default_true")]
+    pub normalize_line_endings: bool,
+
+    /// Keep `svn:keywords` collapsed (`$Id$`) in Git and set the property on
+    /// files added from Git that contain keywords.
+    #[serde(default = "default_true")]
+    pub collapse_keywords: bool,
+
+    /// Turn SVN merges recorded in `svn:mergeinfo` into Git merge commits,
+    /// and record merged-in parents of Git merge commits as mergeinfo.
+    #[serde(default = "default_true")]
+    pub sync_merges: bool,
+
     /// Maximum file size in bytes (0 = no limit). Files larger are skipped.
     #[serde(default)]
     pub max_file_size: u64,
@@ -454,6 +812,11 @@ pub struct SyncConfig {
     /// Glob patterns for files to ignore during sync.
     #[serde(default)]
     pub ignore_patterns: Vec<String>,
+
+    /// Skip symlinks whose target resolves outside the repository instead
+    /// of syncing them as `svn:special` files / Git symlinks.
+    #[serde(default)]
+    pub reject_external_symlinks: bool,
 }
 
 fn default_true() -> bool {
@@ -466,12 +829,24 @@ impl Default for SyncConfig {
             mode: SyncMode::default(),
             auto_merge: true,
             sync_branches: Vec::new(),
+            import_branches: false,
             sync_tags: true,
+            tag_mappings: Vec::new(),
             pr: PrConfig::default(),
+            preserve_svn_author: true,
+            preserve_svn_date: false,
+            commit_as_author: false,
+            sync_ignores: false,
+            sync_externals: false,
+            sync_executable_bit: true,
+            normalize_line_endings: true,
+            collapse_keywords: true,
+            sync_merges: true,
             max_file_size: 0,
             lfs_threshold: 0,
             lfs_patterns: Vec::new(),
             ignore_patterns: Vec::new(),
+            reject_external_symlinks: false,
         }
     }
 }
@@ -516,6 +891,17 @@ impl AppConfig {
         // GitHub token (required for most operations)
         self.github.token = resolve_optional_env(&self.github.token_env, "github.token_env");
 
+        // GitHub App private key
+        if let Some(ref app) = self.github.app {
+            self.github.app_auth = app.load(&self.github.api_url)?;
+        }
+
+        // SSH key passphrase
+        if let Some(ref env_name) = self.github.ssh_key_passphrase_env {
+            self.github.ssh_key_passphrase =
+                resolve_optional_env(env_name, "github.ssh_key_passphrase_env");
+        }
+
         // GitHub webhook secret
         if let Some(ref env_name) = self.github.webhook_secret_env {
             self.github.webhook_secret =
@@ -608,6 +994,16 @@ impl AppConfig {
             debug!("No Git token found in database");
         }
 
+        // SSH key passphrase
+        if self.github.ssh_key_passphrase.is_some() {
+            debug!("SSH key passphrase already set from env var, skipping DB lookup");
+        } else if let Some(val) =
+            decrypt_secret(db, "git_ssh_passphrase", "secret_git_ssh_passphrase")
+        {
+            self.github.ssh_key_passphrase = Some(val);
+            info!("Loaded SSH key passphrase from database (encrypted)");
+        }
+
         // Admin password (stored as bcrypt hash, not encrypted)
         if self.web.admin_password.is_some() {
             debug!("Admin password already set from env var, skipping DB lookup");
@@ -657,12 +1053,34 @@ impl AppConfig {
                 detail: "GitHub repo must not be empty".into(),
             });
         }
-        if !self.github.repo.contains('/') {
+        if self.github.provider == GitProvider::Plain {
+            if !crate::git::remote_url::is_remote_url(&self.github.repo)
+                && self.github.git_base_url.as_deref().unwrap_or("").trim().is_empty()
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: "github.repo".into(),
+                    detail: "with provider 'git', repo must be the remote URL or path \
+                             (or set git_base_url)"
+                        .into(),
+                });
+            }
+            if self.sync.mode == SyncMode::Pr {
+                return Err(ConfigError::InvalidValue {
+                    field: "sync.mode".into(),
+                    detail: "PR mode needs a Git host API; provider 'git' only supports \
+                             direct sync"
+                        .into(),
+                });
+            }
+        } else if !self.github.repo.contains('/') {
             return Err(ConfigError::InvalidValue {
                 field: "github.repo".into(),
                 detail: "GitHub repo must be in 'owner/repo' format".into(),
             });
         }
+        if let Some(ref app) = self.github.app {
+            app.validate(&self.github.provider)?;
+        }
         if self.daemon.poll_interval_secs == 0 {
             return Err(ConfigError::InvalidValue {
                 field: "daemon.poll_interval_secs".into(),
@@ -693,6 +1111,36 @@ impl AppConfig {
         config.validate()?;
         Ok(config)
     }
+
+    /// Configuration for syncing a repository from the repositories table.
+    ///
+    /// The SVN branch path is already part of the repository's SVN URL, so
+    /// the layout is custom with an empty trunk path. The repository's sync
+    /// mode and Git host replace the configured ones, and branch-pair child
+    /// repositories sync their single branch without tags.
+    pub fn for_repository(&self, repo: &crate::models::Repository) -> AppConfig {
+        let mut config = self.clone();
+        config.svn.trunk_path = String::new();
+        config.svn.layout = SvnLayout::Custom;
+        match repo.sync_mode.as_str() {
+            "pr" => config.sync.mode = SyncMode::Pr,
+            "direct" => config.sync.mode = SyncMode::Direct,
+            _ => {}
+        }
+        config.github = self.github.for_repository(repo);
+        if !config.github.provider.has_api() && config.sync.mode == SyncMode::Pr {
+            warn!(
+                repo_name = %repo.name,
+                "PR mode needs a Git host API, using direct sync for this plain Git remote"
+            );
+            config.sync.mode = SyncMode::Direct;
+        }
+        if repo.parent_id.is_some() {
+            config.sync.sync_branches.clear();
+            config.sync.sync_tags = false;
+        }
+        config
+    }
 }
 
 /// Try to read an environment variable by name. Returns `Some(value)` on
@@ -759,12 +1207,23 @@ slack_webhook_url_env = "SLACK_URL"
 email_smtp = "smtp.example.com:587"
 email_from = "sync@example.com"
 email_recipients = ["admin@example.com"]
+error_threshold = 5
+
+[[notifications.routes]]
+events = ["conflict", "error_threshold"]
+channels = ["slack"]
+
+[[notifications.routes]]
+events = ["sync_failed"]
+channels = ["email"]
+repos = ["core"]
 
 [sync]
 mode = "pr"
 auto_merge = true
 sync_branches = ["develop", "release/*"]
 sync_tags = true
+tag_mappings = ["*:v*"]
 
 [sync.pr]
 title_prefix = "[svn-sync]"
@@ -783,6 +1242,19 @@ auto_merge = false
         assert_eq!(config.web.auth_mode, AuthMode::Both);
         assert_eq!(config.sync.mode, SyncMode::Pr);
         assert_eq!(config.sync.pr.reviewers, vec!["charlie"]);
+        assert_eq!(config.sync.pr.branch, "reposync/svn-sync");
+        assert_eq!(config.sync.tag_mappings, vec!["*:v*"]);
+        assert_eq!(config.notifications.error_threshold, 5);
+        assert_eq!(config.notifications.routes.len(), 2);
+        assert_eq!(
+            config.notifications.routes[1].events,
+            vec![NotificationEvent::SyncFailed]
+        );
+        assert_eq!(
+            config.notifications.routes[1].channels,
+            vec![NotificationChannel::Email]
+        );
+        assert_eq!(config.notifications.routes[1].repos, vec!["core"]);
     }
 
     #[test]
@@ -824,6 +1296,109 @@ auto_merge = false
         ));
     }
 
+    #[test]
+    fn test_validate_plain_git_remote() {
+        let mut config: AppConfig = toml::from_str(sample_toml()).unwrap();
+        config.github.provider = GitProvider::Plain;
+        config.github.repo = "git@fileserver:mirrors/project.git".into();
+        config.sync.mode = SyncMode::Direct;
+        assert!(config.validate().is_ok());
+        assert_eq!(config.github.clone_url(), "git@fileserver:mirrors/project.git");
+
+        config.sync.mode = SyncMode::Pr;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidValue { ref field, .. }) if field == "sync.mode"
+        ));
+
+        config.sync.mode = SyncMode::Direct;
+        config.github.repo = "acme/project".into();
+        assert!(config.validate().is_err());
+        config.github.git_base_url = Some("ssh://fileserver/srv/git".into());
+        assert!(config.validate().is_ok());
+        assert!(!config.github.provider.has_api());
+        assert!(GitProvider::GitLab.unavailable_features().is_empty());
+    }
+
+    #[test]
+    fn test_github_app() {
+        let toml_str = r#"
+[daemon]
+[svn]
+url = "https://svn.example.com/repo"
+username = "user"
+password_env = "SVN_PW"
+[github]
+repo = "acme/repo"
+[github.app]
+app_id = 1234
+installation_id = 99
+private_key_path = "/nonexistent/app.pem"
+"#;
+        let mut config: AppConfig = toml::from_str(toml_str).unwrap();
+        let app = config.github.app.clone().expect("app section");
+        assert_eq!((app.app_id, app.installation_id), (1234, 99));
+        assert!(config.validate().is_ok());
+
+        // An unreadable key warns like a missing secret; a bad one is an error.
+        config.resolve_env_vars().unwrap();
+        assert!(config.github.app_auth.is_none());
+        std::env::set_var("TEST_GH_APP_KEY", "not a key");
+        config.github.app.as_mut().unwrap().private_key_env = Some("TEST_GH_APP_KEY".into());
+        let result = config.resolve_env_vars();
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidValue { field, .. }) if field == "github.app.private_key_env"
+        ));
+        std::env::remove_var("TEST_GH_APP_KEY");
+
+        config.github.provider = GitProvider::GitLab;
+        assert!(config.validate().is_err());
+        config.github.provider = GitProvider::GitHub;
+        config.github.app.as_mut().unwrap().installation_id = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_github_app_only_serves_its_host() {
+        let mut config: AppConfig = toml::from_str(sample_toml()).unwrap();
+        config.github.api_url = "https://ghe.example.com/api/v3".into();
+        let app = GitHubAppAuth::new(
+            "https://ghe.example.com/api/v3",
+            1,
+            2,
+            crate::git::github_app::tests::TEST_KEY,
+        )
+        .unwrap();
+        config.github.app_auth = Some(Arc::new(app));
+        assert!(config.github.git_auth(Some("pat")).app.is_some());
+
+        let repo = |provider: &str, api_url: &str| -> crate::models::Repository {
+            serde_json::from_value(serde_json::json!({
+                "id": "r1", "name": "r1", "svn_url": "https://svn.example.com/repo",
+                "svn_branch": "trunk", "svn_username": "svc", "git_provider": provider,
+                "git_api_url": api_url, "git_repo": "team/app", "git_branch": "main",
+                "sync_mode": "direct", "poll_interval_secs": 60, "lfs_threshold_mb": 0,
+                "auto_merge": true, "enabled": true, "created_by": null,
+                "created_at": "", "updated_at": ""
+            }))
+            .unwrap()
+        };
+        let gitlab =
+            config.github.for_repository(&repo("gitlab", "https://gitlab.example.com/api/v4"));
+        assert_eq!(gitlab.provider, GitProvider::GitLab);
+        assert_eq!(gitlab.repo, "team/app");
+        let auth = gitlab.git_auth(Some("glpat"));
+        assert!(auth.app.is_none());
+        assert_eq!(auth.current_token().as_deref(), Some("glpat"));
+
+        let other_github = config.github.for_repository(&repo("github", "https://api.github.com"));
+        assert!(other_github.github_app_auth().is_none());
+        let same_host =
+            config.github.for_repository(&repo("github", "https://ghe.example.com/api/v3/"));
+        assert!(same_host.github_app_auth().is_some());
+    }
+
     #[test]
     fn test_resolve_env_vars() {
         std::env::set_var("TEST_SVN_PW", "s3cret");
@@ -870,5 +1445,17 @@ token_env = "GH_TOKEN"
         assert_eq!(config.web.listen, "127.0.0.1:3000");
         assert_eq!(config.sync.mode, SyncMode::Direct);
         assert!(config.sync.auto_merge);
+        assert!(config.sync.preserve_svn_author);
+        assert!(!config.sync.preserve_svn_date);
+        assert!(!config.sync.commit_as_author);
+        assert!(!config.sync.sync_ignores);
+        assert!(!config.sync.sync_externals);
+        assert!(config.sync.sync_executable_bit);
+        assert!(config.sync.normalize_line_endings);
+        assert!(config.sync.collapse_keywords);
+        assert!(config.sync.sync_merges);
+        assert!(!config.sync.reject_external_symlinks);
+        assert_eq!(config.notifications.error_threshold, 3);
+        assert!(config.notifications.routes.is_empty());
     }
 }
@@ -214,11 +214,32 @@ impl ConflictDetector {
             }
         }
 
+        // A rename on one side whose source the other side modified.
+        let svn_by_path: std::collections::HashMap<&str, &FileChange> =
+            svn_changes.iter().map(|c| (c.path.as_str(), c)).collect();
+        let moved_and_modified = svn_renames
+            .iter()
+            .map(|(from, _)| (*from, git_by_path.get(from)))
+            .chain(git_renames.keys().map(|from| (*from, svn_by_path.get(from))));
+        for (from, other) in moved_and_modified {
+            if matches!(other.map(|c| &c.change_kind), Some(ChangeKind::Modified)) {
+                debug!(from, "rename of a modified path detected");
+                conflicts.push(Conflict::new(from, ConflictType::Rename));
+            }
+        }
+
         info!(count = conflicts.len(), "conflict detection complete");
         conflicts
     }
 }
 
+/// Heuristic binary check: a NUL byte in the first 8000 bytes (the same window
+/// Git uses). Text in another encoding than UTF-8 (Latin-1, CP1252) is not
+/// binary; it merely has no `content` to auto-merge.
+pub fn is_binary_content(content: &[u8]) -> bool {
+    content[..content.len().min(8000)].contains(&0)
+}
+
 /// Classify what kind of conflict exists between two changes to the same path.
 fn classify_conflict(svn: &FileChange, git: &FileChange) -> Option<ConflictType> {
     // Binary conflict.
@@ -226,7 +247,16 @@ fn classify_conflict(svn: &FileChange, git: &FileChange) -> Option<ConflictType>
         return Some(ConflictType::Binary);
     }
 
+    // Both sides arrived at the same content.
+    let same_content = matches!((&svn.content, &git.content), (Some(a), Some(b)) if a == b);
+
     match (&svn.change_kind, &git.change_kind) {
+        (ChangeKind::Modified | ChangeKind::Added, ChangeKind::Modified | ChangeKind::Added)
+            if same_content =>
+        {
+            None
+        }
+
         // Both modified the same file.
         (ChangeKind::Modified, ChangeKind::Modified) => Some(ConflictType::Content),
 
@@ -291,6 +321,20 @@ mod tests {
         assert_eq!(conflicts[0].conflict_type, ConflictType::Content);
     }
 
+    #[test]
+    fn test_identical_content_is_not_a_conflict() {
+        let with_content = |kind, content: &str| FileChange {
+            content: Some(content.to_string()),
+            ..change("a.rs", kind)
+        };
+        let svn = vec![with_content(ChangeKind::Modified, "fn a() {}\n")];
+        let git = vec![with_content(ChangeKind::Modified, "fn a() {}\n")];
+        assert!(ConflictDetector::detect(&svn, &git).is_empty());
+
+        let git = vec![with_content(ChangeKind::Modified, "fn b() {}\n")];
+        assert_eq!(ConflictDetector::detect(&svn, &git).len(), 1);
+    }
+
     #[test]
     fn test_edit_delete_conflict() {
         let svn = vec![change("file.rs", ChangeKind::Modified)];
@@ -322,6 +366,14 @@ mod tests {
         assert_eq!(conflicts[0].conflict_type, ConflictType::Binary);
     }
 
+    #[test]
+    fn test_is_binary_content() {
+        assert!(!is_binary_content(b"fn main() {}\n"));
+        assert!(!is_binary_content("caf\u{e9}".as_bytes()));
+        assert!(is_binary_content(b"\x89PNG\r\n\x1a\n\0\0"));
+        assert!(!is_binary_content(b"caf\xe9\n"));
+    }
+
     #[test]
     fn test_rename_conflict() {
         let svn = vec![FileChange {
@@ -345,6 +397,28 @@ mod tests {
         assert_eq!(conflicts[0].conflict_type, ConflictType::Rename);
     }
 
+    #[test]
+    fn test_rename_of_modified_path_conflicts() {
+        let svn = vec![FileChange {
+            path: "new.rs".to_string(),
+            change_kind: ChangeKind::Renamed {
+                from: "old.rs".to_string(),
+            },
+            content: None,
+            is_binary: false,
+        }];
+        let git = vec![change("old.rs", ChangeKind::Modified)];
+        let conflicts = ConflictDetector::detect(&svn, &git);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].file_path, "old.rs");
+        assert_eq!(conflicts[0].conflict_type, ConflictType::Rename);
+
+        // The same holds with the sides swapped.
+        let conflicts = ConflictDetector::detect(&git, &svn);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].conflict_type, ConflictType::Rename);
+    }
+
     #[test]
     fn test_multiple_conflicts() {
         let svn = vec![
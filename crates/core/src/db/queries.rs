@@ -26,6 +26,19 @@ pub struct CommitMapEntry {
     pub repo_id: Option<String>,
 }
 
+/// A row from the `tag_map` table.
+#[derive(Debug, Clone)]
+pub struct TagMapEntry {
+    pub id: i64,
+    pub svn_tag: String,
+    pub git_tag: String,
+    pub svn_rev: i64,
+    pub git_sha: String,
+    pub direction: String,
+    pub synced_at: String,
+    pub repo_id: Option<String>,
+}
+
 /// A row from the `sync_state` table.
 #[derive(Debug, Clone)]
 pub struct SyncStateEntry {
@@ -52,6 +65,8 @@ pub struct ConflictEntry {
     pub resolved_by: Option<String>,
     pub created_at: String,
     pub resolved_at: Option<String>,
+    pub resolved_content: Option<String>,
+    pub repo_id: Option<String>,
 }
 
 /// A row from the `watermarks` table.
@@ -92,13 +107,26 @@ impl Database {
         direction: &str,
         svn_author: &str,
         git_author: &str,
+    ) -> Result<i64, DatabaseError> {
+        self.insert_commit_map_with_repo(svn_rev, git_sha, direction, svn_author, git_author, None)
+    }
+
+    /// Insert a commit-map entry tagged with an optional `repo_id`.
+    pub fn insert_commit_map_with_repo(
+        &self,
+        svn_rev: i64,
+        git_sha: &str,
+        direction: &str,
+        svn_author: &str,
+        git_author: &str,
+        repo_id: Option<&str>,
     ) -> Result<i64, DatabaseError> {
         let now = Utc::now().to_rfc3339();
         let conn = self.conn();
         conn.execute(
-            "INSERT INTO commit_map (svn_rev, git_sha, direction, synced_at, svn_author, git_author)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![svn_rev, git_sha, direction, now, svn_author, git_author],
+            "INSERT INTO commit_map (svn_rev, git_sha, direction, synced_at, svn_author, git_author, repo_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![svn_rev, git_sha, direction, now, svn_author, git_author, repo_id],
         )?;
         let id = conn.last_insert_rowid();
         debug!(id, svn_rev, git_sha, direction, "inserted commit_map entry");
@@ -129,6 +157,100 @@ impl Database {
         }
     }
 
+    /// Return the most recent `(svn_rev, git_sha)` pairing.
+    ///
+    /// Pairings for `repo_id` are preferred; legacy rows without a repository
+    /// are used only when the repository has none of its own. Both sides held
+    /// the same tree at this point, so it serves as the merge base for
+    /// conflicts.
+    pub fn get_last_commit_pair(
+        &self,
+        repo_id: Option<&str>,
+    ) -> Result<Option<(i64, String)>, DatabaseError> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT svn_rev, git_sha FROM commit_map
+             WHERE repo_id = ?1 OR repo_id IS NULL
+             ORDER BY repo_id IS NULL, id DESC LIMIT 1",
+        )?;
+        let mut rows = stmt.query_map(params![repo_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        match rows.next() {
+            Some(Ok(pair)) => Ok(Some(pair)),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Return the pairing with the highest SVN revision not after `svn_rev`,
+    /// i.e. the Git commit holding the SVN tree as of that revision.
+    ///
+    /// Repository scoping follows [`get_last_commit_pair`](Self::get_last_commit_pair).
+    pub fn get_commit_pair_at_or_before(
+        &self,
+        svn_rev: i64,
+        repo_id: Option<&str>,
+    ) -> Result<Option<(i64, String)>, DatabaseError> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT svn_rev, git_sha FROM commit_map
+             WHERE svn_rev <= ?1 AND (repo_id = ?2 OR repo_id IS NULL)
+             ORDER BY repo_id IS NULL, svn_rev DESC, id DESC LIMIT 1",
+        )?;
+        let mut rows = stmt.query_map(params![svn_rev, repo_id], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+        match rows.next() {
+            Some(Ok(pair)) => Ok(Some(pair)),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Look up an SVN revision by Git SHA within one repository.
+    ///
+    /// Repository scoping follows [`get_last_commit_pair`](Self::get_last_commit_pair).
+    pub fn get_svn_rev_for_git_sha_in_repo(
+        &self,
+        git_sha: &str,
+        repo_id: Option<&str>,
+    ) -> Result<Option<i64>, DatabaseError> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT svn_rev FROM commit_map
+             WHERE git_sha = ?1 AND (repo_id = ?2 OR repo_id IS NULL)
+             ORDER BY repo_id IS NULL, id DESC LIMIT 1",
+        )?;
+        let mut rows = stmt.query_map(params![git_sha, repo_id], |row| row.get(0))?;
+        match rows.next() {
+            Some(Ok(rev)) => Ok(Some(rev)),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Find a Git SHA among the repo IDs starting with `repo_prefix` (e.g.
+    /// the branch pairs of one repository), returning the repo ID and SVN
+    /// revision of the newest match.
+    pub fn find_svn_rev_for_git_sha_by_repo_prefix(
+        &self,
+        git_sha: &str,
+        repo_prefix: &str,
+    ) -> Result<Option<(String, i64)>, DatabaseError> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT repo_id, svn_rev FROM commit_map
+             WHERE git_sha = ?1 AND substr(repo_id, 1, length(?2)) = ?2
+             ORDER BY id DESC LIMIT 1",
+        )?;
+        let mut rows =
+            stmt.query_map(params![git_sha, repo_prefix], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        match rows.next() {
+            Some(Ok(found)) => Ok(Some(found)),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(None),
+        }
+    }
+
     /// Return the most recent N commit-map entries ordered by synced_at desc.
     pub fn list_commit_map(&self, limit: u32) -> Result<Vec<CommitMapEntry>, DatabaseError> {
         let conn = self.conn();
@@ -175,6 +297,54 @@ impl Database {
         Ok(exists)
     }
 
+    // -- tag_map ------------------------------------------------------------
+
+    /// Record a tag that exists on both sides.
+    pub fn insert_tag_map(
+        &self,
+        svn_tag: &str,
+        git_tag: &str,
+        svn_rev: i64,
+        git_sha: &str,
+        direction: &str,
+        repo_id: Option<&str>,
+    ) -> Result<i64, DatabaseError> {
+        let now = Utc::now().to_rfc3339();
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO tag_map (svn_tag, git_tag, svn_rev, git_sha, direction, synced_at, repo_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![svn_tag, git_tag, svn_rev, git_sha, direction, now, repo_id],
+        )?;
+        let id = conn.last_insert_rowid();
+        debug!(id, svn_tag, git_tag, direction, "inserted tag_map entry");
+        Ok(id)
+    }
+
+    /// List the synced tags of one repository (`None` = the global engine).
+    pub fn list_tag_map(&self, repo_id: Option<&str>) -> Result<Vec<TagMapEntry>, DatabaseError> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, svn_tag, git_tag, svn_rev, git_sha, direction, synced_at, repo_id
+             FROM tag_map WHERE repo_id IS ?1 ORDER BY id",
+        )?;
+        let entries = stmt
+            .query_map(params![repo_id], |row| {
+                Ok(TagMapEntry {
+                    id: row.get(0)?,
+                    svn_tag: row.get(1)?,
+                    git_tag: row.get(2)?,
+                    svn_rev: row.get(3)?,
+                    git_sha: row.get(4)?,
+                    direction: row.get(5)?,
+                    synced_at: row.get(6)?,
+                    repo_id: row.get(7)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
     // -- sync_state ---------------------------------------------------------
 
     /// Record the start of a new sync cycle.
@@ -283,8 +453,8 @@ impl Database {
         let conn = self.conn();
         conn.execute(
             "INSERT INTO conflicts (id, file_path, conflict_type, svn_content, git_content,
-             base_content, svn_rev, git_sha, status, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+             base_content, svn_rev, git_sha, status, created_at, repo_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 conflict.id,
                 conflict.file_path,
@@ -295,7 +465,8 @@ impl Database {
                 conflict.svn_revision,
                 conflict.git_hash,
                 conflict.status,
-                now
+                now,
+                conflict.repo_id
             ],
         )?;
         debug!(id = %conflict.id, file_path = %conflict.file_path, "inserted conflict");
@@ -307,7 +478,8 @@ impl Database {
         let conn = self.conn();
         conn.query_row(
             "SELECT id, file_path, conflict_type, svn_content, git_content, base_content,
-             svn_rev, git_sha, status, resolution, resolved_by, created_at, resolved_at
+             svn_rev, git_sha, status, resolution, resolved_by, created_at, resolved_at,
+             resolved_content, repo_id
              FROM conflicts WHERE id = ?1",
             params![id],
             |row| {
@@ -325,6 +497,8 @@ impl Database {
                     resolved_by: row.get(10)?,
                     created_at: row.get(11)?,
                     resolved_at: row.get(12)?,
+                    resolved_content: row.get(13)?,
+                    repo_id: row.get(14)?,
                 })
             },
         )
@@ -356,14 +530,16 @@ impl Database {
         let (sql, bound_params): (String, Vec<Box<dyn rusqlite::types::ToSql>>) = match status {
             Some(s) => (
                 "SELECT id, file_path, conflict_type, svn_content, git_content, base_content,
-                 svn_rev, git_sha, status, resolution, resolved_by, created_at, resolved_at
+                 svn_rev, git_sha, status, resolution, resolved_by, created_at, resolved_at,
+                 resolved_content, repo_id
                  FROM conflicts WHERE status = ?1 ORDER BY created_at DESC LIMIT ?2"
                     .to_string(),
                 vec![Box::new(s.to_string()), Box::new(limit)],
             ),
             None => (
                 "SELECT id, file_path, conflict_type, svn_content, git_content, base_content,
-                 svn_rev, git_sha, status, resolution, resolved_by, created_at, resolved_at
+                 svn_rev, git_sha, status, resolution, resolved_by, created_at, resolved_at,
+                 resolved_content, repo_id
                  FROM conflicts ORDER BY created_at DESC LIMIT ?1"
                     .to_string(),
                 vec![Box::new(limit)],
@@ -389,6 +565,8 @@ impl Database {
                     resolved_by: row.get(10)?,
                     created_at: row.get(11)?,
                     resolved_at: row.get(12)?,
+                    resolved_content: row.get(13)?,
+                    repo_id: row.get(14)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -611,6 +789,28 @@ impl Database {
         Ok(count)
     }
 
+    /// List the file paths of active (non-resolved, non-deferred) conflicts.
+    ///
+    /// Only conflicts recorded for exactly `repo_id` are returned (`None`
+    /// selects conflicts without a repository). The sync engine uses this to
+    /// hold back changes to conflicted paths until resolved.
+    pub fn list_active_conflict_paths(
+        &self,
+        repo_id: Option<&str>,
+    ) -> Result<Vec<String>, DatabaseError> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT file_path FROM conflicts
+             WHERE status NOT IN ('resolved', 'deferred')
+               AND repo_id IS ?1
+             ORDER BY file_path",
+        )?;
+        let paths = stmt
+            .query_map(params![repo_id], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(paths)
+    }
+
     /// Count active (unresolved) conflicts for a specific repository.
     pub fn count_active_conflicts_for_repo(&self, repo_id: &str) -> Result<i64, DatabaseError> {
         let conn = self.conn();
@@ -684,6 +884,7 @@ impl Database {
     }
 
     /// Insert an audit log entry tagged with an optional `repo_id`.
+    #[allow(clippy::too_many_arguments)]
     pub fn insert_audit_log_with_repo(
         &self,
         action: &str,
@@ -1197,6 +1398,16 @@ impl Database {
     pub fn persist_import_progress(
         &self,
         progress: &crate::import::ImportProgress,
+    ) -> Result<(), DatabaseError> {
+        self.persist_import_progress_for(None, progress)
+    }
+
+    /// Persist the progress of the import of one repository to its own row,
+    /// or to the singleton row when `repo_id` is `None`.
+    pub fn persist_import_progress_for(
+        &self,
+        repo_id: Option<&str>,
+        progress: &crate::import::ImportProgress,
     ) -> Result<(), DatabaseError> {
         let phase_str = match progress.phase {
             crate::import::ImportPhase::Idle => "idle",
@@ -1211,12 +1422,26 @@ impl Database {
         let errors_json = serde_json::to_string(&progress.errors).unwrap_or_else(|_| "[]".into());
         let now = Utc::now().to_rfc3339();
         let conn = self.conn();
+        // The singleton row has id 1; repository rows take ids above it so
+        // its upsert never replaces them.
+        let id: i64 = match repo_id {
+            None => 1,
+            Some(rid) => conn.query_row(
+                "SELECT COALESCE(
+                     (SELECT id FROM import_progress WHERE repo_id = ?1),
+                     MAX(COALESCE((SELECT MAX(id) FROM import_progress), 0), 1) + 1)",
+                params![rid],
+                |row| row.get(0),
+            )?,
+        };
         conn.execute(
             "INSERT OR REPLACE INTO import_progress
-             (id, phase, current_rev, total_revs, commits_created, batches_pushed,
+             (id, repo_id, phase, current_rev, total_revs, commits_created, batches_pushed,
               lfs_unique_count, files_skipped, errors_json, started_at, completed_at, updated_at)
-             VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
+                id,
+                repo_id,
                 phase_str,
                 progress.current_rev,
                 progress.total_revs,
@@ -1240,14 +1465,23 @@ impl Database {
     /// data is loaded.
     pub fn load_import_progress(
         &self,
+    ) -> Result<Option<crate::import::ImportProgress>, DatabaseError> {
+        self.load_import_progress_for(None)
+    }
+
+    /// Load the persisted progress of the import of one repository, or of
+    /// the singleton row when `repo_id` is `None`.
+    pub fn load_import_progress_for(
+        &self,
+        repo_id: Option<&str>,
     ) -> Result<Option<crate::import::ImportProgress>, DatabaseError> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
             "SELECT phase, current_rev, total_revs, commits_created, batches_pushed,
                     lfs_unique_count, files_skipped, errors_json, started_at, completed_at
-             FROM import_progress WHERE id = 1",
+             FROM import_progress WHERE (?1 IS NULL AND id = 1) OR repo_id = ?1",
         )?;
-        let mut rows = stmt.query_map([], |row| {
+        let mut rows = stmt.query_map(params![repo_id], |row| {
             let phase_str: String = row.get(0)?;
             let current_rev: i64 = row.get(1)?;
             let total_revs: i64 = row.get(2)?;
@@ -1298,17 +1532,19 @@ impl Database {
                 };
                 let errors: Vec<String> =
                     serde_json::from_str(&errors_json).unwrap_or_default();
-                let mut progress = crate::import::ImportProgress::default();
-                progress.phase = phase;
-                progress.current_rev = current_rev;
-                progress.total_revs = total_revs;
-                progress.commits_created = commits_created as u64;
-                progress.batches_pushed = batches_pushed as u64;
-                progress.lfs_unique_count = lfs_unique_count as u64;
-                progress.files_skipped = files_skipped as u64;
-                progress.errors = errors;
-                progress.started_at = started_at;
-                progress.completed_at = completed_at;
+                let progress = crate::import::ImportProgress {
+                    phase,
+                    current_rev,
+                    total_revs,
+                    commits_created: commits_created as u64,
+                    batches_pushed: batches_pushed as u64,
+                    lfs_unique_count: lfs_unique_count as u64,
+                    files_skipped: files_skipped as u64,
+                    errors,
+                    started_at,
+                    completed_at,
+                    ..Default::default()
+                };
                 Ok(Some(progress))
             }
             Some(Err(e)) => Err(e.into()),
@@ -1402,6 +1638,33 @@ impl Database {
         }
     }
 
+    /// Get the user whose email matches `email` (case-insensitive).
+    pub fn get_user_by_email(&self, email: &str) -> Result<Option<models::User>, DatabaseError> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, username, display_name, email, password_hash, role, enabled, created_at, updated_at
+             FROM users WHERE email = ?1 COLLATE NOCASE AND email <> '' ORDER BY created_at LIMIT 1",
+        )?;
+        let mut rows = stmt.query_map(params![email], |row| {
+            Ok(models::User {
+                id: row.get(0)?,
+                username: row.get(1)?,
+                display_name: row.get(2)?,
+                email: row.get(3)?,
+                password_hash: row.get(4)?,
+                role: row.get(5)?,
+                enabled: row.get::<_, i32>(6)? != 0,
+                created_at: row.get(7)?,
+                updated_at: row.get(8)?,
+            })
+        })?;
+        match rows.next() {
+            Some(Ok(user)) => Ok(Some(user)),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(None),
+        }
+    }
+
     /// List all users.
     pub fn list_users(&self) -> Result<Vec<models::User>, DatabaseError> {
         let conn = self.conn();
@@ -2053,6 +2316,7 @@ impl Database {
         let conn = self.conn();
         conn.execute_batch(
             "DELETE FROM commit_map;
+             DELETE FROM tag_map;
              DELETE FROM sync_records;
              DELETE FROM audit_log;
              DELETE FROM conflicts;
@@ -2066,6 +2330,16 @@ impl Database {
         Ok(())
     }
 
+    /// Clear the `commit_map` rows and the import progress of one
+    /// repository, so that its next import starts from scratch.
+    pub fn clear_repo_import_data(&self, repo_id: &str) -> Result<(), DatabaseError> {
+        let conn = self.conn();
+        conn.execute("DELETE FROM commit_map WHERE repo_id = ?1", params![repo_id])?;
+        conn.execute("DELETE FROM import_progress WHERE repo_id = ?1", params![repo_id])?;
+        debug!(repo_id, "cleared repository import data");
+        Ok(())
+    }
+
     // -- maintenance / retention -----------------------------------------------
 
     /// Run periodic maintenance: prune old audit log, sync records, and commit map entries.
@@ -2213,6 +2487,129 @@ mod tests {
         assert!(!db.is_svn_rev_synced(999).unwrap());
     }
 
+    #[test]
+    fn test_get_last_commit_pair() {
+        let db = setup_db();
+        assert!(db.get_last_commit_pair(None).unwrap().is_none());
+
+        db.insert_commit_map(5, "legacy", "svn_to_git", "a", "A <a@x>").unwrap();
+        db.insert_commit_map_with_repo(10, "aaa", "svn_to_git", "a", "A <a@x>", Some("repo-1"))
+            .unwrap();
+        db.insert_commit_map_with_repo(20, "bbb", "git_to_svn", "b", "B <b@x>", Some("repo-2"))
+            .unwrap();
+        db.insert_commit_map(30, "newer-legacy", "svn_to_git", "a", "A <a@x>").unwrap();
+
+        // A repository's own pairings win over newer legacy rows.
+        assert_eq!(
+            db.get_last_commit_pair(Some("repo-1")).unwrap(),
+            Some((10, "aaa".to_string()))
+        );
+        assert_eq!(
+            db.get_last_commit_pair(Some("repo-3")).unwrap(),
+            Some((30, "newer-legacy".to_string()))
+        );
+        assert_eq!(
+            db.get_last_commit_pair(None).unwrap(),
+            Some((30, "newer-legacy".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_commit_pair_lookups_by_revision_and_sha() {
+        let db = setup_db();
+        db.insert_commit_map(5, "t5", "svn_to_git", "a", "A <a@x>").unwrap();
+        db.insert_commit_map(9, "t9", "svn_to_git", "a", "A <a@x>").unwrap();
+        db.insert_commit_map_with_repo(12, "t9", "svn_to_git", "a", "A <a@x>", Some("branch"))
+            .unwrap();
+
+        assert_eq!(
+            db.get_commit_pair_at_or_before(8, None).unwrap(),
+            Some((5, "t5".to_string()))
+        );
+        assert_eq!(
+            db.get_commit_pair_at_or_before(100, None).unwrap(),
+            Some((9, "t9".to_string()))
+        );
+        assert_eq!(db.get_commit_pair_at_or_before(4, None).unwrap(), None);
+
+        assert_eq!(db.get_svn_rev_for_git_sha_in_repo("t9", None).unwrap(), Some(9));
+        assert_eq!(
+            db.get_svn_rev_for_git_sha_in_repo("t9", Some("branch")).unwrap(),
+            Some(12)
+        );
+        assert_eq!(db.get_svn_rev_for_git_sha_in_repo("nope", None).unwrap(), None);
+
+        assert_eq!(
+            db.find_svn_rev_for_git_sha_by_repo_prefix("t9", "bra").unwrap(),
+            Some(("branch".to_string(), 12))
+        );
+        assert_eq!(db.find_svn_rev_for_git_sha_by_repo_prefix("t5", "bra").unwrap(), None);
+    }
+
+    #[test]
+    fn test_tag_map_is_scoped_by_repo() {
+        let db = setup_db();
+        db.insert_tag_map("1.0", "v1.0", 7, "abc", "svn_to_git", None).unwrap();
+        db.insert_tag_map("2.0", "v2.0", 9, "def", "git_to_svn", Some("r1")).unwrap();
+
+        let global = db.list_tag_map(None).unwrap();
+        assert_eq!(global.len(), 1);
+        assert_eq!(global[0].git_tag, "v1.0");
+        assert_eq!(global[0].svn_rev, 7);
+
+        let scoped = db.list_tag_map(Some("r1")).unwrap();
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].svn_tag, "2.0");
+        assert_eq!(scoped[0].direction, "git_to_svn");
+        assert!(db.list_tag_map(Some("other")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_progress_is_scoped_by_repo() {
+        use crate::import::{ImportPhase, ImportProgress};
+
+        let db = setup_db();
+        let progress = |phase, commits_created| ImportProgress {
+            phase,
+            commits_created,
+            ..Default::default()
+        };
+        db.persist_import_progress_for(Some("r1"), &progress(ImportPhase::Importing, 5))
+            .unwrap();
+        assert!(db.load_import_progress().unwrap().is_none());
+        db.persist_import_progress(&progress(ImportPhase::Completed, 9)).unwrap();
+        db.persist_import_progress_for(Some("r1"), &progress(ImportPhase::Cancelled, 7))
+            .unwrap();
+
+        let repo = db.load_import_progress_for(Some("r1")).unwrap().unwrap();
+        assert_eq!((repo.phase, repo.commits_created), (ImportPhase::Cancelled, 7));
+        let global = db.load_import_progress().unwrap().unwrap();
+        assert_eq!((global.phase, global.commits_created), (ImportPhase::Completed, 9));
+        assert!(db.load_import_progress_for(Some("r2")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_get_user_by_email_ignores_case() {
+        let db = setup_db();
+        let now = Utc::now().to_rfc3339();
+        db.insert_user(&models::User {
+            id: "u1".into(),
+            username: "alice".into(),
+            display_name: "Alice".into(),
+            email: "Alice@Example.com".into(),
+            password_hash: String::new(),
+            role: "user".into(),
+            enabled: true,
+            created_at: now.clone(),
+            updated_at: now,
+        })
+        .unwrap();
+
+        let user = db.get_user_by_email("alice@example.com").unwrap().unwrap();
+        assert_eq!(user.username, "alice");
+        assert!(db.get_user_by_email("bob@example.com").unwrap().is_none());
+    }
+
     #[test]
     fn test_sync_state() {
         let db = setup_db();
@@ -2246,6 +2643,37 @@ mod tests {
         assert_eq!(resolved.status, "resolved");
     }
 
+    #[test]
+    fn test_list_active_conflict_paths() {
+        let db = setup_db();
+        let mut mine = models::Conflict::new("a.txt".into());
+        mine.repo_id = Some("repo-1".into());
+        db.insert_conflict(&mine).unwrap();
+        let mut other = models::Conflict::new("b.txt".into());
+        other.repo_id = Some("repo-2".into());
+        db.insert_conflict(&other).unwrap();
+        let legacy = db
+            .insert_conflict_entry("c.txt", "content", None, None, None, None, None)
+            .unwrap();
+
+        assert_eq!(
+            db.list_active_conflict_paths(Some("repo-1")).unwrap(),
+            vec!["a.txt".to_string()]
+        );
+        assert_eq!(
+            db.list_active_conflict_paths(None).unwrap(),
+            vec!["c.txt".to_string()]
+        );
+
+        db.resolve_conflict(&legacy, "resolved", "accept_git", "admin")
+            .unwrap();
+        assert!(db.list_active_conflict_paths(None).unwrap().is_empty());
+        assert_eq!(
+            db.get_conflict_entry(&mine.id).unwrap().repo_id.as_deref(),
+            Some("repo-1")
+        );
+    }
+
     #[test]
     fn test_watermark_crud() {
         let db = setup_db();
@@ -277,6 +277,23 @@ static MIGRATIONS: &[(u32, &str, &str)] = &[
         ALTER TABLE conflicts ADD COLUMN resolved_content TEXT;
         "#,
     ),
+    (
+        11,
+        "tag map for tag synchronization",
+        r#"
+        CREATE TABLE IF NOT EXISTS tag_map (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            repo_id TEXT,
+            svn_tag TEXT NOT NULL,
+            git_tag TEXT NOT NULL,
+            svn_rev INTEGER NOT NULL,
+            git_sha TEXT NOT NULL,
+            direction TEXT NOT NULL CHECK(direction IN ('svn_to_git', 'git_to_svn')),
+            synced_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_tag_map_repo_git ON tag_map(repo_id, git_tag);
+        "#,
+    ),
 ];
 
 /// Run all pending migrations against `conn`.
@@ -325,7 +342,7 @@ mod tests {
         let conn = Connection::open_in_memory().unwrap();
         run_migrations(&conn).unwrap();
         run_migrations(&conn).unwrap();
-        assert_eq!(get_schema_version(&conn).unwrap(), 10);
+        assert_eq!(get_schema_version(&conn).unwrap(), 11);
     }
 
     #[test]
@@ -353,5 +370,6 @@ mod tests {
         assert!(tables.contains(&"pr_sync_log".to_string()));
         assert!(tables.contains(&"import_progress".to_string()));
         assert!(tables.contains(&"repositories".to_string()));
+        assert!(tables.contains(&"tag_map".to_string()));
     }
 }
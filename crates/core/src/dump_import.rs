@@ -0,0 +1,1083 @@
+//! Full-history import from an SVN dump file.
+//!
+//! [`run_full_import`](crate::import::run_full_import) needs a live SVN
+//! server and runs one export per revision. [`run_dump_import`] instead reads
+//! an `svnadmin dump` / `svnrdump dump` stream (format 2 or 3) from a file or
+//! stdin and rebuilds every revision's tree locally, so large or unreachable
+//! repositories can be imported offline.
+//!
+//! [`DumpReplayer`] keeps each revision's tree in memory as a copy-on-write
+//! structure (copies from older revisions only share pointers) with the file
+//! texts spooled to a temporary content-addressed store. After each revision
+//! it brings the paths that changed under the import root into the Git
+//! working tree, applying the [`FilePolicy`] (including LFS), `svn:special`
+//! symlinks, `svn:executable` and `svn:eol-style`. Texts in a dump are stored
+//! with keywords unexpanded, so nothing needs collapsing.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{info, warn};
+
+use crate::db::Database;
+use crate::file_policy::{FilePolicy, FilePolicyDecision};
+use crate::git::GitClient;
+use crate::identity::mapper::{GitIdentity, IdentityMapper};
+use crate::import::{record_watermarks, CopyStats, ImportConfig, ImportProgress};
+use crate::svn::dump::{DumpNode, DumpReader, DumpRecord, DumpRevision, NodeAction, NodeKind};
+use crate::svn::svndiff::apply_svndiff;
+use crate::svn_props;
+
+/// Open a dump stream; `-` reads stdin.
+pub fn open_dump(path: &Path) -> Result<Box<dyn BufRead + Send>> {
+    if path.as_os_str() == "-" {
+        return Ok(Box::new(std::io::BufReader::new(std::io::stdin())));
+    }
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("failed to open dump file: {}", path.display()))?;
+    Ok(Box::new(std::io::BufReader::new(file)))
+}
+
+// ---------------------------------------------------------------------------
+// Revision trees
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+enum TreeNode {
+    File(Arc<FileNode>),
+    Dir(Arc<DirNode>),
+}
+
+#[derive(Debug, Clone)]
+struct FileNode {
+    /// Key of the text in the [`ContentStore`].
+    content: String,
+    size: u64,
+    props: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct DirNode {
+    children: BTreeMap<String, TreeNode>,
+}
+
+fn components(path: &str) -> Vec<&str> {
+    path.split('/').filter(|c| !c.is_empty()).collect()
+}
+
+fn lookup(root: &Arc<DirNode>, path: &str) -> Option<TreeNode> {
+    let mut node = TreeNode::Dir(root.clone());
+    for name in components(path) {
+        let TreeNode::Dir(dir) = node else {
+            return None;
+        };
+        node = dir.children.get(name)?.clone();
+    }
+    Some(node)
+}
+
+/// Put `node` at `path`, creating missing parent directories.
+fn insert(dir: &mut Arc<DirNode>, parts: &[&str], node: TreeNode) {
+    let Some((name, rest)) = parts.split_first() else {
+        if let TreeNode::Dir(new_root) = node {
+            *dir = new_root;
+        }
+        return;
+    };
+    let dir = Arc::make_mut(dir);
+    if rest.is_empty() {
+        dir.children.insert(name.to_string(), node);
+        return;
+    }
+    let child = dir
+        .children
+        .entry(name.to_string())
+        .or_insert_with(|| TreeNode::Dir(Arc::default()));
+    if let TreeNode::File(_) = child {
+        *child = TreeNode::Dir(Arc::default());
+    }
+    if let TreeNode::Dir(child) = child {
+        insert(child, rest, node);
+    }
+}
+
+fn remove(dir: &mut Arc<DirNode>, parts: &[&str]) {
+    let Some((name, rest)) = parts.split_first() else {
+        *dir = Arc::default();
+        return;
+    };
+    if !dir.children.contains_key(*name) {
+        return;
+    }
+    let dir = Arc::make_mut(dir);
+    if rest.is_empty() {
+        dir.children.remove(*name);
+    } else if let Some(TreeNode::Dir(child)) = dir.children.get_mut(*name) {
+        remove(child, rest);
+    }
+}
+
+/// File texts spooled to disk, keyed by their SHA-256.
+struct ContentStore {
+    dir: tempfile::TempDir,
+}
+
+impl ContentStore {
+    fn new() -> Result<Self> {
+        let dir = tempfile::tempdir().context("failed to create dump content store")?;
+        Ok(Self { dir })
+    }
+
+    fn put(&self, content: &[u8]) -> Result<String> {
+        let key = hex::encode(Sha256::digest(content));
+        let path = self.dir.path().join(&key);
+        if !path.exists() {
+            std::fs::write(&path, content)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
+        Ok(key)
+    }
+
+    fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.dir.path().join(key);
+        std::fs::read(&path).with_context(|| format!("failed to read {}", path.display()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Replay
+// ---------------------------------------------------------------------------
+
+/// One revision of the dump, applied to the working tree.
+#[derive(Debug)]
+pub struct ReplayedRevision {
+    pub revision: DumpRevision,
+    /// Whether any path under the import root changed.
+    pub changed: bool,
+    pub stats: CopyStats,
+}
+
+/// Replays a dump stream into a Git working tree, one revision at a time.
+pub struct DumpReplayer<'a, R> {
+    reader: DumpReader<R>,
+    /// Repository path mapped to the root of the Git tree (e.g. `trunk`);
+    /// empty for the whole repository.
+    root: String,
+    work_dir: PathBuf,
+    policy: &'a FilePolicy,
+    sync_executable_bit: bool,
+    normalize_line_endings: bool,
+    store: ContentStore,
+    head: Arc<DirNode>,
+    /// Tree after each revision, for copies from history.
+    snapshots: BTreeMap<i64, Arc<DirNode>>,
+    /// Revision header read ahead of its nodes.
+    pending: Option<DumpRevision>,
+    /// Paths (relative to `root`) changed by the current revision.
+    touched: BTreeSet<String>,
+    /// `svn:eol-style` of the files in the working tree.
+    eol_styles: BTreeMap<String, String>,
+    eol_dirty: bool,
+}
+
+impl<'a, R: BufRead> DumpReplayer<'a, R> {
+    pub fn new(
+        reader: R,
+        root: &str,
+        work_dir: &Path,
+        policy: &'a FilePolicy,
+    ) -> Result<Self> {
+        Ok(Self {
+            reader: DumpReader::new(reader),
+            root: root.trim_matches('/').to_string(),
+            work_dir: work_dir.to_path_buf(),
+            policy,
+            sync_executable_bit: false,
+            normalize_line_endings: false,
+            store: ContentStore::new()?,
+            head: Arc::default(),
+            snapshots: BTreeMap::new(),
+            pending: None,
+            touched: BTreeSet::new(),
+            eol_styles: BTreeMap::new(),
+            eol_dirty: false,
+        })
+    }
+
+    /// Give files with `svn:executable` the executable mode.
+    pub fn sync_executable_bit(mut self, enabled: bool) -> Self {
+        self.sync_executable_bit = enabled;
+        self
+    }
+
+    /// Mirror `svn:eol-style` in the root `.gitattributes`.
+    pub fn normalize_line_endings(mut self, enabled: bool) -> Self {
+        self.normalize_line_endings = enabled;
+        self
+    }
+
+    /// Read and apply the next revision, leaving its changes in the working
+    /// tree of `git` (deletions staged) for the caller to commit. `None` at
+    /// the end of the dump.
+    pub fn next_revision(&mut self, git: &GitClient) -> Result<Option<ReplayedRevision>> {
+        let revision = match self.pending.take() {
+            Some(revision) => revision,
+            None => match self.reader.next_record()? {
+                None => return Ok(None),
+                Some(DumpRecord::Revision(revision)) => revision,
+                Some(DumpRecord::Node(node)) => {
+                    bail!("dump has a node record before any revision: {}", node.path)
+                }
+            },
+        };
+        while let Some(record) = self.reader.next_record()? {
+            match record {
+                DumpRecord::Revision(next) => {
+                    self.pending = Some(next);
+                    break;
+                }
+                DumpRecord::Node(node) => {
+                    self.apply_node(&node).with_context(|| {
+                        format!("r{}: failed to apply {}", revision.revision, node.path)
+                    })?
+                }
+            }
+        }
+        self.snapshots.insert(revision.revision, self.head.clone());
+
+        let mut stats = CopyStats::default();
+        let changed = !self.touched.is_empty();
+        self.update_work_tree(git, &mut stats)
+            .with_context(|| format!("r{}: failed to update the working tree", revision.revision))?;
+        Ok(Some(ReplayedRevision {
+            revision,
+            changed,
+            stats,
+        }))
+    }
+
+    /// Apply one node record to the in-memory tree.
+    fn apply_node(&mut self, node: &DumpNode) -> Result<()> {
+        let parts = components(&node.path);
+        if matches!(node.action, NodeAction::Delete | NodeAction::Replace) {
+            remove(&mut self.head, &parts);
+        }
+        let new_node = match node.action {
+            NodeAction::Delete => None,
+            NodeAction::Change => match lookup(&self.head, &node.path) {
+                Some(TreeNode::File(file)) => Some(self.apply_file(Some(&file), node)?),
+                // Directory properties do not reach the Git tree.
+                Some(TreeNode::Dir(_)) => return Ok(()),
+                None => bail!("change to a path that does not exist"),
+            },
+            NodeAction::Add | NodeAction::Replace => {
+                let base = match &node.copy_from {
+                    Some((from, rev)) => {
+                        let source = self.snapshots.get(rev).and_then(|tree| lookup(tree, from));
+                        Some(source.with_context(|| {
+                            format!("copy source {}@{} not in the dump", from, rev)
+                        })?)
+                    }
+                    None => None,
+                };
+                match (node.kind, base) {
+                    (Some(NodeKind::Dir), None) => Some(TreeNode::Dir(Arc::default())),
+                    (Some(NodeKind::File), None) => Some(self.apply_file(None, node)?),
+                    (_, Some(TreeNode::Dir(dir))) => Some(TreeNode::Dir(dir)),
+                    (_, Some(TreeNode::File(file))) => Some(self.apply_file(Some(&file), node)?),
+                    (None, None) => bail!("added node without a kind"),
+                }
+            }
+        };
+        if let Some(new_node) = new_node {
+            insert(&mut self.head, &parts, new_node);
+        }
+        self.touch(&node.path);
+        Ok(())
+    }
+
+    /// New file node from `base` (the previous or copied file) and the
+    /// node's properties and text.
+    fn apply_file(&self, base: Option<&FileNode>, node: &DumpNode) -> Result<TreeNode> {
+        let mut props = base.map(|f| f.props.clone()).unwrap_or_default();
+        if let Some(node_props) = &node.props {
+            node_props.apply_to(&mut props);
+        }
+        let (content, size) = match &node.text {
+            Some(text) => {
+                let data = if text.delta {
+                    let source = match base {
+                        Some(base) => self.store.get(&base.content)?,
+                        None => Vec::new(),
+                    };
+                    apply_svndiff(&source, &text.content)?
+                } else {
+                    text.content.clone()
+                };
+                (self.store.put(&data)?, data.len() as u64)
+            }
+            None => match base {
+                Some(base) => (base.content.clone(), base.size),
+                None => (self.store.put(&[])?, 0),
+            },
+        };
+        Ok(TreeNode::File(Arc::new(FileNode {
+            content,
+            size,
+            props,
+        })))
+    }
+
+    /// Record `path` (a repository path) as changed if it affects the root.
+    fn touch(&mut self, path: &str) {
+        let path = path.trim_matches('/');
+        if svn_props::is_under(path, &self.root) || self.root.is_empty() {
+            let rel = path[self.root.len()..].trim_start_matches('/');
+            self.touched.insert(rel.to_string());
+        } else if path.is_empty() || svn_props::is_under(&self.root, path) {
+            // An ancestor of the root was added, replaced or deleted.
+            self.touched.insert(String::new());
+        }
+    }
+
+    fn repo_path(&self, rel: &str) -> String {
+        svn_props::join_path(&self.root, rel)
+    }
+
+    /// Bring every touched path in the working tree up to date.
+    fn update_work_tree(&mut self, git: &GitClient, stats: &mut CopyStats) -> Result<()> {
+        let touched = std::mem::take(&mut self.touched);
+        let mut done = HashSet::new();
+        for rel in &touched {
+            // A path under a directory that was already rewritten is done.
+            let mut ancestor = rel.as_str();
+            let mut covered = false;
+            while !ancestor.is_empty() {
+                ancestor = svn_props::parent_dir(ancestor);
+                if done.contains(ancestor) {
+                    covered = true;
+                    break;
+                }
+            }
+            if covered {
+                continue;
+            }
+            let node = lookup(&self.head, &self.repo_path(rel));
+            // A missing root still owns the Git tree: empty it.
+            let node = match node {
+                None if rel.is_empty() => Some(TreeNode::Dir(Arc::default())),
+                node => node,
+            };
+            self.materialize(git, rel, node.as_ref(), stats)?;
+            done.insert(rel.as_str());
+        }
+
+        if self.normalize_line_endings && self.eol_dirty {
+            self.eol_dirty = false;
+            if svn_props::write_eol_entries(&self.work_dir, &self.eol_styles)?
+                == svn_props::FileUpdate::Removed
+            {
+                git.remove_path(svn_props::GITATTRIBUTES)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Make `rel` in the working tree match `node`.
+    fn materialize(
+        &mut self,
+        git: &GitClient,
+        rel: &str,
+        node: Option<&TreeNode>,
+        stats: &mut CopyStats,
+    ) -> Result<()> {
+        let full = self.work_dir.join(rel);
+        match node {
+            None => self.remove_work_path(git, rel)?,
+            Some(TreeNode::File(file)) => self.write_file(git, rel, file, stats)?,
+            Some(TreeNode::Dir(dir)) => {
+                if svn_props::is_file_or_symlink(&full) {
+                    self.remove_work_path(git, rel)?;
+                }
+                std::fs::create_dir_all(&full)
+                    .with_context(|| format!("failed to create directory: {}", full.display()))?;
+                for entry in std::fs::read_dir(&full)? {
+                    let name = entry?.file_name().to_string_lossy().into_owned();
+                    // Root-level dotfiles (`.git`, generated files) belong
+                    // to Git unless SVN has them.
+                    if dir.children.contains_key(&name) || (rel.is_empty() && name.starts_with('.'))
+                    {
+                        continue;
+                    }
+                    self.remove_work_path(git, &svn_props::join_path(rel, &name))?;
+                }
+                for (name, child) in &dir.children {
+                    self.materialize(git, &svn_props::join_path(rel, name), Some(child), stats)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_work_path(&mut self, git: &GitClient, rel: &str) -> Result<()> {
+        if std::fs::symlink_metadata(self.work_dir.join(rel)).is_ok() {
+            git.remove_path(rel)?;
+        }
+        let before = self.eol_styles.len();
+        self.eol_styles.retain(|path, _| !svn_props::is_under(path, rel));
+        self.eol_dirty |= self.eol_styles.len() != before;
+        Ok(())
+    }
+
+    fn write_file(
+        &mut self,
+        git: &GitClient,
+        rel: &str,
+        file: &FileNode,
+        stats: &mut CopyStats,
+    ) -> Result<()> {
+        let full = self.work_dir.join(rel);
+        if full.is_dir() && !svn_props::is_symlink(&full) {
+            self.remove_work_path(git, rel)?;
+        }
+
+        if file.props.contains_key(svn_props::SVN_SPECIAL) {
+            let content = self.store.get(&file.content)?;
+            if let Some(target) = svn_props::parse_special(&content) {
+                let decision = self.policy.evaluate_symlink(rel, &target);
+                if decision.is_blocked() {
+                    warn!(
+                        path = rel,
+                        target = %target,
+                        reason = decision.label(),
+                        "symlink blocked by policy — not copied to Git"
+                    );
+                    self.remove_work_path(git, rel)?;
+                    stats.skipped += 1;
+                    return Ok(());
+                }
+                svn_props::write_symlink(&full, &target)
+                    .with_context(|| format!("failed to create symlink: {}", full.display()))?;
+                self.set_eol_style(rel, None);
+                stats.copied += 1;
+                return Ok(());
+            }
+        }
+
+        match self.policy.evaluate(rel, file.size) {
+            FilePolicyDecision::Allow => {}
+            FilePolicyDecision::LfsTrack { .. } => {
+                let pattern = crate::lfs::pattern_for_path(rel);
+                if let Err(e) = crate::lfs::ensure_lfs_tracked(&self.work_dir, &pattern) {
+                    warn!(
+                        path = rel,
+                        error = %e,
+                        "failed to update .gitattributes for LFS tracking"
+                    );
+                }
+                stats.lfs_tracked += 1;
+            }
+            decision => {
+                warn!(
+                    path = rel,
+                    reason = decision.label(),
+                    "file blocked by policy — not copied to Git"
+                );
+                self.remove_work_path(git, rel)?;
+                stats.skipped += 1;
+                return Ok(());
+            }
+        }
+
+        if let Some(parent) = full.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+        }
+        let content = self.store.get(&file.content)?;
+        svn_props::write_regular_file(&full, &content)
+            .with_context(|| format!("failed to write {}", full.display()))?;
+        if self.sync_executable_bit {
+            let executable = file.props.contains_key(svn_props::SVN_EXECUTABLE);
+            svn_props::set_executable(&full, executable)
+                .with_context(|| format!("failed to set mode of {}", full.display()))?;
+        }
+        self.set_eol_style(rel, file.props.get(svn_props::SVN_EOL_STYLE).cloned());
+        stats.copied += 1;
+        Ok(())
+    }
+
+    fn set_eol_style(&mut self, rel: &str, style: Option<String>) {
+        let changed = match style {
+            Some(style) => self.eol_styles.insert(rel.to_string(), style.clone()) != Some(style),
+            None => self.eol_styles.remove(rel).is_some(),
+        };
+        self.eol_dirty |= changed;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Team-mode import
+// ---------------------------------------------------------------------------
+
+/// Import the history in a dump stream, committing each revision that
+/// changes `root` (e.g. `trunk`; empty for the whole repository).
+///
+/// Identity mapping, commit messages, `commit_map` and the watermarks match
+/// [`run_full_import`](crate::import::run_full_import), so incremental sync
+/// continues after the last revision in the dump. With
+/// `import_config.repo_id` set, `commit_map` rows and watermarks are kept
+/// under that repository's keys. Commits are pushed once at the end; a
+/// cancelled import pushes what it committed and leaves the watermarks at
+/// the last revision it read, so the sync continues from there.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_dump_import(
+    dump: impl BufRead,
+    root: &str,
+    git_client: &Arc<std::sync::Mutex<GitClient>>,
+    identity_mapper: &IdentityMapper,
+    db: &Database,
+    file_policy: &FilePolicy,
+    import_config: &ImportConfig,
+    progress: Arc<RwLock<ImportProgress>>,
+    ws_broadcast: Option<broadcast::Sender<String>>,
+) -> Result<u64> {
+    use crate::import::push_log_line;
+
+    let lfs_available =
+        crate::import::prepare_lfs(git_client, file_policy, &progress, &ws_broadcast).await;
+
+    let repo_path = {
+        let git_guard = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        git_guard.repo_path().to_path_buf()
+    };
+    let mut replayer = DumpReplayer::new(dump, root, &repo_path, file_policy)?
+        .sync_executable_bit(import_config.sync_executable_bit)
+        .normalize_line_endings(import_config.normalize_line_endings);
+    push_log_line(
+        &progress,
+        &ws_broadcast,
+        format!("[info] Reading SVN dump (root: /{})", root.trim_matches('/')),
+    )
+    .await;
+
+    let repo_id = import_config.repo_id.as_deref();
+    let mut count = 0u64;
+    let mut last_rev = None;
+    let mut cancelled = false;
+    loop {
+        if progress.read().await.cancel_requested {
+            cancelled = true;
+            break;
+        }
+
+        let commit_result = {
+            let git = git_client.lock().unwrap_or_else(|p| p.into_inner());
+            let Some(replayed) = replayer.next_revision(&git)? else {
+                break;
+            };
+            let rev = replayed.revision.revision;
+            last_rev = Some(rev);
+            if !replayed.changed || !git.has_changes()? {
+                None
+            } else {
+                let entry = &replayed.revision;
+                let (author_name, author_email) = match identity_mapper.svn_to_git(entry.author()) {
+                    Ok(GitIdentity { name, email }) => (name, email),
+                    Err(_) => (entry.author().to_string(), format!("{}@svn", entry.author())),
+                };
+                let message = format!(
+                    "{}\n\n[reposync] imported from SVN r{}\nSVN-Author: {}\nSVN-Date: {}",
+                    entry.message(),
+                    rev,
+                    entry.author(),
+                    entry.date()
+                );
+                // The git CLI runs the LFS clean filter; libgit2 does not.
+                let result = if lfs_available && replayed.stats.lfs_tracked > 0 {
+                    git.commit_via_cli(
+                        &message,
+                        &author_name,
+                        &author_email,
+                        &import_config.committer_name,
+                        &import_config.committer_email,
+                    )
+                } else {
+                    git.commit(
+                        &message,
+                        &author_name,
+                        &author_email,
+                        &import_config.committer_name,
+                        &import_config.committer_email,
+                    )
+                };
+                Some((replayed, author_name, author_email, result))
+            }
+        }; // git guard dropped here, before any .await
+
+        let Some((replayed, author_name, author_email, result)) = commit_result else {
+            continue;
+        };
+        let rev = replayed.revision.revision;
+        let sha = result.with_context(|| format!("failed to commit r{}", rev))?.to_string();
+        db.insert_commit_map_with_repo(
+            rev,
+            &sha,
+            "svn_to_git",
+            replayed.revision.author(),
+            &format!("{} <{}>", author_name, author_email),
+            repo_id,
+        )
+        .ok();
+        count += 1;
+        {
+            let mut p = progress.write().await;
+            p.current_rev = rev;
+            p.commits_created = count;
+            p.files_skipped += replayed.stats.skipped as u64;
+        }
+        push_log_line(
+            &progress,
+            &ws_broadcast,
+            format!(
+                "[ok] r{} → {} ({}) \"{}\"",
+                rev,
+                &sha[..8.min(sha.len())],
+                author_name,
+                replayed.revision.message().lines().next().unwrap_or("")
+            ),
+        )
+        .await;
+    }
+
+    if count > 0 {
+        push_log_line(
+            &progress,
+            &ws_broadcast,
+            format!("[info] Pushing {} commits to remote...", count),
+        )
+        .await;
+        let git = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        // Like the first batch of a live import, replace whatever the new
+        // remote branch holds.
+        git.push_force(
+            &import_config.remote_name,
+            &import_config.branch,
+            import_config.push_token.as_deref(),
+        )
+        .context("failed to push imported commits")?;
+    }
+
+    let head_sha = {
+        let git_guard = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        git_guard.get_head_sha().ok()
+    };
+    record_watermarks(db, repo_id, last_rev, head_sha.as_deref());
+    let outcome = if cancelled { "cancelled" } else { "completed" };
+    db.insert_audit_log_with_repo(
+        "import_dump",
+        Some("svn_to_git"),
+        last_rev,
+        None,
+        None,
+        Some(&format!(
+            "Dump import {}: {} commits up to r{}",
+            outcome,
+            count,
+            last_rev.unwrap_or(0)
+        )),
+        true,
+        repo_id,
+    )
+    .ok();
+
+    if cancelled {
+        push_log_line(
+            &progress,
+            &ws_broadcast,
+            format!("[warn] Import cancelled by user after r{}", last_rev.unwrap_or(0)),
+        )
+        .await;
+        let mut p = progress.write().await;
+        p.phase = crate::import::ImportPhase::Cancelled;
+        p.completed_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+    info!(count, last_rev, outcome, "dump import finished");
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync_engine::{git_sha_key_for, svn_rev_key_for};
+    use crate::config::IdentityConfig;
+
+    fn props_block(props: &[(&str, &str)]) -> Vec<u8> {
+        let mut block = Vec::new();
+        for (key, value) in props {
+            let item = format!("K {}\n{}\nV {}\n{}\n", key.len(), key, value.len(), value);
+            block.extend_from_slice(item.as_bytes());
+        }
+        block.extend_from_slice(b"PROPS-END\n");
+        block
+    }
+
+    fn record(headers: &str, props: Option<Vec<u8>>, text: Option<&[u8]>) -> Vec<u8> {
+        let mut out = headers.as_bytes().to_vec();
+        let prop_len = props.as_ref().map(Vec::len);
+        let text_len = text.map(<[u8]>::len);
+        if let Some(len) = prop_len {
+            out.extend_from_slice(format!("Prop-content-length: {}\n", len).as_bytes());
+        }
+        if let Some(len) = text_len {
+            out.extend_from_slice(format!("Text-content-length: {}\n", len).as_bytes());
+        }
+        let total = prop_len.unwrap_or(0) + text_len.unwrap_or(0);
+        out.extend_from_slice(format!("Content-length: {}\n\n", total).as_bytes());
+        out.extend(props.unwrap_or_default());
+        out.extend_from_slice(text.unwrap_or_default());
+        out.extend_from_slice(b"\n\n");
+        out
+    }
+
+    fn revision(rev: i64, author: &str, log: &str) -> Vec<u8> {
+        let props = props_block(&[
+            ("svn:author", author),
+            ("svn:date", "2024-01-01T00:00:00.000000Z"),
+            ("svn:log", log),
+        ]);
+        record(&format!("Revision-number: {}\n", rev), Some(props), None)
+    }
+
+    /// Trunk with an executable text file, a symlink and an oversized file,
+    /// then a delta edit, a branch, copies and a replace, and finally the
+    /// deletion of trunk.
+    fn sample_dump() -> Vec<u8> {
+        let mut dump = b"SVN-fs-dump-format-version: 3\n\nUUID: test-uuid\n\n".to_vec();
+        dump.extend(record("Revision-number: 0\n", Some(props_block(&[])), None));
+
+        dump.extend(revision(1, "alice", "Start"));
+        dump.extend(record("Node-path: trunk\nNode-kind: dir\nNode-action: add\n", None, None));
+        dump.extend(record("Node-path: branches\nNode-kind: dir\nNode-action: add\n", None, None));
+        dump.extend(record(
+            "Node-path: trunk/a.txt\nNode-kind: file\nNode-action: add\n",
+            Some(props_block(&[("svn:executable", "*"), ("svn:eol-style", "native")])),
+            Some(b"one\n"),
+        ));
+        dump.extend(record(
+            "Node-path: trunk/link\nNode-kind: file\nNode-action: add\n",
+            Some(props_block(&[("svn:special", "*")])),
+            Some(b"link a.txt"),
+        ));
+        dump.extend(record(
+            "Node-path: trunk/big.bin\nNode-kind: file\nNode-action: add\n",
+            None,
+            Some(b"0123456789abcdefghij"),
+        ));
+
+        dump.extend(revision(2, "bob", "Edit"));
+        let delta = b"SVN\0\x00\x00\x04\x01\x04\x84two\n";
+        dump.extend(record(
+            "Node-path: trunk/a.txt\nNode-kind: file\nNode-action: change\n\
+             Prop-delta: true\nText-delta: true\n",
+            Some(b"D 14\nsvn:executable\nPROPS-END\n".to_vec()),
+            Some(delta),
+        ));
+
+        dump.extend(revision(3, "alice", "Branch"));
+        dump.extend(record(
+            "Node-path: branches/b1\nNode-kind: dir\nNode-action: add\n\
+             Node-copyfrom-rev: 2\nNode-copyfrom-path: trunk\n",
+            None,
+            None,
+        ));
+
+        dump.extend(revision(4, "alice", "Shuffle"));
+        dump.extend(record("Node-path: trunk/sub\nNode-kind: dir\nNode-action: add\n", None, None));
+        dump.extend(record(
+            "Node-path: trunk/sub/a.txt\nNode-kind: file\nNode-action: add\n\
+             Node-copyfrom-rev: 1\nNode-copyfrom-path: trunk/a.txt\n",
+            None,
+            None,
+        ));
+        dump.extend(record("Node-path: trunk/link\nNode-action: delete\n", None, None));
+        dump.extend(record(
+            "Node-path: trunk/a.txt\nNode-kind: file\nNode-action: replace\n",
+            Some(props_block(&[])),
+            Some(b"three\n"),
+        ));
+
+        dump.extend(revision(5, "alice", "Remove trunk"));
+        dump.extend(record("Node-path: trunk\nNode-action: delete\n", None, None));
+        dump
+    }
+
+    fn init_repo(path: &Path) -> GitClient {
+        let mut opts = git2::RepositoryInitOptions::new();
+        opts.initial_head("main");
+        git2::Repository::init_opts(path, &opts).unwrap();
+        GitClient::new(path).unwrap()
+    }
+
+    #[test]
+    fn test_replay_dump() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let git = init_repo(root);
+        let policy = FilePolicy::new(10, vec![]);
+        let dump = sample_dump();
+        let mut replayer = DumpReplayer::new(dump.as_slice(), "trunk", root, &policy)
+            .unwrap()
+            .sync_executable_bit(true)
+            .normalize_line_endings(true);
+        let mut next = |git: &GitClient| {
+            let replayed = replayer.next_revision(git).unwrap().unwrap();
+            if replayed.changed && git.has_changes().unwrap() {
+                git.commit(replayed.revision.message(), "T", "t@t.com", "T", "t@t.com")
+                    .unwrap();
+            }
+            replayed
+        };
+
+        let r0 = next(&git);
+        assert_eq!(r0.revision.revision, 0);
+        assert!(!r0.changed);
+
+        let r1 = next(&git);
+        assert!(r1.changed);
+        assert_eq!((r1.stats.copied, r1.stats.skipped), (2, 1));
+        assert_eq!(std::fs::read(root.join("a.txt")).unwrap(), b"one\n");
+        #[cfg(unix)]
+        {
+            assert!(svn_props::is_executable(&root.join("a.txt")));
+            assert_eq!(std::fs::read_link(root.join("link")).unwrap(), Path::new("a.txt"));
+        }
+        assert!(!root.join("big.bin").exists());
+        let attributes = std::fs::read_to_string(root.join(".gitattributes")).unwrap();
+        assert!(attributes.contains("/a.txt text"));
+
+        let r2 = next(&git);
+        assert_eq!(r2.revision.author(), "bob");
+        assert_eq!(std::fs::read(root.join("a.txt")).unwrap(), b"two\n");
+        #[cfg(unix)]
+        assert!(!svn_props::is_executable(&root.join("a.txt")));
+
+        // A branch outside the import root leaves the Git tree alone.
+        let head = git.get_head_sha().unwrap();
+        assert!(!next(&git).changed);
+        assert_eq!(git.get_head_sha().unwrap(), head);
+
+        next(&git);
+        assert_eq!(std::fs::read(root.join("sub/a.txt")).unwrap(), b"one\n");
+        assert_eq!(std::fs::read(root.join("a.txt")).unwrap(), b"three\n");
+        assert!(!svn_props::is_file_or_symlink(&root.join("link")));
+        let head = git.get_head_sha().unwrap();
+        assert_eq!(git.get_file_content_at_commit(&head, "link").unwrap(), None);
+        assert_eq!(
+            git.get_file_content_at_commit(&head, "sub/a.txt").unwrap().as_deref(),
+            Some(b"one\n".as_slice())
+        );
+
+        // Deleting trunk empties the Git tree but keeps `.git`.
+        next(&git);
+        let head = git.get_head_sha().unwrap();
+        assert_eq!(git.get_file_content_at_commit(&head, "sub/a.txt").unwrap(), None);
+        assert_eq!(git.get_file_content_at_commit(&head, ".gitattributes").unwrap(), None);
+        assert!(root.join(".git").is_dir());
+        assert!(replayer.next_revision(&git).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_replay_rejects_missing_copy_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = init_repo(dir.path());
+        let policy = FilePolicy::new(0, vec![]);
+        let mut dump = revision(7, "alice", "Copy");
+        dump.extend(record(
+            "Node-path: trunk\nNode-kind: dir\nNode-action: add\n\
+             Node-copyfrom-rev: 3\nNode-copyfrom-path: old\n",
+            None,
+            None,
+        ));
+        let mut replayer = DumpReplayer::new(dump.as_slice(), "", dir.path(), &policy).unwrap();
+        let err = replayer.next_revision(&git).unwrap_err();
+        assert!(format!("{:#}", err).contains("copy source old@3 not in the dump"));
+    }
+
+    /// A work tree whose `origin` is a bare repository in `dir`.
+    fn repo_with_remote(dir: &Path) -> (PathBuf, Arc<std::sync::Mutex<GitClient>>) {
+        let remote = dir.join("remote.git");
+        git2::Repository::init_bare(&remote).unwrap();
+        let git = init_repo(&dir.join("work"));
+        git.repo().remote("origin", &remote.to_string_lossy()).unwrap();
+        (remote, Arc::new(std::sync::Mutex::new(git)))
+    }
+
+    fn import_config(repo_id: Option<&str>) -> ImportConfig {
+        ImportConfig {
+            committer_name: "RepoSync".into(),
+            committer_email: "reposync@localhost".into(),
+            remote_name: "origin".into(),
+            branch: "main".into(),
+            push_token: None,
+            message_prefix: None,
+            sync_executable_bit: true,
+            normalize_line_endings: true,
+            collapse_keywords: false,
+            resume: false,
+            layout: None,
+            filter: Default::default(),
+            repo_id: repo_id.map(String::from),
+            per_repo_progress: false,
+        }
+    }
+
+    async fn import(
+        dump: impl BufRead,
+        git: &Arc<std::sync::Mutex<GitClient>>,
+        db: &Database,
+        import_config: &ImportConfig,
+        progress: Arc<RwLock<ImportProgress>>,
+    ) -> u64 {
+        let mapper = IdentityMapper::new(&IdentityConfig::default()).unwrap();
+        let policy = FilePolicy::new(0, vec![]);
+        run_dump_import(dump, "/trunk/", git, &mapper, db, &policy, import_config, progress, None)
+            .await
+            .unwrap()
+    }
+
+    fn remote_head(remote: &Path) -> String {
+        let pushed = git2::Repository::open_bare(remote).unwrap();
+        pushed.refname_to_id("refs/heads/main").unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_run_dump_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let (remote, git) = repo_with_remote(dir.path());
+        let db = Database::in_memory().unwrap();
+        db.initialize().unwrap();
+        let progress = Arc::new(RwLock::new(ImportProgress::default()));
+        let dump = sample_dump();
+        let import_config = import_config(None);
+        let count = import(dump.as_slice(), &git, &db, &import_config, progress.clone()).await;
+
+        // r0 and the branch-only r3 produce no commits.
+        assert_eq!(count, 4);
+        assert_eq!(progress.read().await.commits_created, 4);
+        let map = db.list_commit_map(10).unwrap();
+        let mut revs: Vec<_> = map.iter().map(|e| e.svn_rev).collect();
+        revs.sort();
+        assert_eq!(revs, vec![1, 2, 4, 5]);
+        let bob = map.iter().find(|e| e.svn_rev == 2).unwrap();
+        assert_eq!(bob.git_author, "bob <bob@svn>");
+
+        let head = git.lock().unwrap().get_head_sha().unwrap();
+        assert_eq!(db.get_watermark("svn_rev").unwrap().as_deref(), Some("5"));
+        assert_eq!(db.get_watermark("git_sha").unwrap().as_deref(), Some(head.as_str()));
+        assert_eq!(remote_head(&remote), head);
+    }
+
+    #[tokio::test]
+    async fn test_run_dump_import_for_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_remote, git) = repo_with_remote(dir.path());
+        let db = Database::in_memory().unwrap();
+        db.initialize().unwrap();
+        let repo: crate::models::Repository = serde_json::from_value(serde_json::json!({
+            "id": "r1", "name": "r1", "svn_url": "https://svn.example.com/repo",
+            "svn_branch": "trunk", "svn_username": "svc", "git_provider": "github",
+            "git_api_url": "", "git_repo": "team/app", "git_branch": "main",
+            "sync_mode": "direct", "poll_interval_secs": 60, "lfs_threshold_mb": 0,
+            "auto_merge": true, "enabled": true, "created_by": null,
+            "created_at": "", "updated_at": ""
+        }))
+        .unwrap();
+        db.insert_repository(&repo).unwrap();
+
+        let progress = Arc::new(RwLock::new(ImportProgress::default()));
+        let dump = sample_dump();
+        let import_config = import_config(Some("r1"));
+        assert_eq!(import(dump.as_slice(), &git, &db, &import_config, progress).await, 4);
+
+        // The repository's engine continues from its own keys; the global
+        // engine's watermarks stay untouched.
+        let head = git.lock().unwrap().get_head_sha().unwrap();
+        let map = db.list_commit_map(10).unwrap();
+        assert!(map.iter().all(|e| e.repo_id.as_deref() == Some("r1")));
+        assert_eq!(db.get_state(&svn_rev_key_for(Some("r1"))).unwrap().as_deref(), Some("5"));
+        assert_eq!(db.get_state(&git_sha_key_for(Some("r1"))).unwrap(), Some(head.clone()));
+        assert_eq!(db.get_repo_watermark("r1").unwrap(), (5, head));
+        assert_eq!(db.get_watermark("svn_rev").unwrap(), None);
+    }
+
+    /// Dump stream that requests cancellation once `limit` bytes are read.
+    struct CancelAfter<'a> {
+        inner: &'a [u8],
+        read: usize,
+        limit: usize,
+        progress: Arc<RwLock<ImportProgress>>,
+    }
+
+    impl std::io::Read for CancelAfter<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = std::io::Read::read(&mut self.inner, buf)?;
+            self.consume_bytes(n);
+            Ok(n)
+        }
+    }
+
+    impl BufRead for CancelAfter<'_> {
+        fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+            self.inner.fill_buf()
+        }
+
+        fn consume(&mut self, amt: usize) {
+            self.inner.consume(amt);
+            self.consume_bytes(amt);
+        }
+    }
+
+    impl CancelAfter<'_> {
+        fn consume_bytes(&mut self, n: usize) {
+            self.read += n;
+            if self.read >= self.limit {
+                self.progress.try_write().unwrap().cancel_requested = true;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_dump_import_keeps_what_it_committed() {
+        let dir = tempfile::tempdir().unwrap();
+        let (remote, git) = repo_with_remote(dir.path());
+        let db = Database::in_memory().unwrap();
+        db.initialize().unwrap();
+        let progress = Arc::new(RwLock::new(ImportProgress::default()));
+        let dump = sample_dump();
+        // Cancel while r2 is being read.
+        let needle = b"Revision-number: 2";
+        let limit = dump.windows(needle.len()).position(|w| w == needle).unwrap() + 1;
+        let reader = CancelAfter { inner: &dump, read: 0, limit, progress: progress.clone() };
+        let count = import(reader, &git, &db, &import_config(None), progress.clone()).await;
+
+        assert!((1..4).contains(&count), "committed {} revisions", count);
+        let p = progress.read().await;
+        assert_eq!(p.phase, crate::import::ImportPhase::Cancelled);
+        assert!(p.completed_at.is_some());
+
+        // The commits are pushed and the watermark is the last revision read,
+        // which is also the last one in commit_map.
+        let head = git.lock().unwrap().get_head_sha().unwrap();
+        assert_eq!(remote_head(&remote), head);
+        assert_eq!(db.get_watermark("git_sha").unwrap(), Some(head.clone()));
+        let (last_rev, last_sha) = db.get_last_commit_pair(None).unwrap().unwrap();
+        assert_eq!(last_sha, head);
+        assert_eq!(db.get_watermark("svn_rev").unwrap(), Some(last_rev.to_string()));
+        assert!(last_rev < 5);
+    }
+}
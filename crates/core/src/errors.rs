@@ -76,6 +76,11 @@ pub enum SvnError {
     #[error("svn network error: {0}")]
     NetworkError(String),
 
+    /// A dump stream (`svnadmin dump` / `svnrdump`) or svndiff delta in it
+    /// is malformed.
+    #[error("invalid svn dump stream: {0}")]
+    DumpFormat(String),
+
     /// Generic I/O wrapper.
     #[error("svn I/O error: {0}")]
     IoError(#[from] std::io::Error),
@@ -112,6 +117,10 @@ pub enum GitError {
     #[error("git apply failed: {0}")]
     ApplyFailed(String),
 
+    /// `git fast-import` rejected the stream or exited early.
+    #[error("git fast-import failed: {0}")]
+    FastImport(String),
+
     /// Generic I/O wrapper.
     #[error("git I/O error: {0}")]
     IoError(#[from] std::io::Error),
@@ -191,6 +200,13 @@ pub enum SyncError {
     /// Identity mapping error during sync.
     #[error("sync identity error: {0}")]
     IdentityError(#[from] IdentityError),
+    /// Conflict lookup or resolution error during sync.
+    #[error("sync conflict error: {0}")]
+    ConflictError(#[from] ConflictError),
+
+    /// A configured SVN/Git branch pair could not be synchronized.
+    #[error("branch sync failed for '{branch}': {detail}")]
+    BranchSyncFailed { branch: String, detail: String },
 }
 
 // ---------------------------------------------------------------------------
@@ -216,6 +232,10 @@ pub enum ConflictError {
     #[error("three-way merge failed: {0}")]
     MergeFailed(String),
 
+    /// The conflict was recorded by another repository's sync engine.
+    #[error("conflict {id} belongs to repository {repo_id}")]
+    WrongRepository { id: String, repo_id: String },
+
     /// Database error when persisting conflict data.
     #[error("conflict database error: {0}")]
     DatabaseError(#[from] DatabaseError),
@@ -11,10 +11,15 @@
 //! | Condition | Decision |
 //! |-----------|----------|
 //! | Path matches an ignore pattern | `Ignored` |
+//! | Include patterns are set and none matches | `Ignored` |
 //! | Size exceeds `max_file_size` (when > 0) | `Oversize` |
 //! | Size exceeds `lfs_threshold` and LFS enabled | `LfsTrack` |
 //! | None of the above | `Allow` |
 //!
+//! Symlinks are evaluated by target instead of size: besides the ignore
+//! patterns, a policy can reject links resolving outside the repository
+//! (`ExternalSymlink`).
+//!
 //! All decisions carry the relative path and size for auditing.
 
 use std::path::Path;
@@ -36,6 +41,8 @@ pub enum FilePolicyDecision {
     Oversize { size: u64, limit: u64 },
     /// File qualifies for LFS tracking (above lfs_threshold, LFS enabled).
     LfsTrack { size: u64, threshold: u64 },
+    /// Symlink pointing outside the repository while those are rejected.
+    ExternalSymlink { target: String },
 }
 
 impl FilePolicyDecision {
@@ -44,9 +51,12 @@ impl FilePolicyDecision {
         matches!(self, Self::Allow | Self::LfsTrack { .. })
     }
 
-    /// `true` if the file is blocked (ignored or oversize).
+    /// `true` if the file is blocked (ignored, oversize or an external symlink).
     pub fn is_blocked(&self) -> bool {
-        matches!(self, Self::Ignored { .. } | Self::Oversize { .. })
+        matches!(
+            self,
+            Self::Ignored { .. } | Self::Oversize { .. } | Self::ExternalSymlink { .. }
+        )
     }
 
     /// Short human-readable label for audit/logging.
@@ -56,6 +66,7 @@ impl FilePolicyDecision {
             Self::Ignored { .. } => "ignored",
             Self::Oversize { .. } => "oversize",
             Self::LfsTrack { .. } => "lfs-track",
+            Self::ExternalSymlink { .. } => "external-symlink",
         }
     }
 }
@@ -74,12 +85,16 @@ pub struct FilePolicy {
     max_file_size: u64,
     /// Glob patterns to exclude. Matched against the *relative* path.
     ignore_patterns: Vec<String>,
+    /// Glob patterns a path must match to be synced; empty = every path.
+    include_patterns: Vec<String>,
     /// LFS size threshold in bytes. 0 = LFS disabled.
     lfs_threshold: u64,
     /// Whether LFS is enabled (threshold > 0 or patterns non-empty).
     lfs_enabled: bool,
     /// Glob patterns for files that should always be LFS-tracked regardless of size.
     lfs_patterns: Vec<String>,
+    /// Whether symlinks resolving outside the repository are blocked.
+    reject_external_symlinks: bool,
 }
 
 impl FilePolicy {
@@ -88,9 +103,11 @@ impl FilePolicy {
         Self {
             max_file_size,
             ignore_patterns,
+            include_patterns: Vec::new(),
             lfs_threshold: 0,
             lfs_enabled: false,
             lfs_patterns: Vec::new(),
+            reject_external_symlinks: false,
         }
     }
 
@@ -108,18 +125,32 @@ impl FilePolicy {
         Self {
             max_file_size,
             ignore_patterns,
+            include_patterns: Vec::new(),
             lfs_threshold,
             lfs_enabled: lfs_threshold > 0 || has_patterns,
             lfs_patterns: lfs_patterns.to_vec(),
+            reject_external_symlinks: false,
         }
     }
 
-    /// Evaluate a file.
-    ///
-    /// `rel_path` is the file's path relative to the repo root (forward-slash
-    /// separated). `size` is the file size in bytes.
-    pub fn evaluate(&self, rel_path: &str, size: u64) -> FilePolicyDecision {
-        // 1. Check ignore patterns first.
+    /// Block symlinks whose target resolves outside the repository.
+    pub fn reject_external_symlinks(mut self, reject: bool) -> Self {
+        self.reject_external_symlinks = reject;
+        self
+    }
+
+    /// Restrict the policy to paths matching one of `include` (when not
+    /// empty) and block those matching one of `exclude`, on top of the
+    /// ignore patterns.
+    pub fn with_path_filter(mut self, include: Vec<String>, exclude: Vec<String>) -> Self {
+        self.include_patterns = include;
+        self.ignore_patterns.extend(exclude);
+        self
+    }
+
+    /// `Ignored` when `rel_path` matches an ignore pattern or misses every
+    /// include pattern.
+    fn filter_path(&self, rel_path: &str) -> Option<FilePolicyDecision> {
         for pattern in &self.ignore_patterns {
             if self.matches_pattern(rel_path, pattern) {
                 debug!(
@@ -127,11 +158,31 @@ impl FilePolicy {
                     pattern = pattern.as_str(),
                     "file matches ignore pattern"
                 );
-                return FilePolicyDecision::Ignored {
+                return Some(FilePolicyDecision::Ignored {
                     pattern: pattern.clone(),
-                };
+                });
             }
         }
+        if !self.include_patterns.is_empty()
+            && !self.include_patterns.iter().any(|p| self.matches_pattern(rel_path, p))
+        {
+            debug!(path = rel_path, "file matches no include pattern");
+            return Some(FilePolicyDecision::Ignored {
+                pattern: format!("!{{{}}}", self.include_patterns.join(",")),
+            });
+        }
+        None
+    }
+
+    /// Evaluate a file.
+    ///
+    /// `rel_path` is the file's path relative to the repo root (forward-slash
+    /// separated). `size` is the file size in bytes.
+    pub fn evaluate(&self, rel_path: &str, size: u64) -> FilePolicyDecision {
+        // 1. Check ignore and include patterns first.
+        if let Some(decision) = self.filter_path(rel_path) {
+            return decision;
+        }
 
         // 2. Check max_file_size (0 = unlimited).
         if self.max_file_size > 0 && size > self.max_file_size {
@@ -180,6 +231,23 @@ impl FilePolicy {
         FilePolicyDecision::Allow
     }
 
+    /// Evaluate a symlink at `rel_path` pointing at `target`.
+    ///
+    /// Only ignore/include patterns and the external-target check apply; the
+    /// size of a symlink is that of its target path.
+    pub fn evaluate_symlink(&self, rel_path: &str, target: &str) -> FilePolicyDecision {
+        if let Some(decision) = self.filter_path(rel_path) {
+            return decision;
+        }
+        if self.reject_external_symlinks && crate::svn_props::symlink_escapes(rel_path, target) {
+            warn!(path = rel_path, target, "symlink points outside the repository — skipping");
+            return FilePolicyDecision::ExternalSymlink {
+                target: target.to_string(),
+            };
+        }
+        FilePolicyDecision::Allow
+    }
+
     /// Evaluate a file on disk (reads metadata for size).
     ///
     /// `base_dir` is the repo/export root, `rel_path` is relative to it.
@@ -198,8 +266,10 @@ impl FilePolicy {
     pub fn has_constraints(&self) -> bool {
         self.max_file_size > 0
             || !self.ignore_patterns.is_empty()
+            || !self.include_patterns.is_empty()
             || self.lfs_enabled
             || !self.lfs_patterns.is_empty()
+            || self.reject_external_symlinks
     }
 
     /// Max file size (for display/logging).
@@ -244,7 +314,7 @@ impl FilePolicy {
 
 impl From<&crate::personal_config::PersonalOptionsConfig> for FilePolicy {
     fn from(opts: &crate::personal_config::PersonalOptionsConfig) -> Self {
-        if opts.lfs_threshold > 0 || !opts.lfs_patterns.is_empty() {
+        let policy = if opts.lfs_threshold > 0 || !opts.lfs_patterns.is_empty() {
             Self::with_lfs(
                 opts.max_file_size,
                 opts.ignore_patterns.clone(),
@@ -253,13 +323,14 @@ impl From<&crate::personal_config::PersonalOptionsConfig> for FilePolicy {
             )
         } else {
             Self::new(opts.max_file_size, opts.ignore_patterns.clone())
-        }
+        };
+        policy.reject_external_symlinks(opts.reject_external_symlinks)
     }
 }
 
 impl From<&crate::config::SyncConfig> for FilePolicy {
     fn from(sync: &crate::config::SyncConfig) -> Self {
-        if sync.lfs_threshold > 0 || !sync.lfs_patterns.is_empty() {
+        let policy = if sync.lfs_threshold > 0 || !sync.lfs_patterns.is_empty() {
             Self::with_lfs(
                 sync.max_file_size,
                 sync.ignore_patterns.clone(),
@@ -268,7 +339,8 @@ impl From<&crate::config::SyncConfig> for FilePolicy {
             )
         } else {
             Self::new(sync.max_file_size, sync.ignore_patterns.clone())
-        }
+        };
+        policy.reject_external_symlinks(sync.reject_external_symlinks)
     }
 }
 
@@ -374,6 +446,34 @@ mod tests {
         assert!(!policy.evaluate("src/main.rs", 10).is_blocked());
     }
 
+    #[test]
+    fn test_path_filter() {
+        let policy = FilePolicy::new(0, vec!["**/*.log".into()])
+            .with_path_filter(vec!["src/**".into(), "README.md".into()], vec!["src/gen/**".into()]);
+        assert!(policy.has_constraints());
+        assert_eq!(policy.evaluate("src/main.rs", 10), FilePolicyDecision::Allow);
+        assert_eq!(policy.evaluate("README.md", 10), FilePolicyDecision::Allow);
+        assert_eq!(
+            policy.evaluate("assets/big.bin", 10),
+            FilePolicyDecision::Ignored {
+                pattern: "!{src/**,README.md}".into()
+            }
+        );
+        assert_eq!(
+            policy.evaluate("src/gen/parser.rs", 10),
+            FilePolicyDecision::Ignored {
+                pattern: "src/gen/**".into()
+            }
+        );
+        assert!(policy.evaluate("src/debug.log", 10).is_blocked());
+        assert!(policy.evaluate_symlink("docs/link", "../src").is_blocked());
+
+        // Without include patterns every path not excluded passes.
+        let policy = FilePolicy::new(0, vec![]).with_path_filter(vec![], vec!["vendor/**".into()]);
+        assert!(policy.evaluate("vendor/lib.c", 10).is_blocked());
+        assert!(!policy.evaluate("lib.c", 10).is_blocked());
+    }
+
     #[test]
     fn test_has_constraints() {
         assert!(!FilePolicy::new(0, vec![]).has_constraints());
@@ -381,6 +481,23 @@ mod tests {
         assert!(FilePolicy::new(0, vec!["*.log".into()]).has_constraints());
     }
 
+    #[test]
+    fn test_evaluate_symlink() {
+        let policy = FilePolicy::new(10, vec!["*.log".into()]);
+        assert_eq!(policy.evaluate_symlink("link", "/etc/passwd"), FilePolicyDecision::Allow);
+        assert!(policy.evaluate_symlink("app.log", "real.log").is_blocked());
+
+        let policy = policy.reject_external_symlinks(true);
+        assert!(policy.has_constraints());
+        assert_eq!(
+            policy.evaluate_symlink("conf/link", "../../etc/passwd"),
+            FilePolicyDecision::ExternalSymlink {
+                target: "../../etc/passwd".into()
+            }
+        );
+        assert_eq!(policy.evaluate_symlink("conf/link", "../shared/app.yml").label(), "allow");
+    }
+
     #[test]
     fn test_from_options_config() {
         let opts = crate::personal_config::PersonalOptionsConfig {
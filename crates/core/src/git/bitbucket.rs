@@ -0,0 +1,706 @@
+//! Bitbucket Server / Data Center REST API (1.0) requests and shapes for
+//! [`GitHubClient`](super::GitHubClient).
+//!
+//! With [`GitProvider::BitbucketServer`](crate::config::GitProvider::BitbucketServer)
+//! the API URL points at `https://<host>/rest/api/1.0` and an `owner/name`
+//! repository is read as project key and repository slug, so requests go to
+//! `/projects/:key/repos/:slug/...` (`~user` keys address personal
+//! repositories).  Build statuses live in the separate
+//! `/rest/build-status/1.0` API next to it.  Pull requests and commits are
+//! converted into the GitHub types the sync engines use.
+
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::errors::GitHubError;
+
+use super::github::{
+    CombinedStatus, CommitStatusState, GitHubClient, GitHubCommit, GitHubCommitDetail,
+    GitHubCommitDetail2, GitHubCommitParent, GitHubGitActor, GitHubUser, ProviderApi,
+    PullRequest, PullRequestRef,
+};
+
+/// Project key and repository slug of an `owner/name` repository.
+pub fn split_repo(repo: &str) -> (&str, &str) {
+    let repo = repo.trim_matches('/');
+    match repo.split_once('/') {
+        Some((key, slug)) => (key, slug),
+        None => ("", repo),
+    }
+}
+
+/// `/projects/:key/repos/:slug` path of a repository.
+pub fn repo_path(repo: &str) -> String {
+    let (key, slug) = split_repo(repo);
+    format!("projects/{}/repos/{}", key, slug)
+}
+
+/// Repository endpoint of `repo` below the API base URL.
+pub fn repo_url(api_url: &str, repo: &str) -> String {
+    format!("{}/{}", api_url.trim_end_matches('/'), repo_path(repo))
+}
+
+/// Server base URL of a `.../rest/api/1.0` API URL.
+pub fn server_url(api_url: &str) -> &str {
+    let url = api_url.trim_end_matches('/');
+    url.strip_suffix("/rest/api/1.0")
+        .or_else(|| url.strip_suffix("/rest/api/latest"))
+        .unwrap_or(url)
+}
+
+/// Build status endpoint of a commit.
+pub fn build_status_url(api_url: &str, sha: &str) -> String {
+    format!("{}/rest/build-status/1.0/commits/{}", server_url(api_url), sha)
+}
+
+/// Web page of a commit, linked from the build statuses RepoSync posts.
+pub fn commit_web_url(api_url: &str, repo: &str, sha: &str) -> String {
+    format!("{}/{}/commits/{}", server_url(api_url), repo_path(repo), sha)
+}
+
+/// Bitbucket build state for a [`CommitStatusState`].
+pub fn status_state(state: &CommitStatusState) -> &'static str {
+    match state {
+        CommitStatusState::Pending => "INPROGRESS",
+        CommitStatusState::Success => "SUCCESSFUL",
+        CommitStatusState::Failure | CommitStatusState::Error => "FAILED",
+    }
+}
+
+/// RFC 3339 form of a Bitbucket millisecond timestamp, comparable with the
+/// timestamps GitHub returns.
+fn timestamp(millis: Option<i64>) -> Option<String> {
+    chrono::DateTime::from_timestamp_millis(millis?)
+        .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+}
+
+/// One page of a paged Bitbucket collection.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    #[serde(default = "Vec::new")]
+    pub values: Vec<T>,
+    #[serde(default = "default_last_page")]
+    pub is_last_page: bool,
+    #[serde(default)]
+    pub next_page_start: Option<u64>,
+}
+
+fn default_last_page() -> bool {
+    true
+}
+
+/// A pull request from `/projects/:key/repos/:slug/pull-requests`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BitbucketPullRequest {
+    pub id: u64,
+    /// Optimistic-locking version, required to merge.
+    pub version: u64,
+    pub title: String,
+    /// `OPEN`, `DECLINED` or `MERGED`.
+    pub state: String,
+    pub from_ref: Ref,
+    pub to_ref: Ref,
+    #[serde(default)]
+    pub closed_date: Option<i64>,
+    #[serde(default)]
+    pub properties: Option<PullRequestProperties>,
+    #[serde(default)]
+    pub links: Option<Links>,
+}
+
+/// Source or target branch of a pull request.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ref {
+    pub display_id: String,
+    #[serde(default)]
+    pub latest_commit: Option<String>,
+}
+
+/// Extra pull request data; merged pull requests carry their merge commit.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestProperties {
+    #[serde(default)]
+    pub merge_commit: Option<CommitRef>,
+}
+
+/// A commit referenced by ID.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitRef {
+    pub id: String,
+}
+
+/// Hyperlinks of a resource.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Links {
+    #[serde(rename = "self", default)]
+    pub self_links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Link {
+    pub href: String,
+}
+
+impl From<BitbucketPullRequest> for PullRequest {
+    fn from(pr: BitbucketPullRequest) -> Self {
+        let merged = pr.state == "MERGED";
+        PullRequest {
+            number: pr.id,
+            title: pr.title,
+            html_url: pr
+                .links
+                .and_then(|l| l.self_links.into_iter().next())
+                .map(|l| l.href)
+                .unwrap_or_default(),
+            state: if pr.state == "OPEN" { "open" } else { "closed" }.into(),
+            head: PullRequestRef {
+                ref_name: pr.from_ref.display_id,
+                sha: pr.from_ref.latest_commit.unwrap_or_default(),
+            },
+            base: PullRequestRef {
+                ref_name: pr.to_ref.display_id,
+                sha: pr.to_ref.latest_commit.unwrap_or_default(),
+            },
+            merged: Some(merged),
+            merge_commit_sha: pr.properties.and_then(|p| p.merge_commit).map(|c| c.id),
+            merged_at: if merged { timestamp(pr.closed_date) } else { None },
+        }
+    }
+}
+
+/// A commit from `/projects/:key/repos/:slug/commits`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Commit {
+    pub id: String,
+    pub message: String,
+    pub author: Person,
+    #[serde(default)]
+    pub author_timestamp: Option<i64>,
+    /// Missing on servers older than 5.0, which only report the author.
+    #[serde(default)]
+    pub committer: Option<Person>,
+    #[serde(default)]
+    pub committer_timestamp: Option<i64>,
+    #[serde(default)]
+    pub parents: Vec<CommitRef>,
+}
+
+/// Author or committer of a commit.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    pub name: String,
+    #[serde(default)]
+    pub email_address: Option<String>,
+}
+
+impl Commit {
+    fn detail(&self) -> GitHubCommitDetail {
+        let author = GitHubGitActor {
+            name: self.author.name.clone(),
+            email: self.author.email_address.clone().unwrap_or_default(),
+            date: timestamp(self.author_timestamp),
+        };
+        let committer = match &self.committer {
+            Some(committer) => GitHubGitActor {
+                name: committer.name.clone(),
+                email: committer.email_address.clone().unwrap_or_default(),
+                date: timestamp(self.committer_timestamp.or(self.author_timestamp)),
+            },
+            None => author.clone(),
+        };
+        GitHubCommitDetail {
+            message: self.message.clone(),
+            author,
+            committer,
+            verification: None,
+        }
+    }
+}
+
+impl From<Commit> for GitHubCommit {
+    fn from(commit: Commit) -> Self {
+        GitHubCommit {
+            commit: commit.detail(),
+            sha: commit.id,
+            author: None,
+        }
+    }
+}
+
+impl From<Commit> for GitHubCommitDetail2 {
+    fn from(commit: Commit) -> Self {
+        GitHubCommitDetail2 {
+            commit: commit.detail(),
+            author: None,
+            parents: commit
+                .parents
+                .iter()
+                .map(|p| GitHubCommitParent { sha: p.id.clone() })
+                .collect(),
+            sha: commit.id,
+        }
+    }
+}
+
+/// A build status from `/rest/build-status/1.0/commits/:sha`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuildStatus {
+    /// `INPROGRESS`, `SUCCESSFUL`, `FAILED`, `CANCELLED` or `UNKNOWN`.
+    pub state: String,
+}
+
+/// Fold GitHub-style combined status out of a commit's build statuses.
+pub fn combined_status(statuses: &[BuildStatus]) -> CombinedStatus {
+    let any = |states: &[&str]| statuses.iter().any(|s| states.contains(&s.state.as_str()));
+    let state = if any(&["FAILED", "CANCELLED"]) {
+        "failure"
+    } else if any(&["INPROGRESS", "UNKNOWN"]) {
+        "pending"
+    } else {
+        "success"
+    };
+    CombinedStatus {
+        state: state.into(),
+        total_count: statuses.len() as u64,
+    }
+}
+
+/// A user from `/users/:slug`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: u64,
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub email_address: Option<String>,
+}
+
+impl From<User> for GitHubUser {
+    fn from(user: User) -> Self {
+        GitHubUser {
+            login: user.slug,
+            id: user.id,
+            name: user.display_name.or(Some(user.name)),
+            email: user.email_address.filter(|e| !e.is_empty()),
+            avatar_url: None,
+        }
+    }
+}
+
+/// Bitbucket Server's REST API (1.0).
+pub(super) struct BitbucketApi;
+
+impl BitbucketApi {
+    /// URL of `path` below the Bitbucket repository `repo`.
+    fn url(client: &GitHubClient, repo: &str, path: &str) -> String {
+        format!("{}{}", repo_url(&client.api_url, repo), path)
+    }
+
+    /// GET every page of a paged Bitbucket collection.
+    async fn get_pages<T: serde::de::DeserializeOwned>(
+        client: &GitHubClient,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> Result<Vec<T>, GitHubError> {
+        let mut items = Vec::new();
+        let mut start = 0u64;
+        for pages in 1.. {
+            let resp = client
+                .retry_request(|| {
+                    client
+                        .http
+                        .get(url)
+                        .query(query)
+                        .query(&[("start", start), ("limit", 100)])
+                })
+                .await?;
+            let page: Page<T> = resp.json().await?;
+            items.extend(page.values);
+            match page.next_page_start {
+                Some(next) if !page.is_last_page => start = next,
+                _ => break,
+            }
+            if pages >= 10 {
+                warn!(pages, url, "reached Bitbucket pagination limit");
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// Fetch a Bitbucket pull request with its version.
+    async fn pull_request(
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<BitbucketPullRequest, GitHubError> {
+        let url = Self::url(client, repo, &format!("/pull-requests/{}", pr_number));
+        let resp = client.send(client.http.get(&url)).await?;
+        Ok(client.check_response(resp).await?.json().await?)
+    }
+
+    /// Look up a Bitbucket user by slug.
+    async fn user(client: &GitHubClient, slug: &str) -> Result<User, GitHubError> {
+        let url = format!("{}/users/{}", client.api_url, slug);
+        let resp = client.send(client.http.get(&url)).await?;
+        Ok(client.check_response(resp).await?.json().await?)
+    }
+}
+
+impl ProviderApi for BitbucketApi {
+    async fn get_commits(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        since_sha: Option<&str>,
+    ) -> Result<Vec<GitHubCommit>, GitHubError> {
+        let url = Self::url(client, repo, "/commits");
+        let query: Vec<_> = since_sha.map(|sha| ("until", sha.to_string())).into_iter().collect();
+        let commits: Vec<Commit> = Self::get_pages(client, &url, &query).await?;
+        debug!(count = commits.len(), "fetched commits");
+        Ok(commits.into_iter().map(GitHubCommit::from).collect())
+    }
+
+    async fn create_webhook(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        callback_url: &str,
+        secret: &str,
+    ) -> Result<serde_json::Value, GitHubError> {
+        let url = Self::url(client, repo, "/webhooks");
+        let body = serde_json::json!({
+            "name": "reposync", "url": callback_url, "active": true,
+            "events": ["repo:refs_changed", "pr:merged"],
+            "configuration": { "secret": secret }
+        });
+        let resp = client.send(client.http.post(&url).json(&body)).await?;
+        let hook: serde_json::Value = client.check_response(resp).await?.json().await?;
+        info!(hook_id = %hook["id"], "created webhook");
+        Ok(hook)
+    }
+
+    async fn create_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> Result<PullRequest, GitHubError> {
+        let url = Self::url(client, repo, "/pull-requests");
+        let payload = serde_json::json!({
+            "title": title, "description": body,
+            "fromRef": { "id": format!("refs/heads/{}", head) },
+            "toRef": { "id": format!("refs/heads/{}", base) }
+        });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        let pr: BitbucketPullRequest = client.check_response(resp).await?.json().await?;
+        info!(number = pr.id, "created pull request");
+        Ok(pr.into())
+    }
+
+    async fn merge_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<(), GitHubError> {
+        // Merging needs the pull request's current version.
+        let pr = Self::pull_request(client, repo, pr_number).await?;
+        let url = Self::url(client, repo, &format!("/pull-requests/{}/merge", pr_number));
+        // A merge commit, whatever the repository's default strategy: a
+        // squash or rebase would replace the synced commits, which carry the
+        // sync marker, with unmarked ones that are then replayed to SVN.
+        let payload = serde_json::json!({ "strategyId": "no-ff" });
+        let resp = client
+            .send(client.http.post(&url).query(&[("version", pr.version)]).json(&payload))
+            .await?;
+        client.check_response(resp).await?;
+        info!(pr_number, "merged pull request");
+        Ok(())
+    }
+
+    async fn add_labels(
+        &self,
+        _client: &GitHubClient,
+        _repo: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<(), GitHubError> {
+        debug!(number, ?labels, "Bitbucket Server has no labels, skipping");
+        Ok(())
+    }
+
+    async fn request_reviewers(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+        reviewers: &[String],
+    ) -> Result<(), GitHubError> {
+        let url = Self::url(client, repo, &format!("/pull-requests/{}/participants", pr_number));
+        for username in reviewers {
+            let payload = serde_json::json!({ "user": { "name": username }, "role": "REVIEWER" });
+            let resp = client.send(client.http.post(&url).json(&payload)).await?;
+            client.check_response(resp).await?;
+        }
+        debug!(pr_number, ?reviewers, "requested reviewers");
+        Ok(())
+    }
+
+    async fn get_combined_status(
+        &self,
+        client: &GitHubClient,
+        _repo: &str,
+        sha: &str,
+    ) -> Result<CombinedStatus, GitHubError> {
+        let url = build_status_url(&client.api_url, sha);
+        let statuses: Vec<BuildStatus> = Self::get_pages(client, &url, &[]).await?;
+        let status = combined_status(&statuses);
+        debug!(sha, state = %status.state, "fetched combined status");
+        Ok(status)
+    }
+
+    async fn get_user(
+        &self,
+        client: &GitHubClient,
+        username: &str,
+    ) -> Result<GitHubUser, GitHubError> {
+        Ok(Self::user(client, username).await?.into())
+    }
+
+    async fn post_commit_status(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+        state: CommitStatusState,
+        description: &str,
+    ) -> Result<(), GitHubError> {
+        let url = build_status_url(&client.api_url, sha);
+        let payload = serde_json::json!({
+            "state": status_state(&state), "key": "reposync", "name": "RepoSync",
+            "url": commit_web_url(&client.api_url, repo, sha),
+            "description": description
+        });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        client.check_response(resp).await?;
+        debug!(sha, state = %state, "posted build status");
+        Ok(())
+    }
+
+    async fn get_merged_pull_requests(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        base: &str,
+        since: Option<&str>,
+    ) -> Result<Vec<PullRequest>, GitHubError> {
+        let url = Self::url(client, repo, "/pull-requests");
+        let query = [
+            ("state", "MERGED".to_string()),
+            ("at", format!("refs/heads/{}", base)),
+            ("order", "NEWEST".to_string()),
+        ];
+        let prs: Vec<BitbucketPullRequest> = Self::get_pages(client, &url, &query).await?;
+        let merged: Vec<PullRequest> = prs
+            .into_iter()
+            .map(PullRequest::from)
+            .filter(|pr| match since {
+                Some(since) => pr.merged_at.as_deref().is_some_and(|m| m >= since),
+                None => true,
+            })
+            .collect();
+        debug!(count = merged.len(), base, "fetched merged pull requests");
+        Ok(merged)
+    }
+
+    async fn get_pr_commits(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<Vec<GitHubCommit>, GitHubError> {
+        let url = Self::url(client, repo, &format!("/pull-requests/{}/commits", pr_number));
+        let commits: Vec<Commit> = Self::get_pages(client, &url, &[]).await?;
+        debug!(count = commits.len(), pr_number, "fetched PR commits");
+        // Bitbucket lists the newest commit first, GitHub the oldest.
+        Ok(commits.into_iter().rev().map(GitHubCommit::from).collect())
+    }
+
+    async fn get_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<PullRequest, GitHubError> {
+        let pr = Self::pull_request(client, repo, pr_number).await?;
+        debug!(number = pr.id, state = %pr.state, "fetched pull request");
+        Ok(pr.into())
+    }
+
+    async fn get_commit(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+    ) -> Result<GitHubCommitDetail2, GitHubError> {
+        let url = Self::url(client, repo, &format!("/commits/{}", sha));
+        let resp = client.send(client.http.get(&url)).await?;
+        let commit: Commit = client.check_response(resp).await?.json().await?;
+        debug!(sha, parents = commit.parents.len(), "fetched commit details");
+        Ok(commit.into())
+    }
+
+    async fn repo_exists(&self, client: &GitHubClient, repo: &str) -> Result<bool, GitHubError> {
+        let url = repo_url(&client.api_url, repo);
+        let resp = client.send(client.http.head(&url)).await?;
+        Ok(resp.status().is_success())
+    }
+
+    async fn create_repo(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        private: bool,
+        description: &str,
+    ) -> Result<serde_json::Value, GitHubError> {
+        let (key, slug) = split_repo(repo);
+        let url = format!("{}/projects/{}/repos", client.api_url, key);
+        let payload = serde_json::json!({
+            "name": slug,
+            "scmId": "git",
+            "public": !private,
+            "description": description,
+        });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        let created: serde_json::Value = client.check_response(resp).await?.json().await?;
+        info!(repo, private, "created repository");
+        Ok(created)
+    }
+
+    async fn get_authenticated_user(
+        &self,
+        client: &GitHubClient,
+    ) -> Result<GitHubUser, GitHubError> {
+        // Bitbucket has no `/user`; every response names the caller instead.
+        let url = format!("{}/application-properties", client.api_url);
+        let resp = client.send(client.http.get(&url)).await?;
+        let resp = client.check_response(resp).await?;
+        let username = resp
+            .headers()
+            .get("x-ausername")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| GitHubError::AuthenticationFailed("token is not authenticated".into()))?;
+        let user: GitHubUser = Self::user(client, &username).await?.into();
+        debug!(login = %user.login, "fetched authenticated user");
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_urls() {
+        assert_eq!(repo_path("PROJ/app"), "projects/PROJ/repos/app");
+        assert_eq!(repo_path("~ann/scratch/"), "projects/~ann/repos/scratch");
+        let api = "https://bitbucket.example.com/rest/api/1.0/";
+        assert_eq!(
+            repo_url(api, "PROJ/app"),
+            "https://bitbucket.example.com/rest/api/1.0/projects/PROJ/repos/app"
+        );
+        assert_eq!(
+            build_status_url(api, "abc"),
+            "https://bitbucket.example.com/rest/build-status/1.0/commits/abc"
+        );
+        assert_eq!(
+            commit_web_url("https://bitbucket.example.com/rest/api/latest", "PROJ/app", "abc"),
+            "https://bitbucket.example.com/projects/PROJ/repos/app/commits/abc"
+        );
+    }
+
+    #[test]
+    fn test_pull_request_conversion() {
+        let pr: BitbucketPullRequest = serde_json::from_value(serde_json::json!({
+            "id": 12,
+            "version": 3,
+            "title": "Sync",
+            "state": "MERGED",
+            "fromRef": {"id": "refs/heads/reposync/pr", "displayId": "reposync/pr",
+                        "latestCommit": "abc"},
+            "toRef": {"id": "refs/heads/main", "displayId": "main", "latestCommit": "012"},
+            "closedDate": 1706702400000i64,
+            "properties": {"mergeCommit": {"displayId": "def", "id": "def0"}},
+            "links": {"self": [{"href": "https://bb.example.com/pull-requests/12"}]}
+        }))
+        .unwrap();
+        let pr = PullRequest::from(pr);
+        assert_eq!(pr.number, 12);
+        assert_eq!(pr.state, "closed");
+        assert_eq!(pr.merged, Some(true));
+        assert_eq!(pr.merge_commit_sha.as_deref(), Some("def0"));
+        assert_eq!(pr.merged_at.as_deref(), Some("2024-01-31T12:00:00Z"));
+        assert_eq!((pr.head.ref_name.as_str(), pr.head.sha.as_str()), ("reposync/pr", "abc"));
+        assert_eq!(pr.base.ref_name, "main");
+        assert!(pr.html_url.ends_with("/pull-requests/12"));
+    }
+
+    #[test]
+    fn test_commit_conversions() {
+        let commit: Commit = serde_json::from_value(serde_json::json!({
+            "id": "abc",
+            "displayId": "abc",
+            "message": "Fix",
+            "author": {"name": "ann", "emailAddress": "ann@example.com"},
+            "authorTimestamp": 1706702400000i64,
+            "parents": [{"id": "p1", "displayId": "p1"}, {"id": "p2", "displayId": "p2"}]
+        }))
+        .unwrap();
+        let detail = GitHubCommitDetail2::from(commit.clone());
+        assert_eq!(detail.parents.len(), 2);
+        // Without a committer the author stands in.
+        assert_eq!(detail.commit.committer.email, "ann@example.com");
+        let commit = GitHubCommit::from(commit);
+        assert_eq!(commit.sha, "abc");
+        assert_eq!(commit.commit.author.date.as_deref(), Some("2024-01-31T12:00:00Z"));
+    }
+
+    #[test]
+    fn test_combined_status() {
+        let statuses = |states: &[&str]| -> Vec<BuildStatus> {
+            states.iter().map(|s| BuildStatus { state: s.to_string() }).collect()
+        };
+        assert_eq!(combined_status(&[]).total_count, 0);
+        assert_eq!(combined_status(&statuses(&["SUCCESSFUL"])).state, "success");
+        assert_eq!(combined_status(&statuses(&["SUCCESSFUL", "INPROGRESS"])).state, "pending");
+        assert_eq!(combined_status(&statuses(&["INPROGRESS", "FAILED"])).state, "failure");
+        assert_eq!(status_state(&CommitStatusState::Error), "FAILED");
+    }
+
+    #[test]
+    fn test_page_defaults() {
+        let page: Page<BuildStatus> = serde_json::from_value(serde_json::json!({
+            "size": 1, "limit": 25, "start": 0, "isLastPage": false, "nextPageStart": 25,
+            "values": [{"state": "SUCCESSFUL", "key": "ci"}]
+        }))
+        .unwrap();
+        assert!(!page.is_last_page);
+        assert_eq!(page.next_page_start, Some(25));
+        let page: Page<BuildStatus> = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(page.is_last_page && page.values.is_empty());
+    }
+}
@@ -0,0 +1,653 @@
+//! Bulk commit writer over `git fast-import`.
+//!
+//! Imports that replay thousands of revisions should not stage every one
+//! through the index and working tree.  [`FastImport`] streams commits to a
+//! `git fast-import` process instead: each commit lists only the paths that
+//! changed (computed by [`TreeManifest`]) and carries a mark, which callers
+//! set to the SVN revision so the mark→SHA table maps revisions to commits.
+//!
+//! A stream started with [`FastImport::start_refs`] is not tied to one ref:
+//! commits go to the ref named with each [`commit_on`](FastImport::commit_on),
+//! [`reset`](FastImport::reset) moves a ref to an earlier commit and
+//! [`tag`](FastImport::tag) writes annotated tags, which is how a whole SVN
+//! layout with its branches and tags is imported in one pass.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use git2::{ObjectType, Oid, Repository, TreeWalkMode, TreeWalkResult};
+use tracing::{debug, info};
+
+use crate::errors::GitError;
+
+/// Git mode of a file in a fast-import commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileMode {
+    Regular,
+    Executable,
+    Symlink,
+}
+
+impl FileMode {
+    /// The octal mode written to the stream.
+    pub fn octal(&self) -> &'static str {
+        match self {
+            FileMode::Regular => "100644",
+            FileMode::Executable => "100755",
+            FileMode::Symlink => "120000",
+        }
+    }
+
+    fn from_git(mode: i32) -> Option<Self> {
+        match mode {
+            0o100644 => Some(FileMode::Regular),
+            0o100755 => Some(FileMode::Executable),
+            0o120000 => Some(FileMode::Symlink),
+            _ => None,
+        }
+    }
+}
+
+/// One path change in a commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileChange {
+    /// Add or replace `path`.  The blob is read from `source` when the commit
+    /// is written: the file content, or the link target for a symlink.
+    Modify {
+        path: String,
+        mode: FileMode,
+        source: PathBuf,
+    },
+    /// Remove `path` (a file or a whole directory).
+    Delete { path: String },
+}
+
+impl FileChange {
+    pub fn path(&self) -> &str {
+        match self {
+            FileChange::Modify { path, .. } | FileChange::Delete { path } => path,
+        }
+    }
+}
+
+/// A commit to stream to `git fast-import`.
+#[derive(Debug, Clone)]
+pub struct FastImportCommit<'a> {
+    /// Mark identifying the commit; must be positive.
+    pub mark: i64,
+    pub author_name: &'a str,
+    pub author_email: &'a str,
+    /// Author time in seconds since the epoch (UTC).
+    pub author_time: i64,
+    pub committer_name: &'a str,
+    pub committer_email: &'a str,
+    /// Committer time in seconds since the epoch (UTC).
+    pub committer_time: i64,
+    pub message: &'a str,
+    pub changes: &'a [FileChange],
+}
+
+/// A running `git fast-import` process that appends commits to one ref, or
+/// to several (see [`FastImport::start_refs`]).
+pub struct FastImport {
+    child: Child,
+    stdin: BufWriter<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+    marks: tempfile::NamedTempFile,
+    stderr: tempfile::NamedTempFile,
+    ref_name: String,
+    /// Commit the first streamed commit builds on.
+    parent: Option<String>,
+    commits: usize,
+    checkpoints: usize,
+}
+
+impl FastImport {
+    /// Start `git fast-import` in `repo_path`, appending commits to
+    /// `ref_name` (e.g. `refs/heads/main`) on top of `parent`.
+    pub fn start(repo_path: &Path, ref_name: &str, parent: Option<&str>) -> Result<Self, GitError> {
+        Self::spawn(repo_path, ref_name, parent, false)
+    }
+
+    /// Start `git fast-import` in `repo_path` for commits to any ref, named
+    /// with each [`commit_on`](Self::commit_on).  Refs are force-updated, so
+    /// a [`reset`](Self::reset) may move a ref off its previous history.
+    pub fn start_refs(repo_path: &Path) -> Result<Self, GitError> {
+        Self::spawn(repo_path, "", None, true)
+    }
+
+    fn spawn(
+        repo_path: &Path,
+        ref_name: &str,
+        parent: Option<&str>,
+        force: bool,
+    ) -> Result<Self, GitError> {
+        let marks = tempfile::NamedTempFile::new()?;
+        let stderr = tempfile::NamedTempFile::new()?;
+        let mut command = Command::new("git");
+        command
+            .arg("fast-import")
+            .arg("--quiet")
+            .arg("--done")
+            .arg(format!("--export-marks={}", marks.path().display()));
+        if force {
+            command.arg("--force");
+        }
+        let mut child = command
+            .current_dir(repo_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            // A file rather than a pipe: nobody drains stderr while we write.
+            .stderr(Stdio::from(stderr.reopen()?))
+            .spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        info!(repo = %repo_path.display(), ref_name, "started git fast-import");
+        Ok(Self {
+            child,
+            stdin: BufWriter::new(stdin),
+            stdout: BufReader::new(stdout),
+            marks,
+            stderr,
+            ref_name: ref_name.to_string(),
+            parent: parent.map(str::to_string),
+            commits: 0,
+            checkpoints: 0,
+        })
+    }
+
+    /// Number of commits streamed so far.
+    pub fn commit_count(&self) -> usize {
+        self.commits
+    }
+
+    /// Stream one commit.  The commit only becomes visible under its ref at
+    /// the next [`checkpoint`](Self::checkpoint) or [`finish`](Self::finish).
+    pub fn commit(&mut self, commit: &FastImportCommit<'_>) -> Result<(), GitError> {
+        let from = if self.commits == 0 { self.parent.clone() } else { None };
+        let ref_name = self.ref_name.clone();
+        self.write(&ref_name, commit, from.as_deref())
+    }
+
+    /// Stream one commit to `ref_name`, on top of the commit the ref points
+    /// at in the stream (none for a new ref, which starts a new history).
+    pub fn commit_on(
+        &mut self,
+        ref_name: &str,
+        commit: &FastImportCommit<'_>,
+    ) -> Result<(), GitError> {
+        self.write(ref_name, commit, None)
+    }
+
+    fn write(
+        &mut self,
+        ref_name: &str,
+        commit: &FastImportCommit<'_>,
+        from: Option<&str>,
+    ) -> Result<(), GitError> {
+        if commit.mark <= 0 {
+            return Err(GitError::FastImport(format!("invalid mark {}", commit.mark)));
+        }
+        let result = self.write_commit(ref_name, commit, from);
+        result.map_err(|e| self.fail(e))?;
+        self.commits += 1;
+        debug!(mark = commit.mark, changes = commit.changes.len(), "streamed commit");
+        Ok(())
+    }
+
+    /// Point `ref_name` at `from` (a SHA or `:<mark>`); the next commit on
+    /// the ref builds on it.  Without `from`, that commit starts a new
+    /// history.
+    pub fn reset(&mut self, ref_name: &str, from: Option<&str>) -> Result<(), GitError> {
+        let result = writeln!(self.stdin, "reset {}", ref_name).and_then(|()| match from {
+            Some(from) => writeln!(self.stdin, "from {}", from),
+            None => Ok(()),
+        });
+        result.map_err(|e| self.fail(e))
+    }
+
+    /// Write the annotated tag `refs/tags/<name>` on `from` (a SHA or
+    /// `:<mark>`).  `time` is in seconds since the epoch (UTC).
+    pub fn tag(
+        &mut self,
+        name: &str,
+        from: &str,
+        tagger_name: &str,
+        tagger_email: &str,
+        time: i64,
+        message: &str,
+    ) -> Result<(), GitError> {
+        let out = &mut self.stdin;
+        let result = writeln!(out, "tag {}", name)
+            .and_then(|()| writeln!(out, "from {}", from))
+            .and_then(|()| {
+                writeln!(
+                    out,
+                    "tagger {} <{}> {} +0000",
+                    sanitize_ident(tagger_name),
+                    sanitize_ident(tagger_email),
+                    time
+                )
+            })
+            .and_then(|()| write_data(out, message.as_bytes()));
+        result.map_err(|e| self.fail(e))?;
+        debug!(name, from, "streamed tag");
+        Ok(())
+    }
+
+    fn write_commit(
+        &mut self,
+        ref_name: &str,
+        commit: &FastImportCommit<'_>,
+        from: Option<&str>,
+    ) -> std::io::Result<()> {
+        let out = &mut self.stdin;
+        writeln!(out, "commit {}", ref_name)?;
+        writeln!(out, "mark :{}", commit.mark)?;
+        writeln!(
+            out,
+            "author {} <{}> {} +0000",
+            sanitize_ident(commit.author_name),
+            sanitize_ident(commit.author_email),
+            commit.author_time
+        )?;
+        writeln!(
+            out,
+            "committer {} <{}> {} +0000",
+            sanitize_ident(commit.committer_name),
+            sanitize_ident(commit.committer_email),
+            commit.committer_time
+        )?;
+        write_data(out, commit.message.as_bytes())?;
+        if let Some(from) = from {
+            writeln!(out, "from {}", from)?;
+        }
+        for change in commit.changes {
+            match change {
+                FileChange::Modify { path, mode, source } => {
+                    let content = match mode {
+                        FileMode::Symlink => std::fs::read_link(source)?
+                            .to_string_lossy()
+                            .into_owned()
+                            .into_bytes(),
+                        _ => std::fs::read(source)?,
+                    };
+                    writeln!(out, "M {} inline {}", mode.octal(), quote_path(path))?;
+                    write_data(out, &content)?;
+                }
+                FileChange::Delete { path } => writeln!(out, "D {}", quote_path(path))?,
+            }
+        }
+        writeln!(out)
+    }
+
+    /// Flush everything streamed so far into the repository and update the
+    /// ref.  Returns the mark→SHA table of every commit written so far.
+    pub fn checkpoint(&mut self) -> Result<BTreeMap<i64, String>, GitError> {
+        self.checkpoints += 1;
+        let token = format!("reposync-checkpoint-{}", self.checkpoints);
+        let result = self.write_checkpoint(&token);
+        result.map_err(|e| self.fail(e))?;
+
+        // `progress` is echoed once the checkpoint before it has completed.
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match self.stdout.read_line(&mut line) {
+                Ok(0) => {
+                    let e = std::io::Error::from(std::io::ErrorKind::UnexpectedEof);
+                    return Err(self.fail(e));
+                }
+                Ok(_) if line.trim_end() == format!("progress {}", token) => break,
+                Ok(_) => {}
+                Err(e) => return Err(self.fail(e)),
+            }
+        }
+        debug!(commits = self.commits, "fast-import checkpoint");
+        read_marks(self.marks.path())
+    }
+
+    fn write_checkpoint(&mut self, token: &str) -> std::io::Result<()> {
+        writeln!(self.stdin, "checkpoint")?;
+        writeln!(self.stdin, "progress {}", token)?;
+        self.stdin.flush()
+    }
+
+    /// End the stream and wait for `git fast-import` to exit.  Returns the
+    /// mark→SHA table of every commit written.
+    pub fn finish(mut self) -> Result<BTreeMap<i64, String>, GitError> {
+        let result = writeln!(self.stdin, "done").and_then(|()| self.stdin.flush());
+        result.map_err(|e| self.fail(e))?;
+        let Self { mut child, stdin, marks, stderr, .. } = self;
+        // Closing stdin lets the process exit.
+        drop(stdin);
+        let status = child.wait()?;
+        if !status.success() {
+            let detail = read_stderr(&stderr);
+            return Err(GitError::FastImport(format!("exited with {}: {}", status, detail)));
+        }
+        let marks = read_marks(marks.path())?;
+        info!(commits = marks.len(), "git fast-import finished");
+        Ok(marks)
+    }
+
+    /// Stop the process after a failed write or read and turn its stderr
+    /// into the error.
+    fn fail(&mut self, err: std::io::Error) -> GitError {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let detail = read_stderr(&self.stderr);
+        if detail.is_empty() {
+            GitError::FastImport(err.to_string())
+        } else {
+            GitError::FastImport(detail)
+        }
+    }
+}
+
+fn write_data(out: &mut impl Write, data: &[u8]) -> std::io::Result<()> {
+    writeln!(out, "data {}", data.len())?;
+    out.write_all(data)?;
+    writeln!(out)
+}
+
+fn read_stderr(file: &tempfile::NamedTempFile) -> String {
+    let mut detail = String::new();
+    if let Ok(mut f) = file.reopen() {
+        let _ = f.read_to_string(&mut detail);
+    }
+    detail.trim().to_string()
+}
+
+/// Parse an exported marks file (`:<mark> <sha>` per line).
+fn read_marks(path: &Path) -> Result<BTreeMap<i64, String>, GitError> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let (mark, sha) = line.strip_prefix(':')?.split_once(' ')?;
+            Some((mark.parse().ok()?, sha.trim().to_string()))
+        })
+        .collect())
+}
+
+/// Names and emails may not contain the `<`, `>` or newline delimiters.
+fn sanitize_ident(value: &str) -> String {
+    value.chars().filter(|c| !matches!(c, '<' | '>' | '\n')).collect::<String>().trim().to_string()
+}
+
+/// Quote a path the way fast-import expects when it contains a newline or
+/// starts with a double quote; other paths are written as-is.
+fn quote_path(path: &str) -> String {
+    if !path.contains('\n') && !path.starts_with('"') {
+        return path.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in path.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// ---------------------------------------------------------------------------
+// Tree manifest
+// ---------------------------------------------------------------------------
+
+/// The files of the last committed tree (path → mode and blob id), used to
+/// turn a full directory snapshot into the path changes of a commit.
+#[derive(Debug, Default, Clone)]
+pub struct TreeManifest {
+    entries: BTreeMap<String, (FileMode, Oid)>,
+}
+
+impl TreeManifest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The files of commit `sha` in `repo`.  Submodules are not listed.
+    pub fn from_commit(repo: &Repository, sha: &str) -> Result<Self, GitError> {
+        let tree = repo.find_commit(Oid::from_str(sha)?)?.tree()?;
+        let mut entries = BTreeMap::new();
+        tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+            if let Some(mode) = FileMode::from_git(entry.filemode()) {
+                let name = entry.name().unwrap_or_default();
+                entries.insert(format!("{}{}", dir, name), (mode, entry.id()));
+            }
+            TreeWalkResult::Ok
+        })?;
+        Ok(Self { entries })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Make the manifest match the snapshot in `dir` and return the changes
+    /// that take the previous tree there.  Only paths for which `managed`
+    /// returns true are compared; other paths are left as they are.
+    pub fn update_from_dir(
+        &mut self,
+        dir: &Path,
+        managed: impl Fn(&str) -> bool,
+    ) -> Result<Vec<FileChange>, GitError> {
+        let mut current = BTreeMap::new();
+        let mut sources = BTreeMap::new();
+        scan_dir(dir, "", &managed, &mut current, &mut sources)?;
+
+        let mut changes = Vec::new();
+        for path in self.entries.keys() {
+            if managed(path) && !current.contains_key(path) {
+                changes.push(FileChange::Delete { path: path.clone() });
+            }
+        }
+        for (path, entry) in &current {
+            if self.entries.get(path) != Some(entry) {
+                changes.push(FileChange::Modify {
+                    path: path.clone(),
+                    mode: entry.0,
+                    source: sources.remove(path).expect("scanned path has a source"),
+                });
+            }
+        }
+        self.entries.retain(|path, _| !managed(path));
+        self.entries.extend(current);
+        Ok(changes)
+    }
+}
+
+fn scan_dir(
+    dir: &Path,
+    rel: &str,
+    managed: &impl Fn(&str) -> bool,
+    entries: &mut BTreeMap<String, (FileMode, Oid)>,
+    sources: &mut BTreeMap<String, PathBuf>,
+) -> Result<(), GitError> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = crate::svn_props::join_path(rel, &name);
+        if rel.is_empty() && name == ".git" {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            scan_dir(&entry.path(), &path, managed, entries, sources)?;
+            continue;
+        }
+        if !managed(&path) {
+            continue;
+        }
+        let (mode, content) = if file_type.is_symlink() {
+            let target = std::fs::read_link(entry.path())?;
+            (FileMode::Symlink, target.to_string_lossy().into_owned().into_bytes())
+        } else if crate::svn_props::is_executable(&entry.path()) {
+            (FileMode::Executable, std::fs::read(entry.path())?)
+        } else {
+            (FileMode::Regular, std::fs::read(entry.path())?)
+        };
+        let oid = Oid::hash_object(ObjectType::Blob, &content)?;
+        entries.insert(path.clone(), (mode, oid));
+        sources.insert(path, entry.path());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let out = Command::new("git").args(args).current_dir(dir).output().unwrap();
+        assert!(out.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&out.stderr));
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    }
+
+    fn commit<'a>(mark: i64, message: &'a str, changes: &'a [FileChange]) -> FastImportCommit<'a> {
+        FastImportCommit {
+            mark,
+            author_name: "Alice <svn>",
+            author_email: "alice@example.com",
+            author_time: 1_700_000_000 + mark,
+            committer_name: "Importer",
+            committer_email: "importer@example.com",
+            committer_time: 1_700_000_100,
+            message,
+            changes,
+        }
+    }
+
+    #[test]
+    fn test_manifest_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/a.txt"), "a\n").unwrap();
+        std::fs::write(dir.path().join(".keep"), "unmanaged\n").unwrap();
+
+        let managed = |path: &str| !path.starts_with('.');
+        let mut manifest = TreeManifest::new();
+        let changes = manifest.update_from_dir(dir.path(), managed).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(&changes[0],
+            FileChange::Modify { path, mode: FileMode::Regular, .. } if path == "src/a.txt"));
+        assert!(manifest.update_from_dir(dir.path(), managed).unwrap().is_empty());
+
+        std::fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+        std::fs::remove_file(dir.path().join("src/a.txt")).unwrap();
+        let changes = manifest.update_from_dir(dir.path(), managed).unwrap();
+        let paths: Vec<&str> = changes.iter().map(FileChange::path).collect();
+        assert_eq!(paths, ["src/a.txt", "b.txt"]);
+        assert!(matches!(changes[0], FileChange::Delete { .. }));
+        assert_eq!(manifest.len(), 1);
+    }
+
+    #[test]
+    fn test_fast_import_commits() {
+        let repo_dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(repo_dir.path()).unwrap();
+        let content = tempfile::tempdir().unwrap();
+        std::fs::write(content.path().join("a.txt"), "one\n").unwrap();
+        std::fs::write(content.path().join("weird"), "two\n").unwrap();
+
+        let mut manifest = TreeManifest::new();
+        let mut fi = FastImport::start(repo_dir.path(), "refs/heads/main", None).unwrap();
+        let changes = manifest.update_from_dir(content.path(), |_| true).unwrap();
+        fi.commit(&commit(3, "first\n", &changes)).unwrap();
+        let marks = fi.checkpoint().unwrap();
+        assert_eq!(marks.len(), 1);
+        assert_eq!(git(repo_dir.path(), &["rev-parse", "refs/heads/main"]), marks[&3]);
+
+        std::fs::remove_file(content.path().join("weird")).unwrap();
+        std::fs::write(content.path().join("\"odd\nname"), "three\n").unwrap();
+        let changes = manifest.update_from_dir(content.path(), |_| true).unwrap();
+        fi.commit(&commit(7, "second\n", &changes)).unwrap();
+        assert_eq!(fi.commit_count(), 2);
+        let marks = fi.finish().unwrap();
+        assert_eq!(marks.keys().copied().collect::<Vec<_>>(), [3, 7]);
+
+        let head = &marks[&7];
+        assert_eq!(git(repo_dir.path(), &["rev-parse", "main^"]), marks[&3]);
+        let files = git(repo_dir.path(), &["ls-tree", "-r", "--name-only", "-z", head]);
+        let files: Vec<&str> = files.split('\0').filter(|f| !f.is_empty()).collect();
+        assert_eq!(files, ["\"odd\nname", "a.txt"]);
+        let author = git(repo_dir.path(), &["log", "-1", "--format=%an <%ae> %at", head]);
+        assert_eq!(author, "Alice svn <alice@example.com> 1700000007");
+
+        // Continuing an existing branch builds on its head.
+        let manifest = TreeManifest::from_commit(&repo, head).unwrap();
+        assert_eq!(manifest.len(), 2);
+        let mut fi = FastImport::start(repo_dir.path(), "refs/heads/main", Some(head)).unwrap();
+        let changes = [FileChange::Delete { path: "a.txt".into() }];
+        fi.commit(&commit(9, "third\n", &changes)).unwrap();
+        let marks = fi.finish().unwrap();
+        assert_eq!(git(repo_dir.path(), &["rev-parse", "main^"]), *head);
+        assert_eq!(git(repo_dir.path(), &["rev-parse", "main"]), marks[&9]);
+    }
+
+    #[test]
+    fn test_fast_import_refs() {
+        let repo_dir = tempfile::tempdir().unwrap();
+        Repository::init(repo_dir.path()).unwrap();
+        let content = tempfile::tempdir().unwrap();
+        std::fs::write(content.path().join("a.txt"), "one\n").unwrap();
+
+        let mut manifest = TreeManifest::new();
+        let mut fi = FastImport::start_refs(repo_dir.path()).unwrap();
+        let changes = manifest.update_from_dir(content.path(), |_| true).unwrap();
+        fi.commit_on("refs/heads/main", &commit(1, "trunk\n", &changes)).unwrap();
+
+        // A branch forks from the first commit; another only points at it.
+        fi.reset("refs/heads/fork", Some(":1")).unwrap();
+        let changes = [FileChange::Delete { path: "a.txt".into() }];
+        fi.commit_on("refs/heads/fork", &commit(2, "branch\n", &changes)).unwrap();
+        fi.reset("refs/heads/copy", Some(":1")).unwrap();
+        fi.tag("v1", ":2", "Bob", "bob@example.com", 1_700_000_500, "release\n").unwrap();
+
+        // Resetting without a commit starts the ref over.
+        fi.reset("refs/heads/main", None).unwrap();
+        let changes = manifest.update_from_dir(content.path(), |_| true).unwrap();
+        assert!(changes.is_empty());
+        let empty: [FileChange; 0] = [];
+        fi.commit_on("refs/heads/main", &commit(3, "again\n", &empty)).unwrap();
+        let marks = fi.finish().unwrap();
+
+        let dir = repo_dir.path();
+        assert_eq!(git(dir, &["rev-parse", "fork^"]), marks[&1]);
+        assert_eq!(git(dir, &["rev-parse", "copy"]), marks[&1]);
+        assert_eq!(git(dir, &["rev-parse", "main"]), marks[&3]);
+        assert_eq!(git(dir, &["rev-list", "--count", "main"]), "1");
+        assert_eq!(git(dir, &["cat-file", "-t", "v1"]), "tag");
+        assert_eq!(git(dir, &["rev-parse", "v1^{commit}"]), marks[&2]);
+        let tagger = git(dir, &["for-each-ref", "--format=%(taggername) %(contents)", "refs/tags"]);
+        assert_eq!(tagger, "Bob release");
+    }
+
+    #[test]
+    fn test_fast_import_rejects_bad_stream() {
+        let repo_dir = tempfile::tempdir().unwrap();
+        Repository::init(repo_dir.path()).unwrap();
+        let mut fi = FastImport::start(repo_dir.path(), "refs/heads/main", None).unwrap();
+        assert!(fi.commit(&commit(0, "no mark\n", &[])).is_err());
+        let missing = [FileChange::Modify {
+            path: "gone".into(),
+            mode: FileMode::Regular,
+            source: repo_dir.path().join("does-not-exist"),
+        }];
+        assert!(matches!(
+            fi.commit(&commit(1, "missing source\n", &missing)),
+            Err(GitError::FastImport(_))
+        ));
+    }
+}
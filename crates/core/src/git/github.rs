@@ -1,4 +1,10 @@
 //! GitHub REST API client.
+//!
+//! The same client serves Gitea, whose API mirrors GitHub's, as well as
+//! GitLab and Bitbucket Server, whose requests and responses are translated
+//! in [`super::gitlab`] and [`super::bitbucket`].
+
+use std::sync::Arc;
 
 use hmac::{Hmac, Mac};
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT};
@@ -10,6 +16,8 @@ use tracing::{debug, info, instrument, warn};
 use crate::config::GitProvider;
 use crate::errors::GitHubError;
 
+use super::{bitbucket, gitlab, GitHubAppAuth};
+
 type HmacSha256 = Hmac<Sha256>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +32,18 @@ pub struct GitHubCommitDetail {
     pub message: String,
     pub author: GitHubGitActor,
     pub committer: GitHubGitActor,
+    /// Signature check of the commit (GitHub and Gitea only).
+    #[serde(default)]
+    pub verification: Option<CommitVerification>,
+}
+
+/// Result of the host's commit signature check.  `verified` means the
+/// signing key belongs to an account that owns the committer's email.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitVerification {
+    pub verified: bool,
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,13 +124,234 @@ impl std::fmt::Display for CommitStatusState {
     }
 }
 
+/// Combined commit status from `GET /repos/{owner}/{repo}/commits/{ref}/status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombinedStatus {
+    pub state: String,
+    #[serde(default)]
+    pub total_count: u64,
+}
+
+/// A check run from `GET /repos/{owner}/{repo}/commits/{ref}/check-runs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRunList {
+    check_runs: Vec<CheckRun>,
+}
+
+/// Overall CI outcome for a commit, from its statuses and check runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksState {
+    /// No status or check run reported: either CI has not registered the
+    /// commit yet or the repository has no CI at all.
+    Unreported,
+    Pending,
+    Success,
+    Failure,
+}
+
+impl ChecksState {
+    /// Combine commit statuses and check runs.
+    pub fn evaluate(status: &CombinedStatus, check_runs: &[CheckRun]) -> Self {
+        if status.total_count == 0 && check_runs.is_empty() {
+            return Self::Unreported;
+        }
+        let status_state = match (status.total_count, status.state.as_str()) {
+            (0, _) | (_, "success") => Self::Success,
+            (_, "pending") => Self::Pending,
+            _ => Self::Failure,
+        };
+        let runs_state = check_runs.iter().fold(Self::Success, |acc, run| {
+            let run_state = match (run.status.as_str(), run.conclusion.as_deref()) {
+                ("completed", Some("success" | "neutral" | "skipped")) => Self::Success,
+                ("completed", _) => Self::Failure,
+                _ => Self::Pending,
+            };
+            match (acc, run_state) {
+                (Self::Failure, _) | (_, Self::Failure) => Self::Failure,
+                (Self::Pending, _) | (_, Self::Pending) => Self::Pending,
+                _ => Self::Success,
+            }
+        });
+        match (status_state, runs_state) {
+            (Self::Failure, _) | (_, Self::Failure) => Self::Failure,
+            (Self::Pending, _) | (_, Self::Pending) => Self::Pending,
+            _ => Self::Success,
+        }
+    }
+}
+
+/// Call a [`ProviderApi`] method with the implementation of the client's
+/// provider.  Plain Git remotes have no API and never get here.
+macro_rules! provider_api {
+    ($client:expr, $method:ident($($arg:expr),*)) => {{
+        let client: &GitHubClient = $client;
+        match client.provider {
+            GitProvider::GitLab => gitlab::GitLabApi.$method(client, $($arg),*).await,
+            GitProvider::BitbucketServer => {
+                bitbucket::BitbucketApi.$method(client, $($arg),*).await
+            }
+            GitProvider::GitHub | GitProvider::Gitea | GitProvider::Plain => {
+                GitHubApi.$method(client, $($arg),*).await
+            }
+        }
+    }};
+}
+
+/// Requests and responses of one provider's REST API.  Implementations
+/// translate them into the GitHub types the sync engines use; the client
+/// handles authentication, retries and error responses.
+pub(super) trait ProviderApi {
+    async fn get_commits(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        since_sha: Option<&str>,
+    ) -> Result<Vec<GitHubCommit>, GitHubError>;
+
+    async fn create_webhook(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        callback_url: &str,
+        secret: &str,
+    ) -> Result<serde_json::Value, GitHubError>;
+
+    async fn create_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> Result<PullRequest, GitHubError>;
+
+    async fn merge_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<(), GitHubError>;
+
+    async fn add_labels(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<(), GitHubError>;
+
+    async fn request_reviewers(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+        reviewers: &[String],
+    ) -> Result<(), GitHubError>;
+
+    async fn get_combined_status(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+    ) -> Result<CombinedStatus, GitHubError>;
+
+    /// Check runs are GitHub's; other providers report statuses only.
+    async fn list_check_runs(
+        &self,
+        _client: &GitHubClient,
+        _repo: &str,
+        _sha: &str,
+    ) -> Result<Vec<CheckRun>, GitHubError> {
+        Ok(Vec::new())
+    }
+
+    async fn get_user(
+        &self,
+        client: &GitHubClient,
+        username: &str,
+    ) -> Result<GitHubUser, GitHubError>;
+
+    async fn post_commit_status(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+        state: CommitStatusState,
+        description: &str,
+    ) -> Result<(), GitHubError>;
+
+    async fn get_merged_pull_requests(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        base: &str,
+        since: Option<&str>,
+    ) -> Result<Vec<PullRequest>, GitHubError>;
+
+    /// Commits of a pull request, oldest first.
+    async fn get_pr_commits(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<Vec<GitHubCommit>, GitHubError>;
+
+    async fn get_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<PullRequest, GitHubError>;
+
+    async fn get_commit(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+    ) -> Result<GitHubCommitDetail2, GitHubError>;
+
+    /// Only providers that report signature verification know a verified
+    /// committer.
+    async fn verified_committer_email(
+        &self,
+        _client: &GitHubClient,
+        _repo: &str,
+        _sha: &str,
+    ) -> Result<Option<String>, GitHubError> {
+        Ok(None)
+    }
+
+    async fn repo_exists(&self, client: &GitHubClient, repo: &str) -> Result<bool, GitHubError>;
+
+    async fn create_repo(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        private: bool,
+        description: &str,
+    ) -> Result<serde_json::Value, GitHubError>;
+
+    async fn get_authenticated_user(&self, client: &GitHubClient)
+        -> Result<GitHubUser, GitHubError>;
+}
+
 /// Asynchronous GitHub REST API client.
 #[derive(Clone)]
 pub struct GitHubClient {
-    http: reqwest::Client,
-    api_url: String,
+    pub(super) http: reqwest::Client,
+    pub(super) api_url: String,
     token: String,
-    provider: GitProvider,
+    pub(super) provider: GitProvider,
+    /// GitHub App whose installation tokens replace `token`.
+    pub(super) app_auth: Option<Arc<GitHubAppAuth>>,
 }
 
 impl GitHubClient {
@@ -124,9 +365,14 @@ impl GitHubClient {
                 headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github+json"));
                 headers.insert("X-GitHub-Api-Version", HeaderValue::from_static("2022-11-28"));
             }
-            GitProvider::Gitea => {
+            GitProvider::Gitea | GitProvider::GitLab | GitProvider::Plain => {
                 headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
             }
+            GitProvider::BitbucketServer => {
+                headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+                // Skip the XSRF check Bitbucket applies to bodiless POSTs.
+                headers.insert("X-Atlassian-Token", HeaderValue::from_static("no-check"));
+            }
         }
         let http = reqwest::Client::builder()
             .default_headers(headers)
@@ -139,15 +385,41 @@ impl GitHubClient {
             api_url,
             token,
             provider,
+            app_auth: None,
         }
     }
 
-    /// Apply provider-appropriate authentication to a request.
-    fn auth(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        match self.provider {
+    /// Authenticate as a GitHub App installation instead of with the token.
+    pub fn with_app_auth(mut self, app_auth: Option<Arc<GitHubAppAuth>>) -> Self {
+        self.app_auth = app_auth;
+        self
+    }
+
+    /// Apply provider-appropriate authentication to a request.  A GitHub
+    /// App's installation token is renewed first if it is about to expire.
+    async fn auth(
+        &self,
+        req: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, GitHubError> {
+        Ok(match self.provider {
             GitProvider::Gitea => req.header("Authorization", format!("token {}", self.token)),
-            GitProvider::GitHub => req.bearer_auth(&self.token),
-        }
+            GitProvider::GitHub => match self.app_auth {
+                Some(ref app) => req.bearer_auth(app.installation_token().await?),
+                None => req.bearer_auth(&self.token),
+            },
+            GitProvider::BitbucketServer => req.bearer_auth(&self.token),
+            GitProvider::GitLab => req.header("PRIVATE-TOKEN", &self.token),
+            // Plain Git remotes have no API; callers check `has_api` first.
+            GitProvider::Plain => req,
+        })
+    }
+
+    /// Authenticate and send a request.
+    pub(super) async fn send(
+        &self,
+        req: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, GitHubError> {
+        Ok(self.auth(req).await?.send().await?)
     }
 
     #[instrument(skip(self))]
@@ -156,35 +428,7 @@ impl GitHubClient {
         repo: &str,
         since_sha: Option<&str>,
     ) -> Result<Vec<GitHubCommit>, GitHubError> {
-        let first_url = format!("{}/repos/{}/commits", self.api_url, repo);
-        let since_sha = since_sha.map(|s| s.to_string());
-        let resp = self.retry_request(|| {
-            let mut req = self.auth(self.http.get(&first_url));
-            if let Some(ref sha) = since_sha {
-                req = req.query(&[("sha", sha.as_str())]);
-            }
-            req.query(&[("per_page", "100")])
-        }).await?;
-
-        let mut next_link = Self::parse_next_link(resp.headers());
-        let mut all_commits: Vec<GitHubCommit> = resp.json().await?;
-        let mut pages = 1usize;
-
-        while let Some(ref url) = next_link {
-            if pages >= 10 {
-                warn!(pages, "reached GitHub pagination limit for get_commits");
-                break;
-            }
-            let url_clone = url.clone();
-            let resp = self.retry_request(|| self.auth(self.http.get(&url_clone))).await?;
-            next_link = Self::parse_next_link(resp.headers());
-            let page: Vec<GitHubCommit> = resp.json().await?;
-            all_commits.extend(page);
-            pages += 1;
-        }
-
-        debug!(count = all_commits.len(), pages, "fetched commits");
-        Ok(all_commits)
+        provider_api!(self, get_commits(repo, since_sha))
     }
 
     #[instrument(skip(self, secret))]
@@ -194,27 +438,15 @@ impl GitHubClient {
         callback_url: &str,
         secret: &str,
     ) -> Result<serde_json::Value, GitHubError> {
-        let url = format!("{}/repos/{}/hooks", self.api_url, repo);
-        let body = serde_json::json!({
-            "name": "web", "active": true, "events": ["push", "pull_request"],
-            "config": { "url": callback_url, "content_type": "json", "secret": secret, "insecure_ssl": "0" }
-        });
-        let resp = self.auth(self
-                .http
-                .post(&url))
-            .json(&body)
-            .send()
-            .await?;
-        let resp = self.check_response(resp).await?;
-        let hook: serde_json::Value = resp.json().await?;
-        info!(hook_id = %hook["id"], "created webhook");
-        Ok(hook)
+        provider_api!(self, create_webhook(repo, callback_url, secret))
     }
 
-    /// Verify a GitHub/Gitea webhook signature.
+    /// Verify a GitHub/Gitea/Bitbucket Server webhook signature, or the GitLab
+    /// webhook token passed as `signature`.
     pub fn verify_webhook_signature(payload: &[u8], signature: &str, secret: &str, provider: &GitProvider) -> bool {
         let hex_sig = match provider {
-            GitProvider::GitHub => {
+            // Bitbucket Server signs like GitHub, in `X-Hub-Signature`.
+            GitProvider::GitHub | GitProvider::BitbucketServer => {
                 match signature.strip_prefix("sha256=") {
                     Some(s) => s,
                     None => {
@@ -224,6 +456,11 @@ impl GitHubClient {
                 }
             }
             GitProvider::Gitea => signature, // Gitea sends raw hex, no prefix
+            GitProvider::GitLab => return gitlab::verify_webhook_token(signature, secret),
+            GitProvider::Plain => {
+                warn!("plain Git remotes do not send webhooks");
+                return false;
+            }
         };
         let expected_bytes = match hex::decode(hex_sig) {
             Ok(b) => b,
@@ -252,44 +489,68 @@ impl GitHubClient {
         head: &str,
         base: &str,
     ) -> Result<PullRequest, GitHubError> {
-        let url = format!("{}/repos/{}/pulls", self.api_url, repo);
-        let payload =
-            serde_json::json!({ "title": title, "body": body, "head": head, "base": base });
-        let resp = self.auth(self
-                .http
-                .post(&url))
-            .json(&payload)
-            .send()
-            .await?;
-        let resp = self.check_response(resp).await?;
-        let pr: PullRequest = resp.json().await?;
-        info!(number = pr.number, "created pull request");
-        Ok(pr)
+        provider_api!(self, create_pull_request(repo, title, body, head, base))
     }
 
     #[instrument(skip(self))]
     pub async fn merge_pull_request(&self, repo: &str, pr_number: u64) -> Result<(), GitHubError> {
-        let url = format!("{}/repos/{}/pulls/{}/merge", self.api_url, repo, pr_number);
-        let payload = serde_json::json!({ "merge_method": "merge" });
-        let resp = self.auth(self
-                .http
-                .put(&url))
-            .json(&payload)
-            .send()
-            .await?;
-        let _resp = self.check_response(resp).await?;
-        info!(pr_number, "merged pull request");
-        Ok(())
+        provider_api!(self, merge_pull_request(repo, pr_number))
+    }
+
+    /// Add labels to a pull request (or issue).  Bitbucket Server pull
+    /// requests have no labels, so this does nothing there.
+    #[instrument(skip(self))]
+    pub async fn add_labels(
+        &self,
+        repo: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<(), GitHubError> {
+        provider_api!(self, add_labels(repo, number, labels))
+    }
+
+    /// Request reviews on a pull request.
+    #[instrument(skip(self))]
+    pub async fn request_reviewers(
+        &self,
+        repo: &str,
+        pr_number: u64,
+        reviewers: &[String],
+    ) -> Result<(), GitHubError> {
+        provider_api!(self, request_reviewers(repo, pr_number, reviewers))
+    }
+
+    /// Get the combined commit status for a ref.
+    #[instrument(skip(self))]
+    pub async fn get_combined_status(
+        &self,
+        repo: &str,
+        sha: &str,
+    ) -> Result<CombinedStatus, GitHubError> {
+        provider_api!(self, get_combined_status(repo, sha))
+    }
+
+    /// List the check runs for a ref. Only GitHub has check runs, so this is
+    /// always empty elsewhere.
+    #[instrument(skip(self))]
+    pub async fn list_check_runs(
+        &self,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<CheckRun>, GitHubError> {
+        provider_api!(self, list_check_runs(repo, sha))
+    }
+
+    /// Overall CI outcome for a commit.
+    pub async fn checks_state(&self, repo: &str, sha: &str) -> Result<ChecksState, GitHubError> {
+        let status = self.get_combined_status(repo, sha).await?;
+        let check_runs = self.list_check_runs(repo, sha).await?;
+        Ok(ChecksState::evaluate(&status, &check_runs))
     }
 
     #[instrument(skip(self))]
     pub async fn get_user(&self, username: &str) -> Result<GitHubUser, GitHubError> {
-        let url = format!("{}/users/{}", self.api_url, username);
-        let resp = self.auth(self.http.get(&url)).send().await?;
-        let resp = self.check_response(resp).await?;
-        let user: GitHubUser = resp.json().await?;
-        debug!(login = %user.login, "fetched user");
-        Ok(user)
+        provider_api!(self, get_user(username))
     }
 
     #[instrument(skip(self))]
@@ -300,17 +561,7 @@ impl GitHubClient {
         state: CommitStatusState,
         description: &str,
     ) -> Result<(), GitHubError> {
-        let url = format!("{}/repos/{}/statuses/{}", self.api_url, repo, sha);
-        let payload = serde_json::json!({ "state": state.to_string(), "description": description, "context": "reposync" });
-        let resp = self.auth(self
-                .http
-                .post(&url))
-            .json(&payload)
-            .send()
-            .await?;
-        let _resp = self.check_response(resp).await?;
-        debug!(sha, state = %state, "posted commit status");
-        Ok(())
+        provider_api!(self, post_commit_status(repo, sha, state, description))
     }
 
     // -- Personal Branch Mode methods -----------------------------------------
@@ -323,55 +574,7 @@ impl GitHubClient {
         base: &str,
         since: Option<&str>,
     ) -> Result<Vec<PullRequest>, GitHubError> {
-        let first_url = format!("{}/repos/{}/pulls", self.api_url, repo);
-        let base = base.to_string();
-        let since = since.map(|s| s.to_string());
-        let resp = self.retry_request(|| {
-            let mut req = self.auth(self.http.get(&first_url)).query(&[
-                ("state", "closed"),
-                ("base", base.as_str()),
-                ("per_page", "100"),
-            ]);
-            if since.is_some() {
-                req = req.query(&[("sort", "updated"), ("direction", "desc")]);
-            }
-            req
-        }).await?;
-
-        let mut next_link = Self::parse_next_link(resp.headers());
-        let mut all_prs: Vec<PullRequest> = resp.json().await?;
-        let mut pages = 1usize;
-
-        while let Some(ref url) = next_link {
-            if pages >= 10 {
-                warn!(pages, "reached GitHub pagination limit for get_merged_pull_requests");
-                break;
-            }
-            let url_clone = url.clone();
-            let resp = self.retry_request(|| self.auth(self.http.get(&url_clone))).await?;
-            next_link = Self::parse_next_link(resp.headers());
-            let page: Vec<PullRequest> = resp.json().await?;
-            all_prs.extend(page);
-            pages += 1;
-        }
-
-        // Filter to only merged PRs, optionally after a timestamp
-        let merged: Vec<PullRequest> = all_prs
-            .into_iter()
-            .filter(|pr| pr.merged == Some(true))
-            .filter(|pr| {
-                if let Some(ref since_dt) = since {
-                    pr.merged_at
-                        .as_deref()
-                        .map(|m| m >= since_dt.as_str())
-                        .unwrap_or(false)
-                } else {
-                    true
-                }
-            })
-            .collect();
-        debug!(count = merged.len(), base = %base, pages, "fetched merged pull requests");
-        Ok(merged)
+        provider_api!(self, get_merged_pull_requests(repo, base, since))
     }
 
     /// Get commits for a specific pull request.
@@ -381,20 +584,7 @@ impl GitHubClient {
         repo: &str,
         pr_number: u64,
     ) -> Result<Vec<GitHubCommit>, GitHubError> {
-        let url = format!(
-            "{}/repos/{}/pulls/{}/commits",
-            self.api_url, repo, pr_number
-        );
-        let resp = self.auth(self
-                .http
-                .get(&url))
-            .query(&[("per_page", "100")])
-            .send()
-            .await?;
-        let resp = self.check_response(resp).await?;
-        let commits: Vec<GitHubCommit> = resp.json().await?;
-        debug!(count = commits.len(), pr_number, "fetched PR commits");
-        Ok(commits)
+        provider_api!(self, get_pr_commits(repo, pr_number))
     }
 
     /// Get a single pull request by number.
@@ -404,12 +594,7 @@ impl GitHubClient {
         repo: &str,
         pr_number: u64,
     ) -> Result<PullRequest, GitHubError> {
-        let url = format!("{}/repos/{}/pulls/{}", self.api_url, repo, pr_number);
-        let resp = self.auth(self.http.get(&url)).send().await?;
-        let resp = self.check_response(resp).await?;
-        let pr: PullRequest = resp.json().await?;
-        debug!(number = pr.number, state = %pr.state, "fetched pull request");
-        Ok(pr)
+        provider_api!(self, get_pull_request(repo, pr_number))
     }
 
     /// Get a single commit by SHA (includes parent count for merge detection).
@@ -419,66 +604,48 @@ impl GitHubClient {
         repo: &str,
         sha: &str,
     ) -> Result<GitHubCommitDetail2, GitHubError> {
-        let url = format!("{}/repos/{}/commits/{}", self.api_url, repo, sha);
-        let resp = self.auth(self.http.get(&url)).send().await?;
-        let resp = self.check_response(resp).await?;
-        let commit: GitHubCommitDetail2 = resp.json().await?;
-        debug!(
-            sha,
-            parents = commit.parents.len(),
-            "fetched commit details"
-        );
-        Ok(commit)
+        provider_api!(self, get_commit(repo, sha))
+    }
+
+    /// Email of the committer of `sha` if the host verified the commit's
+    /// signature, i.e. the signer owns that address.  `None` for unsigned or
+    /// unverified commits and for hosts that do not report verification.
+    pub async fn verified_committer_email(
+        &self,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Option<String>, GitHubError> {
+        provider_api!(self, verified_committer_email(repo, sha))
     }
 
     /// Check whether a repository exists.
     #[instrument(skip(self))]
     pub async fn repo_exists(&self, repo: &str) -> Result<bool, GitHubError> {
-        let url = format!("{}/repos/{}", self.api_url, repo);
-        let resp = self.auth(self.http.head(&url)).send().await?;
-        Ok(resp.status().is_success())
+        provider_api!(self, repo_exists(repo))
     }
 
-    /// Create a new GitHub repository.
+    /// Create the `owner/name` repository.  GitHub and Gitea create it in
+    /// the authenticated user's namespace (a GitHub App in the `owner`
+    /// organization); GitLab creates it in the `owner` namespace (user or
+    /// group path) and Bitbucket Server in the project whose key is `owner`.
     #[instrument(skip(self))]
     pub async fn create_repo(
         &self,
-        name: &str,
+        repo: &str,
         private: bool,
         description: &str,
     ) -> Result<serde_json::Value, GitHubError> {
-        let url = format!("{}/user/repos", self.api_url);
-        let payload = serde_json::json!({
-            "name": name,
-            "private": private,
-            "description": description,
-            "auto_init": false,
-        });
-        let resp = self.auth(self
-                .http
-                .post(&url))
-            .json(&payload)
-            .send()
-            .await?;
-        let resp = self.check_response(resp).await?;
-        let repo: serde_json::Value = resp.json().await?;
-        info!(repo_name = name, private, "created repository");
-        Ok(repo)
+        provider_api!(self, create_repo(repo, private, description))
     }
 
     /// Get the authenticated user's login.
     #[instrument(skip(self))]
     pub async fn get_authenticated_user(&self) -> Result<GitHubUser, GitHubError> {
-        let url = format!("{}/user", self.api_url);
-        let resp = self.auth(self.http.get(&url)).send().await?;
-        let resp = self.check_response(resp).await?;
-        let user: GitHubUser = resp.json().await?;
-        debug!(login = %user.login, "fetched authenticated user");
-        Ok(user)
+        provider_api!(self, get_authenticated_user())
     }
 
     /// Parse the `rel="next"` URL from a GitHub `Link` response header.
-    fn parse_next_link(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    pub(super) fn parse_next_link(headers: &reqwest::header::HeaderMap) -> Option<String> {
         let link_val = headers.get("link")?.to_str().ok()?;
         for part in link_val.split(',') {
             let mut url_part = None;
@@ -501,14 +668,17 @@ impl GitHubClient {
     /// Execute an HTTP request with exponential backoff retry.
     ///
     /// Retries up to 3 times on 429 (Too Many Requests) and 5xx server errors.
-    async fn retry_request<F>(&self, make_req: F) -> Result<reqwest::Response, GitHubError>
+    pub(super) async fn retry_request<F>(
+        &self,
+        make_req: F,
+    ) -> Result<reqwest::Response, GitHubError>
     where
         F: Fn() -> reqwest::RequestBuilder,
     {
         const MAX_RETRIES: u32 = 3;
         let mut attempt = 0u32;
         loop {
-            let resp = make_req().send().await?;
+            let resp = self.send(make_req()).await?;
             let status = resp.status();
 
             if status.is_success() {
@@ -544,7 +714,7 @@ impl GitHubClient {
     /// Auth (401/403) and rate-limit (429) errors are mapped to their specific
     /// error variants.  All other non-success statuses include the safe body
     /// snippet in the `ApiError` variant.
-    async fn check_response(
+    pub(super) async fn check_response(
         &self,
         resp: reqwest::Response,
     ) -> Result<reqwest::Response, GitHubError> {
@@ -640,6 +810,359 @@ impl GitHubClient {
     }
 }
 
+/// GitHub's REST API, which Gitea mirrors.
+struct GitHubApi;
+
+impl ProviderApi for GitHubApi {
+    async fn get_commits(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        since_sha: Option<&str>,
+    ) -> Result<Vec<GitHubCommit>, GitHubError> {
+        let first_url = format!("{}/repos/{}/commits", client.api_url, repo);
+        let since_sha = since_sha.map(|s| s.to_string());
+        let resp = client.retry_request(|| {
+            let mut req = client.http.get(&first_url);
+            if let Some(ref sha) = since_sha {
+                req = req.query(&[("sha", sha.as_str())]);
+            }
+            req.query(&[("per_page", "100")])
+        }).await?;
+
+        let mut next_link = GitHubClient::parse_next_link(resp.headers());
+        let mut all_commits: Vec<GitHubCommit> = resp.json().await?;
+        let mut pages = 1usize;
+
+        while let Some(ref url) = next_link {
+            if pages >= 10 {
+                warn!(pages, "reached GitHub pagination limit for get_commits");
+                break;
+            }
+            let url_clone = url.clone();
+            let resp = client.retry_request(|| client.http.get(&url_clone)).await?;
+            next_link = GitHubClient::parse_next_link(resp.headers());
+            let page: Vec<GitHubCommit> = resp.json().await?;
+            all_commits.extend(page);
+            pages += 1;
+        }
+
+        debug!(count = all_commits.len(), pages, "fetched commits");
+        Ok(all_commits)
+    }
+
+    async fn create_webhook(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        callback_url: &str,
+        secret: &str,
+    ) -> Result<serde_json::Value, GitHubError> {
+        let url = format!("{}/repos/{}/hooks", client.api_url, repo);
+        let body = serde_json::json!({
+            "name": "web", "active": true, "events": ["push", "pull_request"],
+            "config": {
+                "url": callback_url, "content_type": "json", "secret": secret, "insecure_ssl": "0"
+            }
+        });
+        let resp = client.send(client.http.post(&url).json(&body)).await?;
+        let resp = client.check_response(resp).await?;
+        let hook: serde_json::Value = resp.json().await?;
+        info!(hook_id = %hook["id"], "created webhook");
+        Ok(hook)
+    }
+
+    async fn create_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> Result<PullRequest, GitHubError> {
+        let url = format!("{}/repos/{}/pulls", client.api_url, repo);
+        let payload =
+            serde_json::json!({ "title": title, "body": body, "head": head, "base": base });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        let resp = client.check_response(resp).await?;
+        let pr: PullRequest = resp.json().await?;
+        info!(number = pr.number, "created pull request");
+        Ok(pr)
+    }
+
+    async fn merge_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<(), GitHubError> {
+        let url = format!("{}/repos/{}/pulls/{}/merge", client.api_url, repo, pr_number);
+        let payload = serde_json::json!({ "merge_method": "merge" });
+        let resp = client.send(client.http.put(&url).json(&payload)).await?;
+        let _resp = client.check_response(resp).await?;
+        info!(pr_number, "merged pull request");
+        Ok(())
+    }
+
+    async fn add_labels(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<(), GitHubError> {
+        let url = format!("{}/repos/{}/issues/{}/labels", client.api_url, repo, number);
+        let payload = serde_json::json!({ "labels": labels });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        let _resp = client.check_response(resp).await?;
+        debug!(number, ?labels, "added labels");
+        Ok(())
+    }
+
+    async fn request_reviewers(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+        reviewers: &[String],
+    ) -> Result<(), GitHubError> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}/requested_reviewers",
+            client.api_url, repo, pr_number
+        );
+        let payload = serde_json::json!({ "reviewers": reviewers });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        let _resp = client.check_response(resp).await?;
+        debug!(pr_number, ?reviewers, "requested reviewers");
+        Ok(())
+    }
+
+    async fn get_combined_status(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+    ) -> Result<CombinedStatus, GitHubError> {
+        let url = format!("{}/repos/{}/commits/{}/status", client.api_url, repo, sha);
+        let resp = client.send(client.http.get(&url)).await?;
+        let resp = client.check_response(resp).await?;
+        let status: CombinedStatus = resp.json().await?;
+        debug!(sha, state = %status.state, "fetched combined status");
+        Ok(status)
+    }
+
+    async fn list_check_runs(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<CheckRun>, GitHubError> {
+        // Gitea shares this API but has no check runs.
+        if client.provider != GitProvider::GitHub {
+            return Ok(Vec::new());
+        }
+        let url = format!("{}/repos/{}/commits/{}/check-runs", client.api_url, repo, sha);
+        let resp = client.send(client.http.get(&url).query(&[("per_page", "100")])).await?;
+        let resp = client.check_response(resp).await?;
+        let list: CheckRunList = resp.json().await?;
+        debug!(sha, count = list.check_runs.len(), "fetched check runs");
+        Ok(list.check_runs)
+    }
+
+    async fn get_user(
+        &self,
+        client: &GitHubClient,
+        username: &str,
+    ) -> Result<GitHubUser, GitHubError> {
+        let url = format!("{}/users/{}", client.api_url, username);
+        let resp = client.send(client.http.get(&url)).await?;
+        let resp = client.check_response(resp).await?;
+        let user: GitHubUser = resp.json().await?;
+        debug!(login = %user.login, "fetched user");
+        Ok(user)
+    }
+
+    async fn post_commit_status(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+        state: CommitStatusState,
+        description: &str,
+    ) -> Result<(), GitHubError> {
+        let url = format!("{}/repos/{}/statuses/{}", client.api_url, repo, sha);
+        let payload = serde_json::json!({
+            "state": state.to_string(), "description": description, "context": "reposync"
+        });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        let _resp = client.check_response(resp).await?;
+        debug!(sha, state = %state, "posted commit status");
+        Ok(())
+    }
+
+    async fn get_merged_pull_requests(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        base: &str,
+        since: Option<&str>,
+    ) -> Result<Vec<PullRequest>, GitHubError> {
+        let first_url = format!("{}/repos/{}/pulls", client.api_url, repo);
+        let base = base.to_string();
+        let since = since.map(|s| s.to_string());
+        let resp = client.retry_request(|| {
+            let mut req = client.http.get(&first_url).query(&[
+                ("state", "closed"),
+                ("base", base.as_str()),
+                ("per_page", "100"),
+            ]);
+            if since.is_some() {
+                req = req.query(&[("sort", "updated"), ("direction", "desc")]);
+            }
+            req
+        }).await?;
+
+        let mut next_link = GitHubClient::parse_next_link(resp.headers());
+        let mut all_prs: Vec<PullRequest> = resp.json().await?;
+        let mut pages = 1usize;
+
+        while let Some(ref url) = next_link {
+            if pages >= 10 {
+                warn!(pages, "reached GitHub pagination limit for get_merged_pull_requests");
+                break;
+            }
+            let url_clone = url.clone();
+            let resp = client.retry_request(|| client.http.get(&url_clone)).await?;
+            next_link = GitHubClient::parse_next_link(resp.headers());
+            let page: Vec<PullRequest> = resp.json().await?;
+            all_prs.extend(page);
+            pages += 1;
+        }
+
+        // Filter to only merged PRs, optionally after a timestamp
+        let merged: Vec<PullRequest> = all_prs
+            .into_iter()
+            .filter(|pr| pr.merged == Some(true))
+            .filter(|pr| {
+                if let Some(ref since_dt) = since {
+                    pr.merged_at
+                        .as_deref()
+                        .map(|m| m >= since_dt.as_str())
+                        .unwrap_or(false)
+                } else {
+                    true
+                }
+            })
+            .collect();
+        debug!(count = merged.len(), base = %base, pages, "fetched merged pull requests");
+        Ok(merged)
+    }
+
+    async fn get_pr_commits(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<Vec<GitHubCommit>, GitHubError> {
+        let url = format!(
+            "{}/repos/{}/pulls/{}/commits",
+            client.api_url, repo, pr_number
+        );
+        let resp = client.send(client.http.get(&url).query(&[("per_page", "100")])).await?;
+        let resp = client.check_response(resp).await?;
+        let commits: Vec<GitHubCommit> = resp.json().await?;
+        debug!(count = commits.len(), pr_number, "fetched PR commits");
+        Ok(commits)
+    }
+
+    async fn get_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<PullRequest, GitHubError> {
+        let url = format!("{}/repos/{}/pulls/{}", client.api_url, repo, pr_number);
+        let resp = client.send(client.http.get(&url)).await?;
+        let resp = client.check_response(resp).await?;
+        let pr: PullRequest = resp.json().await?;
+        debug!(number = pr.number, state = %pr.state, "fetched pull request");
+        Ok(pr)
+    }
+
+    async fn get_commit(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+    ) -> Result<GitHubCommitDetail2, GitHubError> {
+        let url = format!("{}/repos/{}/commits/{}", client.api_url, repo, sha);
+        let resp = client.send(client.http.get(&url)).await?;
+        let resp = client.check_response(resp).await?;
+        let commit: GitHubCommitDetail2 = resp.json().await?;
+        debug!(
+            sha,
+            parents = commit.parents.len(),
+            "fetched commit details"
+        );
+        Ok(commit)
+    }
+
+    async fn verified_committer_email(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Option<String>, GitHubError> {
+        let commit = self.get_commit(client, repo, sha).await?.commit;
+        let verified = commit.verification.is_some_and(|v| v.verified);
+        Ok(Some(commit.committer.email).filter(|e| verified && !e.is_empty()))
+    }
+
+    async fn repo_exists(&self, client: &GitHubClient, repo: &str) -> Result<bool, GitHubError> {
+        let url = format!("{}/repos/{}", client.api_url, repo);
+        let resp = client.send(client.http.head(&url)).await?;
+        Ok(resp.status().is_success())
+    }
+
+    async fn create_repo(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        private: bool,
+        description: &str,
+    ) -> Result<serde_json::Value, GitHubError> {
+        let name = repo.rsplit('/').next().unwrap_or(repo);
+        let url = match (&client.app_auth, repo.split_once('/')) {
+            (Some(_), Some((owner, _))) => format!("{}/orgs/{}/repos", client.api_url, owner),
+            _ => format!("{}/user/repos", client.api_url),
+        };
+        let payload = serde_json::json!({
+            "name": name,
+            "private": private,
+            "description": description,
+            "auto_init": false,
+        });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        let resp = client.check_response(resp).await?;
+        let repo: serde_json::Value = resp.json().await?;
+        info!(repo_name = name, private, "created repository");
+        Ok(repo)
+    }
+
+    async fn get_authenticated_user(
+        &self,
+        client: &GitHubClient,
+    ) -> Result<GitHubUser, GitHubError> {
+        let url = format!("{}/user", client.api_url);
+        let resp = client.send(client.http.get(&url)).await?;
+        let resp = client.check_response(resp).await?;
+        let user: GitHubUser = resp.json().await?;
+        debug!(login = %user.login, "fetched authenticated user");
+        Ok(user)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -657,6 +1180,79 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_verify_webhook_signature_bitbucket_server() {
+        let secret = "my-secret";
+        let payload = br#"{"eventKey":"repo:refs_changed"}"#;
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(payload);
+        let hex_sig = hex::encode(mac.finalize().into_bytes());
+        let provider = GitProvider::BitbucketServer;
+        let signature = format!("sha256={}", hex_sig);
+        assert!(GitHubClient::verify_webhook_signature(payload, &signature, secret, &provider));
+        assert!(!GitHubClient::verify_webhook_signature(payload, &hex_sig, secret, &provider));
+    }
+
+    #[test]
+    fn test_checks_state_evaluate() {
+        let status = |state: &str, total_count| CombinedStatus {
+            state: state.into(),
+            total_count,
+        };
+        let run = |status: &str, conclusion: Option<&str>| CheckRun {
+            name: "ci".into(),
+            status: status.into(),
+            conclusion: conclusion.map(String::from),
+        };
+
+        // Nothing reported: CI has not picked the commit up or there is none.
+        assert_eq!(ChecksState::evaluate(&status("pending", 0), &[]), ChecksState::Unreported);
+        assert_eq!(ChecksState::evaluate(&status("pending", 1), &[]), ChecksState::Pending);
+        assert_eq!(ChecksState::evaluate(&status("failure", 1), &[]), ChecksState::Failure);
+        assert_eq!(
+            ChecksState::evaluate(
+                &status("success", 1),
+                &[run("completed", Some("success")), run("in_progress", None)]
+            ),
+            ChecksState::Pending
+        );
+        assert_eq!(
+            ChecksState::evaluate(
+                &status("pending", 0),
+                &[run("in_progress", None), run("completed", Some("failure"))]
+            ),
+            ChecksState::Failure
+        );
+        assert_eq!(
+            ChecksState::evaluate(&status("pending", 0), &[run("completed", Some("skipped"))]),
+            ChecksState::Success
+        );
+    }
+
+    #[test]
+    fn test_commit_verification_parsed() {
+        let commit: GitHubCommitDetail2 = serde_json::from_value(serde_json::json!({
+            "sha": "abc",
+            "commit": {
+                "message": "Fix",
+                "author": {"name": "Ann", "email": "ann@example.com", "date": null},
+                "committer": {"name": "Ann", "email": "ann@example.com", "date": null},
+                "verification": {"verified": true, "reason": "valid", "signature": "-----"}
+            },
+            "author": null,
+            "parents": []
+        }))
+        .unwrap();
+        assert!(commit.commit.verification.is_some_and(|v| v.verified));
+        let unsigned: GitHubCommitDetail = serde_json::from_value(serde_json::json!({
+            "message": "Fix",
+            "author": {"name": "Ann", "email": "ann@example.com", "date": null},
+            "committer": {"name": "Ann", "email": "ann@example.com", "date": null}
+        }))
+        .unwrap();
+        assert!(unsigned.verification.is_none());
+    }
+
     #[test]
     fn test_verify_webhook_signature_invalid() {
         assert!(!GitHubClient::verify_webhook_signature(
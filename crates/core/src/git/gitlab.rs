@@ -0,0 +1,710 @@
+//! GitLab REST API (v4) requests and shapes for [`GitHubClient`](super::GitHubClient).
+//!
+//! With [`GitProvider::GitLab`](crate::config::GitProvider::GitLab) the
+//! client talks to `/projects/:id/...` endpoints instead of
+//! `/repos/:owner/:repo/...`.  Projects are addressed by their URL-encoded
+//! path (`group/sub/name`), merge requests by their project-local `iid`.
+//! The responses are converted into the GitHub types the sync engines use,
+//! so merge requests become [`PullRequest`]s numbered by `iid`.
+
+use serde::Deserialize;
+use subtle::ConstantTimeEq;
+use tracing::{debug, info, warn};
+
+use crate::errors::GitHubError;
+
+use super::github::{
+    CombinedStatus, CommitStatusState, GitHubClient, GitHubCommit, GitHubCommitDetail,
+    GitHubCommitDetail2, GitHubCommitParent, GitHubGitActor, GitHubUser, ProviderApi,
+    PullRequest, PullRequestRef,
+};
+
+/// `/projects/:id` path of a project: its full path with every `/` encoded.
+pub fn project_path(repo: &str) -> String {
+    format!("projects/{}", encode_path(repo))
+}
+
+/// `/namespaces/:id` path of a group or user namespace.
+fn namespace_path(namespace: &str) -> String {
+    format!("namespaces/{}", encode_path(namespace))
+}
+
+/// Percent-encodes a full path for use as a single `:id` segment.
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len() + 8);
+    for b in path.trim_matches('/').bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(b as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    encoded
+}
+
+/// Project endpoint of `repo` below the API base URL.
+pub fn project_url(api_url: &str, repo: &str) -> String {
+    format!("{}/{}", api_url.trim_end_matches('/'), project_path(repo))
+}
+
+/// Verify the `X-Gitlab-Token` header of a webhook delivery.  GitLab sends
+/// the configured secret itself instead of a signature.
+pub fn verify_webhook_token(token: &str, secret: &str) -> bool {
+    token.len() == secret.len() && bool::from(token.as_bytes().ct_eq(secret.as_bytes()))
+}
+
+/// GitLab commit status name for a [`CommitStatusState`].
+pub fn status_state(state: &CommitStatusState) -> &'static str {
+    match state {
+        CommitStatusState::Pending => "pending",
+        CommitStatusState::Success => "success",
+        CommitStatusState::Failure => "failed",
+        CommitStatusState::Error => "failed",
+    }
+}
+
+/// A merge request from `/projects/:id/merge_requests`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeRequest {
+    pub iid: u64,
+    pub title: String,
+    pub web_url: String,
+    /// `opened`, `closed`, `locked` or `merged`.
+    pub state: String,
+    pub source_branch: String,
+    pub target_branch: String,
+    #[serde(default)]
+    pub sha: Option<String>,
+    #[serde(default)]
+    pub merge_commit_sha: Option<String>,
+    #[serde(default)]
+    pub squash_commit_sha: Option<String>,
+    #[serde(default)]
+    pub merged_at: Option<String>,
+    #[serde(default)]
+    pub diff_refs: Option<DiffRefs>,
+}
+
+/// Base and head of a merge request's diff.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiffRefs {
+    #[serde(default)]
+    pub base_sha: Option<String>,
+}
+
+impl From<MergeRequest> for PullRequest {
+    fn from(mr: MergeRequest) -> Self {
+        let merged = mr.state == "merged";
+        PullRequest {
+            number: mr.iid,
+            title: mr.title,
+            html_url: mr.web_url,
+            state: if mr.state == "opened" { "open" } else { "closed" }.into(),
+            head: PullRequestRef {
+                ref_name: mr.source_branch,
+                sha: mr.sha.unwrap_or_default(),
+            },
+            base: PullRequestRef {
+                ref_name: mr.target_branch,
+                sha: mr.diff_refs.and_then(|d| d.base_sha).unwrap_or_default(),
+            },
+            merged: Some(merged),
+            // A squash merge leaves the squashed commit on the target.
+            merge_commit_sha: mr.merge_commit_sha.or(mr.squash_commit_sha),
+            merged_at: mr.merged_at,
+        }
+    }
+}
+
+/// A commit from `/projects/:id/repository/commits`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Commit {
+    pub id: String,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+    #[serde(default)]
+    pub authored_date: Option<String>,
+    pub committer_name: String,
+    pub committer_email: String,
+    #[serde(default)]
+    pub committed_date: Option<String>,
+    #[serde(default)]
+    pub parent_ids: Vec<String>,
+}
+
+impl Commit {
+    fn detail(&self) -> GitHubCommitDetail {
+        GitHubCommitDetail {
+            message: self.message.clone(),
+            author: GitHubGitActor {
+                name: self.author_name.clone(),
+                email: self.author_email.clone(),
+                date: self.authored_date.clone(),
+            },
+            committer: GitHubGitActor {
+                name: self.committer_name.clone(),
+                email: self.committer_email.clone(),
+                date: self.committed_date.clone(),
+            },
+            verification: None,
+        }
+    }
+}
+
+impl From<Commit> for GitHubCommit {
+    fn from(commit: Commit) -> Self {
+        GitHubCommit {
+            commit: commit.detail(),
+            sha: commit.id,
+            author: None,
+        }
+    }
+}
+
+impl From<Commit> for GitHubCommitDetail2 {
+    fn from(commit: Commit) -> Self {
+        GitHubCommitDetail2 {
+            commit: commit.detail(),
+            author: None,
+            parents: commit
+                .parent_ids
+                .iter()
+                .map(|sha| GitHubCommitParent { sha: sha.clone() })
+                .collect(),
+            sha: commit.id,
+        }
+    }
+}
+
+/// A commit status from `/projects/:id/repository/commits/:sha/statuses`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitStatus {
+    /// `created`, `waiting_for_resource`, `preparing`, `pending`, `running`,
+    /// `success`, `failed`, `canceled`, `skipped` or `manual`.
+    pub status: String,
+}
+
+/// Fold GitHub-style combined status out of a commit's GitLab statuses.
+pub fn combined_status(statuses: &[CommitStatus]) -> CombinedStatus {
+    let any = |states: &[&str]| statuses.iter().any(|s| states.contains(&s.status.as_str()));
+    let state = if any(&["failed", "canceled"]) {
+        "failure"
+    } else if any(&["created", "waiting_for_resource", "preparing", "pending", "running"]) {
+        "pending"
+    } else {
+        "success"
+    };
+    CombinedStatus {
+        state: state.into(),
+        total_count: statuses.len() as u64,
+    }
+}
+
+/// A group or user namespace from `/namespaces/:id`.
+#[derive(Debug, Clone, Deserialize)]
+struct Namespace {
+    id: u64,
+}
+
+/// A user from `/user` or `/users?username=`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct User {
+    pub id: u64,
+    pub username: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// `/user` returns the account email, other lookups the public one.
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub public_email: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+}
+
+impl From<User> for GitHubUser {
+    fn from(user: User) -> Self {
+        GitHubUser {
+            login: user.username,
+            id: user.id,
+            name: user.name,
+            email: user.email.or(user.public_email).filter(|e| !e.is_empty()),
+            avatar_url: user.avatar_url,
+        }
+    }
+}
+
+/// GitLab's REST API (v4).
+pub(super) struct GitLabApi;
+
+impl GitLabApi {
+    /// URL of `path` below the GitLab project `repo`.
+    fn url(client: &GitHubClient, repo: &str, path: &str) -> String {
+        format!("{}{}", project_url(&client.api_url, repo), path)
+    }
+
+    /// GET every page of a GitLab list endpoint, following `Link` headers.
+    async fn get_pages<T: serde::de::DeserializeOwned>(
+        client: &GitHubClient,
+        url: &str,
+        query: &[(&str, String)],
+    ) -> Result<Vec<T>, GitHubError> {
+        let resp = client.retry_request(|| client.http.get(url).query(query)).await?;
+        let mut next_link = GitHubClient::parse_next_link(resp.headers());
+        let mut items: Vec<T> = resp.json().await?;
+        let mut pages = 1usize;
+        while let Some(ref next) = next_link {
+            if pages >= 10 {
+                warn!(pages, url, "reached GitLab pagination limit");
+                break;
+            }
+            let resp = client.retry_request(|| client.http.get(next)).await?;
+            next_link = GitHubClient::parse_next_link(resp.headers());
+            items.extend(resp.json::<Vec<T>>().await?);
+            pages += 1;
+        }
+        Ok(items)
+    }
+
+    /// Look up a GitLab user by username.
+    async fn user(client: &GitHubClient, username: &str) -> Result<User, GitHubError> {
+        let url = format!("{}/users", client.api_url);
+        let resp = client.send(client.http.get(&url).query(&[("username", username)])).await?;
+        let users: Vec<User> = client.check_response(resp).await?.json().await?;
+        users.into_iter().next().ok_or_else(|| GitHubError::ApiError {
+            status: 404,
+            body: format!("GitLab user '{}' not found", username),
+        })
+    }
+}
+
+impl ProviderApi for GitLabApi {
+    async fn get_commits(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        since_sha: Option<&str>,
+    ) -> Result<Vec<GitHubCommit>, GitHubError> {
+        let url = Self::url(client, repo, "/repository/commits");
+        let mut query = vec![("per_page", "100".to_string())];
+        if let Some(sha) = since_sha {
+            query.push(("ref_name", sha.to_string()));
+        }
+        let commits: Vec<Commit> = Self::get_pages(client, &url, &query).await?;
+        debug!(count = commits.len(), "fetched commits");
+        Ok(commits.into_iter().map(GitHubCommit::from).collect())
+    }
+
+    async fn create_webhook(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        callback_url: &str,
+        secret: &str,
+    ) -> Result<serde_json::Value, GitHubError> {
+        let url = Self::url(client, repo, "/hooks");
+        let body = serde_json::json!({
+            "url": callback_url, "token": secret, "push_events": true,
+            "merge_requests_events": true, "enable_ssl_verification": true
+        });
+        let resp = client.send(client.http.post(&url).json(&body)).await?;
+        let hook: serde_json::Value = client.check_response(resp).await?.json().await?;
+        info!(hook_id = %hook["id"], "created webhook");
+        Ok(hook)
+    }
+
+    async fn create_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> Result<PullRequest, GitHubError> {
+        let url = Self::url(client, repo, "/merge_requests");
+        let payload = serde_json::json!({
+            "title": title, "description": body, "source_branch": head, "target_branch": base
+        });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        let mr: MergeRequest = client.check_response(resp).await?.json().await?;
+        info!(number = mr.iid, "created merge request");
+        Ok(mr.into())
+    }
+
+    async fn merge_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<(), GitHubError> {
+        let url = Self::url(client, repo, &format!("/merge_requests/{}/merge", pr_number));
+        // Squashing would replace the synced commits, which carry the sync
+        // marker, with one unmarked commit that is then replayed to SVN.
+        let payload = serde_json::json!({ "squash": false });
+        let resp = client.send(client.http.put(&url).json(&payload)).await?;
+        client.check_response(resp).await?;
+        info!(pr_number, "merged merge request");
+        Ok(())
+    }
+
+    async fn add_labels(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        number: u64,
+        labels: &[String],
+    ) -> Result<(), GitHubError> {
+        let url = Self::url(client, repo, &format!("/merge_requests/{}", number));
+        let payload = serde_json::json!({ "add_labels": labels.join(",") });
+        let resp = client.send(client.http.put(&url).json(&payload)).await?;
+        client.check_response(resp).await?;
+        debug!(number, ?labels, "added labels");
+        Ok(())
+    }
+
+    async fn request_reviewers(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+        reviewers: &[String],
+    ) -> Result<(), GitHubError> {
+        // Merge requests take reviewers by user ID.
+        let mut reviewer_ids = Vec::with_capacity(reviewers.len());
+        for username in reviewers {
+            reviewer_ids.push(Self::user(client, username).await?.id);
+        }
+        let url = Self::url(client, repo, &format!("/merge_requests/{}", pr_number));
+        let payload = serde_json::json!({ "reviewer_ids": reviewer_ids });
+        let resp = client.send(client.http.put(&url).json(&payload)).await?;
+        client.check_response(resp).await?;
+        debug!(pr_number, ?reviewers, "requested reviewers");
+        Ok(())
+    }
+
+    async fn get_combined_status(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+    ) -> Result<CombinedStatus, GitHubError> {
+        let url = Self::url(client, repo, &format!("/repository/commits/{}/statuses", sha));
+        let query = [("per_page", "100".to_string())];
+        let statuses: Vec<CommitStatus> = Self::get_pages(client, &url, &query).await?;
+        let status = combined_status(&statuses);
+        debug!(sha, state = %status.state, "fetched combined status");
+        Ok(status)
+    }
+
+    async fn get_user(
+        &self,
+        client: &GitHubClient,
+        username: &str,
+    ) -> Result<GitHubUser, GitHubError> {
+        Ok(Self::user(client, username).await?.into())
+    }
+
+    async fn post_commit_status(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+        state: CommitStatusState,
+        description: &str,
+    ) -> Result<(), GitHubError> {
+        let url = Self::url(client, repo, &format!("/statuses/{}", sha));
+        let payload = serde_json::json!({
+            "state": status_state(&state), "description": description, "name": "reposync"
+        });
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        client.check_response(resp).await?;
+        debug!(sha, state = %state, "posted commit status");
+        Ok(())
+    }
+
+    async fn get_merged_pull_requests(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        base: &str,
+        since: Option<&str>,
+    ) -> Result<Vec<PullRequest>, GitHubError> {
+        let url = Self::url(client, repo, "/merge_requests");
+        let mut query = vec![
+            ("state", "merged".to_string()),
+            ("target_branch", base.to_string()),
+            ("per_page", "100".to_string()),
+        ];
+        if let Some(since) = since {
+            query.push(("updated_after", since.to_string()));
+        }
+        let mrs: Vec<MergeRequest> = Self::get_pages(client, &url, &query).await?;
+        let merged: Vec<PullRequest> = mrs
+            .into_iter()
+            .map(PullRequest::from)
+            .filter(|pr| match since {
+                Some(since) => pr.merged_at.as_deref().is_some_and(|m| m >= since),
+                None => true,
+            })
+            .collect();
+        debug!(count = merged.len(), base, "fetched merged merge requests");
+        Ok(merged)
+    }
+
+    async fn get_pr_commits(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<Vec<GitHubCommit>, GitHubError> {
+        let url = Self::url(client, repo, &format!("/merge_requests/{}/commits", pr_number));
+        let query = [("per_page", "100".to_string())];
+        let commits: Vec<Commit> = Self::get_pages(client, &url, &query).await?;
+        debug!(count = commits.len(), pr_number, "fetched MR commits");
+        // GitLab lists the newest commit first, GitHub the oldest.
+        Ok(commits.into_iter().rev().map(GitHubCommit::from).collect())
+    }
+
+    async fn get_pull_request(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        pr_number: u64,
+    ) -> Result<PullRequest, GitHubError> {
+        let url = Self::url(client, repo, &format!("/merge_requests/{}", pr_number));
+        let resp = client.send(client.http.get(&url)).await?;
+        let mr: MergeRequest = client.check_response(resp).await?.json().await?;
+        debug!(number = mr.iid, state = %mr.state, "fetched merge request");
+        Ok(mr.into())
+    }
+
+    async fn get_commit(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        sha: &str,
+    ) -> Result<GitHubCommitDetail2, GitHubError> {
+        let url = Self::url(client, repo, &format!("/repository/commits/{}", sha));
+        let resp = client.send(client.http.get(&url)).await?;
+        let commit: Commit = client.check_response(resp).await?.json().await?;
+        debug!(sha, parents = commit.parent_ids.len(), "fetched commit details");
+        Ok(commit.into())
+    }
+
+    async fn repo_exists(&self, client: &GitHubClient, repo: &str) -> Result<bool, GitHubError> {
+        let url = project_url(&client.api_url, repo);
+        let resp = client.send(client.http.head(&url)).await?;
+        Ok(resp.status().is_success())
+    }
+
+    async fn create_repo(
+        &self,
+        client: &GitHubClient,
+        repo: &str,
+        private: bool,
+        description: &str,
+    ) -> Result<serde_json::Value, GitHubError> {
+        let repo = repo.trim_matches('/');
+        let (namespace, name) = match repo.rsplit_once('/') {
+            Some((namespace, name)) => (Some(namespace), name),
+            None => (None, repo),
+        };
+        let mut payload = serde_json::json!({
+            "name": name,
+            "path": name,
+            "visibility": if private { "private" } else { "public" },
+            "description": description,
+            "initialize_with_readme": false,
+        });
+        // Without a namespace_id GitLab creates the project under the token's user.
+        if let Some(namespace) = namespace {
+            let url = format!("{}/{}", client.api_url, namespace_path(namespace));
+            let resp = client.send(client.http.get(&url)).await?;
+            let found: Namespace = client.check_response(resp).await?.json().await?;
+            debug!(namespace, namespace_id = found.id, "resolved project namespace");
+            payload["namespace_id"] = found.id.into();
+        }
+        let url = format!("{}/projects", client.api_url);
+        let resp = client.send(client.http.post(&url).json(&payload)).await?;
+        let project: serde_json::Value = client.check_response(resp).await?.json().await?;
+        info!(repo_name = name, private, "created project");
+        Ok(project)
+    }
+
+    async fn get_authenticated_user(
+        &self,
+        client: &GitHubClient,
+    ) -> Result<GitHubUser, GitHubError> {
+        let url = format!("{}/user", client.api_url);
+        let resp = client.send(client.http.get(&url)).await?;
+        let user: GitHubUser = client.check_response(resp).await?.json::<User>().await?.into();
+        debug!(login = %user.login, "fetched authenticated user");
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GitProvider;
+
+    #[test]
+    fn test_project_path() {
+        assert_eq!(project_path("group/app"), "projects/group%2Fapp");
+        assert_eq!(project_path("/group/sub/my.app/"), "projects/group%2Fsub%2Fmy.app");
+        assert_eq!(
+            project_url("https://gitlab.example.com/api/v4/", "g/a b"),
+            "https://gitlab.example.com/api/v4/projects/g%2Fa%20b"
+        );
+    }
+
+    #[test]
+    fn test_merge_request_into_pull_request() {
+        let mr: MergeRequest = serde_json::from_value(serde_json::json!({
+            "iid": 7,
+            "title": "Sync",
+            "web_url": "https://gitlab.example.com/g/a/-/merge_requests/7",
+            "state": "merged",
+            "source_branch": "reposync/pr",
+            "target_branch": "main",
+            "sha": "abc",
+            "merge_commit_sha": null,
+            "squash_commit_sha": "def",
+            "merged_at": "2024-01-31T12:00:00Z",
+            "diff_refs": {"base_sha": "012", "head_sha": "abc", "start_sha": "012"}
+        }))
+        .unwrap();
+        let pr = PullRequest::from(mr);
+        assert_eq!(pr.number, 7);
+        assert_eq!(pr.state, "closed");
+        assert_eq!(pr.merged, Some(true));
+        assert_eq!(pr.merge_commit_sha.as_deref(), Some("def"));
+        assert_eq!((pr.head.ref_name.as_str(), pr.head.sha.as_str()), ("reposync/pr", "abc"));
+        assert_eq!((pr.base.ref_name.as_str(), pr.base.sha.as_str()), ("main", "012"));
+    }
+
+    #[test]
+    fn test_commit_conversions() {
+        let commit: Commit = serde_json::from_value(serde_json::json!({
+            "id": "abc",
+            "message": "Fix\n",
+            "author_name": "Ann",
+            "author_email": "ann@example.com",
+            "authored_date": "2024-01-31T12:00:00Z",
+            "committer_name": "Bob",
+            "committer_email": "bob@example.com",
+            "committed_date": "2024-02-01T12:00:00Z",
+            "parent_ids": ["p1", "p2"]
+        }))
+        .unwrap();
+        let detail = GitHubCommitDetail2::from(commit.clone());
+        assert_eq!(detail.parents.len(), 2);
+        assert_eq!(detail.commit.committer.name, "Bob");
+        let commit = GitHubCommit::from(commit);
+        assert_eq!(commit.sha, "abc");
+        assert_eq!(commit.commit.author.email, "ann@example.com");
+    }
+
+    #[test]
+    fn test_combined_status() {
+        let statuses = |states: &[&str]| -> Vec<CommitStatus> {
+            states.iter().map(|s| CommitStatus { status: s.to_string() }).collect()
+        };
+        assert_eq!(combined_status(&[]).total_count, 0);
+        assert_eq!(combined_status(&statuses(&["success", "skipped"])).state, "success");
+        assert_eq!(combined_status(&statuses(&["success", "running"])).state, "pending");
+        assert_eq!(combined_status(&statuses(&["running", "failed"])).state, "failure");
+        assert_eq!(status_state(&CommitStatusState::Failure), "failed");
+        assert_eq!(status_state(&CommitStatusState::Error), "failed");
+    }
+
+    #[test]
+    fn test_verify_webhook_token() {
+        assert!(verify_webhook_token("s3cret", "s3cret"));
+        assert!(!verify_webhook_token("s3creT", "s3cret"));
+        assert!(!verify_webhook_token("s3cret-longer", "s3cret"));
+        assert!(!verify_webhook_token("", "s3cret"));
+    }
+
+    /// Serves one canned JSON response per request and returns the request
+    /// lines and bodies it saw.
+    fn api_server(
+        responses: Vec<&'static str>,
+    ) -> (String, std::thread::JoinHandle<Vec<(String, String)>>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let api_url = format!("http://{}/api/v4", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut seen = Vec::new();
+            for json in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0usize;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    let (name, value) = line.split_once(": ").unwrap();
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    json.len(),
+                    json
+                )
+                .unwrap();
+                let body = String::from_utf8(body).unwrap();
+                seen.push((request_line.trim_end().to_string(), body));
+            }
+            seen
+        });
+        (api_url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_create_repo_in_group_namespace() {
+        let (api_url, server) = api_server(vec![
+            r#"{"id":42,"full_path":"group/sub","kind":"group"}"#,
+            r#"{"id":7,"path_with_namespace":"group/sub/app"}"#,
+        ]);
+        let client = GitHubClient::new(api_url, "glpat-test", GitProvider::GitLab);
+        let project = client.create_repo("group/sub/app", true, "mirror").await.unwrap();
+        assert_eq!(project["path_with_namespace"], "group/sub/app");
+
+        let seen = server.join().unwrap();
+        assert_eq!(seen[0].0, "GET /api/v4/namespaces/group%2Fsub HTTP/1.1");
+        assert_eq!(seen[1].0, "POST /api/v4/projects HTTP/1.1");
+        let payload: serde_json::Value = serde_json::from_str(&seen[1].1).unwrap();
+        assert_eq!(payload["namespace_id"], 42);
+        assert_eq!(payload["path"], "app");
+        assert_eq!(payload["visibility"], "private");
+    }
+
+    #[tokio::test]
+    async fn test_create_repo_without_namespace() {
+        let (api_url, server) = api_server(vec![r#"{"id":7,"path_with_namespace":"me/app"}"#]);
+        let client = GitHubClient::new(api_url, "glpat-test", GitProvider::GitLab);
+        client.create_repo("app", false, "").await.unwrap();
+
+        let seen = server.join().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].0, "POST /api/v4/projects HTTP/1.1");
+        let payload: serde_json::Value = serde_json::from_str(&seen[0].1).unwrap();
+        assert!(payload.get("namespace_id").is_none());
+    }
+}
@@ -1,9 +1,17 @@
 //! Git operations for RepoSync.
 
+pub mod auth;
+pub mod bitbucket;
 pub mod client;
+pub mod fast_import;
 pub mod github;
+pub mod github_app;
+pub mod gitlab;
 pub mod remote_url;
 
+pub use auth::{GitAuth, SshKey};
 pub use client::GitClient;
+pub use fast_import::{FastImport, FastImportCommit, TreeManifest};
 pub use github::GitHubClient;
+pub use github_app::GitHubAppAuth;
 pub use remote_url::derive_git_remote_url;
@@ -15,15 +15,33 @@
 ///    - `https://api.github.com` → `https://github.com`
 ///    - `https://<host>/api/v3`  → `https://<host>`
 ///    - `https://<host>/api/v3/` → `https://<host>` (trailing slash)
+///    - `https://<host>/api/v1`  → `https://<host>` (Gitea)
+///    - `https://<host>/api/v4`  → `https://<host>` (GitLab)
+///    - `https://<host>/rest/api/1.0` → `https://<host>/scm` (Bitbucket Server)
 ///    - Anything else            → strip trailing slash, use as-is
 ///
 /// The resulting URL is `{base}/{repo}.git` where `repo` is in `owner/name`
-/// format.
+/// format.  A `repo` that already names a remote (see [`is_remote_url`]), as
+/// with plain Git remotes, is returned unchanged.
 pub fn derive_git_remote_url(api_url: &str, git_base_url: Option<&str>, repo: &str) -> String {
+    if is_remote_url(repo) {
+        return repo.trim().to_string();
+    }
     let base = derive_git_base_url(api_url, git_base_url);
     format!("{}/{}.git", base, repo)
 }
 
+/// Whether `repo` is a remote in its own right: a URL (`ssh://`, `https://`,
+/// `file://`), an scp-style `user@host:path` or a filesystem path.
+pub fn is_remote_url(repo: &str) -> bool {
+    let repo = repo.trim();
+    repo.contains("://")
+        || repo.starts_with('/')
+        || repo.starts_with("./")
+        || repo.starts_with("../")
+        || repo.split_once(':').is_some_and(|(host, _)| !host.is_empty() && !host.contains('/'))
+}
+
 /// Derive just the Git base URL (without repo path).
 ///
 /// See [`derive_git_remote_url`] for resolution rules.
@@ -54,6 +72,16 @@ pub fn derive_git_base_url(api_url: &str, git_base_url: Option<&str>) -> String
         return base.to_string();
     }
 
+    // GitLab: "https://<host>/api/v4" → "https://<host>"
+    if let Some(base) = url.strip_suffix("/api/v4") {
+        return base.to_string();
+    }
+
+    // Bitbucket Server: "https://<host>/rest/api/1.0" → "https://<host>/scm"
+    if let Some(base) = url.strip_suffix("/rest/api/1.0") {
+        return format!("{}/scm", base);
+    }
+
     // Fallback: use the API URL itself (already stripped of trailing slash).
     url.to_string()
 }
@@ -180,6 +208,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_remote_url_gitlab_subgroup() {
+        assert_eq!(
+            derive_git_remote_url("https://gitlab.company.com/api/v4/", None, "div/team/repo"),
+            "https://gitlab.company.com/div/team/repo.git"
+        );
+    }
+
+    #[test]
+    fn test_remote_url_bitbucket_server() {
+        assert_eq!(
+            derive_git_remote_url("https://bitbucket.company.com/rest/api/1.0", None, "PROJ/repo"),
+            "https://bitbucket.company.com/scm/PROJ/repo.git"
+        );
+    }
+
+    #[test]
+    fn test_remote_url_plain_remote_verbatim() {
+        for remote in [
+            "git@fileserver:mirrors/project.git",
+            "ssh://git@fileserver/srv/git/project.git",
+            "file:///srv/git/project.git",
+            "/srv/git/project.git",
+        ] {
+            assert!(is_remote_url(remote));
+            assert_eq!(derive_git_remote_url("", None, remote), remote);
+        }
+        assert!(!is_remote_url("acme/project"));
+        assert!(!is_remote_url("~jdoe/project"));
+    }
+
     #[test]
     fn test_remote_url_explicit_override() {
         assert_eq!(
@@ -18,9 +18,11 @@ use tracing::{debug, error, info, warn};
 
 use crate::db::Database;
 use crate::file_policy::{FilePolicy, FilePolicyDecision};
-use crate::git::GitClient;
+use crate::git::{FastImport, FastImportCommit, GitClient, TreeManifest};
 use crate::identity::mapper::{GitIdentity, IdentityMapper};
 use crate::svn::SvnClient;
+use crate::svn_props::PropTargets;
+use crate::sync_engine::{git_sha_key_for, svn_rev_key_for};
 
 // ---------------------------------------------------------------------------
 // Progress tracking
@@ -206,6 +208,22 @@ fn copy_tree_policy_inner(
         let src_path = entry.path();
         let dst_path = dst.join(&file_name);
 
+        if entry.file_type()?.is_symlink() {
+            let rel = src_path
+                .strip_prefix(export_root)
+                .unwrap_or(&src_path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            copy_symlink_with_policy(&src_path, &dst_path, &rel, policy, stats)?;
+            continue;
+        }
+        // A symlink replaced by a file or directory: never write through it.
+        if crate::svn_props::is_symlink(&dst_path) {
+            std::fs::remove_file(&dst_path).with_context(|| {
+                format!("failed to remove symlink: {}", dst_path.display())
+            })?;
+        }
+
         if src_path.is_dir() {
             if !dst_path.exists() {
                 std::fs::create_dir_all(&dst_path).with_context(|| {
@@ -287,10 +305,329 @@ fn copy_tree_policy_inner(
                     );
                     stats.skipped += 1;
                 }
+                // Only symlinks get this decision; they are handled above.
+                FilePolicyDecision::ExternalSymlink { .. } => stats.skipped += 1,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Recreate the symlink `src` (SVN export) at `dst` (Git working tree) with
+/// the same target, unless the policy blocks it.
+fn copy_symlink_with_policy(
+    src: &Path,
+    dst: &Path,
+    rel: &str,
+    policy: &FilePolicy,
+    stats: &mut CopyStats,
+) -> Result<()> {
+    let target = std::fs::read_link(src)
+        .with_context(|| format!("failed to read symlink: {}", src.display()))?;
+    let target = target.to_string_lossy();
+    let decision = policy.evaluate_symlink(rel, &target);
+    if decision.is_blocked() {
+        warn!(
+            path = rel,
+            target = %target,
+            reason = decision.label(),
+            "symlink blocked by policy — not copied to Git"
+        );
+        stats.skipped += 1;
+        return Ok(());
+    }
+    if dst.is_dir() && !crate::svn_props::is_symlink(dst) {
+        std::fs::remove_dir_all(dst)
+            .with_context(|| format!("failed to remove directory: {}", dst.display()))?;
+    }
+    crate::svn_props::write_symlink(dst, &target)
+        .with_context(|| format!("failed to create symlink: {}", dst.display()))?;
+    stats.copied += 1;
+    Ok(())
+}
+
+/// Export `path` at `rev` into `dest`, leaving keywords unexpanded when
+/// they are kept collapsed in Git.
+pub(crate) async fn export_tree(
+    svn: &SvnClient,
+    path: &str,
+    rev: i64,
+    dest: &Path,
+    collapse_keywords: bool,
+) -> std::result::Result<(), crate::errors::SvnError> {
+    if collapse_keywords {
+        svn.export_unexpanded(path, rev, dest).await
+    } else {
+        svn.export(path, rev, dest).await
+    }
+}
+
+/// Apply the per-file SVN properties at `rev` to the tree staged in `root`:
+/// `svn:executable` and `svn:eol-style`, as enabled.
+///
+/// With `targets`, only the paths the revision changed are looked up; the
+/// other files keep their exported mode and their entry in the staged
+/// `.gitattributes`.  Without, the whole tree is.
+pub(crate) async fn sync_file_props(
+    svn: &SvnClient,
+    rev: i64,
+    root: &Path,
+    targets: Option<&PropTargets>,
+    executable: bool,
+    eol_style: bool,
+) -> Result<()> {
+    use crate::svn_props;
+
+    if let Some(targets) = targets {
+        if !executable && !eol_style {
+            return Ok(());
+        }
+        let props = svn_props::fetch_target_props(svn, targets, rev).await?;
+        if executable {
+            svn_props::update_executable_bits(root, targets, &props)
+                .context("failed to set executable bits")?;
+        }
+        if eol_style {
+            let mut entries = svn_props::read_eol_entries(root);
+            svn_props::update_eol_entries(&mut entries, root, targets, &props);
+            svn_props::write_eol_entries(root, &entries)?;
+        }
+        return Ok(());
+    }
+    if executable {
+        let executables = svn_props::fetch_executables(svn, "", rev, true).await?;
+        svn_props::sync_executable_bits(root, &executables)
+            .context("failed to set executable bits")?;
+    }
+    if eol_style {
+        let styles = svn_props::fetch_file_props(svn, "", svn_props::SVN_EOL_STYLE, rev, true)
+            .await?;
+        svn_props::write_eol_entries(root, &styles)?;
+    }
+    Ok(())
+}
+
+/// Whether a tree path is owned by the import.  Root-level dotfiles other
+/// than `.gitattributes` belong to the Git side: like
+/// [`copy_tree_with_policy`], the import never replaces or deletes them.
+pub fn is_imported_path(path: &str) -> bool {
+    !path.starts_with('.') || path == crate::svn_props::GITATTRIBUTES
+}
+
+/// Turn the SVN export in `export_dir` into the tree to commit, in place,
+/// enforcing `policy` as [`copy_tree_with_policy`] does.  Root-level
+/// dotfiles are dropped and `.gitattributes` starts from `gitattributes`,
+/// the content committed so far.  With `lfs_repo`, LFS-tracked files are
+/// replaced by their pointers and the objects stored in that repository.
+pub fn stage_export(
+    export_dir: &Path,
+    policy: &FilePolicy,
+    gitattributes: Option<&[u8]>,
+    lfs_repo: Option<&Path>,
+    db: &Database,
+) -> Result<CopyStats> {
+    for entry in std::fs::read_dir(export_dir)
+        .with_context(|| format!("failed to read directory: {}", export_dir.display()))?
+    {
+        let entry = entry?;
+        if !entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        }
+        .with_context(|| format!("failed to remove {}", path.display()))?;
+        debug!(path = %path.display(), "dropped dotfile/dotdir from export root");
+    }
+    if let Some(content) = gitattributes {
+        std::fs::write(export_dir.join(crate::svn_props::GITATTRIBUTES), content)
+            .context("failed to write .gitattributes")?;
+    }
+
+    let mut stats = CopyStats::default();
+    stage_export_inner(export_dir, export_dir, policy, lfs_repo, &mut stats)?;
+    if stats.skipped > 0 {
+        let _ = db.insert_audit_log(
+            "file_policy_skip",
+            Some("svn_to_git"),
+            None,
+            None,
+            None,
+            Some(&format!(
+                "Skipped {} files by policy during SVN→Git copy",
+                stats.skipped
+            )),
+            true,
+        );
+    }
+    Ok(stats)
+}
+
+fn stage_export_inner(
+    dir: &Path,
+    export_root: &Path,
+    policy: &FilePolicy,
+    lfs_repo: Option<&Path>,
+    stats: &mut CopyStats,
+) -> Result<()> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read directory: {}", dir.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let rel = path
+            .strip_prefix(export_root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        if !is_imported_path(&rel) || rel == crate::svn_props::GITATTRIBUTES {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+
+        let decision = if file_type.is_symlink() {
+            let target = std::fs::read_link(&path)
+                .with_context(|| format!("failed to read symlink: {}", path.display()))?;
+            policy.evaluate_symlink(&rel, &target.to_string_lossy())
+        } else if file_type.is_dir() {
+            stage_export_inner(&path, export_root, policy, lfs_repo, stats)?;
+            continue;
+        } else {
+            policy.evaluate_path(export_root, &rel)
+        };
+        match &decision {
+            FilePolicyDecision::Allow => stats.copied += 1,
+            FilePolicyDecision::LfsTrack { size, threshold } => {
+                let pattern = crate::lfs::pattern_for_path(&rel);
+                if let Err(e) = crate::lfs::ensure_lfs_tracked(export_root, &pattern) {
+                    warn!(
+                        path = rel.as_str(),
+                        pattern = pattern.as_str(),
+                        error = %e,
+                        "failed to update .gitattributes for LFS tracking"
+                    );
+                }
+                // Without LFS the file is committed as-is.
+                if let Some(repo) = lfs_repo {
+                    let content = std::fs::read(&path)
+                        .with_context(|| format!("failed to read {}", path.display()))?;
+                    match crate::lfs::store_lfs_object(repo, &content) {
+                        Ok(pointer) => std::fs::write(&path, pointer)
+                            .with_context(|| format!("failed to write {}", path.display()))?,
+                        Err(e) => warn!(path = rel.as_str(), error = %e, "LFS: store failed"),
+                    }
+                }
+                info!(
+                    path = rel.as_str(),
+                    size,
+                    threshold,
+                    pattern = pattern.as_str(),
+                    "LFS: file staged and .gitattributes updated"
+                );
+                stats.copied += 1;
+                stats.lfs_tracked += 1;
+            }
+            blocked => {
+                warn!(
+                    path = rel.as_str(),
+                    reason = blocked.label(),
+                    "file blocked by policy — not copied to Git"
+                );
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("failed to remove {}", path.display()))?;
+                stats.skipped += 1;
             }
         }
     }
+    Ok(())
+}
+
+/// Seconds since the epoch of an SVN log date, or now when it is missing or
+/// malformed.
+pub fn svn_date_to_epoch(date: &str) -> i64 {
+    chrono::DateTime::parse_from_rfc3339(date)
+        .map(|d| d.timestamp())
+        .unwrap_or_else(|_| chrono::Utc::now().timestamp())
+}
+
+/// Where an interrupted import continues: the persisted progress and the
+/// last `(svn_rev, git_sha)` recorded in `commit_map`, both those of
+/// `import_config`'s repository.
+///
+/// Commits reach `commit_map` only at fast-import checkpoints (taken with
+/// every push batch) and when the stream ends, so the last entry is the last
+/// checkpoint.  Returns `None` when there is nothing to resume: no persisted
+/// progress, a finished import or no checkpoint yet.  Fails when the
+/// imported branch does not point at the checkpoint commit, since continuing
+/// would graft the remaining revisions onto the wrong history.
+pub fn resume_point(
+    db: &Database,
+    git: &GitClient,
+    import_config: &ImportConfig,
+) -> Result<Option<(ImportProgress, i64, String)>> {
+    let Some(saved) = db.load_import_progress_for(import_config.progress_repo_id())? else {
+        return Ok(None);
+    };
+    if matches!(saved.phase, ImportPhase::Idle | ImportPhase::Completed) {
+        return Ok(None);
+    }
+    let Some((rev, sha)) = db.get_last_commit_pair(import_config.repo_id.as_deref())? else {
+        return Ok(None);
+    };
+    let branch = &import_config.branch;
+    let tip = git.branch_sha(branch)?;
+    if tip.as_deref() != Some(sha.as_str()) {
+        anyhow::bail!(
+            "cannot resume the import: branch '{}' is at {} but the last imported \
+             revision r{} is {}; use Reset & Reimport to start over",
+            branch,
+            tip.as_deref().unwrap_or("nothing"),
+            rev,
+            sha
+        );
+    }
+    Ok(Some((saved, rev, sha)))
+}
 
+/// Insert the `commit_map` rows of the streamed commits `marks` resolves
+/// (SVN revision → SHA) under `repo_id`, keeping those it does not know yet.
+fn record_marks(
+    db: &Database,
+    marks: &std::collections::BTreeMap<i64, String>,
+    unmapped: &mut Vec<(i64, String, String)>,
+    repo_id: Option<&str>,
+) {
+    unmapped.retain(|(rev, svn_author, git_author)| match marks.get(rev) {
+        Some(sha) => {
+            db.insert_commit_map_with_repo(*rev, sha, "svn_to_git", svn_author, git_author, repo_id)
+                .ok();
+            false
+        }
+        None => true,
+    });
+}
+
+/// End the fast-import stream, record the remaining `commit_map` rows and
+/// check the imported branch out in the working tree.
+fn finish_fast_import(
+    fast_import: FastImport,
+    git_client: &Arc<std::sync::Mutex<GitClient>>,
+    import_config: &ImportConfig,
+    db: &Database,
+    unmapped: &mut Vec<(i64, String, String)>,
+) -> Result<()> {
+    let marks = fast_import.finish().context("git fast-import failed")?;
+    record_marks(db, &marks, unmapped, import_config.repo_id.as_deref());
+    if !marks.is_empty() {
+        let branch = &import_config.branch;
+        let git = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        git.checkout_branch(branch)
+            .with_context(|| format!("failed to check out '{}'", branch))?;
+    }
     Ok(())
 }
 
@@ -321,9 +658,11 @@ fn remove_stale_inner(src: &Path, dst: &Path, is_root: bool) -> Result<()> {
 
         let src_path = src.join(&file_name);
         let dst_path = entry.path();
+        // Symlinks are compared as entries of their own, never followed.
+        let src_meta = std::fs::symlink_metadata(&src_path).ok();
 
-        if dst_path.is_dir() {
-            if src_path.is_dir() {
+        if entry.file_type()?.is_dir() {
+            if src_meta.as_ref().is_some_and(|m| m.is_dir()) {
                 remove_stale_inner(&src_path, &dst_path, false)?;
             } else {
                 std::fs::remove_dir_all(&dst_path).with_context(|| {
@@ -331,7 +670,7 @@ fn remove_stale_inner(src: &Path, dst: &Path, is_root: bool) -> Result<()> {
                 })?;
                 debug!(path = %dst_path.display(), "removed stale directory");
             }
-        } else if !src_path.exists() {
+        } else if src_meta.is_none() {
             std::fs::remove_file(&dst_path).with_context(|| {
                 format!("failed to remove stale file: {}", dst_path.display())
             })?;
@@ -356,17 +695,139 @@ pub struct ImportConfig {
     pub remote_name: String,
     /// Git branch to push to (e.g. "main").
     pub branch: String,
-    /// Git push token.
+    /// Git push token, used when the Git client holds none.
     pub push_token: Option<String>,
     /// Commit message prefix format.  `{rev}`, `{author}`, `{date}` are
     /// available as placeholders.  If empty, uses the original SVN message.
     pub message_prefix: Option<String>,
+    /// Give files with `svn:executable` Git mode `100755`.
+    pub sync_executable_bit: bool,
+    /// Mirror `svn:eol-style` in the root `.gitattributes`.
+    pub normalize_line_endings: bool,
+    /// Collapse keywords expanded through `svn:keywords`.
+    pub collapse_keywords: bool,
+    /// Continue an interrupted import after its last checkpoint instead of
+    /// starting from r1 (see [`resume_point`]).
+    pub resume: bool,
+    /// Import trunk, branches and tags of this layout instead of the single
+    /// path the SVN client points at; its paths are relative to the client
+    /// URL (see [`run_layout_import`](crate::layout_import::run_layout_import)).
+    pub layout: Option<crate::layout_import::ImportLayout>,
+    /// Revision range and path filters; the default imports everything.
+    pub filter: ImportFilter,
+    /// Repository whose sync engine continues after the import; the
+    /// `commit_map` rows and the branch and tag watermarks of a layout
+    /// import are kept under its keys.  `None` for the global engine.
+    pub repo_id: Option<String>,
+    /// Keep the persisted progress in the `import_progress` row of
+    /// `repo_id` instead of the singleton row, for an import started for
+    /// one repository.
+    pub per_repo_progress: bool,
+}
+
+impl ImportConfig {
+    /// Repository whose `import_progress` row this import persists to
+    /// (`None` = the singleton row).
+    pub fn progress_repo_id(&self) -> Option<&str> {
+        self.repo_id.as_deref().filter(|_| self.per_repo_progress)
+    }
+}
+
+/// Which part of the SVN history an import covers.
+///
+/// An import starting after r1 begins with a baseline commit holding the
+/// tree of the revision before the range.  The path globs are matched
+/// against paths relative to the import root through [`FilePolicy`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportFilter {
+    /// First revision to import.
+    pub from_rev: Option<i64>,
+    /// Import the revisions committed after this date (`YYYY-MM-DD` or RFC
+    /// 3339) instead of starting at `from_rev`.
+    pub from_date: Option<String>,
+    /// Last revision to import; HEAD when unset.
+    pub to_rev: Option<i64>,
+    /// Only import paths matching one of these globs (all when empty).
+    pub include_paths: Vec<String>,
+    /// Never import paths matching one of these globs.
+    pub exclude_paths: Vec<String>,
+}
+
+impl ImportFilter {
+    /// Whether the filter narrows the revisions to import.
+    pub fn has_range(&self) -> bool {
+        self.from_rev.is_some() || self.from_date.is_some() || self.to_rev.is_some()
+    }
+
+    /// Reject contradictory or malformed settings before an import starts.
+    pub fn validate(&self) -> Result<()> {
+        if self.from_rev.is_some() && self.from_date.is_some() {
+            anyhow::bail!("a start revision and a start date cannot both be given");
+        }
+        for rev in [self.from_rev, self.to_rev].into_iter().flatten() {
+            if rev < 1 {
+                anyhow::bail!("invalid revision r{}: revisions start at r1", rev);
+            }
+        }
+        if let (Some(from), Some(to)) = (self.from_rev, self.to_rev) {
+            if from > to {
+                anyhow::bail!("start revision r{} is after end revision r{}", from, to);
+            }
+        }
+        if let Some(date) = &self.from_date {
+            let valid = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+                || chrono::DateTime::parse_from_rfc3339(date).is_ok();
+            if !valid {
+                anyhow::bail!("invalid start date '{}': expected YYYY-MM-DD or RFC 3339", date);
+            }
+        }
+        if let Some(glob) = self
+            .include_paths
+            .iter()
+            .chain(&self.exclude_paths)
+            .find(|glob| glob.trim().is_empty())
+        {
+            anyhow::bail!("invalid path filter '{}': globs cannot be empty", glob);
+        }
+        Ok(())
+    }
+
+    /// `policy` restricted to the include/exclude globs.
+    pub fn policy(&self, policy: &FilePolicy) -> FilePolicy {
+        policy
+            .clone()
+            .with_path_filter(self.include_paths.clone(), self.exclude_paths.clone())
+    }
+
+    /// First and last revision to import, resolving the start date against
+    /// the repository.  Fails when the range holds no revision.
+    pub async fn revisions(&self, svn_client: &SvnClient, head_rev: i64) -> Result<(i64, i64)> {
+        let start = match (&self.from_date, self.from_rev) {
+            (Some(date), _) => {
+                let rev = svn_client
+                    .revision_at_date(date)
+                    .await
+                    .with_context(|| format!("failed to resolve the revision at {}", date))?;
+                rev + 1
+            }
+            (None, Some(rev)) => rev,
+            (None, None) => 1,
+        };
+        let end = self.to_rev.map_or(head_rev, |rev| rev.min(head_rev));
+        if start > end {
+            anyhow::bail!("no revisions to import between r{} and r{}", start, end);
+        }
+        Ok((start, end))
+    }
 }
 
 /// Run a full SVN history import, replaying every revision as a Git commit.
 ///
 /// Progress is updated in real-time via `progress` and optionally broadcast
-/// via `ws_broadcast` for the web UI.
+/// via `ws_broadcast` for the web UI.  With `import_config.layout` set, the
+/// whole layout is imported instead.  `import_config.filter` narrows the
+/// revisions and paths imported.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_full_import(
     svn_client: &SvnClient,
     git_client: &Arc<std::sync::Mutex<GitClient>>,
@@ -377,6 +838,35 @@ pub async fn run_full_import(
     progress: Arc<RwLock<ImportProgress>>,
     ws_broadcast: Option<broadcast::Sender<String>>,
 ) -> Result<u64> {
+    import_config.filter.validate()?;
+    let file_policy = &import_config.filter.policy(file_policy);
+    if let Some(layout) = &import_config.layout {
+        if import_config.filter.has_range() {
+            anyhow::bail!("a revision range cannot be combined with a branches and tags import");
+        }
+        // Layout imports write `commit_map` only once everything is
+        // imported, so rows already there belong to an earlier import that
+        // running again would duplicate.
+        if db.get_last_commit_pair(import_config.repo_id.as_deref())?.is_some() {
+            anyhow::bail!(
+                "a branches and tags import cannot continue already imported history; \
+                 use Reset & Reimport to start over"
+            );
+        }
+        return crate::layout_import::run_layout_import(
+            svn_client,
+            git_client,
+            identity_mapper,
+            db,
+            file_policy,
+            import_config,
+            layout,
+            progress,
+            ws_broadcast,
+        )
+        .await;
+    }
+
     // Helper to push a log line and broadcast it.
     let log = |progress: &Arc<RwLock<ImportProgress>>,
                ws: &Option<broadcast::Sender<String>>,
@@ -402,56 +892,7 @@ pub async fn run_full_import(
     };
 
     // LFS preflight: check availability and install hooks in the repo
-    let lfs_available = if file_policy.lfs_enabled() {
-        match crate::lfs::preflight_check() {
-            Ok(version) => {
-                log(
-                    &progress,
-                    &ws_broadcast,
-                    format!("[info] Git LFS available: {}", version),
-                )
-                .await;
-
-                // Install LFS hooks/filters in the repo so `git add` invokes
-                // the clean filter and creates pointer files for tracked patterns.
-                let rp = {
-                    let git_guard = git_client.lock().unwrap_or_else(|p| p.into_inner());
-                    git_guard.repo_workdir()
-                };
-                match crate::lfs::install_lfs_hooks(&rp) {
-                    Ok(()) => {
-                        log(
-                            &progress,
-                            &ws_broadcast,
-                            "[info] Git LFS installed in repo (filters active)".into(),
-                        )
-                        .await;
-                        true
-                    }
-                    Err(e) => {
-                        log(
-                            &progress,
-                            &ws_broadcast,
-                            format!("[warn] git lfs install failed: {} — LFS tracking will not work", e),
-                        )
-                        .await;
-                        false
-                    }
-                }
-            }
-            Err(e) => {
-                log(
-                    &progress,
-                    &ws_broadcast,
-                    format!("[warn] Git LFS not available: {} — large files will be committed directly", e),
-                )
-                .await;
-                false
-            }
-        }
-    } else {
-        false
-    };
+    let lfs_available = prepare_lfs(git_client, file_policy, &progress, &ws_broadcast).await;
 
     // Get SVN info
     log(
@@ -461,10 +902,40 @@ pub async fn run_full_import(
     )
     .await;
 
+    // Pick up after the last checkpoint of an interrupted import.  This
+    // reads the persisted progress, so it must run before it is overwritten.
+    let resumed = if import_config.resume {
+        let git_guard = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        resume_point(db, &git_guard, import_config)?
+    } else {
+        None
+    };
+    if let Some((saved, rev, sha)) = &resumed {
+        {
+            let mut p = progress.write().await;
+            p.commits_created = saved.commits_created;
+            p.batches_pushed = saved.batches_pushed;
+            p.files_skipped = saved.files_skipped;
+            p.lfs_unique_count = saved.lfs_unique_count;
+            p.errors = saved.errors.clone();
+            p.started_at = saved.started_at.clone().or(p.started_at.take());
+        }
+        log(
+            &progress,
+            &ws_broadcast,
+            format!(
+                "[info] Resuming import after checkpoint r{} ({})",
+                rev,
+                &sha[..8.min(sha.len())]
+            ),
+        )
+        .await;
+    }
+
     // Persist initial importing state
     {
         let p = progress.read().await;
-        if let Err(e) = db.persist_import_progress(&p) {
+        if let Err(e) = db.persist_import_progress_for(import_config.progress_repo_id(), &p) {
             warn!("failed to persist import progress: {}", e);
         }
     }
@@ -474,18 +945,19 @@ pub async fn run_full_import(
         .await
         .context("failed to get SVN info")?;
     let head_rev = svn_info.latest_rev;
+    let (start_rev, end_rev) = import_config.filter.revisions(svn_client, head_rev).await?;
 
     {
         let mut p = progress.write().await;
-        p.total_revs = head_rev;
+        p.total_revs = end_rev - start_rev + 1;
     }
 
     log(
         &progress,
         &ws_broadcast,
         format!(
-            "[info] SVN HEAD is r{}, importing {} revisions",
-            head_rev, head_rev
+            "[info] SVN HEAD is r{}, importing r{} to r{}",
+            head_rev, start_rev, end_rev
         ),
     )
     .await;
@@ -499,7 +971,7 @@ pub async fn run_full_import(
     .await;
 
     let log_entries = svn_client
-        .log(1, head_rev)
+        .log(start_rev, end_rev)
         .await
         .context("failed to get SVN log")?;
 
@@ -515,27 +987,166 @@ pub async fn run_full_import(
     )
     .await;
 
-    let repo_path = {
+    // Revisions up to the checkpoint are already in Git.
+    let first_idx = match &resumed {
+        Some((_, rev, _)) => log_entries.partition_point(|e| e.revision <= *rev),
+        None => 0,
+    };
+    if first_idx > 0 {
+        log(
+            &progress,
+            &ws_broadcast,
+            format!(
+                "[info] Skipping {} already imported revisions, {} left",
+                first_idx,
+                log_entries.len() - first_idx
+            ),
+        )
+        .await;
+    }
+
+    let ref_name = format!("refs/heads/{}", import_config.branch);
+    let (repo_path, parent, mut manifest, mut gitattributes, push_url) = {
         let git_guard = git_client.lock().unwrap_or_else(|p| p.into_inner());
-        git_guard.repo_path().to_path_buf()
+        let parent = git_guard.branch_sha(&import_config.branch)?;
+        let (manifest, gitattributes) = match &parent {
+            Some(sha) => (
+                TreeManifest::from_commit(git_guard.repo(), sha)?,
+                git_guard.get_file_content_at_commit(sha, crate::svn_props::GITATTRIBUTES)?,
+            ),
+            None => (TreeManifest::new(), None),
+        };
+        let push_url = git_guard.remote_push_url(&import_config.remote_name)?;
+        (git_guard.repo_path().to_path_buf(), parent, manifest, gitattributes, push_url)
     };
+    // Credentials for the batch pushes below, passed to `git push` through
+    // its environment. The environment is built anew for every push so that
+    // a GitHub App installation token is renewed during long imports.
+    let push_auth = || {
+        let git_guard = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        git_guard.auth().or_token(import_config.push_token.as_deref())
+    };
+
+    // Commits are streamed to `git fast-import`, one mark per SVN revision.
+    let mut fast_import = FastImport::start(&repo_path, &ref_name, parent.as_deref())
+        .context("failed to start git fast-import")?;
+    // Streamed commits not yet in commit_map: (rev, SVN author, Git identity).
+    let mut unmapped: Vec<(i64, String, String)> = Vec::new();
+    let lfs_repo = lfs_available.then_some(repo_path.as_path());
 
-    let mut count = 0u64;
+    let mut count = resumed.as_ref().map_or(0, |(saved, _, _)| saved.commits_created);
     let mut commits_since_push = 0u64;
     const PUSH_BATCH_SIZE: u64 = 50;
 
-    for (idx, entry) in log_entries.iter().enumerate() {
+    // Starting mid-history, one snapshot of the revision before the range
+    // stands in for the history left out.  A resumed import has it already.
+    let baseline_rev = (start_rev > 1 && resumed.is_none()).then_some(start_rev - 1);
+    // Once a tree with its file properties is in place, each revision only
+    // looks up the properties of what it changed.
+    let repo_base = svn_info.repo_path();
+    let mut props_known = parent.is_some();
+    if let Some(rev) = baseline_rev {
+        let export_dir = tempfile::tempdir().context("failed to create temp dir")?;
+        let collapse_keywords = import_config.collapse_keywords;
+        match export_tree(svn_client, "", rev, export_dir.path(), collapse_keywords).await {
+            Ok(()) => {
+                stage_export(
+                    export_dir.path(),
+                    file_policy,
+                    gitattributes.as_deref(),
+                    lfs_repo,
+                    db,
+                )
+                .with_context(|| format!("failed to stage the baseline at r{}", rev))?;
+                let props = sync_file_props(
+                    svn_client,
+                    rev,
+                    export_dir.path(),
+                    None,
+                    import_config.sync_executable_bit,
+                    import_config.normalize_line_endings,
+                );
+                match props.await {
+                    Ok(()) => props_known = true,
+                    Err(e) => {
+                        let msg =
+                            format!("[warn] r{}: failed to sync file properties: {:#}", rev, e);
+                        log(&progress, &ws_broadcast, msg).await;
+                    }
+                }
+                let changes = manifest
+                    .update_from_dir(export_dir.path(), is_imported_path)
+                    .with_context(|| format!("failed to read the baseline tree of r{}", rev))?;
+                gitattributes =
+                    std::fs::read(export_dir.path().join(crate::svn_props::GITATTRIBUTES)).ok();
+                if !changes.is_empty() {
+                    let message = format!(
+                        "Baseline snapshot of SVN r{}\n\n[reposync] imported from SVN r{}",
+                        rev, rev
+                    );
+                    // Dated like the revision it stands for, so rerunning the
+                    // import yields the same commit.
+                    let date = match svn_client.rev_prop(rev, "svn:date").await {
+                        Ok(Some(date)) => svn_date_to_epoch(&date),
+                        Ok(None) => chrono::Utc::now().timestamp(),
+                        Err(e) => {
+                            let msg = format!("[warn] r{}: no svn:date, using now: {}", rev, e);
+                            log(&progress, &ws_broadcast, msg).await;
+                            chrono::Utc::now().timestamp()
+                        }
+                    };
+                    fast_import
+                        .commit(&FastImportCommit {
+                            mark: rev,
+                            author_name: &import_config.committer_name,
+                            author_email: &import_config.committer_email,
+                            author_time: date,
+                            committer_name: &import_config.committer_name,
+                            committer_email: &import_config.committer_email,
+                            committer_time: date,
+                            message: &message,
+                            changes: &changes,
+                        })
+                        .with_context(|| format!("failed to write the baseline at r{}", rev))?;
+                    unmapped.push((
+                        rev,
+                        String::new(),
+                        format!(
+                            "{} <{}>",
+                            import_config.committer_name, import_config.committer_email
+                        ),
+                    ));
+                    count += 1;
+                    commits_since_push += 1;
+                    progress.write().await.commits_created = count;
+                    let msg = format!("[ok] r{}: baseline snapshot [{} files]", rev, changes.len());
+                    log(&progress, &ws_broadcast, msg).await;
+                }
+            }
+            // The path may not exist yet before the range; the first
+            // imported revision then carries the whole tree.
+            Err(e) => {
+                let msg = format!("[warn] r{}: no baseline snapshot: {}", rev, e);
+                log(&progress, &ws_broadcast, msg).await;
+            }
+        }
+    }
+
+    for (idx, entry) in log_entries.iter().enumerate().skip(first_idx) {
         // Check for cancellation
         {
             let p = progress.read().await;
             if p.cancel_requested {
+                drop(p);
+                finish_fast_import(fast_import, git_client, import_config, db, &mut unmapped)?;
                 let mut p = progress.write().await;
                 p.phase = ImportPhase::Cancelled;
                 p.completed_at = Some(chrono::Utc::now().to_rfc3339());
+                drop(p);
                 log(
                     &progress,
                     &ws_broadcast,
-                    "[warn] Import cancelled by user".into(),
+                    "[warn] Import cancelled by user — starting it again resumes it".into(),
                 )
                 .await;
                 return Ok(count);
@@ -547,6 +1158,8 @@ pub async fn run_full_import(
             let mut p = progress.write().await;
             p.current_rev = idx as i64 + 1;
         }
+        // A revision that fails below leaves the next one to a full lookup.
+        let props_were_known = std::mem::take(&mut props_known);
 
         // Export this revision
         let export_dir = match tempfile::tempdir() {
@@ -560,61 +1173,64 @@ pub async fn run_full_import(
             }
         };
 
-        // P6 optimization: for revisions after the first, try applying an
-        // incremental SVN diff instead of a full export.  Falls back to full
-        // export if the diff cannot be applied.
-        let mut used_incremental = false;
-        if idx > 0 {
-            match svn_client.diff_full(rev).await {
-                Ok(diff_text) if !diff_text.trim().is_empty() => {
-                    match crate::sync_engine::apply_diff_to_path(&repo_path, &diff_text).await {
-                        Ok(()) => {
-                            used_incremental = true;
-                            debug!(rev, "applied incremental SVN diff");
-                        }
-                        Err(e) => {
-                            debug!(rev, error = %e, "incremental diff failed, falling back to full export");
-                        }
-                    }
-                }
-                _ => {
-                    debug!(rev, "no diff available, using full export");
-                }
-            }
+        let collapse_keywords = import_config.collapse_keywords;
+        if let Err(e) = export_tree(svn_client, "", rev, export_dir.path(), collapse_keywords).await
+        {
+            let msg = format!("[error] r{}: SVN export failed: {}", rev, e);
+            log(&progress, &ws_broadcast, msg.clone()).await;
+            let mut p = progress.write().await;
+            p.errors.push(msg);
+            continue;
         }
 
-        if !used_incremental {
-            if let Err(e) = svn_client.export("", rev, export_dir.path()).await {
-                let msg = format!("[error] r{}: SVN export failed: {}", rev, e);
+        // Shape the export into the tree to commit, with policy enforcement.
+        let staged = stage_export(
+            export_dir.path(),
+            file_policy,
+            gitattributes.as_deref(),
+            lfs_repo,
+            db,
+        );
+        let copy_stats = match staged {
+            Ok(s) => s,
+            Err(e) => {
+                let msg = format!("[error] r{}: copy failed: {}", rev, e);
                 log(&progress, &ws_broadcast, msg.clone()).await;
                 let mut p = progress.write().await;
                 p.errors.push(msg);
                 continue;
             }
+        };
 
-            // Remove stale files from Git working tree
-            if let Err(e) = remove_stale_files(export_dir.path(), &repo_path) {
-                let msg = format!("[warn] r{}: failed to remove stale files: {}", rev, e);
+        let targets = PropTargets::from_log(&entry.changed_paths, &repo_base);
+        let props = sync_file_props(
+            svn_client,
+            rev,
+            export_dir.path(),
+            props_were_known.then_some(&targets),
+            import_config.sync_executable_bit,
+            import_config.normalize_line_endings,
+        );
+        match props.await {
+            Ok(()) => props_known = true,
+            Err(e) => {
+                let msg = format!("[warn] r{}: failed to sync file properties: {:#}", rev, e);
                 log(&progress, &ws_broadcast, msg).await;
             }
         }
 
-        // Copy with policy enforcement (only needed for full export path)
-        let copy_stats = if used_incremental {
-            CopyStats::default()
-        } else {
-            match copy_tree_with_policy(export_dir.path(), &repo_path, file_policy, db)
-            {
-            Ok(s) => s,
+        // Only the paths that differ from the previous revision are streamed.
+        let changes = match manifest.update_from_dir(export_dir.path(), is_imported_path) {
+            Ok(changes) => changes,
             Err(e) => {
-                let msg = format!("[error] r{}: copy failed: {}", rev, e);
+                let msg = format!("[error] r{}: failed to read the staged tree: {}", rev, e);
                 log(&progress, &ws_broadcast, msg.clone()).await;
                 let mut p = progress.write().await;
                 p.errors.push(msg);
                 continue;
             }
-        }
         };
+        gitattributes = std::fs::read(export_dir.path().join(crate::svn_props::GITATTRIBUTES)).ok();
 
         // Update file stats — use current file count (not cumulative)
         {
@@ -645,211 +1261,195 @@ pub async fn run_full_import(
             entry.message, rev, entry.author, entry.date
         );
 
-        // Commit — use CLI when LFS files are present so that `git add`
-        // invokes the LFS clean filter and stores large files as pointers.
-        // libgit2's Index::add_all() bypasses LFS filters entirely.
-        let use_cli = lfs_available && copy_stats.lfs_tracked > 0;
-        let (commit_result, push_repo_path) = {
-            let git_client_guard = git_client.lock().unwrap_or_else(|p| p.into_inner());
-            let rp = git_client_guard.repo_workdir();
-            let result = if use_cli {
-                debug!(
-                    rev,
-                    lfs_count = copy_stats.lfs_tracked,
-                    "using git CLI for commit (LFS files present)"
-                );
-                git_client_guard.commit_via_cli(
-                    &message,
-                    &author_name,
-                    &author_email,
-                    &import_config.committer_name,
-                    &import_config.committer_email,
-                )
-            } else {
-                git_client_guard.commit(
-                    &message,
-                    &author_name,
-                    &author_email,
-                    &import_config.committer_name,
-                    &import_config.committer_email,
-                )
+        if changes.is_empty() {
+            // Property-only revisions leave the tree unchanged.
+            let msg = format!("[skip] r{}: no changes to commit", rev);
+            log(&progress, &ws_broadcast, msg).await;
+        } else {
+            fast_import
+                .commit(&FastImportCommit {
+                    mark: rev,
+                    author_name: &author_name,
+                    author_email: &author_email,
+                    author_time: svn_date_to_epoch(&entry.date),
+                    committer_name: &import_config.committer_name,
+                    committer_email: &import_config.committer_email,
+                    committer_time: chrono::Utc::now().timestamp(),
+                    message: &message,
+                    changes: &changes,
+                })
+                .with_context(|| format!("failed to write r{} to git fast-import", rev))?;
+
+            // Log with details
+            let mut detail_parts = vec![format!("{} files", copy_stats.copied)];
+            if copy_stats.lfs_tracked > 0 {
+                detail_parts.push(format!("LFS: {}", copy_stats.lfs_tracked));
+            }
+            if copy_stats.skipped > 0 {
+                detail_parts.push(format!("skipped: {}", copy_stats.skipped));
+            }
+            let details = detail_parts.join(", ");
+
+            let log_line = format!(
+                "[ok] r{} ({}) \"{}\" [{} changed, {}]",
+                rev,
+                author_name,
+                entry.message.lines().next().unwrap_or("").chars().take(60).collect::<String>(),
+                changes.len(),
+                details,
+            );
+            log(&progress, &ws_broadcast, log_line).await;
+
+            unmapped.push((
+                rev,
+                entry.author.clone(),
+                format!("{} <{}>", author_name, author_email),
+            ));
+
+            count += 1;
+            commits_since_push += 1;
+            {
+                let mut p = progress.write().await;
+                p.commits_created = count;
+            }
+        }
+
+        // Incremental push every PUSH_BATCH_SIZE commits
+        if commits_since_push >= PUSH_BATCH_SIZE {
+            // Make the streamed commits visible under the branch first.
+            let marks = fast_import.checkpoint().context("git fast-import checkpoint failed")?;
+            record_marks(db, &marks, &mut unmapped, import_config.repo_id.as_deref());
+            {
+                let p = progress.read().await;
+                if let Err(e) =
+                    db.persist_import_progress_for(import_config.progress_repo_id(), &p)
+                {
+                    warn!("failed to persist import progress at checkpoint: {}", e);
+                }
+            }
+
+            let is_first_push = {
+                let p = progress.read().await;
+                p.batches_pushed == 0
             };
-            (result, rp)
-        }; // git_client_guard dropped here, before any .await
-        match commit_result {
-            Ok(oid) => {
-                let sha = oid.to_string();
-                let short_sha = &sha[..8.min(sha.len())];
-
-                // Log with details
-                let mut detail_parts = vec![format!("{} files", copy_stats.copied)];
-                if copy_stats.lfs_tracked > 0 {
-                    detail_parts.push(format!("LFS: {}", copy_stats.lfs_tracked));
+            let push_type = if is_first_push { "force-push" } else { "push" };
+            log(
+                &progress,
+                &ws_broadcast,
+                format!("[info] {} batch of {} commits to remote...", push_type, commits_since_push),
+            )
+            .await;
+
+            // Use spawn_blocking to avoid blocking the tokio runtime
+            let remote = import_config.remote_name.clone();
+            let branch = import_config.branch.clone();
+            let force = is_first_push;
+            let rp = repo_path.clone();
+            let auth = push_auth();
+            let url = push_url.clone();
+
+            // Heartbeat task: log "still pushing..." every 30s
+            let hb_progress = progress.clone();
+            let hb_ws = ws_broadcast.clone();
+            let hb_cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+            let hb_cancel2 = hb_cancel.clone();
+            let hb_handle = tokio::spawn(async move {
+                let start = std::time::Instant::now();
+                loop {
+                    tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+                    if hb_cancel2.load(std::sync::atomic::Ordering::Relaxed) {
+                        break;
+                    }
+                    let elapsed = start.elapsed().as_secs();
+                    push_log_line(
+                        &hb_progress,
+                        &hb_ws,
+                        format!("[push] still uploading... ({}m {}s elapsed)", elapsed / 60, elapsed % 60),
+                    ).await;
                 }
-                if copy_stats.skipped > 0 {
-                    detail_parts.push(format!("skipped: {}", copy_stats.skipped));
+            });
+
+            let push_result = tokio::task::spawn_blocking(move || {
+                let start = std::time::Instant::now();
+                info!(remote = %remote, branch = %branch, force, "spawn_blocking push starting");
+
+                let mut args = vec!["push".to_string(), "--progress".to_string()];
+                if force {
+                    args.push("--force".to_string());
                 }
-                let details = detail_parts.join(", ");
+                args.push(remote.clone());
+                args.push(branch.clone());
 
-                let log_line = format!(
-                    "[ok] r{} → {} ({}) \"{}\" [{}]",
-                    rev,
-                    short_sha,
-                    author_name,
-                    entry.message.lines().next().unwrap_or("").chars().take(60).collect::<String>(),
-                    details,
-                );
-                log(&progress, &ws_broadcast, log_line).await;
+                let output = std::process::Command::new("git")
+                    .args(&args)
+                    .current_dir(&rp)
+                    .env("GIT_TERMINAL_PROMPT", "0")
+                    .envs(auth.command_env(&url))
+                    .output();
 
-                // Record in DB
-                db.insert_commit_map(
-                    rev,
-                    &sha,
-                    "svn_to_git",
-                    &entry.author,
-                    &format!("{} <{}>", author_name, author_email),
-                )
-                .ok();
+                let elapsed = start.elapsed();
 
-                count += 1;
-                commits_since_push += 1;
-                {
-                    let mut p = progress.write().await;
-                    p.commits_created = count;
+                match output {
+                    Ok(out) => {
+                        let stderr = String::from_utf8_lossy(&out.stderr).to_string();
+                        if out.status.success() {
+                            info!(elapsed_secs = elapsed.as_secs_f64(), "push completed");
+                            Ok(stderr)
+                        } else {
+                            error!(stderr = %stderr, elapsed_secs = elapsed.as_secs_f64(), "push failed");
+                            Err(format!("git push failed (exit {:?}, {:.1}s): {}", out.status.code(), elapsed.as_secs_f64(), stderr.trim()))
+                        }
+                    }
+                    Err(e) => {
+                        error!(error = %e, "failed to spawn git push");
+                        Err(format!("failed to spawn git push: {}", e))
+                    }
                 }
+            }).await;
 
-                let repo_path = push_repo_path;
+            // Stop heartbeat
+            hb_cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+            hb_handle.abort();
 
-                // Incremental push every PUSH_BATCH_SIZE commits
-                if commits_since_push >= PUSH_BATCH_SIZE {
-                    let is_first_push = {
+            match push_result {
+                Ok(Ok(stderr)) => {
+                    // Log any git push output (remote warnings, etc.)
+                    for line in stderr.lines() {
+                        let trimmed = line.trim();
+                        if !trimmed.is_empty() {
+                            log(&progress, &ws_broadcast, format!("[push] {}", trimmed)).await;
+                        }
+                    }
+                    {
+                        let mut p = progress.write().await;
+                        p.batches_pushed += 1;
+                    }
+                    // Persist progress after batch push
+                    {
                         let p = progress.read().await;
-                        p.batches_pushed == 0
-                    };
-                    let push_type = if is_first_push { "force-push" } else { "push" };
+                        if let Err(e) =
+                            db.persist_import_progress_for(import_config.progress_repo_id(), &p)
+                        {
+                            warn!("failed to persist import progress after batch push: {}", e);
+                        }
+                    }
                     log(
                         &progress,
                         &ws_broadcast,
-                        format!("[info] {} batch of {} commits to remote...", push_type, commits_since_push),
+                        format!("[ok] Batch pushed ({} of {} total commits)", count, log_entries.len()),
                     )
                     .await;
-
-                    // Use spawn_blocking to avoid blocking the tokio runtime
-                    let remote = import_config.remote_name.clone();
-                    let branch = import_config.branch.clone();
-                    let force = is_first_push;
-                    let rp = repo_path.clone();
-
-                    // Heartbeat task: log "still pushing..." every 30s
-                    let hb_progress = progress.clone();
-                    let hb_ws = ws_broadcast.clone();
-                    let hb_cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
-                    let hb_cancel2 = hb_cancel.clone();
-                    let hb_handle = tokio::spawn(async move {
-                        let start = std::time::Instant::now();
-                        loop {
-                            tokio::time::sleep(std::time::Duration::from_secs(15)).await;
-                            if hb_cancel2.load(std::sync::atomic::Ordering::Relaxed) {
-                                break;
-                            }
-                            let elapsed = start.elapsed().as_secs();
-                            push_log_line(
-                                &hb_progress,
-                                &hb_ws,
-                                format!("[push] still uploading... ({}m {}s elapsed)", elapsed / 60, elapsed % 60),
-                            ).await;
-                        }
-                    });
-
-                    let push_result = tokio::task::spawn_blocking(move || {
-                        let start = std::time::Instant::now();
-                        info!(remote = %remote, branch = %branch, force, "spawn_blocking push starting");
-
-                        let mut args = vec!["push".to_string(), "--progress".to_string()];
-                        if force {
-                            args.push("--force".to_string());
-                        }
-                        args.push(remote.clone());
-                        args.push(branch.clone());
-
-                        let output = std::process::Command::new("git")
-                            .args(&args)
-                            .current_dir(&rp)
-                            .env("GIT_TERMINAL_PROMPT", "0")
-                            .output();
-
-                        let elapsed = start.elapsed();
-
-                        match output {
-                            Ok(out) => {
-                                let stderr = String::from_utf8_lossy(&out.stderr).to_string();
-                                if out.status.success() {
-                                    info!(elapsed_secs = elapsed.as_secs_f64(), "push completed");
-                                    Ok(stderr)
-                                } else {
-                                    error!(stderr = %stderr, elapsed_secs = elapsed.as_secs_f64(), "push failed");
-                                    Err(format!("git push failed (exit {:?}, {:.1}s): {}", out.status.code(), elapsed.as_secs_f64(), stderr.trim()))
-                                }
-                            }
-                            Err(e) => {
-                                error!(error = %e, "failed to spawn git push");
-                                Err(format!("failed to spawn git push: {}", e))
-                            }
-                        }
-                    }).await;
-
-                    // Stop heartbeat
-                    hb_cancel.store(true, std::sync::atomic::Ordering::Relaxed);
-                    hb_handle.abort();
-
-                    match push_result {
-                        Ok(Ok(stderr)) => {
-                            // Log any git push output (remote warnings, etc.)
-                            for line in stderr.lines() {
-                                let trimmed = line.trim();
-                                if !trimmed.is_empty() {
-                                    log(&progress, &ws_broadcast, format!("[push] {}", trimmed)).await;
-                                }
-                            }
-                            {
-                                let mut p = progress.write().await;
-                                p.batches_pushed += 1;
-                            }
-                            // Persist progress after batch push
-                            {
-                                let p = progress.read().await;
-                                if let Err(e) = db.persist_import_progress(&p) {
-                                    warn!("failed to persist import progress after batch push: {}", e);
-                                }
-                            }
-                            log(
-                                &progress,
-                                &ws_broadcast,
-                                format!("[ok] Batch pushed ({} of {} total commits)", count, log_entries.len()),
-                            )
-                            .await;
-                        }
-                        Ok(Err(e)) => {
-                            let msg = format!("[warn] Batch push failed (will retry at end): {}", e);
-                            log(&progress, &ws_broadcast, msg).await;
-                        }
-                        Err(e) => {
-                            let msg = format!("[warn] Batch push task panicked: {}", e);
-                            log(&progress, &ws_broadcast, msg).await;
-                        }
-                    }
-                    commits_since_push = 0;
+                }
+                Ok(Err(e)) => {
+                    let msg = format!("[warn] Batch push failed (will retry at end): {}", e);
+                    log(&progress, &ws_broadcast, msg).await;
+                }
+                Err(e) => {
+                    let msg = format!("[warn] Batch push task panicked: {}", e);
+                    log(&progress, &ws_broadcast, msg).await;
                 }
             }
-            Err(e) => {
-                // Empty commits (property-only revisions) are expected
-                let msg = format!(
-                    "[skip] r{}: no changes to commit ({})",
-                    rev,
-                    e.to_string().lines().next().unwrap_or("unknown")
-                );
-                log(&progress, &ws_broadcast, msg).await;
-            }
+            commits_since_push = 0;
         }
 
         // Broadcast progress JSON update
@@ -872,12 +1472,14 @@ pub async fn run_full_import(
         // Persist progress to DB every 10 revisions
         if (idx + 1) % 10 == 0 {
             let p = progress.read().await;
-            if let Err(e) = db.persist_import_progress(&p) {
+            if let Err(e) = db.persist_import_progress_for(import_config.progress_repo_id(), &p) {
                 warn!("failed to persist import progress at rev {}: {}", idx + 1, e);
             }
         }
     }
 
+    finish_fast_import(fast_import, git_client, import_config, db, &mut unmapped)?;
+
     // Push remaining commits (those since last batch push)
     if commits_since_push > 0 {
         let max_retries = 3;
@@ -908,6 +1510,8 @@ pub async fn run_full_import(
             let branch = import_config.branch.clone();
             let force = is_first_push;
             let rp = repo_path.clone();
+            let auth = push_auth();
+            let url = push_url.clone();
 
             let push_result = tokio::task::spawn_blocking(move || {
                 let mut args = vec!["push".to_string(), "--progress".to_string()];
@@ -915,7 +1519,7 @@ pub async fn run_full_import(
                 args.push(remote); args.push(branch);
                 let output = std::process::Command::new("git")
                     .args(&args).current_dir(&rp)
-                    .env("GIT_TERMINAL_PROMPT", "0").output();
+                    .env("GIT_TERMINAL_PROMPT", "0").envs(auth.command_env(&url)).output();
                 match output {
                     Ok(out) if out.status.success() => Ok(String::from_utf8_lossy(&out.stderr).to_string()),
                     Ok(out) => Err(format!("exit {:?}: {}", out.status.code(), String::from_utf8_lossy(&out.stderr).trim())),
@@ -969,23 +1573,22 @@ pub async fn run_full_import(
     // Persist progress before final watermarks
     {
         let p = progress.read().await;
-        if let Err(e) = db.persist_import_progress(&p) {
+        if let Err(e) = db.persist_import_progress_for(import_config.progress_repo_id(), &p) {
             warn!("failed to persist import progress before watermarks: {}", e);
         }
     }
 
     // Set watermarks
-    if let Some(last) = log_entries.last() {
-        db.set_watermark("svn_rev", &last.revision.to_string())
-            .ok();
-    }
-
-    {
+    let head_sha = {
         let git_guard = git_client.lock().unwrap_or_else(|p| p.into_inner());
-        if let Ok(sha) = git_guard.get_head_sha() {
-            db.set_watermark("git_sha", &sha).ok();
-        }
-    }
+        git_guard.get_head_sha().ok()
+    };
+    record_watermarks(
+        db,
+        import_config.repo_id.as_deref(),
+        log_entries.last().map(|e| e.revision).or(baseline_rev),
+        head_sha.as_deref(),
+    );
 
     // Final audit log
     db.insert_audit_log(
@@ -1011,8 +1614,95 @@ pub async fn run_full_import(
     Ok(count)
 }
 
+/// Record where incremental sync continues after an import: under the sync
+/// engine keys and in the repository row for `repo_id`, in the global
+/// watermarks otherwise.
+pub(crate) fn record_watermarks(
+    db: &Database,
+    repo_id: Option<&str>,
+    rev: Option<i64>,
+    sha: Option<&str>,
+) {
+    match repo_id {
+        Some(rid) => {
+            if let Some(rev) = rev {
+                db.set_state(&svn_rev_key_for(repo_id), &rev.to_string()).ok();
+                db.update_repo_watermark(rid, rev, sha.unwrap_or_default()).ok();
+            }
+            if let Some(sha) = sha {
+                db.set_state(&git_sha_key_for(repo_id), sha).ok();
+            }
+        }
+        None => {
+            if let Some(rev) = rev {
+                db.set_watermark("svn_rev", &rev.to_string()).ok();
+            }
+            if let Some(sha) = sha {
+                db.set_watermark("git_sha", sha).ok();
+            }
+        }
+    }
+}
+
+/// LFS preflight for an import: when the policy uses LFS, check that Git LFS
+/// is available and install its hooks in the repository. Returns whether the
+/// LFS filters are active.
+pub(crate) async fn prepare_lfs(
+    git_client: &Arc<std::sync::Mutex<GitClient>>,
+    file_policy: &FilePolicy,
+    progress: &Arc<RwLock<ImportProgress>>,
+    ws_broadcast: &Option<broadcast::Sender<String>>,
+) -> bool {
+    if !file_policy.lfs_enabled() {
+        return false;
+    }
+    let version = match crate::lfs::preflight_check() {
+        Ok(version) => version,
+        Err(e) => {
+            push_log_line(
+                progress,
+                ws_broadcast,
+                format!(
+                    "[warn] Git LFS not available: {} — large files will be committed directly",
+                    e
+                ),
+            )
+            .await;
+            return false;
+        }
+    };
+    push_log_line(progress, ws_broadcast, format!("[info] Git LFS available: {}", version)).await;
+
+    // Install LFS hooks/filters in the repo so `git add` invokes
+    // the clean filter and creates pointer files for tracked patterns.
+    let rp = {
+        let git_guard = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        git_guard.repo_workdir()
+    };
+    match crate::lfs::install_lfs_hooks(&rp) {
+        Ok(()) => {
+            push_log_line(
+                progress,
+                ws_broadcast,
+                "[info] Git LFS installed in repo (filters active)".into(),
+            )
+            .await;
+            true
+        }
+        Err(e) => {
+            push_log_line(
+                progress,
+                ws_broadcast,
+                format!("[warn] git lfs install failed: {} — LFS tracking will not work", e),
+            )
+            .await;
+            false
+        }
+    }
+}
+
 /// Helper to push a log line and broadcast it via WebSocket.
-async fn push_log_line(
+pub(crate) async fn push_log_line(
     progress: &Arc<RwLock<ImportProgress>>,
     ws: &Option<broadcast::Sender<String>>,
     line: String,
@@ -1166,3 +1856,111 @@ async fn async_git_push(
     );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_filter() {
+        let filter = ImportFilter::default();
+        assert!(!filter.has_range());
+        filter.validate().unwrap();
+
+        let filter = ImportFilter {
+            from_rev: Some(40_000),
+            to_rev: Some(41_000),
+            include_paths: vec!["src/**".into()],
+            exclude_paths: vec!["src/vendor/**".into()],
+            ..Default::default()
+        };
+        assert!(filter.has_range());
+        filter.validate().unwrap();
+        let policy = filter.policy(&FilePolicy::new(0, vec!["**/*.log".into()]));
+        assert!(policy.evaluate("src/lib.rs", 1).should_sync());
+        assert!(policy.evaluate("docs/index.md", 1).is_blocked());
+        assert!(policy.evaluate("src/vendor/zlib.c", 1).is_blocked());
+        assert!(policy.evaluate("src/trace.log", 1).is_blocked());
+
+        for date in ["2024-01-31", "2024-01-31T12:00:00Z"] {
+            let filter = ImportFilter { from_date: Some(date.into()), ..Default::default() };
+            filter.validate().unwrap();
+        }
+        let invalid = [
+            ImportFilter { from_rev: Some(0), ..Default::default() },
+            ImportFilter { from_rev: Some(5), to_rev: Some(4), ..Default::default() },
+            ImportFilter {
+                from_rev: Some(5),
+                from_date: Some("2024-01-31".into()),
+                ..Default::default()
+            },
+            ImportFilter { from_date: Some("last week".into()), ..Default::default() },
+            ImportFilter { exclude_paths: vec![" ".into()], ..Default::default() },
+        ];
+        for filter in invalid {
+            assert!(filter.validate().is_err(), "{:?}", filter);
+        }
+    }
+
+    #[test]
+    fn test_resume_point() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut opts = git2::RepositoryInitOptions::new();
+        opts.initial_head("main");
+        git2::Repository::init_opts(dir.path(), &opts).unwrap();
+        let git = GitClient::new(dir.path()).unwrap();
+        let db = Database::in_memory().unwrap();
+        db.initialize().unwrap();
+        let mut import_config = ImportConfig {
+            committer_name: "RepoSync".into(),
+            committer_email: "reposync@localhost".into(),
+            remote_name: "origin".into(),
+            branch: "main".into(),
+            push_token: None,
+            message_prefix: None,
+            sync_executable_bit: false,
+            normalize_line_endings: false,
+            collapse_keywords: false,
+            resume: true,
+            layout: None,
+            filter: Default::default(),
+            repo_id: None,
+            per_repo_progress: false,
+        };
+        assert!(resume_point(&db, &git, &import_config).unwrap().is_none());
+
+        std::fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+        let first = git.commit("r1", "T", "t@t.com", "T", "t@t.com").unwrap().to_string();
+        db.insert_commit_map(1, &first, "svn_to_git", "t", "T <t@t.com>").unwrap();
+        let mut progress = ImportProgress {
+            phase: ImportPhase::Importing,
+            commits_created: 1,
+            ..Default::default()
+        };
+        db.persist_import_progress(&progress).unwrap();
+        let (saved, rev, sha) = resume_point(&db, &git, &import_config).unwrap().unwrap();
+        assert_eq!((saved.commits_created, rev, sha), (1, 1, first.clone()));
+
+        // A repository's import resumes from its own progress only.
+        import_config.repo_id = Some("r1".into());
+        import_config.per_repo_progress = true;
+        assert!(resume_point(&db, &git, &import_config).unwrap().is_none());
+        db.persist_import_progress_for(Some("r1"), &progress).unwrap();
+        let (_, rev, sha) = resume_point(&db, &git, &import_config).unwrap().unwrap();
+        assert_eq!((rev, sha), (1, first));
+        import_config.repo_id = None;
+        import_config.per_repo_progress = false;
+
+        // A finished import starts over.
+        progress.phase = ImportPhase::Completed;
+        db.persist_import_progress(&progress).unwrap();
+        assert!(resume_point(&db, &git, &import_config).unwrap().is_none());
+
+        // The branch moved on since the checkpoint.
+        progress.phase = ImportPhase::Cancelled;
+        db.persist_import_progress(&progress).unwrap();
+        std::fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+        git.commit("stray", "T", "t@t.com", "T", "t@t.com").unwrap();
+        assert!(resume_point(&db, &git, &import_config).is_err());
+    }
+}
@@ -0,0 +1,995 @@
+//! Full-history import of a standard trunk/branches/tags layout.
+//!
+//! [`run_full_import`](crate::import::run_full_import) replays a single path.
+//! [`LayoutReplayer`] replays a whole layout in one pass over the repository
+//! log instead: each revision is exported once per line it touches (trunk,
+//! a directory below `branches` or one below `tags`) and streamed to
+//! `git fast-import` on that line's ref.
+//!
+//! A branch or tag copied from another line starts at the commit that line
+//! had at the copy's source revision, so the Git graph shows where it
+//! forked; a copy without further changes adds no commit of its own. Tags
+//! become annotated Git tags when the import finishes. A deleted branch
+//! keeps its last commit, while deleted tags are dropped.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::db::Database;
+use crate::file_policy::FilePolicy;
+use crate::git::{FastImport, FastImportCommit, GitClient, TreeManifest};
+use crate::identity::mapper::{GitIdentity, IdentityMapper};
+use crate::import::{
+    export_tree, is_imported_path, push_log_line, record_watermarks, stage_export,
+    svn_date_to_epoch, sync_file_props, CopyStats, ImportConfig, ImportPhase, ImportProgress,
+};
+use crate::ref_map::{map_svn_to_git, RefMapping};
+use crate::svn::{SvnClient, SvnInfo, SvnLogEntry};
+use crate::svn_props::{is_under, join_path, PropTargets, GITATTRIBUTES};
+use crate::sync_engine::{
+    branch_repo_id_for, git_sha_key_for, svn_rev_key_for, svn_tag_rev_key_for,
+};
+
+/// Namespace of the refs tag lines are committed to until they are tagged.
+const TAG_LINE_REFS: &str = "refs/reposync/tags";
+
+/// The directories of a standard layout and the Git names of its lines.
+#[derive(Debug, Clone)]
+pub struct ImportLayout {
+    /// Trunk directory (e.g. `trunk`).
+    pub trunk_path: String,
+    /// Directory holding one subdirectory per branch (e.g. `branches`).
+    pub branches_path: String,
+    /// Directory holding one subdirectory per tag (e.g. `tags`).
+    pub tags_path: String,
+    /// Git branch trunk is imported to.
+    pub trunk_branch: String,
+    /// SVN→Git branch name mappings; names none of them cover are kept.
+    pub branch_mappings: Vec<RefMapping>,
+    /// SVN→Git tag name mappings; names none of them cover are kept.
+    pub tag_mappings: Vec<RefMapping>,
+}
+
+impl ImportLayout {
+    /// `trunk_path` with `branches` and `tags` beside it, imported under
+    /// their SVN names.
+    pub fn standard(trunk_path: &str, trunk_branch: &str) -> Self {
+        Self {
+            trunk_path: trunk_path.trim_matches('/').to_string(),
+            branches_path: "branches".into(),
+            tags_path: "tags".into(),
+            trunk_branch: trunk_branch.to_string(),
+            branch_mappings: Vec::new(),
+            tag_mappings: Vec::new(),
+        }
+    }
+
+    /// The layout of `svn.*_path`, with branch names mapped by
+    /// `sync.sync_branches` and tag names by `sync.tag_mappings`.
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            trunk_path: config.svn.trunk_path.trim_matches('/').to_string(),
+            branches_path: config.svn.branches_path.trim_matches('/').to_string(),
+            tags_path: config.svn.tags_path.trim_matches('/').to_string(),
+            trunk_branch: config.github.default_branch.clone(),
+            branch_mappings: RefMapping::parse_all(&config.sync.sync_branches),
+            tag_mappings: RefMapping::parse_all(&config.sync.tag_mappings),
+        }
+    }
+
+    /// The line `path` belongs to and the path below the line's root, for a
+    /// path relative to the layout base.  The `branches` and `tags`
+    /// directories themselves belong to no line.
+    fn classify<'p>(&self, path: &'p str) -> Option<(LineKey, &'p str)> {
+        let path = path.trim_matches('/');
+        if let Some(rest) = below(path, &self.trunk_path) {
+            return Some((LineKey::trunk(), rest));
+        }
+        let dirs = [(LineKind::Branch, &self.branches_path), (LineKind::Tag, &self.tags_path)];
+        for (kind, dir) in dirs {
+            let Some(rest) = below(path, dir).filter(|rest| !rest.is_empty()) else {
+                continue;
+            };
+            let (name, rest) = rest.split_once('/').unwrap_or((rest, ""));
+            return Some((LineKey { kind, name: name.to_string() }, rest));
+        }
+        None
+    }
+
+    /// Path of a line's root relative to the layout base.
+    fn line_path(&self, key: &LineKey) -> String {
+        match key.kind {
+            LineKind::Trunk => self.trunk_path.clone(),
+            LineKind::Branch => join_path(&self.branches_path, &key.name),
+            LineKind::Tag => join_path(&self.tags_path, &key.name),
+        }
+    }
+
+    /// Git name of a line, or `None` when it makes no valid ref or a branch
+    /// would take the trunk branch's name.
+    fn git_name(&self, key: &LineKey) -> Option<String> {
+        let (name, namespace) = match key.kind {
+            LineKind::Trunk => return Some(self.trunk_branch.clone()),
+            LineKind::Branch => (
+                map_svn_to_git(&self.branch_mappings, &key.name).unwrap_or(key.name.clone()),
+                "refs/heads",
+            ),
+            LineKind::Tag => (
+                map_svn_to_git(&self.tag_mappings, &key.name).unwrap_or(key.name.clone()),
+                "refs/tags",
+            ),
+        };
+        let valid = git2::Reference::is_valid_name(&format!("{}/{}", namespace, name));
+        (valid && !(key.kind == LineKind::Branch && name == self.trunk_branch)).then_some(name)
+    }
+}
+
+/// The part of `path` below `dir`: empty for `dir` itself, `None` outside it.
+fn below<'p>(path: &'p str, dir: &str) -> Option<&'p str> {
+    if dir.is_empty() || !is_under(path, dir) {
+        return None;
+    }
+    Some(path[dir.len()..].trim_start_matches('/'))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum LineKind {
+    Trunk,
+    Branch,
+    Tag,
+}
+
+/// A line of history: trunk, one branch or one tag.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct LineKey {
+    kind: LineKind,
+    /// Branch or tag name; empty for trunk.
+    name: String,
+}
+
+impl LineKey {
+    fn trunk() -> Self {
+        Self { kind: LineKind::Trunk, name: String::new() }
+    }
+
+    /// `commit_map` repo ID of the line's commits: `mainline_repo_id` for
+    /// trunk, and the one the sync engine of `mainline_repo_id` keeps a
+    /// branch pair under for a branch.
+    fn repo_id(&self, mainline_repo_id: Option<&str>) -> Option<String> {
+        match self.kind {
+            LineKind::Branch => Some(branch_repo_id_for(mainline_repo_id, &self.name)),
+            _ => mainline_repo_id.map(str::to_string),
+        }
+    }
+}
+
+/// Import state of one line.
+struct Line {
+    /// Ref the line's commits are written to.
+    ref_name: String,
+    git_name: String,
+    /// Files of the commit the ref points at.
+    manifest: TreeManifest,
+    /// `.gitattributes` of that commit.
+    gitattributes: Option<Vec<u8>>,
+    /// `(revision, commit)` each time the ref moved, oldest first; commits
+    /// are SHAs or `:<mark>`.
+    history: Vec<(i64, String)>,
+    /// Whether the line exists at the current revision.
+    live: bool,
+    /// The last revision that touched the line, for a tag's annotation.
+    last_change: Option<LineChange>,
+}
+
+struct LineChange {
+    revision: i64,
+    tagger_name: String,
+    tagger_email: String,
+    time: i64,
+    message: String,
+}
+
+/// Where a copied line starts: the source commit (SHA or `:<mark>`) with
+/// its files and `.gitattributes`.
+struct CopyBase {
+    from: String,
+    manifest: TreeManifest,
+    gitattributes: Option<Vec<u8>>,
+}
+
+/// A `commit_map` row waiting for its commit's SHA.
+struct CommitRow {
+    mark: i64,
+    revision: i64,
+    line: LineKey,
+    svn_author: String,
+    git_author: String,
+}
+
+/// A commit written by [`LayoutReplayer::replay`].
+#[derive(Debug, Clone)]
+pub struct LayoutCommit {
+    pub revision: i64,
+    /// SVN path of the line (e.g. `branches/1.x`).
+    pub svn_path: String,
+    /// Git branch or tag the commit is on.
+    pub git_name: String,
+    /// Number of changed paths.
+    pub changes: usize,
+    pub stats: CopyStats,
+}
+
+/// What [`LayoutReplayer::finish`] imported.
+#[derive(Debug, Clone, Default)]
+pub struct LayoutSummary {
+    pub commits: u64,
+    /// Git branches with at least one commit, trunk first.
+    pub branches: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl LayoutSummary {
+    /// Full names of the imported branches and tags, for pushing.
+    pub fn refs(&self) -> Vec<String> {
+        let heads = self.branches.iter().map(|b| format!("refs/heads/{}", b));
+        heads.chain(self.tags.iter().map(|t| format!("refs/tags/{}", t))).collect()
+    }
+}
+
+/// Replays the revisions of a standard layout into Git branches and tags.
+pub struct LayoutReplayer<'a> {
+    /// Client for the repository root.
+    svn: SvnClient,
+    root_url: String,
+    layout: ImportLayout,
+    /// Path of the layout base below the repository root.
+    prefix: String,
+    repo_path: PathBuf,
+    policy: &'a FilePolicy,
+    db: &'a Database,
+    /// Repo ID of the mainline the watermarks are recorded for.
+    repo_id: Option<String>,
+    lfs: bool,
+    sync_executable_bit: bool,
+    normalize_line_endings: bool,
+    collapse_keywords: bool,
+    fast_import: FastImport,
+    lines: BTreeMap<LineKey, Line>,
+    /// Lines without a valid Git name.
+    ignored: HashSet<LineKey>,
+    next_mark: i64,
+    rows: Vec<CommitRow>,
+    last_rev: Option<i64>,
+}
+
+impl<'a> LayoutReplayer<'a> {
+    /// Start a replay into the repository of `git`.  The layout paths are
+    /// relative to `svn_info.url`.  When the trunk branch already has
+    /// commits, trunk continues from its tip.
+    pub fn new(
+        svn_client: &SvnClient,
+        svn_info: &SvnInfo,
+        layout: ImportLayout,
+        git: &GitClient,
+        policy: &'a FilePolicy,
+        db: &'a Database,
+    ) -> Result<Self> {
+        let root_url = svn_info.root_url.trim_end_matches('/').to_string();
+        let prefix = svn_info.repo_path();
+        let repo_path = git.repo_path().to_path_buf();
+        let mut fast_import =
+            FastImport::start_refs(&repo_path).context("failed to start git fast-import")?;
+
+        let mut trunk = Line {
+            ref_name: format!("refs/heads/{}", layout.trunk_branch),
+            git_name: layout.trunk_branch.clone(),
+            manifest: TreeManifest::new(),
+            gitattributes: None,
+            history: Vec::new(),
+            live: false,
+            last_change: None,
+        };
+        if let Some(sha) = git.branch_sha(&layout.trunk_branch)? {
+            trunk.manifest = TreeManifest::from_commit(git.repo(), &sha)?;
+            trunk.gitattributes = git.get_file_content_at_commit(&sha, GITATTRIBUTES)?;
+            fast_import.reset(&trunk.ref_name, Some(&sha))?;
+            trunk.history.push((0, sha));
+            trunk.live = true;
+        }
+        let mut lines = BTreeMap::new();
+        lines.insert(LineKey::trunk(), trunk);
+
+        Ok(Self {
+            svn: svn_client.with_url(root_url.as_str()),
+            root_url,
+            layout,
+            prefix,
+            repo_path,
+            policy,
+            db,
+            repo_id: None,
+            lfs: false,
+            sync_executable_bit: false,
+            normalize_line_endings: false,
+            collapse_keywords: false,
+            fast_import,
+            lines,
+            ignored: HashSet::new(),
+            next_mark: 1,
+            rows: Vec::new(),
+            last_rev: None,
+        })
+    }
+
+    /// Record commits and watermarks under the keys of the sync engine with
+    /// `repo_id` instead of those of the global engine.
+    pub fn repo_id(mut self, repo_id: Option<String>) -> Self {
+        self.repo_id = repo_id.filter(|id| !id.is_empty());
+        self
+    }
+
+    /// Store LFS-tracked files as LFS objects in the repository.
+    pub fn lfs(mut self, enabled: bool) -> Self {
+        self.lfs = enabled;
+        self
+    }
+
+    /// Give files with `svn:executable` the executable mode.
+    pub fn sync_executable_bit(mut self, enabled: bool) -> Self {
+        self.sync_executable_bit = enabled;
+        self
+    }
+
+    /// Mirror `svn:eol-style` in each line's root `.gitattributes`.
+    pub fn normalize_line_endings(mut self, enabled: bool) -> Self {
+        self.normalize_line_endings = enabled;
+        self
+    }
+
+    /// Collapse keywords expanded through `svn:keywords`.
+    pub fn collapse_keywords(mut self, enabled: bool) -> Self {
+        self.collapse_keywords = enabled;
+        self
+    }
+
+    /// The repository log from r1 to `head_rev`, with changed paths.
+    pub async fn history(&self, head_rev: i64) -> Result<Vec<SvnLogEntry>> {
+        self.svn.log(1, head_rev).await.context("failed to get SVN log")
+    }
+
+    /// Replay the changes `entry` makes to the layout.  Commits take their
+    /// identities, times and message from `template`; its mark and changes
+    /// are replaced.
+    pub async fn replay(
+        &mut self,
+        entry: &SvnLogEntry,
+        template: &FastImportCommit<'_>,
+    ) -> Result<Vec<LayoutCommit>> {
+        let rev = entry.revision;
+        self.last_rev = Some(rev);
+
+        let mut deleted = BTreeSet::new();
+        let mut created: BTreeMap<LineKey, Option<(String, i64)>> = BTreeMap::new();
+        let mut touched = BTreeSet::new();
+        for changed in &entry.changed_paths {
+            let Some(path) = self.relative(&changed.path) else {
+                continue;
+            };
+            // Deleting a line or a directory above it ends the line.
+            if matches!(changed.action.as_str(), "D" | "R") {
+                for (key, line) in &self.lines {
+                    if line.live && is_under(&self.layout.line_path(key), path) {
+                        deleted.insert(key.clone());
+                    }
+                }
+            }
+            let Some((key, rest)) = self.layout.classify(path) else {
+                continue;
+            };
+            if rest.is_empty() && matches!(changed.action.as_str(), "A" | "R") {
+                let source = changed.copy_from_path.clone().zip(changed.copy_from_rev);
+                created.insert(key.clone(), source);
+            }
+            touched.insert(key);
+        }
+
+        for key in &deleted {
+            if !created.contains_key(key) {
+                if let Some(line) = self.lines.get_mut(key) {
+                    line.live = false;
+                    info!(rev, line = %self.layout.line_path(key), "SVN line deleted");
+                }
+            }
+        }
+
+        let mut commits = Vec::new();
+        for key in touched {
+            if self.ignored.contains(&key) {
+                continue;
+            }
+            let live = self.lines.get(&key).is_some_and(|line| line.live);
+            let created_line = match created.get(&key) {
+                Some(source) => self.create_line(&key, rev, source.as_ref())?,
+                // Content added below a line whose root was never added
+                // (e.g. a trunk that predates the log) starts the line.
+                None if !live && !deleted.contains(&key) => self.create_line(&key, rev, None)?,
+                None => live,
+            };
+            if !created_line {
+                continue;
+            }
+            if let Some(commit) = self.replay_line(&key, entry, template).await? {
+                commits.push(commit);
+            }
+        }
+        Ok(commits)
+    }
+
+    /// Start (or restart) `key` at `rev`, from the line commit its copy
+    /// source had at the source revision.  Returns `false` for a line
+    /// without a valid Git name.
+    fn create_line(
+        &mut self,
+        key: &LineKey,
+        rev: i64,
+        source: Option<&(String, i64)>,
+    ) -> Result<bool> {
+        let Some(git_name) = self.layout.git_name(key) else {
+            warn!(line = %self.layout.line_path(key), "no valid Git name for SVN line, skipping");
+            self.ignored.insert(key.clone());
+            return Ok(false);
+        };
+        let ref_name = match key.kind {
+            LineKind::Tag => format!("{}/{}", TAG_LINE_REFS, git_name),
+            _ => format!("refs/heads/{}", git_name),
+        };
+        let base = match source {
+            Some((path, source_rev)) => self.copy_base(path, *source_rev)?,
+            None => None,
+        };
+
+        let mut line = self.lines.remove(key).unwrap_or(Line {
+            ref_name,
+            git_name,
+            manifest: TreeManifest::new(),
+            gitattributes: None,
+            history: Vec::new(),
+            live: true,
+            last_change: None,
+        });
+        match base {
+            Some(base) => {
+                self.fast_import.reset(&line.ref_name, Some(&base.from))?;
+                line.manifest = base.manifest;
+                line.gitattributes = base.gitattributes;
+                line.history.push((rev, base.from));
+            }
+            // A live line (trunk continuing an existing branch) keeps its
+            // history.
+            None if line.live => {}
+            None => {
+                if !line.history.is_empty() {
+                    // A line re-created from scratch starts a new history.
+                    self.fast_import.reset(&line.ref_name, None)?;
+                }
+                line.manifest = TreeManifest::new();
+                line.gitattributes = None;
+            }
+        }
+        line.live = true;
+        info!(
+            rev,
+            line = %self.layout.line_path(key),
+            git = %line.git_name,
+            source = ?source,
+            "SVN line started"
+        );
+        self.lines.insert(key.clone(), line);
+        Ok(true)
+    }
+
+    /// The commit, files and `.gitattributes` a copy of `path` at
+    /// `source_rev` starts from, when `path` is the root of an imported line
+    /// that had a commit by then.
+    fn copy_base(
+        &mut self,
+        path: &str,
+        source_rev: i64,
+    ) -> Result<Option<CopyBase>> {
+        let Some((key, rest)) = self.relative(path).and_then(|p| self.layout.classify(p)) else {
+            return Ok(None);
+        };
+        let Some(line) = self.lines.get(&key).filter(|_| rest.is_empty()) else {
+            return Ok(None);
+        };
+        let idx = line.history.partition_point(|(rev, _)| *rev <= source_rev);
+        if idx == 0 {
+            return Ok(None);
+        }
+        let from = line.history[idx - 1].1.clone();
+        if idx == line.history.len() {
+            return Ok(Some(CopyBase {
+                from,
+                manifest: line.manifest.clone(),
+                gitattributes: line.gitattributes.clone(),
+            }));
+        }
+
+        // The source line has moved on: read its tree back from the
+        // repository, where the checkpoint puts every commit so far.
+        let marks = self.fast_import.checkpoint()?;
+        let sha = match from.strip_prefix(':').and_then(|m| m.parse::<i64>().ok()) {
+            Some(mark) => marks.get(&mark).cloned().context("copy source has no commit")?,
+            None => from.clone(),
+        };
+        let git = GitClient::new(&self.repo_path)?;
+        let manifest = TreeManifest::from_commit(git.repo(), &sha)?;
+        let gitattributes = git.get_file_content_at_commit(&sha, GITATTRIBUTES)?;
+        Ok(Some(CopyBase { from, manifest, gitattributes }))
+    }
+
+    /// Export `key` at the entry's revision and commit what changed.
+    async fn replay_line(
+        &mut self,
+        key: &LineKey,
+        entry: &SvnLogEntry,
+        template: &FastImportCommit<'_>,
+    ) -> Result<Option<LayoutCommit>> {
+        let rev = entry.revision;
+        let svn_path = self.layout.line_path(key);
+        let path = join_path(&self.prefix, &svn_path);
+        let export_dir = tempfile::tempdir().context("failed to create temp dir")?;
+        // Pegged, since the line may be gone at HEAD.
+        let pegged = format!("{}@{}", path, rev);
+        export_tree(&self.svn, &pegged, rev, export_dir.path(), self.collapse_keywords)
+            .await
+            .with_context(|| format!("SVN export of {} failed", svn_path))?;
+
+        let lfs_repo = self.lfs.then_some(self.repo_path.as_path());
+        let gitattributes = self.lines[key].gitattributes.clone();
+        let stats = stage_export(
+            export_dir.path(),
+            self.policy,
+            gitattributes.as_deref(),
+            lfs_repo,
+            self.db,
+        )
+        .with_context(|| format!("failed to stage {}", svn_path))?;
+        if self.sync_executable_bit || self.normalize_line_endings {
+            // A line created in this revision is looked up as a whole.
+            let line_svn = self.svn.with_url(format!("{}/{}", self.root_url, path));
+            let targets = PropTargets::from_log(&entry.changed_paths, &path);
+            let props = sync_file_props(
+                &line_svn,
+                rev,
+                export_dir.path(),
+                Some(&targets),
+                self.sync_executable_bit,
+                self.normalize_line_endings,
+            );
+            if let Err(e) = props.await {
+                warn!(rev, line = %svn_path, error = %e, "failed to sync file properties");
+            }
+        }
+
+        let line = self.lines.get_mut(key).expect("replayed line exists");
+        let changes = line
+            .manifest
+            .update_from_dir(export_dir.path(), is_imported_path)
+            .with_context(|| format!("failed to read the staged tree of {}", svn_path))?;
+        line.gitattributes = std::fs::read(export_dir.path().join(GITATTRIBUTES)).ok();
+        line.last_change = Some(LineChange {
+            revision: rev,
+            tagger_name: template.author_name.to_string(),
+            tagger_email: template.author_email.to_string(),
+            time: template.author_time,
+            message: template.message.to_string(),
+        });
+        if changes.is_empty() {
+            return Ok(None);
+        }
+
+        let mark = self.next_mark;
+        self.next_mark += 1;
+        let commit = FastImportCommit { mark, changes: &changes, ..template.clone() };
+        self.fast_import
+            .commit_on(&line.ref_name, &commit)
+            .with_context(|| {
+                format!("failed to write r{} of {} to git fast-import", rev, svn_path)
+            })?;
+        line.history.push((rev, format!(":{}", mark)));
+        self.rows.push(CommitRow {
+            mark,
+            revision: rev,
+            line: key.clone(),
+            svn_author: entry.author.clone(),
+            git_author: format!("{} <{}>", template.author_name, template.author_email),
+        });
+        Ok(Some(LayoutCommit {
+            revision: rev,
+            svn_path,
+            git_name: line.git_name.clone(),
+            changes: changes.len(),
+            stats,
+        }))
+    }
+
+    /// Path of a repository path relative to the layout base.
+    fn relative<'p>(&self, path: &'p str) -> Option<&'p str> {
+        let path = path.trim_matches('/');
+        if self.prefix.is_empty() {
+            Some(path)
+        } else {
+            below(path, &self.prefix)
+        }
+    }
+
+    /// End the stream of a cancelled import without recording any of it, so
+    /// that starting the import again replays everything from the start.
+    pub fn abandon(self) -> Result<()> {
+        let commits = self.rows.len();
+        self.fast_import.finish().context("git fast-import failed")?;
+        info!(commits, "layout replay abandoned");
+        Ok(())
+    }
+
+    /// Tag the existing tag lines, end the stream and record the import:
+    /// `commit_map` rows, `tag_map` rows and the branch and tag watermarks
+    /// incremental sync continues from.  Trunk's watermarks are left to the
+    /// caller, as for a single-path import.
+    pub fn finish(mut self) -> Result<LayoutSummary> {
+        let mut tags = Vec::new();
+        for (key, line) in &self.lines {
+            if key.kind != LineKind::Tag || !line.live {
+                continue;
+            }
+            let (Some((_, from)), Some(change)) = (line.history.last(), &line.last_change) else {
+                continue;
+            };
+            self.fast_import.tag(
+                &line.git_name,
+                from,
+                &change.tagger_name,
+                &change.tagger_email,
+                change.time,
+                &change.message,
+            )?;
+            tags.push((key, line, from, change.revision));
+        }
+        let marks = self.fast_import.finish().context("git fast-import failed")?;
+        let resolve = |from: &str| match from.strip_prefix(':') {
+            Some(mark) => mark.parse::<i64>().ok().and_then(|m| marks.get(&m).cloned()),
+            None => Some(from.to_string()),
+        };
+
+        for row in &self.rows {
+            if let Some(sha) = marks.get(&row.mark) {
+                self.db
+                    .insert_commit_map_with_repo(
+                        row.revision,
+                        sha,
+                        "svn_to_git",
+                        &row.svn_author,
+                        &row.git_author,
+                        row.line.repo_id(self.repo_id.as_deref()).as_deref(),
+                    )
+                    .ok();
+            }
+        }
+
+        let repo = git2::Repository::open(&self.repo_path)?;
+        let mut summary = LayoutSummary { commits: self.rows.len() as u64, ..Default::default() };
+        for (key, line) in &self.lines {
+            let Some(sha) = line.history.last().and_then(|(_, from)| resolve(from)) else {
+                continue;
+            };
+            match key.kind {
+                LineKind::Trunk => summary.branches.insert(0, line.git_name.clone()),
+                LineKind::Branch => {
+                    summary.branches.push(line.git_name.clone());
+                    // A live branch continues syncing from here.
+                    if let (true, Some(rev)) = (line.live, self.last_rev) {
+                        let repo_id = key.repo_id(self.repo_id.as_deref());
+                        let repo_id = repo_id.as_deref();
+                        self.db.set_state(&svn_rev_key_for(repo_id), &rev.to_string())?;
+                        self.db.set_state(&git_sha_key_for(repo_id), &sha)?;
+                    }
+                }
+                LineKind::Tag => {
+                    if let Ok(mut reference) = repo.find_reference(&line.ref_name) {
+                        reference.delete()?;
+                    }
+                }
+            }
+        }
+        for (key, line, from, rev) in tags {
+            let Some(sha) = resolve(from) else {
+                continue;
+            };
+            self.db.insert_tag_map(
+                &key.name,
+                &line.git_name,
+                rev,
+                &sha,
+                "svn_to_git",
+                self.repo_id.as_deref(),
+            )?;
+            summary.tags.push(line.git_name.clone());
+        }
+        if let Some(rev) = self.last_rev {
+            self.db.set_state(&svn_tag_rev_key_for(self.repo_id.as_deref()), &rev.to_string())?;
+        }
+
+        info!(
+            commits = summary.commits,
+            branches = summary.branches.len(),
+            tags = summary.tags.len(),
+            "layout replay finished"
+        );
+        Ok(summary)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Team-mode import
+// ---------------------------------------------------------------------------
+
+/// Import trunk, branches and tags of `layout`, whose paths are relative to
+/// the URL of `svn_client`.
+///
+/// Identity mapping, commit messages, progress reporting and trunk's
+/// watermarks match [`run_full_import`](crate::import::run_full_import).
+/// Branch commits are recorded in `commit_map` under the repo ID of their
+/// branch pair, tags in `tag_map`.  Everything is pushed once at the end,
+/// so an interrupted layout import cannot be resumed.  A cancelled one
+/// records nothing and starts over when run again; after a failure it has to
+/// be started over with a reset.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_layout_import(
+    svn_client: &SvnClient,
+    git_client: &Arc<std::sync::Mutex<GitClient>>,
+    identity_mapper: &IdentityMapper,
+    db: &Database,
+    file_policy: &FilePolicy,
+    import_config: &ImportConfig,
+    layout: &ImportLayout,
+    progress: Arc<RwLock<ImportProgress>>,
+    ws_broadcast: Option<broadcast::Sender<String>>,
+) -> Result<u64> {
+    let lfs_available =
+        crate::import::prepare_lfs(git_client, file_policy, &progress, &ws_broadcast).await;
+    push_log_line(&progress, &ws_broadcast, "[info] Connecting to SVN repository...".into())
+        .await;
+    {
+        let p = progress.read().await;
+        if let Err(e) = db.persist_import_progress_for(import_config.progress_repo_id(), &p) {
+            warn!("failed to persist import progress: {}", e);
+        }
+    }
+
+    let svn_info = svn_client.info().await.context("failed to get SVN info")?;
+    let mut replayer = {
+        let git = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        LayoutReplayer::new(svn_client, &svn_info, layout.clone(), &git, file_policy, db)?
+    }
+    .repo_id(import_config.repo_id.clone())
+    .lfs(lfs_available)
+    .sync_executable_bit(import_config.sync_executable_bit)
+    .normalize_line_endings(import_config.normalize_line_endings)
+    .collapse_keywords(import_config.collapse_keywords);
+
+    push_log_line(
+        &progress,
+        &ws_broadcast,
+        format!(
+            "[info] SVN HEAD is r{}, importing /{} with /{}/* and /{}/*",
+            svn_info.latest_rev, layout.trunk_path, layout.branches_path, layout.tags_path
+        ),
+    )
+    .await;
+    let log_entries = replayer.history(svn_info.latest_rev).await?;
+    progress.write().await.total_revs = log_entries.len() as i64;
+
+    let mut count = 0u64;
+    let mut cancelled = false;
+    for (idx, entry) in log_entries.iter().enumerate() {
+        if progress.read().await.cancel_requested {
+            cancelled = true;
+            break;
+        }
+        let rev = entry.revision;
+        progress.write().await.current_rev = idx as i64 + 1;
+
+        let (author_name, author_email) = match identity_mapper.svn_to_git(&entry.author) {
+            Ok(GitIdentity { name, email }) => (name, email),
+            Err(_) => (entry.author.clone(), format!("{}@svn", entry.author)),
+        };
+        let message = format!(
+            "{}\n\n[reposync] imported from SVN r{}\nSVN-Author: {}\nSVN-Date: {}",
+            entry.message, rev, entry.author, entry.date
+        );
+        let template = FastImportCommit {
+            mark: 0,
+            author_name: &author_name,
+            author_email: &author_email,
+            author_time: svn_date_to_epoch(&entry.date),
+            committer_name: &import_config.committer_name,
+            committer_email: &import_config.committer_email,
+            committer_time: chrono::Utc::now().timestamp(),
+            message: &message,
+            changes: &[],
+        };
+        let commits = replayer
+            .replay(entry, &template)
+            .await
+            .with_context(|| format!("failed to import r{}", rev))?;
+
+        for commit in &commits {
+            count += 1;
+            let line = format!(
+                "[ok] r{} {} → {} ({}) \"{}\" [{} changed, {} files]",
+                rev,
+                commit.svn_path,
+                commit.git_name,
+                author_name,
+                entry.message.lines().next().unwrap_or("").chars().take(60).collect::<String>(),
+                commit.changes,
+                commit.stats.copied,
+            );
+            push_log_line(&progress, &ws_broadcast, line).await;
+        }
+        {
+            let mut p = progress.write().await;
+            p.commits_created = count;
+            p.files_skipped += commits.iter().map(|c| c.stats.skipped as u64).sum::<u64>();
+            if (idx + 1) % 10 == 0 {
+                if let Err(e) =
+                    db.persist_import_progress_for(import_config.progress_repo_id(), &p)
+                {
+                    warn!("failed to persist import progress at rev {}: {}", idx + 1, e);
+                }
+            }
+        }
+    }
+
+    if cancelled {
+        replayer.abandon()?;
+        let mut p = progress.write().await;
+        p.phase = ImportPhase::Cancelled;
+        p.completed_at = Some(chrono::Utc::now().to_rfc3339());
+        drop(p);
+        push_log_line(
+            &progress,
+            &ws_broadcast,
+            "[warn] Import cancelled by user — nothing was pushed or recorded".into(),
+        )
+        .await;
+        return Ok(count);
+    }
+
+    let summary = replayer.finish()?;
+    {
+        let git = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        if summary.branches.contains(&layout.trunk_branch) {
+            git.checkout_branch(&layout.trunk_branch)
+                .with_context(|| format!("failed to check out '{}'", layout.trunk_branch))?;
+        }
+    }
+
+    let refs = summary.refs();
+    if !refs.is_empty() {
+        push_log_line(
+            &progress,
+            &ws_broadcast,
+            format!(
+                "[info] Pushing {} branches and {} tags to remote...",
+                summary.branches.len(),
+                summary.tags.len()
+            ),
+        )
+        .await;
+        let remote = import_config.remote_name.clone();
+        let git = git_client.clone();
+        tokio::task::spawn_blocking(move || {
+            let git = git.lock().unwrap_or_else(|p| p.into_inner());
+            git.push_refs(&remote, &refs)
+        })
+        .await
+        .context("push task failed")?
+        .context("failed to push imported branches and tags")?;
+        progress.write().await.batches_pushed += 1;
+        push_log_line(&progress, &ws_broadcast, format!("[ok] All {} commits pushed", count))
+            .await;
+    }
+
+    let head_sha = {
+        let git = git_client.lock().unwrap_or_else(|p| p.into_inner());
+        git.get_head_sha().ok()
+    };
+    record_watermarks(
+        db,
+        import_config.repo_id.as_deref(),
+        log_entries.last().map(|e| e.revision),
+        head_sha.as_deref(),
+    );
+    db.insert_audit_log(
+        "import_full",
+        Some("svn_to_git"),
+        Some(svn_info.latest_rev),
+        None,
+        None,
+        Some(&format!(
+            "Layout import: {} commits on {} branches, {} tags from {} revisions",
+            count,
+            summary.branches.len(),
+            summary.tags.len(),
+            log_entries.len()
+        )),
+        true,
+    )
+    .ok();
+
+    info!(count, revisions = log_entries.len(), "layout import completed");
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(kind: LineKind, name: &str) -> LineKey {
+        LineKey { kind, name: name.into() }
+    }
+
+    #[test]
+    fn test_classify_layout_paths() {
+        let layout = ImportLayout::standard("trunk", "main");
+        assert_eq!(layout.classify("/trunk"), Some((LineKey::trunk(), "")));
+        assert_eq!(layout.classify("/trunk/src/a.c"), Some((LineKey::trunk(), "src/a.c")));
+        assert_eq!(
+            layout.classify("/branches/1.x/src/a.c"),
+            Some((key(LineKind::Branch, "1.x"), "src/a.c"))
+        );
+        assert_eq!(layout.classify("tags/v1.0"), Some((key(LineKind::Tag, "v1.0"), "")));
+        assert_eq!(layout.classify("/branches"), None);
+        assert_eq!(layout.classify("/trunkish/a"), None);
+        assert_eq!(layout.classify("/other/a"), None);
+        assert_eq!(layout.line_path(&key(LineKind::Tag, "v1.0")), "tags/v1.0");
+    }
+
+    #[test]
+    fn test_layout_git_names() {
+        let mut layout = ImportLayout::standard("trunk", "main");
+        layout.branch_mappings = RefMapping::parse_all(&["release-*:release/*".into()]);
+        layout.tag_mappings = RefMapping::parse_all(&["*:v*".into()]);
+        let name = |kind, name| layout.git_name(&key(kind, name));
+        assert_eq!(layout.git_name(&LineKey::trunk()).as_deref(), Some("main"));
+        assert_eq!(name(LineKind::Branch, "release-2.1").as_deref(), Some("release/2.1"));
+        assert_eq!(name(LineKind::Branch, "feature").as_deref(), Some("feature"));
+        assert_eq!(name(LineKind::Tag, "1.0").as_deref(), Some("v1.0"));
+        // Invalid ref names and the trunk branch's name are refused.
+        assert_eq!(name(LineKind::Branch, "bad name"), None);
+        assert_eq!(name(LineKind::Branch, "main"), None);
+    }
+
+    #[test]
+    fn test_summary_refs() {
+        let summary = LayoutSummary {
+            commits: 3,
+            branches: vec!["main".into(), "1.x".into()],
+            tags: vec!["v1".into()],
+        };
+        assert_eq!(summary.refs(), ["refs/heads/main", "refs/heads/1.x", "refs/tags/v1"]);
+        let branch = key(LineKind::Branch, "1.x");
+        assert_eq!(branch.repo_id(None).as_deref(), Some("branch:1.x"));
+        assert_eq!(branch.repo_id(Some("r1")).as_deref(), Some("r1:branch:1.x"));
+        assert_eq!(LineKey::trunk().repo_id(Some("r1")).as_deref(), Some("r1"));
+        assert_eq!(LineKey::trunk().repo_id(None), None);
+    }
+}
@@ -8,17 +8,21 @@ pub mod config;
 pub mod conflict;
 pub mod crypto;
 pub mod db;
+pub mod dump_import;
 pub mod errors;
 pub mod file_policy;
 pub mod git;
 pub mod identity;
 pub mod import;
+pub mod layout_import;
 pub mod ldap_auth;
 pub mod lfs;
 pub mod models;
 pub mod notify;
 pub mod personal_config;
+pub mod ref_map;
 pub mod svn;
+pub mod svn_props;
 pub mod sync_engine;
 
 // Re-exports for convenience.
@@ -78,6 +78,7 @@ pub struct Conflict {
     pub status: String,
     pub resolution: Option<String>,
     pub resolved_by: Option<String>,
+    pub repo_id: Option<String>,
 }
 
 impl Conflict {
@@ -95,6 +96,7 @@ impl Conflict {
             status: "detected".to_string(),
             resolution: None,
             resolved_by: None,
+            repo_id: None,
         }
     }
 }
@@ -383,6 +385,21 @@ fn default_sync_status() -> String {
     "idle".to_string()
 }
 
+impl Repository {
+    /// URL of the SVN tree this repository syncs (`svn_url` + `svn_branch`).
+    pub fn synced_svn_url(&self) -> String {
+        if self.svn_branch.is_empty() {
+            self.svn_url.clone()
+        } else {
+            format!(
+                "{}/{}",
+                self.svn_url.trim_end_matches('/'),
+                self.svn_branch.trim_start_matches('/')
+            )
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Personal Branch Mode types
 // ---------------------------------------------------------------------------
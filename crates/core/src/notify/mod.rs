@@ -1,14 +1,15 @@
 //! Notification subsystem for conflict alerts, sync errors, and summaries.
 //!
 //! Supports Slack webhook and SMTP email channels. The [`Notifier`] facade
-//! dispatches to all configured channels and logs failures without aborting.
+//! routes each event to the channels selected by `notifications.routes` and
+//! logs failures without aborting.
 
 pub mod email;
 pub mod slack;
 
 use tracing::{info, warn};
 
-use crate::config::NotificationConfig;
+use crate::config::{NotificationChannel, NotificationConfig, NotificationEvent, NotificationRoute};
 use crate::conflict::Conflict;
 use crate::errors::NotificationError;
 use crate::sync_engine::SyncStats;
@@ -17,6 +18,8 @@ use crate::sync_engine::SyncStats;
 pub struct Notifier {
     slack: Option<slack::SlackNotifier>,
     email: Option<email::EmailNotifier>,
+    routes: Vec<NotificationRoute>,
+    error_threshold: u32,
 }
 
 impl Notifier {
@@ -39,75 +42,113 @@ impl Notifier {
             _ => None,
         };
 
-        Self { slack, email }
+        Self {
+            slack,
+            email,
+            routes: config.routes.clone(),
+            error_threshold: config.error_threshold,
+        }
+    }
+
+    /// Number of consecutive failed cycles that triggers
+    /// [`notify_error_threshold`](Self::notify_error_threshold) (0 = never).
+    pub fn error_threshold(&self) -> u32 {
+        self.error_threshold
     }
 
-    /// Send a conflict notification to all configured channels.
-    pub async fn notify_conflict(&self, conflict: &Conflict) -> Result<(), NotificationError> {
+    /// Return whether `event` for `repo` is delivered to any channel.
+    pub fn is_routed(&self, event: NotificationEvent, repo: Option<&str>) -> bool {
+        let channels = self.route(event, repo);
+        (channels.slack && self.slack.is_some()) || (channels.email && self.email.is_some())
+    }
+
+    /// Send a conflict notification to the channels routed for conflicts.
+    pub async fn notify_conflict(
+        &self,
+        repo: Option<&str>,
+        conflict: &Conflict,
+    ) -> Result<(), NotificationError> {
+        let channels = self.route(NotificationEvent::Conflict, repo);
         info!(
             file = %conflict.file_path,
             conflict_type = %conflict.conflict_type,
             "sending conflict notification"
         );
 
-        let mut errors = Vec::new();
-
-        if let Some(ref slack) = self.slack {
-            let message = format_conflict_slack(conflict);
-            if let Err(e) = slack.send_message(&message).await {
-                warn!(error = %e, "Slack notification failed");
-                errors.push(format!("Slack: {}", e));
-            }
-        }
-
-        if let Some(ref email) = self.email {
-            let subject = format!("[RepoSync] Conflict detected: {}", conflict.file_path);
-            let body = format_conflict_email_html(conflict);
-            if let Err(e) = email.send(&subject, &body).await {
-                warn!(error = %e, "email notification failed");
-                errors.push(format!("Email: {}", e));
-            }
-        }
-
-        if !errors.is_empty() && (self.slack.is_some() || self.email.is_some()) {
-            // Only error if ALL channels failed.
-            let has_slack = self.slack.is_some();
-            let has_email = self.email.is_some();
-            let total_channels = has_slack as usize + has_email as usize;
-            if errors.len() >= total_channels {
-                return Err(NotificationError::AllChannelsFailed(errors.join("; ")));
-            }
-        }
-
-        Ok(())
+        let message = format_conflict_slack(repo, conflict);
+        let subject = format!(
+            "[RepoSync]{} Conflict detected: {}",
+            repo_tag(repo),
+            conflict.file_path
+        );
+        let body = format_conflict_email_html(repo, conflict);
+        self.dispatch(channels, &message, &subject, &body).await
     }
 
-    /// Send a sync error notification to all configured channels.
-    pub async fn notify_sync_error(&self, error: &str) -> Result<(), NotificationError> {
+    /// Send a notification for a single failed sync cycle.
+    pub async fn notify_sync_error(
+        &self,
+        repo: Option<&str>,
+        error: &str,
+    ) -> Result<(), NotificationError> {
+        let channels = self.route(NotificationEvent::SyncFailed, repo);
         info!("sending sync error notification");
 
-        if let Some(ref slack) = self.slack {
-            let message = format!(":x: *RepoSync Error*\n```{}```", error);
-            let _ = slack.send_message(&message).await;
-        }
-
-        if let Some(ref email) = self.email {
-            let subject = "[RepoSync] Sync Error";
-            let body = format!(
-                "<html><body>\
-                <h2 style=\"color: red;\">RepoSync Sync Error</h2>\
-                <pre>{}</pre>\
-                </body></html>",
-                html_escape(error)
-            );
-            let _ = email.send(subject, &body).await;
-        }
+        let message = format!(":x: *RepoSync Error{}*\n```{}```", repo_label(repo), error);
+        let subject = format!("[RepoSync]{} Sync Error", repo_tag(repo));
+        let body = format!(
+            "<html><body>\
+            <h2 style=\"color: red;\">RepoSync Sync Error{}</h2>\
+            <pre>{}</pre>\
+            </body></html>",
+            html_escape(&repo_label(repo)),
+            html_escape(error)
+        );
+        self.dispatch(channels, &message, &subject, &body).await
+    }
 
-        Ok(())
+    /// Send an alert that a repository has failed `consecutive` cycles in a
+    /// row, `error` being the most recent failure.
+    pub async fn notify_error_threshold(
+        &self,
+        repo: Option<&str>,
+        consecutive: u64,
+        error: &str,
+    ) -> Result<(), NotificationError> {
+        let channels = self.route(NotificationEvent::ErrorThreshold, repo);
+        info!(consecutive, "sending error threshold notification");
+
+        let message = format!(
+            ":rotating_light: *RepoSync is failing{}*\n\
+             {} consecutive sync cycles failed. Last error:\n```{}```",
+            repo_label(repo),
+            consecutive,
+            error
+        );
+        let subject = format!(
+            "[RepoSync]{} {} consecutive sync failures",
+            repo_tag(repo),
+            consecutive
+        );
+        let body = format!(
+            "<html><body>\
+            <h2 style=\"color: red;\">RepoSync is failing{}</h2>\
+            <p>{} consecutive sync cycles failed. Last error:</p>\
+            <pre>{}</pre>\
+            </body></html>",
+            html_escape(&repo_label(repo)),
+            consecutive,
+            html_escape(error)
+        );
+        self.dispatch(channels, &message, &subject, &body).await
     }
 
     /// Send a sync-complete summary notification (optional).
-    pub async fn notify_sync_complete(&self, stats: &SyncStats) -> Result<(), NotificationError> {
+    pub async fn notify_sync_complete(
+        &self,
+        repo: Option<&str>,
+        stats: &SyncStats,
+    ) -> Result<(), NotificationError> {
         // Only send if there were actual changes.
         if stats.svn_to_git_count == 0
             && stats.git_to_svn_count == 0
@@ -116,49 +157,123 @@ impl Notifier {
             return Ok(());
         }
 
+        let channels = self.route(NotificationEvent::SyncComplete, repo);
         info!("sending sync completion notification");
 
-        if let Some(ref slack) = self.slack {
-            let message = format!(
-                ":white_check_mark: *RepoSync Cycle Complete*\n\
-                 - SVN -> Git: {} commits\n\
-                 - Git -> SVN: {} commits\n\
-                 - Conflicts: {} ({} auto-resolved)",
-                stats.svn_to_git_count,
-                stats.git_to_svn_count,
-                stats.conflicts_detected,
-                stats.conflicts_auto_resolved,
+        let message = format!(
+            ":white_check_mark: *RepoSync Cycle Complete{}*\n\
+             - SVN -> Git: {} commits\n\
+             - Git -> SVN: {} commits\n\
+             - Conflicts: {} ({} auto-resolved)",
+            repo_label(repo),
+            stats.svn_to_git_count,
+            stats.git_to_svn_count,
+            stats.conflicts_detected,
+            stats.conflicts_auto_resolved,
+        );
+        let subject = format!("[RepoSync]{} Sync Complete", repo_tag(repo));
+        let body = format!(
+            "<html><body>\
+            <h2>RepoSync Sync Cycle Complete{}</h2>\
+            <table>\
+            <tr><td>SVN -&gt; Git</td><td>{}</td></tr>\
+            <tr><td>Git -&gt; SVN</td><td>{}</td></tr>\
+            <tr><td>Conflicts</td><td>{}</td></tr>\
+            <tr><td>Auto-resolved</td><td>{}</td></tr>\
+            </table>\
+            </body></html>",
+            html_escape(&repo_label(repo)),
+            stats.svn_to_git_count,
+            stats.git_to_svn_count,
+            stats.conflicts_detected,
+            stats.conflicts_auto_resolved,
+        );
+        self.dispatch(channels, &message, &subject, &body).await
+    }
+
+    /// Return whether any notification channel is configured.
+    pub fn is_configured(&self) -> bool {
+        self.slack.is_some() || self.email.is_some()
+    }
+
+    /// Resolve the channels `event` for `repo` is delivered to.
+    ///
+    /// Without routes, conflicts and error-threshold alerts go to every
+    /// channel. Otherwise the channels of all matching routes are combined.
+    fn route(&self, event: NotificationEvent, repo: Option<&str>) -> Channels {
+        if self.routes.is_empty() {
+            let all = matches!(
+                event,
+                NotificationEvent::Conflict | NotificationEvent::ErrorThreshold
             );
-            let _ = slack.send_message(&message).await;
+            return Channels {
+                slack: all,
+                email: all,
+            };
         }
 
-        if let Some(ref email) = self.email {
-            let subject = "[RepoSync] Sync Complete";
-            let body = format!(
-                "<html><body>\
-                <h2>RepoSync Sync Cycle Complete</h2>\
-                <table>\
-                <tr><td>SVN -&gt; Git</td><td>{}</td></tr>\
-                <tr><td>Git -&gt; SVN</td><td>{}</td></tr>\
-                <tr><td>Conflicts</td><td>{}</td></tr>\
-                <tr><td>Auto-resolved</td><td>{}</td></tr>\
-                </table>\
-                </body></html>",
-                stats.svn_to_git_count,
-                stats.git_to_svn_count,
-                stats.conflicts_detected,
-                stats.conflicts_auto_resolved,
-            );
-            let _ = email.send(subject, &body).await;
+        let mut channels = Channels::default();
+        for route in &self.routes {
+            if !route.events.contains(&event) {
+                continue;
+            }
+            let repo_matches = route.repos.is_empty()
+                || repo.is_some_and(|r| route.repos.iter().any(|name| name == r));
+            if !repo_matches {
+                continue;
+            }
+            if route.channels.is_empty() {
+                channels.slack = true;
+                channels.email = true;
+            } else {
+                channels.slack |= route.channels.contains(&NotificationChannel::Slack);
+                channels.email |= route.channels.contains(&NotificationChannel::Email);
+            }
+        }
+        channels
+    }
+
+    /// Deliver a message to the selected channels. Only errors if every
+    /// attempted channel failed.
+    async fn dispatch(
+        &self,
+        channels: Channels,
+        slack_message: &str,
+        email_subject: &str,
+        email_body: &str,
+    ) -> Result<(), NotificationError> {
+        let mut attempted = 0;
+        let mut errors = Vec::new();
+
+        if let Some(slack) = self.slack.as_ref().filter(|_| channels.slack) {
+            attempted += 1;
+            if let Err(e) = slack.send_message(slack_message).await {
+                warn!(error = %e, "Slack notification failed");
+                errors.push(format!("Slack: {}", e));
+            }
+        }
+
+        if let Some(email) = self.email.as_ref().filter(|_| channels.email) {
+            attempted += 1;
+            if let Err(e) = email.send(email_subject, email_body).await {
+                warn!(error = %e, "email notification failed");
+                errors.push(format!("Email: {}", e));
+            }
+        }
+
+        if attempted > 0 && errors.len() >= attempted {
+            return Err(NotificationError::AllChannelsFailed(errors.join("; ")));
         }
 
         Ok(())
     }
+}
 
-    /// Return whether any notification channel is configured.
-    pub fn is_configured(&self) -> bool {
-        self.slack.is_some() || self.email.is_some()
-    }
+/// Channels selected for a single notification.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Channels {
+    slack: bool,
+    email: bool,
 }
 
 // ---------------------------------------------------------------------------
@@ -166,13 +281,16 @@ impl Notifier {
 // ---------------------------------------------------------------------------
 
 /// Format a conflict notification for Slack (Markdown).
-fn format_conflict_slack(conflict: &Conflict) -> String {
+fn format_conflict_slack(repo: Option<&str>, conflict: &Conflict) -> String {
     let mut msg = format!(
-        ":warning: *Sync Conflict Detected*\n\
+        ":warning: *Sync Conflict Detected{}*\n\
          *File:* `{}`\n\
          *Type:* {}\n\
          *Status:* {}",
-        conflict.file_path, conflict.conflict_type, conflict.status,
+        repo_label(repo),
+        conflict.file_path,
+        conflict.conflict_type,
+        conflict.status,
     );
 
     if let Some(rev) = conflict.svn_rev {
@@ -187,10 +305,10 @@ fn format_conflict_slack(conflict: &Conflict) -> String {
 }
 
 /// Format a conflict notification as an HTML email.
-fn format_conflict_email_html(conflict: &Conflict) -> String {
+fn format_conflict_email_html(repo: Option<&str>, conflict: &Conflict) -> String {
     let mut html = format!(
         "<html><body>\
-        <h2 style=\"color: #d4a017;\">Sync Conflict Detected</h2>\
+        <h2 style=\"color: #d4a017;\">Sync Conflict Detected{}</h2>\
         <table style=\"border-collapse: collapse;\">\
         <tr><td style=\"padding: 4px 12px; font-weight: bold;\">File</td>\
             <td style=\"padding: 4px 12px;\"><code>{}</code></td></tr>\
@@ -198,6 +316,7 @@ fn format_conflict_email_html(conflict: &Conflict) -> String {
             <td style=\"padding: 4px 12px;\">{}</td></tr>\
         <tr><td style=\"padding: 4px 12px; font-weight: bold;\">Status</td>\
             <td style=\"padding: 4px 12px;\">{}</td></tr>",
+        html_escape(&repo_label(repo)),
         html_escape(&conflict.file_path),
         html_escape(&conflict.conflict_type.to_string()),
         html_escape(&conflict.status.to_string()),
@@ -224,6 +343,16 @@ fn format_conflict_email_html(conflict: &Conflict) -> String {
     html
 }
 
+/// Title suffix naming the repository, e.g. ` (core)`.
+fn repo_label(repo: Option<&str>) -> String {
+    repo.map(|r| format!(" ({})", r)).unwrap_or_default()
+}
+
+/// Subject prefix naming the repository, e.g. ` [core]`.
+fn repo_tag(repo: Option<&str>) -> String {
+    repo.map(|r| format!(" [{}]", r)).unwrap_or_default()
+}
+
 /// Minimal HTML escaping for user-provided strings.
 fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
@@ -261,7 +390,8 @@ mod tests {
             resolved_by: None,
         };
 
-        let msg = format_conflict_slack(&conflict);
+        let msg = format_conflict_slack(Some("core"), &conflict);
+        assert!(msg.contains("(core)"));
         assert!(msg.contains("src/main.rs"));
         assert!(msg.contains("r42"));
         assert!(msg.contains("abc12345"));
@@ -283,11 +413,68 @@ mod tests {
             resolved_by: None,
         };
 
-        let html = format_conflict_email_html(&conflict);
+        let html = format_conflict_email_html(None, &conflict);
         assert!(html.contains("lib/util.rs"));
         assert!(html.contains("edit_delete"));
     }
 
+    #[test]
+    fn test_default_routes() {
+        let notifier = Notifier::new(&NotificationConfig::default());
+        let all = Channels {
+            slack: true,
+            email: true,
+        };
+        assert_eq!(notifier.route(NotificationEvent::Conflict, None), all);
+        assert_eq!(notifier.route(NotificationEvent::ErrorThreshold, Some("core")), all);
+        assert_eq!(
+            notifier.route(NotificationEvent::SyncFailed, None),
+            Channels::default()
+        );
+        assert_eq!(notifier.error_threshold(), 3);
+    }
+
+    #[test]
+    fn test_routes_by_event_and_repo() {
+        let config = NotificationConfig {
+            routes: vec![
+                NotificationRoute {
+                    events: vec![NotificationEvent::Conflict, NotificationEvent::SyncFailed],
+                    channels: vec![NotificationChannel::Slack],
+                    repos: Vec::new(),
+                },
+                NotificationRoute {
+                    events: vec![NotificationEvent::SyncFailed],
+                    channels: Vec::new(),
+                    repos: vec!["core".into()],
+                },
+            ],
+            ..Default::default()
+        };
+        let notifier = Notifier::new(&config);
+
+        let slack_only = Channels {
+            slack: true,
+            email: false,
+        };
+        assert_eq!(notifier.route(NotificationEvent::Conflict, Some("core")), slack_only);
+        assert_eq!(notifier.route(NotificationEvent::SyncFailed, Some("web")), slack_only);
+        assert_eq!(notifier.route(NotificationEvent::SyncFailed, None), slack_only);
+        assert_eq!(
+            notifier.route(NotificationEvent::SyncFailed, Some("core")),
+            Channels {
+                slack: true,
+                email: true,
+            }
+        );
+        assert_eq!(
+            notifier.route(NotificationEvent::ErrorThreshold, Some("core")),
+            Channels::default()
+        );
+        // No channel is configured, so nothing is actually delivered.
+        assert!(!notifier.is_routed(NotificationEvent::Conflict, Some("core")));
+    }
+
     #[test]
     fn test_html_escape() {
         assert_eq!(html_escape("<script>"), "&lt;script&gt;");
@@ -4,11 +4,14 @@
 //! `SvnConfig` and `GitHubConfig` patterns from the team-mode [`AppConfig`].
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 use crate::errors::ConfigError;
+use crate::git::auth::{GitAuth, SshKey};
+use crate::git::GitHubAppAuth;
 
 // ---------------------------------------------------------------------------
 // Top-level personal config
@@ -124,12 +127,28 @@ pub struct PersonalGitHubConfig {
     #[serde(default)]
     pub git_base_url: Option<String>,
 
-    /// Repository in `owner/repo` format.
+    /// Repository in `owner/repo` format.  With the `git` provider this is
+    /// the remote URL or path instead.
     pub repo: String,
 
-    /// Environment variable holding the GitHub personal access token.
+    /// Environment variable holding the GitHub personal access token.  May
+    /// be left empty for plain Git remotes reached without a token.
+    #[serde(default)]
     pub token_env: String,
 
+    /// Private key for SSH remotes (`ssh://…` or `git@host:path`).
+    #[serde(default)]
+    pub ssh_key_path: Option<PathBuf>,
+
+    /// Environment variable holding the passphrase of an encrypted SSH key.
+    #[serde(default)]
+    pub ssh_key_passphrase_env: Option<String>,
+
+    /// `known_hosts` file SSH host keys are verified against.  Default:
+    /// `~/.ssh/known_hosts`.
+    #[serde(default)]
+    pub ssh_known_hosts: Option<PathBuf>,
+
     /// Default branch name (e.g. `main`).
     #[serde(default = "default_branch")]
     pub default_branch: String,
@@ -138,13 +157,30 @@ pub struct PersonalGitHubConfig {
     #[serde(default = "default_true")]
     pub auto_create: bool,
 
+    /// Git hosting provider (github, gitea, gitlab, bitbucket-server or
+    /// git for a plain remote). Default: github.
+    #[serde(default)]
+    pub provider: crate::config::GitProvider,
+
     /// Whether the auto-created repo should be private.
     #[serde(default = "default_true")]
     pub private: bool,
 
+    /// Authenticate as a GitHub App installation instead of with a token.
+    #[serde(default)]
+    pub app: Option<crate::config::GitHubAppConfig>,
+
     /// Resolved token (populated by `resolve_env_vars`).
     #[serde(skip)]
     pub token: Option<String>,
+
+    /// Installation token source of `app` (populated by `resolve_env_vars`).
+    #[serde(skip)]
+    pub app_auth: Option<Arc<GitHubAppAuth>>,
+
+    /// Resolved SSH key passphrase.
+    #[serde(skip)]
+    pub ssh_key_passphrase: Option<String>,
 }
 
 impl PersonalGitHubConfig {
@@ -159,6 +195,19 @@ impl PersonalGitHubConfig {
             &self.repo,
         )
     }
+
+    /// Credentials for Git clone, fetch and push: the resolved token (or the
+    /// GitHub App's installation tokens) plus the configured SSH key.
+    pub fn git_auth(&self) -> GitAuth {
+        let key = self.ssh_key_path.as_ref().map(|path| SshKey {
+            private_key: path.clone(),
+            passphrase: self.ssh_key_passphrase.clone(),
+            known_hosts: self.ssh_known_hosts.clone(),
+        });
+        GitAuth::from_token(self.token.as_deref())
+            .with_ssh_key(key)
+            .with_app(self.app_auth.clone())
+    }
 }
 
 fn default_github_api_url() -> String {
@@ -245,10 +294,17 @@ impl Default for CommitFormatConfig {
 /// Miscellaneous sync behaviour options.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersonalOptionsConfig {
-    /// Normalize CRLF to LF during sync.
+    /// Normalize CRLF to LF during sync: `svn:eol-style` is mirrored in
+    /// `.gitattributes` and files added from Git get the `svn:eol-style` their
+    /// `.gitattributes` rules call for.
     #[serde(default = "default_true")]
     pub normalize_line_endings: bool,
 
+    /// Keep `svn:keywords` collapsed (`$Id$`) in Git and set the property on
+    /// files added from Git that contain keywords.
+    #[serde(default = "default_true")]
+    pub collapse_keywords: bool,
+
     /// Sync the executable bit from SVN `svn:executable` to Git.
     #[serde(default = "default_true")]
     pub sync_executable_bit: bool,
@@ -261,7 +317,20 @@ pub struct PersonalOptionsConfig {
     #[serde(default)]
     pub ignore_patterns: Vec<String>,
 
-    /// Whether to sync SVN externals (metadata only).
+    /// Skip symlinks whose target resolves outside the repository.
+    #[serde(default)]
+    pub reject_external_symlinks: bool,
+
+    /// Mirror `svn:ignore` / `svn:global-ignores` as a generated block in
+    /// `.gitignore` files, and turn edits of that block back into those
+    /// properties. Other `.gitignore` lines are kept and synced as file
+    /// content.
+    #[serde(default)]
+    pub sync_ignores: bool,
+
+    /// Whether to sync SVN externals (metadata only): definitions are kept in
+    /// `.svnexternals` files and edits to them are synced back. The
+    /// externals' content comes with the SVN export either way.
     #[serde(default)]
     pub sync_externals: bool,
 
@@ -290,9 +359,12 @@ impl Default for PersonalOptionsConfig {
     fn default() -> Self {
         Self {
             normalize_line_endings: true,
+            collapse_keywords: true,
             sync_executable_bit: true,
             max_file_size: 0,
             ignore_patterns: Vec::new(),
+            reject_external_symlinks: false,
+            sync_ignores: false,
             sync_externals: false,
             sync_direct_pushes: false,
             auto_merge: true,
@@ -329,7 +401,16 @@ impl PersonalConfig {
         info!("resolving environment variable references in personal config");
 
         self.svn.password = resolve_optional_env(&self.svn.password_env, "svn.password_env");
-        self.github.token = resolve_optional_env(&self.github.token_env, "github.token_env");
+        if !self.github.token_env.is_empty() {
+            self.github.token = resolve_optional_env(&self.github.token_env, "github.token_env");
+        }
+        if let Some(ref env_name) = self.github.ssh_key_passphrase_env {
+            self.github.ssh_key_passphrase =
+                resolve_optional_env(env_name, "github.ssh_key_passphrase_env");
+        }
+        if let Some(ref app) = self.github.app {
+            self.github.app_auth = app.load(&self.github.api_url)?;
+        }
 
         debug!("personal config environment variable resolution complete");
         Ok(())
@@ -355,12 +436,26 @@ impl PersonalConfig {
                 detail: "GitHub repo must not be empty".into(),
             });
         }
-        if !self.github.repo.contains('/') {
+        if self.github.provider == crate::config::GitProvider::Plain {
+            if !crate::git::remote_url::is_remote_url(&self.github.repo)
+                && self.github.git_base_url.as_deref().unwrap_or("").trim().is_empty()
+            {
+                return Err(ConfigError::InvalidValue {
+                    field: "github.repo".into(),
+                    detail: "with provider 'git', repo must be the remote URL or path \
+                             (or set git_base_url)"
+                        .into(),
+                });
+            }
+        } else if !self.github.repo.contains('/') {
             return Err(ConfigError::InvalidValue {
                 field: "github.repo".into(),
                 detail: "GitHub repo must be in 'owner/repo' format".into(),
             });
         }
+        if let Some(ref app) = self.github.app {
+            app.validate(&self.github.provider)?;
+        }
         if self.developer.name.is_empty() {
             return Err(ConfigError::InvalidValue {
                 field: "developer.name".into(),
@@ -442,9 +537,12 @@ svn_username = "your_svn_username"
 
 [options]
 normalize_line_endings = true
+# collapse_keywords = true
 sync_executable_bit = true
 # max_file_size = 0         # 0 = no limit
 # ignore_patterns = []
+# reject_external_symlinks = false
+# sync_ignores = false
 # sync_externals = false
 # sync_direct_pushes = false
 auto_merge = true
@@ -563,6 +661,17 @@ ignore_patterns = ["*.tmp", "build/"]
         ));
     }
 
+    #[test]
+    fn test_validate_plain_git_remote() {
+        let mut config: PersonalConfig = toml::from_str(sample_personal_toml()).unwrap();
+        config.github.provider = crate::config::GitProvider::Plain;
+        config.github.repo = "file:///srv/git/project.git".into();
+        config.github.token_env = String::new();
+        assert!(config.validate().is_ok());
+        config.github.repo = "project".into();
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_validate_rejects_empty_developer() {
         let mut config: PersonalConfig = toml::from_str(sample_personal_toml()).unwrap();
@@ -610,8 +719,11 @@ svn_username = "user"
         assert_eq!(config.github.default_branch, "main");
         assert!(config.github.auto_create);
         assert!(config.options.normalize_line_endings);
+        assert!(config.options.collapse_keywords);
+        assert!(!config.options.reject_external_symlinks);
         assert!(config.options.auto_merge);
         assert!(!config.options.sync_direct_pushes);
+        assert!(!config.options.sync_ignores);
     }
 
     #[test]
@@ -0,0 +1,132 @@
+//! Name mapping between SVN branch/tag directories and Git refs.
+//!
+//! Each entry of `sync.sync_branches` is either a single pattern used for
+//! both sides (`"develop"`, `"release-*"`) or an `svn:git` pair
+//! (`"release-*:release/*"`). A pattern may contain one `*`, which matches
+//! any non-empty text; the matched text is substituted for the `*` of the
+//! other side. A lone `"*"` maps every branch to the same name.
+//!
+//! | Entry | SVN | Git |
+//! |-------|-----|-----|
+//! | `develop` | `branches/develop` | `develop` |
+//! | `release-*:release/*` | `branches/release-2.1` | `release/2.1` |
+//! | `*` | `branches/foo` | `foo` |
+
+/// A single SVN ↔ Git name mapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefMapping {
+    svn: String,
+    git: String,
+}
+
+impl RefMapping {
+    /// Parse an entry of the form `pattern` or `svn_pattern:git_pattern`.
+    pub fn parse(entry: &str) -> Self {
+        let entry = entry.trim();
+        match entry.split_once(':') {
+            Some((svn, git)) => Self {
+                svn: svn.trim().trim_matches('/').to_string(),
+                git: git.trim().to_string(),
+            },
+            None => Self {
+                svn: entry.trim_matches('/').to_string(),
+                git: entry.to_string(),
+            },
+        }
+    }
+
+    /// Parse every entry, skipping blank ones.
+    pub fn parse_all(entries: &[String]) -> Vec<Self> {
+        entries
+            .iter()
+            .filter(|e| !e.trim().is_empty())
+            .map(|e| Self::parse(e))
+            .collect()
+    }
+
+    /// Map an SVN name to its Git name, if this mapping covers it.
+    pub fn svn_to_git(&self, svn_name: &str) -> Option<String> {
+        translate(&self.svn, &self.git, svn_name)
+    }
+
+    /// Map a Git name to its SVN name, if this mapping covers it.
+    pub fn git_to_svn(&self, git_name: &str) -> Option<String> {
+        translate(&self.git, &self.svn, git_name)
+    }
+}
+
+/// Map `svn_name` with the first mapping that covers it.
+pub fn map_svn_to_git(mappings: &[RefMapping], svn_name: &str) -> Option<String> {
+    mappings.iter().find_map(|m| m.svn_to_git(svn_name))
+}
+
+/// Map `git_name` with the first mapping that covers it.
+pub fn map_git_to_svn(mappings: &[RefMapping], git_name: &str) -> Option<String> {
+    mappings.iter().find_map(|m| m.git_to_svn(git_name))
+}
+
+/// Match `name` against `from` and render the capture into `to`.
+fn translate(from: &str, to: &str, name: &str) -> Option<String> {
+    let captured = capture(from, name)?;
+    Some(match (captured, to.split_once('*')) {
+        (Some(text), Some((before, after))) => format!("{}{}{}", before, text, after),
+        _ => to.to_string(),
+    })
+}
+
+/// Match `name` against a pattern with at most one `*`.
+///
+/// Returns `Some(Some(text))` for a wildcard match, `Some(None)` for an
+/// exact match and `None` when the name does not match.
+fn capture<'a>(pattern: &str, name: &'a str) -> Option<Option<&'a str>> {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => {
+            let rest = name.strip_prefix(prefix)?;
+            let text = rest.strip_suffix(suffix)?;
+            (!text.is_empty()).then_some(Some(text))
+        }
+        None => (pattern == name).then_some(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_mapping() {
+        let m = RefMapping::parse("develop");
+        assert_eq!(m.svn_to_git("develop").as_deref(), Some("develop"));
+        assert_eq!(m.git_to_svn("develop").as_deref(), Some("develop"));
+        assert_eq!(m.svn_to_git("develop-2"), None);
+    }
+
+    #[test]
+    fn test_wildcard_pair_mapping() {
+        let m = RefMapping::parse("release-*:release/*");
+        assert_eq!(m.svn_to_git("release-2.1").as_deref(), Some("release/2.1"));
+        assert_eq!(m.git_to_svn("release/2.1").as_deref(), Some("release-2.1"));
+        assert_eq!(m.svn_to_git("release-"), None);
+        assert_eq!(m.git_to_svn("hotfix/2.1"), None);
+    }
+
+    #[test]
+    fn test_catch_all_mapping() {
+        let m = RefMapping::parse("*");
+        assert_eq!(m.svn_to_git("feature-x").as_deref(), Some("feature-x"));
+        assert_eq!(m.git_to_svn("feature-x").as_deref(), Some("feature-x"));
+    }
+
+    #[test]
+    fn test_first_matching_mapping_wins() {
+        let mappings = RefMapping::parse_all(&[
+            "".to_string(),
+            "legacy:old/legacy".to_string(),
+            "*".to_string(),
+        ]);
+        assert_eq!(mappings.len(), 2);
+        assert_eq!(map_svn_to_git(&mappings, "legacy").as_deref(), Some("old/legacy"));
+        assert_eq!(map_svn_to_git(&mappings, "other").as_deref(), Some("other"));
+        assert_eq!(map_git_to_svn(&mappings, "old/legacy").as_deref(), Some("legacy"));
+    }
+}
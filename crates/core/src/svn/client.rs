@@ -9,7 +9,8 @@ use tokio::process::Command;
 use tracing::{debug, info, instrument, warn};
 
 use super::parser::{
-    parse_svn_diff_summarize, parse_svn_info, parse_svn_log, SvnDiffEntry, SvnInfo, SvnLogEntry,
+    parse_svn_diff_summarize, parse_svn_info, parse_svn_log, parse_svn_propget, SvnDiffEntry,
+    SvnInfo, SvnLogEntry, SvnProperty,
 };
 use crate::errors::SvnError;
 
@@ -55,6 +56,15 @@ impl SvnClient {
         &self.url
     }
 
+    /// Return a client for another URL with the same credentials.
+    pub fn with_url(&self, url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            username: self.username.clone(),
+            password: self.password.clone(),
+        }
+    }
+
     /// Update the password at runtime (credential hot-reload from DB).
     pub fn set_password(&mut self, password: impl Into<String>) {
         self.password = password.into();
@@ -71,6 +81,17 @@ impl SvnClient {
         parse_svn_info(&output)
     }
 
+    /// Youngest revision of the repository at `date`, an SVN date such as
+    /// `2024-01-31` or `2024-01-31T12:00:00Z`.  Resolved on the repository
+    /// root, which exists at every revision.
+    #[instrument(skip(self), fields(url = %self.url))]
+    pub async fn revision_at_date(&self, date: &str) -> Result<i64, SvnError> {
+        let root = self.info().await?.root_url;
+        let rev = format!("{{{}}}", date);
+        let output = self.run_svn(&["info", "--xml", "-r", &rev, &root]).await?;
+        Ok(parse_svn_info(&output)?.latest_rev)
+    }
+
     #[instrument(skip(self), fields(url = %self.url))]
     pub async fn log(&self, start_rev: i64, end_rev: i64) -> Result<Vec<SvnLogEntry>, SvnError> {
         let end_str = if end_rev < 0 {
@@ -125,6 +146,16 @@ impl SvnClient {
         Ok(())
     }
 
+    /// Checkout only the top directory of the URL at HEAD (`--depth empty`);
+    /// [`update_target`](Self::update_target) brings in the paths needed.
+    #[instrument(skip(self), fields(url = %self.url))]
+    pub async fn checkout_head_empty(&self, path: &Path) -> Result<(), SvnError> {
+        let path_str = path.to_string_lossy().to_string();
+        self.run_svn(&["checkout", "--depth", "empty", &self.url, &path_str]).await?;
+        debug!(path = %path.display(), "svn sparse checkout (HEAD) completed");
+        Ok(())
+    }
+
     #[instrument(skip(self, message), fields(path = %path.display()))]
     pub async fn commit(&self, path: &Path, message: &str, _author: &str) -> Result<i64, SvnError> {
         let path_str = path.to_string_lossy().to_string();
@@ -164,6 +195,24 @@ impl SvnClient {
         Ok(())
     }
 
+    /// The value of the revision property `prop_name` of `rev`, `None` when
+    /// it is unset.
+    #[instrument(skip(self), fields(url = %self.url, rev, prop_name))]
+    pub async fn rev_prop(&self, rev: i64, prop_name: &str) -> Result<Option<String>, SvnError> {
+        let rev_str = rev.to_string();
+        let output = match self
+            .run_svn(&["propget", "--revprop", "-r", &rev_str, prop_name, &self.url])
+            .await
+        {
+            Ok(output) => output,
+            Err(SvnError::CommandFailed { stderr, .. }) if stderr.contains("W200017") => {
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(Some(output.trim_end_matches(['\r', '\n']).to_string()))
+    }
+
     #[instrument(skip(self), fields(url = %self.url))]
     pub async fn list_branches(&self, branches_path: &str) -> Result<Vec<String>, SvnError> {
         let branches_url = format!("{}/{}", self.url, branches_path);
@@ -177,6 +226,9 @@ impl SvnClient {
         Ok(branches)
     }
 
+    /// Create `branches_path/name` as a copy of `source_path@source_rev`.
+    ///
+    /// Returns the revision that created the branch.
     #[instrument(skip(self), fields(url = %self.url))]
     pub async fn create_branch(
         &self,
@@ -184,22 +236,108 @@ impl SvnClient {
         source_path: &str,
         branches_path: &str,
         source_rev: i64,
-    ) -> Result<(), SvnError> {
-        let src_url = format!("{}/{}", self.url, source_path);
-        let dest_url = format!("{}/{}/{}", self.url, branches_path, name);
-        let rev_str = source_rev.to_string();
+    ) -> Result<i64, SvnError> {
         let message = format!(
             "Create branch {} from {} at r{}",
             name, source_path, source_rev
         );
-        self.run_svn(&["copy", "-r", &rev_str, &src_url, &dest_url, "-m", &message])
+        let dest_path = format!("{}/{}", branches_path, name);
+        let rev = self
+            .copy(source_path, source_rev, &dest_path, &message)
             .await?;
-        info!(name, source_rev, "created branch");
-        Ok(())
+        info!(name, source_rev, rev, "created branch");
+        Ok(rev)
+    }
+
+    /// Server-side copy of `source_path@source_rev` to `dest_path`, as used
+    /// for branches and tags. Returns the committed revision.
+    #[instrument(skip(self, message), fields(url = %self.url))]
+    pub async fn copy(
+        &self,
+        source_path: &str,
+        source_rev: i64,
+        dest_path: &str,
+        message: &str,
+    ) -> Result<i64, SvnError> {
+        let src_url = format!("{}/{}", self.url, source_path);
+        let dest_url = format!("{}/{}", self.url, dest_path);
+        let rev_str = source_rev.to_string();
+        let output = self
+            .run_svn(&["copy", "-r", &rev_str, &src_url, &dest_url, "-m", message])
+            .await?;
+        parse_committed_revision(&output).ok_or_else(|| SvnError::CommandFailed {
+            exit_code: 0,
+            stderr: format!("could not parse committed revision from: {}", output),
+        })
+    }
+
+    /// Log of a path below the client URL, e.g. `tags`.
+    #[instrument(skip(self), fields(url = %self.url))]
+    pub async fn log_path(
+        &self,
+        path: &str,
+        start_rev: i64,
+        end_rev: i64,
+    ) -> Result<Vec<SvnLogEntry>, SvnError> {
+        let url = format!("{}/{}", self.url, path);
+        let rev_range = format!("{}:{}", start_rev, end_rev);
+        let output = self
+            .run_svn(&["log", "--xml", "--verbose", "-r", &rev_range, &url])
+            .await?;
+        parse_svn_log(&output)
+    }
+
+    /// Return the log entry that created `path`, i.e. its oldest revision
+    /// without following copies. For a branch this is the copy commit, whose
+    /// changed paths carry the copy source.
+    #[instrument(skip(self), fields(url = %self.url))]
+    pub async fn creation_entry(&self, path: &str) -> Result<Option<SvnLogEntry>, SvnError> {
+        let url = format!("{}/{}", self.url, path);
+        let output = self
+            .run_svn(&[
+                "log", "--xml", "--verbose", "--stop-on-copy", "--limit", "1", "-r", "1:HEAD",
+                &url,
+            ])
+            .await?;
+        Ok(parse_svn_log(&output)?.into_iter().next())
+    }
+
+    /// Last revision at or before `rev` that changed `path`, if the path
+    /// existed then.
+    #[instrument(skip(self), fields(url = %self.url))]
+    pub async fn last_changed_rev(&self, path: &str, rev: i64) -> Result<Option<i64>, SvnError> {
+        let url = format!("{}/{}@{}", self.url, path, rev);
+        let rev_range = format!("{}:1", rev);
+        let output = self
+            .run_svn(&["log", "--xml", "--limit", "1", "-r", &rev_range, &url])
+            .await?;
+        Ok(parse_svn_log(&output)?.first().map(|e| e.revision))
     }
 
     #[instrument(skip(self), fields(url = %self.url, rev))]
     pub async fn export(&self, path: &str, rev: i64, dest: &Path) -> Result<(), SvnError> {
+        self.export_with(path, rev, dest, false).await
+    }
+
+    /// Like [`export`](Self::export), but with `svn:keywords` left
+    /// unexpanded, as stored in the repository.
+    #[instrument(skip(self), fields(url = %self.url, rev))]
+    pub async fn export_unexpanded(
+        &self,
+        path: &str,
+        rev: i64,
+        dest: &Path,
+    ) -> Result<(), SvnError> {
+        self.export_with(path, rev, dest, true).await
+    }
+
+    async fn export_with(
+        &self,
+        path: &str,
+        rev: i64,
+        dest: &Path,
+        ignore_keywords: bool,
+    ) -> Result<(), SvnError> {
         let src_url = if path.is_empty() {
             self.url.clone()
         } else {
@@ -207,8 +345,12 @@ impl SvnClient {
         };
         let rev_str = rev.to_string();
         let dest_str = dest.to_string_lossy().to_string();
-        self.run_svn(&["export", "--force", "-r", &rev_str, &src_url, &dest_str])
-            .await?;
+        let mut args = vec!["export", "--force", "-r", &rev_str];
+        if ignore_keywords {
+            args.push("--ignore-keywords");
+        }
+        args.extend([src_url.as_str(), dest_str.as_str()]);
+        self.run_svn(&args).await?;
         info!(dest = %dest.display(), rev, "svn export completed");
         Ok(())
     }
@@ -223,13 +365,33 @@ impl SvnClient {
         Ok(output)
     }
 
+    /// Run `svn update` on one target of a working copy, setting its depth
+    /// (e.g. `empty` for a directory of a sparse checkout) if given.  A
+    /// target missing from the repository is skipped.
+    #[instrument(skip(self), fields(path = %path.display()))]
+    pub async fn update_target(
+        &self,
+        path: &Path,
+        target: &str,
+        depth: Option<&str>,
+    ) -> Result<(), SvnError> {
+        let mut args = vec!["update"];
+        if let Some(depth) = depth {
+            args.extend(["--set-depth", depth]);
+        }
+        args.push(target);
+        self.run_svn_in_dir(path, &args).await?;
+        debug!(target, "svn update completed");
+        Ok(())
+    }
+
     /// Run `svn add` on files in a working copy.
     #[instrument(skip(self, files), fields(path = %path.display()))]
     pub async fn add(&self, path: &Path, files: &[&str]) -> Result<(), SvnError> {
         if files.is_empty() {
             return Ok(());
         }
-        let mut args = vec!["add", "--force"];
+        let mut args = vec!["add", "--force", "--parents"];
         args.extend(files);
         self.run_svn_in_dir(path, &args).await?;
         debug!(count = files.len(), "svn add completed");
@@ -249,6 +411,166 @@ impl SvnClient {
         Ok(())
     }
 
+    /// Schedule files for deletion with `svn rm --keep-local`, leaving them
+    /// on disk (e.g. to re-add them as a different node kind).
+    #[instrument(skip(self, files), fields(path = %path.display()))]
+    pub async fn rm_keep_local(&self, path: &Path, files: &[&str]) -> Result<(), SvnError> {
+        if files.is_empty() {
+            return Ok(());
+        }
+        let mut args = vec!["rm", "--keep-local", "--force"];
+        args.extend(files);
+        self.run_svn_in_dir(path, &args).await?;
+        debug!(count = files.len(), "svn rm --keep-local completed");
+        Ok(())
+    }
+
+    /// Read versioned property `name` on `path` at `rev`. With `recursive`,
+    /// every path below `path` that has the property is included.
+    ///
+    /// Result paths are relative to `path` (`""` for `path` itself).
+    #[instrument(skip(self), fields(url = %self.url))]
+    pub async fn propget(
+        &self,
+        path: &str,
+        name: &str,
+        rev: i64,
+        recursive: bool,
+    ) -> Result<Vec<SvnProperty>, SvnError> {
+        let base = if path.is_empty() {
+            self.url.clone()
+        } else {
+            format!("{}/{}", self.url, path)
+        };
+        let rev_str = rev.to_string();
+        let mut args = vec!["propget", "--xml", "-r", &rev_str];
+        if recursive {
+            args.push("-R");
+        }
+        args.extend([name, base.as_str()]);
+        let output = match self.run_svn(&args).await {
+            Ok(output) => output,
+            // Unset properties are reported as a warning on some versions.
+            Err(SvnError::CommandFailed { stderr, .. }) if stderr.contains("W200017") => {
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e),
+        };
+        let props = parse_svn_propget(&output)?
+            .into_iter()
+            .filter_map(|mut p| {
+                let rel = p.path.strip_prefix(base.as_str())?;
+                if !rel.is_empty() && !rel.starts_with('/') {
+                    return None;
+                }
+                p.path = rel.trim_start_matches('/').to_string();
+                Some(p)
+            })
+            .collect();
+        Ok(props)
+    }
+
+    /// Every property of `paths` (relative to the client URL) at `rev`, and
+    /// with `recursive` of everything below them. Targets are pegged at
+    /// `rev` and passed in batches, so one call covers many files.
+    ///
+    /// Result paths are relative to the client URL.
+    #[instrument(skip(self, paths), fields(url = %self.url, targets = paths.len()))]
+    pub async fn proplist(
+        &self,
+        paths: &[String],
+        rev: i64,
+        recursive: bool,
+    ) -> Result<Vec<SvnProperty>, SvnError> {
+        const BATCH_SIZE: usize = 100;
+        let base = self.url.trim_end_matches('/');
+        let rev_str = rev.to_string();
+        let mut props = Vec::new();
+        for batch in paths.chunks(BATCH_SIZE) {
+            let urls: Vec<String> = batch
+                .iter()
+                .map(|path| match path.as_str() {
+                    "" => format!("{}@{}", base, rev),
+                    path => format!("{}/{}@{}", base, path, rev),
+                })
+                .collect();
+            let mut args = vec!["proplist", "--xml", "-v", "-r", &rev_str];
+            if recursive {
+                args.push("-R");
+            }
+            args.extend(urls.iter().map(String::as_str));
+            let output = self.run_svn(&args).await?;
+            props.extend(parse_svn_propget(&output)?.into_iter().filter_map(|mut p| {
+                let rel = p.path.strip_prefix(base)?;
+                if !rel.is_empty() && !rel.starts_with('/') {
+                    return None;
+                }
+                p.path = rel.trim_start_matches('/').to_string();
+                Some(p)
+            }));
+        }
+        Ok(props)
+    }
+
+    /// Run `svn propset` on `target` in a working copy.
+    #[instrument(skip(self, value), fields(path = %path.display()))]
+    pub async fn propset(
+        &self,
+        path: &Path,
+        name: &str,
+        value: &str,
+        target: &str,
+    ) -> Result<(), SvnError> {
+        self.run_svn_in_dir(path, &["propset", name, value, target]).await?;
+        debug!(name, target, "svn propset completed");
+        Ok(())
+    }
+
+    /// Run `svn propdel` on `target` in a working copy. Deleting a property
+    /// that is not set is not an error.
+    #[instrument(skip(self), fields(path = %path.display()))]
+    pub async fn propdel(&self, path: &Path, name: &str, target: &str) -> Result<(), SvnError> {
+        match self.run_svn_in_dir(path, &["propdel", name, target]).await {
+            Ok(_) => {}
+            Err(SvnError::CommandFailed { stderr, .. }) if stderr.contains("W200017") => {}
+            Err(e) => return Err(e),
+        }
+        debug!(name, target, "svn propdel completed");
+        Ok(())
+    }
+
+    /// Run `svn move` from `from` to `to` in a working copy.
+    #[instrument(skip(self), fields(path = %path.display()))]
+    pub async fn mv(&self, path: &Path, from: &str, to: &str) -> Result<(), SvnError> {
+        self.run_svn_in_dir(path, &["move", "--parents", from, to]).await?;
+        debug!(from, to, "svn move completed");
+        Ok(())
+    }
+
+    /// Run `svn copy` from `from` to `to` in a working copy.
+    #[instrument(skip(self), fields(path = %path.display()))]
+    pub async fn cp(&self, path: &Path, from: &str, to: &str) -> Result<(), SvnError> {
+        self.run_svn_in_dir(path, &["copy", "--parents", from, to]).await?;
+        debug!(from, to, "svn copy completed");
+        Ok(())
+    }
+
+    /// Record `source@rev` as merged into `target` without touching its
+    /// contents (`svn merge --record-only`), updating `svn:mergeinfo`.
+    #[instrument(skip(self), fields(path = %path.display()))]
+    pub async fn merge_record_only(
+        &self,
+        path: &Path,
+        source: &str,
+        rev: i64,
+        target: &str,
+    ) -> Result<(), SvnError> {
+        let source = format!("{}@{}", source, rev);
+        self.run_svn_in_dir(path, &["merge", "--record-only", &source, target]).await?;
+        debug!(source, target, "svn merge --record-only completed");
+        Ok(())
+    }
+
     /// Get the working copy status (modified, added, deleted files).
     #[instrument(skip(self), fields(path = %path.display()))]
     pub async fn status(&self, path: &Path) -> Result<String, SvnError> {
@@ -267,9 +589,25 @@ impl SvnClient {
         self.run_svn(&["cat", "-r", &rev_str, &url]).await
     }
 
+    /// Get the raw bytes of a file at a specific revision.
+    ///
+    /// Unlike [`cat`](Self::cat) the output is not decoded, so binary files
+    /// come back intact.
+    #[instrument(skip(self), fields(file_path = %file_path, rev))]
+    pub async fn cat_bytes(&self, file_path: &str, rev: i64) -> Result<Vec<u8>, SvnError> {
+        let rev_str = rev.to_string();
+        let url = format!("{}/{}", self.url, file_path);
+        self.run_svn_raw(&["cat", "-r", &rev_str, &url]).await
+    }
+
     // -- Internal helpers ----------------------------------------------------
 
     async fn run_svn(&self, args: &[&str]) -> Result<String, SvnError> {
+        let stdout = self.run_svn_raw(args).await?;
+        Ok(String::from_utf8_lossy(&stdout).to_string())
+    }
+
+    async fn run_svn_raw(&self, args: &[&str]) -> Result<Vec<u8>, SvnError> {
         let mut cmd = Command::new("svn");
         cmd.args(args)
             .arg("--non-interactive")
@@ -304,7 +642,7 @@ impl SvnClient {
             warn!(exit_code, %stderr, "svn command failed");
             return Err(SvnError::CommandFailed { exit_code, stderr });
         }
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        Ok(output.stdout)
     }
 
     async fn run_svn_in_dir(&self, dir: &Path, args: &[&str]) -> Result<String, SvnError> {
@@ -0,0 +1,532 @@
+//! Streaming reader for SVN dump files (`svnadmin dump` / `svnrdump dump`).
+//!
+//! A dump is a sequence of RFC 822-style header blocks, each optionally
+//! followed by a property block and file text. [`DumpReader`] yields the
+//! revision and node records one at a time so a whole repository never has
+//! to be held in memory. Format versions 2 (full texts) and 3 (`--deltas`,
+//! svndiff texts and property deltas) are supported; applying the deltas is
+//! left to the caller, see [`super::svndiff`].
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, Read};
+
+use crate::errors::SvnError;
+
+/// Highest dump format version understood by [`DumpReader`].
+pub const MAX_FORMAT_VERSION: u32 = 3;
+
+/// One record of a dump stream.
+#[derive(Debug, Clone)]
+pub enum DumpRecord {
+    /// Start of a revision; its nodes follow.
+    Revision(DumpRevision),
+    /// A change to one path in the current revision.
+    Node(DumpNode),
+}
+
+/// A revision header with its revision properties.
+#[derive(Debug, Clone, Default)]
+pub struct DumpRevision {
+    pub revision: i64,
+    pub props: BTreeMap<String, String>,
+}
+
+impl DumpRevision {
+    /// `svn:author`, empty for anonymous commits.
+    pub fn author(&self) -> &str {
+        self.props.get("svn:author").map(String::as_str).unwrap_or("")
+    }
+
+    /// `svn:date` (ISO 8601).
+    pub fn date(&self) -> &str {
+        self.props.get("svn:date").map(String::as_str).unwrap_or("")
+    }
+
+    /// `svn:log`, the commit message.
+    pub fn message(&self) -> &str {
+        self.props.get("svn:log").map(String::as_str).unwrap_or("")
+    }
+}
+
+/// Kind of the node a record changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    File,
+    Dir,
+}
+
+/// What a node record does to its path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeAction {
+    Add,
+    Change,
+    Delete,
+    Replace,
+}
+
+/// A node record: one path added, changed, deleted or replaced.
+#[derive(Debug, Clone)]
+pub struct DumpNode {
+    /// Repository path, without a leading slash.
+    pub path: String,
+    /// `None` for deletions, which do not carry a kind.
+    pub kind: Option<NodeKind>,
+    pub action: NodeAction,
+    /// Source path and revision of a copy.
+    pub copy_from: Option<(String, i64)>,
+    /// Property block, if the record has one.
+    pub props: Option<NodeProps>,
+    /// File text, if the record has one.
+    pub text: Option<NodeText>,
+}
+
+/// Properties of a node record.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeProps {
+    /// `Prop-delta: true`: `set` and `deleted` change the existing
+    /// properties; otherwise `set` replaces them.
+    pub delta: bool,
+    pub set: BTreeMap<String, String>,
+    pub deleted: Vec<String>,
+}
+
+impl NodeProps {
+    /// Apply this block to the node's existing properties.
+    pub fn apply_to(&self, props: &mut BTreeMap<String, String>) {
+        if !self.delta {
+            props.clear();
+        }
+        for name in &self.deleted {
+            props.remove(name);
+        }
+        props.extend(self.set.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
+
+/// File text of a node record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeText {
+    /// `Text-delta: true`: `content` is an svndiff delta against the
+    /// previous text of the node (or of its copy source).
+    pub delta: bool,
+    pub content: Vec<u8>,
+}
+
+fn invalid(detail: impl Into<String>) -> SvnError {
+    SvnError::DumpFormat(detail.into())
+}
+
+/// Sequential reader over a dump stream.
+pub struct DumpReader<R> {
+    reader: R,
+    format_version: u32,
+    uuid: Option<String>,
+}
+
+impl<R: BufRead> DumpReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            format_version: 0,
+            uuid: None,
+        }
+    }
+
+    /// Format version from the stream header (0 until it has been read).
+    pub fn format_version(&self) -> u32 {
+        self.format_version
+    }
+
+    /// Repository UUID, once its record has been read.
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+
+    /// Read the next revision or node record; `None` at the end of the
+    /// stream.
+    pub fn next_record(&mut self) -> Result<Option<DumpRecord>, SvnError> {
+        loop {
+            let Some(headers) = self.read_headers()? else {
+                return Ok(None);
+            };
+            if let Some(version) = headers.get("SVN-fs-dump-format-version") {
+                let version: u32 = version
+                    .parse()
+                    .map_err(|_| invalid(format!("bad format version '{}'", version)))?;
+                if version > MAX_FORMAT_VERSION {
+                    return Err(invalid(format!("unsupported dump format version {}", version)));
+                }
+                self.format_version = version;
+                continue;
+            }
+            if let Some(uuid) = headers.get("UUID") {
+                self.uuid = Some(uuid.clone());
+                continue;
+            }
+
+            let (props, text) = self.read_content(&headers)?;
+            if let Some(rev) = headers.get("Revision-number") {
+                let revision = rev
+                    .parse()
+                    .map_err(|_| invalid(format!("bad revision number '{}'", rev)))?;
+                let props = props.map(|p| p.set).unwrap_or_default();
+                return Ok(Some(DumpRecord::Revision(DumpRevision { revision, props })));
+            }
+            if let Some(path) = headers.get("Node-path") {
+                return Ok(Some(DumpRecord::Node(parse_node(path, &headers, props, text)?)));
+            }
+            return Err(invalid("record is neither a revision nor a node"));
+        }
+    }
+
+    /// Read one header block, skipping blank lines before it. `None` at the
+    /// end of the stream.
+    fn read_headers(&mut self) -> Result<Option<BTreeMap<String, String>>, SvnError> {
+        let mut headers = BTreeMap::new();
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            if self.reader.read_until(b'\n', &mut line)? == 0 {
+                if headers.is_empty() {
+                    return Ok(None);
+                }
+                return Ok(Some(headers));
+            }
+            let text = String::from_utf8_lossy(&line);
+            let text = text.trim_end_matches(['\n', '\r']);
+            if text.is_empty() {
+                if headers.is_empty() {
+                    continue;
+                }
+                return Ok(Some(headers));
+            }
+            let (key, value) = text
+                .split_once(": ")
+                .or_else(|| text.strip_suffix(':').map(|key| (key, "")))
+                .ok_or_else(|| invalid(format!("malformed header line '{}'", text)))?;
+            headers.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    /// Read the property block and text announced by `headers`.
+    fn read_content(
+        &mut self,
+        headers: &BTreeMap<String, String>,
+    ) -> Result<(Option<NodeProps>, Option<Vec<u8>>), SvnError> {
+        let length = |name: &str| -> Result<Option<usize>, SvnError> {
+            headers
+                .get(name)
+                .map(|v| v.parse().map_err(|_| invalid(format!("bad {} '{}'", name, v))))
+                .transpose()
+        };
+        let prop_len = length("Prop-content-length")?;
+        let text_len = length("Text-content-length")?;
+        let content_len = length("Content-length")?;
+
+        let props = match prop_len {
+            Some(len) => {
+                let block = self.read_exact(len)?;
+                let delta = headers.get("Prop-delta").map(String::as_str) == Some("true");
+                Some(parse_props(&block, delta)?)
+            }
+            None => None,
+        };
+        let text = text_len.map(|len| self.read_exact(len)).transpose()?;
+        let used = prop_len
+            .unwrap_or(0)
+            .checked_add(text_len.unwrap_or(0))
+            .ok_or_else(|| invalid("content lengths overflow"))?;
+        if let Some(len) = content_len {
+            let extra = len
+                .checked_sub(used)
+                .ok_or_else(|| invalid("Content-length is less than its parts"))?;
+            let mut padding = (&mut self.reader).take(extra as u64);
+            if std::io::copy(&mut padding, &mut std::io::sink())? != extra as u64 {
+                return Err(invalid("truncated record"));
+            }
+        }
+        Ok((props, text))
+    }
+
+    /// Read `len` bytes. The buffer grows with the data actually read, so
+    /// a length announced by a corrupt header cannot allocate more than the
+    /// stream holds.
+    fn read_exact(&mut self, len: usize) -> Result<Vec<u8>, SvnError> {
+        let mut buf = Vec::new();
+        (&mut self.reader).take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() != len {
+            return Err(invalid(format!("truncated record: {} of {} bytes", buf.len(), len)));
+        }
+        Ok(buf)
+    }
+}
+
+fn parse_node(
+    path: &str,
+    headers: &BTreeMap<String, String>,
+    props: Option<NodeProps>,
+    text: Option<Vec<u8>>,
+) -> Result<DumpNode, SvnError> {
+    let kind = match headers.get("Node-kind").map(String::as_str) {
+        Some("file") => Some(NodeKind::File),
+        Some("dir") => Some(NodeKind::Dir),
+        Some(other) => return Err(invalid(format!("unknown node kind '{}'", other))),
+        None => None,
+    };
+    let action = match headers.get("Node-action").map(String::as_str) {
+        Some("add") => NodeAction::Add,
+        Some("change") => NodeAction::Change,
+        Some("delete") => NodeAction::Delete,
+        Some("replace") => NodeAction::Replace,
+        other => return Err(invalid(format!("bad node action {:?} for '{}'", other, path))),
+    };
+    let copy_from = match (
+        headers.get("Node-copyfrom-path"),
+        headers.get("Node-copyfrom-rev"),
+    ) {
+        (Some(from), Some(rev)) => {
+            let rev = rev
+                .parse()
+                .map_err(|_| invalid(format!("bad copyfrom revision '{}'", rev)))?;
+            Some((from.trim_start_matches('/').to_string(), rev))
+        }
+        _ => None,
+    };
+    let text = text.map(|content| NodeText {
+        delta: headers.get("Text-delta").map(String::as_str) == Some("true"),
+        content,
+    });
+    Ok(DumpNode {
+        path: path.trim_start_matches('/').to_string(),
+        kind,
+        action,
+        copy_from,
+        props,
+        text,
+    })
+}
+
+/// Parse a property block: `K`/`V` pairs and `D` deletions up to
+/// `PROPS-END`.
+fn parse_props(block: &[u8], delta: bool) -> Result<NodeProps, SvnError> {
+    fn line(block: &[u8], pos: &mut usize) -> Result<String, SvnError> {
+        let rest = block.get(*pos..).unwrap_or_default();
+        let len = rest
+            .iter()
+            .position(|b| *b == b'\n')
+            .ok_or_else(|| invalid("property block without PROPS-END"))?;
+        *pos += len + 1;
+        Ok(String::from_utf8_lossy(&rest[..len]).into_owned())
+    }
+    // A length-prefixed item followed by its newline.
+    fn item(block: &[u8], pos: &mut usize, len: usize) -> Result<String, SvnError> {
+        let end = pos
+            .checked_add(len)
+            .filter(|end| *end < block.len())
+            .ok_or_else(|| invalid("property block ends inside an item"))?;
+        let bytes = &block[*pos..end];
+        *pos = end + 1;
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+    fn tagged(line: &str, tag: &str) -> Option<usize> {
+        line.strip_prefix(tag)?.strip_prefix(' ')?.parse().ok()
+    }
+
+    let mut props = NodeProps {
+        delta,
+        ..NodeProps::default()
+    };
+    let mut pos = 0;
+    loop {
+        let header = line(block, &mut pos)?;
+        if header == "PROPS-END" {
+            return Ok(props);
+        }
+        if let Some(len) = tagged(&header, "K") {
+            let key = item(block, &mut pos, len)?;
+            let value_header = line(block, &mut pos)?;
+            let len = tagged(&value_header, "V")
+                .ok_or_else(|| invalid(format!("malformed value line '{}'", value_header)))?;
+            let value = item(block, &mut pos, len)?;
+            props.set.insert(key, value);
+        } else if let Some(len) = tagged(&header, "D") {
+            props.deleted.push(item(block, &mut pos, len)?);
+        } else {
+            return Err(invalid(format!("malformed property line '{}'", header)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP_V2: &str = "SVN-fs-dump-format-version: 2
+
+UUID: 0b3f7d2c-0000-4000-8000-000000000000
+
+Revision-number: 0
+Prop-content-length: 56
+Content-length: 56
+
+K 8
+svn:date
+V 27
+2024-01-01T00:00:00.000000Z
+PROPS-END
+
+Revision-number: 1
+Prop-content-length: 104
+Content-length: 104
+
+K 10
+svn:author
+V 5
+alice
+K 8
+svn:date
+V 27
+2024-01-02T00:00:00.000000Z
+K 7
+svn:log
+V 5
+Start
+PROPS-END
+
+Node-path: trunk
+Node-kind: dir
+Node-action: add
+Prop-content-length: 10
+Content-length: 10
+
+PROPS-END
+
+
+Node-path: trunk/a.txt
+Node-kind: file
+Node-action: add
+Prop-content-length: 36
+Text-content-length: 6
+Content-length: 42
+
+K 14
+svn:executable
+V 1
+*
+PROPS-END
+hello
+
+
+Node-path: trunk/old.txt
+Node-action: delete
+
+
+";
+
+    #[test]
+    fn test_read_dump_v2() {
+        let mut reader = DumpReader::new(DUMP_V2.as_bytes());
+        let Some(DumpRecord::Revision(r0)) = reader.next_record().unwrap() else {
+            panic!("expected revision 0");
+        };
+        assert_eq!(reader.format_version(), 2);
+        assert_eq!(reader.uuid(), Some("0b3f7d2c-0000-4000-8000-000000000000"));
+        assert_eq!(r0.revision, 0);
+        assert_eq!(r0.author(), "");
+
+        let Some(DumpRecord::Revision(r1)) = reader.next_record().unwrap() else {
+            panic!("expected revision 1");
+        };
+        assert_eq!(r1.revision, 1);
+        assert_eq!(r1.author(), "alice");
+        assert_eq!(r1.date(), "2024-01-02T00:00:00.000000Z");
+        assert_eq!(r1.message(), "Start");
+
+        let Some(DumpRecord::Node(dir)) = reader.next_record().unwrap() else {
+            panic!("expected the trunk node");
+        };
+        assert_eq!(dir.path, "trunk");
+        assert_eq!(dir.kind, Some(NodeKind::Dir));
+        assert_eq!(dir.action, NodeAction::Add);
+        assert_eq!(dir.props, Some(NodeProps::default()));
+        assert!(dir.text.is_none());
+
+        let Some(DumpRecord::Node(file)) = reader.next_record().unwrap() else {
+            panic!("expected the file node");
+        };
+        assert_eq!(file.path, "trunk/a.txt");
+        let props = file.props.unwrap();
+        assert!(!props.delta);
+        assert_eq!(props.set.get("svn:executable").map(String::as_str), Some("*"));
+        let text = file.text.unwrap();
+        assert!(!text.delta);
+        assert_eq!(text.content, b"hello\n");
+
+        let Some(DumpRecord::Node(deleted)) = reader.next_record().unwrap() else {
+            panic!("expected the delete node");
+        };
+        assert_eq!(deleted.action, NodeAction::Delete);
+        assert_eq!(deleted.kind, None);
+        assert!(deleted.props.is_none());
+
+        assert!(reader.next_record().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_read_dump_v3_deltas() {
+        let mut dump = b"SVN-fs-dump-format-version: 3\n\n".to_vec();
+        dump.extend_from_slice(
+            b"Node-path: trunk/b.txt\nNode-kind: file\nNode-action: add\n\
+              Node-copyfrom-rev: 1\nNode-copyfrom-path: /trunk/a.txt\n\
+              Prop-delta: true\nText-delta: true\n\
+              Prop-content-length: 30\nText-content-length: 4\nContent-length: 34\n\n\
+              D 14\nsvn:executable\nPROPS-END\nSVN\0\n\n",
+        );
+        let mut reader = DumpReader::new(dump.as_slice());
+        let Some(DumpRecord::Node(node)) = reader.next_record().unwrap() else {
+            panic!("expected a node");
+        };
+        assert_eq!(reader.format_version(), 3);
+        assert_eq!(node.copy_from, Some(("trunk/a.txt".to_string(), 1)));
+        let props = node.props.unwrap();
+        assert!(props.delta);
+        assert_eq!(props.deleted, vec!["svn:executable".to_string()]);
+        let text = node.text.unwrap();
+        assert!(text.delta);
+        assert_eq!(text.content, b"SVN\0");
+
+        let mut existing = BTreeMap::from([
+            ("svn:executable".to_string(), "*".to_string()),
+            ("svn:eol-style".to_string(), "native".to_string()),
+        ]);
+        props.apply_to(&mut existing);
+        assert_eq!(existing.keys().collect::<Vec<_>>(), vec!["svn:eol-style"]);
+    }
+
+    #[test]
+    fn test_read_dump_errors() {
+        let mut reader = DumpReader::new(b"SVN-fs-dump-format-version: 4\n\n".as_slice());
+        assert!(reader.next_record().is_err());
+
+        let truncated = b"Revision-number: 1\nProp-content-length: 50\nContent-length: 50\n\nK 1";
+        let mut reader = DumpReader::new(truncated.as_slice());
+        assert!(reader.next_record().is_err());
+
+        // Lengths from a corrupt stream fail without allocating or
+        // overflowing.
+        let huge = format!(
+            "Revision-number: 1\nProp-content-length: {0}\nContent-length: {0}\n\nPROPS-END\n",
+            usize::MAX
+        );
+        assert!(DumpReader::new(huge.as_bytes()).next_record().is_err());
+        let item = format!(
+            "Revision-number: 1\nProp-content-length: 30\nContent-length: 30\n\n\
+             K {}\nx\nPROPS-END\n",
+            usize::MAX
+        );
+        assert!(DumpReader::new(item.as_bytes()).next_record().is_err());
+        let short =
+            b"Revision-number: 1\nProp-content-length: 10\nContent-length: 5\n\nPROPS-END\n";
+        assert!(DumpReader::new(short.as_slice()).next_record().is_err());
+    }
+}
@@ -1,7 +1,9 @@
 //! SVN CLI wrapper for RepoSync.
 
 pub mod client;
+pub mod dump;
 pub mod parser;
+pub mod svndiff;
 
 pub use client::SvnClient;
 pub use parser::*;
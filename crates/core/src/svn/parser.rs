@@ -13,6 +13,19 @@ pub struct SvnInfo {
     pub url: String,
 }
 
+impl SvnInfo {
+    /// Repository path of `url`, without surrounding slashes (`""` for the
+    /// repository root).
+    pub fn repo_path(&self) -> String {
+        self.url
+            .trim_end_matches('/')
+            .strip_prefix(self.root_url.trim_end_matches('/'))
+            .unwrap_or_default()
+            .trim_matches('/')
+            .to_string()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SvnLogEntry {
     pub revision: i64,
@@ -26,6 +39,8 @@ pub struct SvnLogEntry {
 pub struct SvnChangedPath {
     pub action: String,
     pub path: String,
+    /// Node kind, `"file"` or `"dir"` (empty if the server did not report it).
+    pub kind: String,
     pub copy_from_path: Option<String>,
     pub copy_from_rev: Option<i64>,
 }
@@ -38,6 +53,14 @@ pub struct SvnDiffEntry {
     pub item: String,
 }
 
+/// A versioned property value on one path, from `svn propget --xml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SvnProperty {
+    pub path: String,
+    pub name: String,
+    pub value: String,
+}
+
 pub fn parse_svn_info(xml: &str) -> Result<SvnInfo, SvnError> {
     debug!("parsing svn info XML ({} bytes)", xml.len());
     let url = extract_tag_content(xml, "url")
@@ -119,6 +142,61 @@ pub fn parse_svn_diff_summarize(xml: &str) -> Result<Vec<SvnDiffEntry>, SvnError
     Ok(entries)
 }
 
+/// Parse `svn propget --xml` or `svn proplist -v --xml` output. Target
+/// paths are returned as printed, with URL escapes decoded.
+pub fn parse_svn_propget(xml: &str) -> Result<Vec<SvnProperty>, SvnError> {
+    debug!("parsing svn propget XML ({} bytes)", xml.len());
+    let mut props = Vec::new();
+    for target in xml.split("<target").skip(1) {
+        let target = match target.find("</target>") {
+            Some(pos) => &target[..pos],
+            None => target,
+        };
+        let path = extract_attribute_from_fragment(target, "path")
+            .ok_or_else(|| SvnError::XmlParseError("missing path on propget target".into()))?;
+        let path = percent_decode(&xml_unescape(&path));
+        for property in target.split("<property").skip(1) {
+            let name = extract_attribute_from_fragment(property, "name").unwrap_or_default();
+            let value = match (property.find('>'), property.find("</property>")) {
+                (Some(start), Some(end)) if start < end => xml_unescape(&property[start + 1..end]),
+                _ => String::new(),
+            };
+            props.push(SvnProperty {
+                path: path.clone(),
+                name,
+                value,
+            });
+        }
+    }
+    debug!(count = props.len(), "parsed svn properties");
+    Ok(props)
+}
+
+/// Decode `%XX` escapes as found in URLs printed by `svn`.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(b) => {
+                out.push(b);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
 fn extract_tag_content(xml: &str, tag: &str) -> Option<String> {
     let open = format!("<{}", tag);
     let close = format!("</{}>", tag);
@@ -201,6 +279,7 @@ fn parse_changed_paths(entry_xml: &str) -> Vec<SvnChangedPath> {
             None => continue,
         };
         let action = extract_attribute_from_fragment(fragment, "action").unwrap_or_default();
+        let kind = extract_attribute_from_fragment(fragment, "kind").unwrap_or_default();
         let copy_from_path = extract_attribute_from_fragment(fragment, "copyfrom-path");
         let copy_from_rev = extract_attribute_from_fragment(fragment, "copyfrom-rev")
             .and_then(|s| s.parse::<i64>().ok());
@@ -211,6 +290,7 @@ fn parse_changed_paths(entry_xml: &str) -> Vec<SvnChangedPath> {
         paths.push(SvnChangedPath {
             action,
             path,
+            kind,
             copy_from_path,
             copy_from_rev,
         });
@@ -231,6 +311,30 @@ mod tests {
 <commit revision="1234"></commit></entry></info>"#;
         let info = parse_svn_info(xml).unwrap();
         assert_eq!(info.latest_rev, 1234);
+        assert_eq!(info.repo_path(), "trunk");
+    }
+
+    #[test]
+    fn test_parse_svn_propget() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+<target path="https://svn.example.com/repo/trunk">
+<property name="svn:ignore">build
+*.o
+</property>
+</target>
+<target path="https://svn.example.com/repo/trunk/my%20lib">
+<property name="svn:ignore">a &amp; b</property>
+</target>
+</properties>"#;
+        let props = parse_svn_propget(xml).unwrap();
+        assert_eq!(props.len(), 2);
+        assert_eq!(props[0].path, "https://svn.example.com/repo/trunk");
+        assert_eq!(props[0].name, "svn:ignore");
+        assert_eq!(props[0].value, "build\n*.o\n");
+        assert_eq!(props[1].path, "https://svn.example.com/repo/trunk/my lib");
+        assert_eq!(props[1].value, "a & b");
+        assert!(parse_svn_propget("<properties>\n</properties>").unwrap().is_empty());
     }
 
     #[test]
@@ -308,6 +412,7 @@ mod tests {
             Some("/trunk")
         );
         assert_eq!(entries[0].changed_paths[0].copy_from_rev, Some(199));
+        assert_eq!(entries[0].changed_paths[0].kind, "dir");
     }
 
     #[test]
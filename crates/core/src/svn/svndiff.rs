@@ -0,0 +1,244 @@
+//! Decoder for the svndiff binary delta format used by dump streams.
+//!
+//! A delta is the 4-byte header `SVN\0` (svndiff0) or `SVN\1` (svndiff1,
+//! zlib-compressed sections) followed by windows. Each window copies bytes
+//! from a view of the source, from the target produced so far, or from its
+//! own new-data section. svndiff2 (LZ4) is not produced by dump tools and is
+//! rejected.
+
+use std::io::Read;
+
+use crate::errors::SvnError;
+
+/// Instruction: copy from the source view.
+const COPY_SOURCE: u8 = 0;
+/// Instruction: copy from the target produced so far (may overlap).
+const COPY_TARGET: u8 = 1;
+/// Instruction: copy from the window's new data.
+const COPY_NEW: u8 = 2;
+
+fn invalid(detail: impl Into<String>) -> SvnError {
+    SvnError::DumpFormat(format!("svndiff: {}", detail.into()))
+}
+
+/// Cursor over a byte slice with the svndiff variable-length integers.
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn byte(&mut self) -> Result<u8, SvnError> {
+        let b = *self.data.get(self.pos).ok_or_else(|| invalid("unexpected end of data"))?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    /// Big-endian base-128 integer; the high bit marks a continuation byte.
+    fn varint(&mut self) -> Result<usize, SvnError> {
+        let mut value: usize = 0;
+        loop {
+            let b = self.byte()?;
+            value = value
+                .checked_mul(128)
+                .and_then(|v| v.checked_add(usize::from(b & 0x7f)))
+                .ok_or_else(|| invalid("integer overflow"))?;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SvnError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| invalid("section runs past the end of the delta"))?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+}
+
+/// Undo svndiff1 section compression: the original length followed by
+/// either the raw bytes (when compression did not help) or a zlib stream.
+fn decompress(section: &[u8]) -> Result<Vec<u8>, SvnError> {
+    let mut cursor = Cursor::new(section);
+    let len = cursor.varint()?;
+    let rest = &section[cursor.pos..];
+    if rest.len() == len {
+        return Ok(rest.to_vec());
+    }
+    // The declared length only bounds the output; the buffer grows with
+    // what the stream really inflates to.
+    let mut out = Vec::new();
+    flate2::read::ZlibDecoder::new(rest)
+        .take((len as u64).saturating_add(1))
+        .read_to_end(&mut out)
+        .map_err(|e| invalid(format!("zlib: {}", e)))?;
+    if out.len() != len {
+        return Err(invalid("decompressed section has the wrong length"));
+    }
+    Ok(out)
+}
+
+/// Apply the svndiff `delta` to `source`, returning the target text.
+pub fn apply_svndiff(source: &[u8], delta: &[u8]) -> Result<Vec<u8>, SvnError> {
+    let mut cursor = Cursor::new(delta);
+    if cursor.take(3).ok() != Some(b"SVN".as_slice()) {
+        return Err(invalid("missing SVN header"));
+    }
+    let compressed = match cursor.byte()? {
+        0 => false,
+        1 => true,
+        version => return Err(invalid(format!("unsupported version {}", version))),
+    };
+
+    let mut target = Vec::new();
+    while !cursor.is_empty() {
+        let sview_offset = cursor.varint()?;
+        let sview_len = cursor.varint()?;
+        let tview_len = cursor.varint()?;
+        let ins_len = cursor.varint()?;
+        let new_len = cursor.varint()?;
+        let sview = sview_offset
+            .checked_add(sview_len)
+            .and_then(|end| source.get(sview_offset..end))
+            .ok_or_else(|| invalid("source view out of range"))?;
+        let (instructions, new_data) = if compressed {
+            (decompress(cursor.take(ins_len)?)?, decompress(cursor.take(new_len)?)?)
+        } else {
+            (cursor.take(ins_len)?.to_vec(), cursor.take(new_len)?.to_vec())
+        };
+
+        let start = target.len();
+        let mut new_pos = 0usize;
+        let mut ins = Cursor::new(&instructions);
+        while !ins.is_empty() {
+            let op = ins.byte()?;
+            let len = match usize::from(op & 0x3f) {
+                0 => ins.varint()?,
+                len => len,
+            };
+            if len > tview_len - (target.len() - start) {
+                return Err(invalid("instruction overruns the target view"));
+            }
+            match op >> 6 {
+                COPY_SOURCE => {
+                    let offset = ins.varint()?;
+                    let bytes = offset
+                        .checked_add(len)
+                        .and_then(|end| sview.get(offset..end))
+                        .ok_or_else(|| invalid("source copy out of range"))?;
+                    target.extend_from_slice(bytes);
+                }
+                COPY_TARGET => {
+                    let offset = ins.varint()?;
+                    if offset >= target.len() - start {
+                        return Err(invalid("target copy out of range"));
+                    }
+                    // Byte by byte: the copy may overlap the bytes it writes.
+                    for i in 0..len {
+                        let b = target[start + offset + i];
+                        target.push(b);
+                    }
+                }
+                COPY_NEW => {
+                    let bytes = new_pos
+                        .checked_add(len)
+                        .and_then(|end| new_data.get(new_pos..end))
+                        .ok_or_else(|| invalid("new-data copy out of range"))?;
+                    target.extend_from_slice(bytes);
+                    new_pos += len;
+                }
+                _ => return Err(invalid("unknown instruction")),
+            }
+        }
+        if target.len() - start != tview_len {
+            return Err(invalid("window produced the wrong number of bytes"));
+        }
+    }
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// One window with single-byte lengths.
+    fn window(sview: (u8, u8), tview_len: u8, ins: &[u8], new_data: &[u8]) -> Vec<u8> {
+        let mut w = vec![sview.0, sview.1, tview_len, ins.len() as u8, new_data.len() as u8];
+        w.extend_from_slice(ins);
+        w.extend_from_slice(new_data);
+        w
+    }
+
+    #[test]
+    fn test_apply_svndiff() {
+        let mut delta = b"SVN\0".to_vec();
+        delta.extend(window((0, 0), 6, &[0x86], b"hello\n"));
+        assert_eq!(apply_svndiff(b"", &delta).unwrap(), b"hello\n");
+
+        // Keep "hello " from the source, append "world" from new data, then
+        // repeat "ld" twice through an overlapping target copy.
+        let mut delta = b"SVN\0".to_vec();
+        delta.extend(window((0, 6), 15, &[0x06, 0x00, 0x85, 0x44, 0x09], b"world"));
+        assert_eq!(apply_svndiff(b"hello ", &delta).unwrap(), b"hello worldldld");
+
+        // Windows append to each other; an empty delta is an empty text.
+        let mut delta = b"SVN\0".to_vec();
+        delta.extend(window((0, 2), 2, &[0x02, 0x00], b""));
+        delta.extend(window((2, 1), 1, &[0x01, 0x00], b""));
+        assert_eq!(apply_svndiff(b"abc", &delta).unwrap(), b"abc");
+        assert_eq!(apply_svndiff(b"old", b"SVN\0").unwrap(), b"");
+
+        assert!(apply_svndiff(b"", b"XYZ\0").is_err());
+        assert!(apply_svndiff(b"", b"SVN\x02").is_err());
+        let mut delta = b"SVN\0".to_vec();
+        delta.extend(window((0, 5), 1, &[0x01, 0x00], b""));
+        assert!(apply_svndiff(b"ab", &delta).is_err());
+        let mut delta = b"SVN\0".to_vec();
+        delta.extend(window((0, 0), 2, &[0x81], b"x"));
+        assert!(apply_svndiff(b"", &delta).is_err());
+
+        // A target copy longer than the window's view is refused before it
+        // runs.
+        let mut delta = b"SVN\0".to_vec();
+        delta.extend(window((0, 0), 2, &[0x81, 0x40, 0xff, 0xff, 0x7f, 0x00], b"x"));
+        assert!(apply_svndiff(b"", &delta).is_err());
+    }
+
+    #[test]
+    fn test_apply_svndiff1() {
+        let text = b"compressible compressible compressible";
+        let mut zlib = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+        zlib.write_all(text).unwrap();
+        let zlib = zlib.finish().unwrap();
+        assert!(zlib.len() < text.len());
+
+        // The instruction section is stored raw, the new data compressed.
+        let ins = [0x01, 0x80 | text.len() as u8];
+        let mut new_data = vec![text.len() as u8];
+        new_data.extend_from_slice(&zlib);
+        let mut delta = b"SVN\x01".to_vec();
+        delta.extend(window((0, 0), text.len() as u8, &ins, &new_data));
+        assert_eq!(apply_svndiff(b"", &delta).unwrap(), text);
+
+        // A section claiming a huge original length fails on the data.
+        let mut new_data = vec![0xff, 0xff, 0xff, 0xff, 0x7f];
+        new_data.extend_from_slice(&zlib);
+        let mut delta = b"SVN\x01".to_vec();
+        delta.extend(window((0, 0), text.len() as u8, &ins, &new_data));
+        assert!(apply_svndiff(b"", &delta).is_err());
+    }
+}
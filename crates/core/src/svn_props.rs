@@ -0,0 +1,1745 @@
+//! Translation between SVN properties and Git metadata files and modes.
+//!
+//! `svn:ignore` applies to the entries of one directory and
+//! `svn:global-ignores` to a directory and everything below it. Both map onto
+//! a generated `.gitignore` in that directory: `svn:ignore` patterns are
+//! anchored (`/pattern`) while `svn:global-ignores` patterns are not.
+//!
+//! `svn:externals` is kept verbatim in a `.svnexternals` file in the
+//! directory holding it; the sync engines decide how the externals
+//! themselves are materialized.
+//!
+//! `svn:executable` on a file corresponds to Git mode `100755`, and files
+//! with `svn:special` (stored by SVN as `link <target>`) to Git symlinks
+//! (mode `120000`).
+//! `svn:eol-style` becomes a `text eol=...` entry in a generated block of
+//! the root `.gitattributes`, and keywords listed in `svn:keywords` are kept
+//! collapsed (`$Id$`) in Git so their expansion does not show up as changes.
+//!
+//! `svn:mergeinfo` on the synced root records which revisions of other
+//! branches were merged in; the newest merged revision of each source
+//! becomes an extra parent of the corresponding Git commit.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use tracing::debug;
+
+use crate::errors::SvnError;
+use crate::svn::{SvnChangedPath, SvnClient};
+
+/// Property listing names to ignore in one directory.
+pub const SVN_IGNORE: &str = "svn:ignore";
+
+/// Inheritable property listing names to ignore in a directory tree.
+pub const SVN_GLOBAL_IGNORES: &str = "svn:global-ignores";
+
+/// File name of the generated ignore file.
+pub const GITIGNORE: &str = ".gitignore";
+
+/// Property defining external checkouts below a directory.
+pub const SVN_EXTERNALS: &str = "svn:externals";
+
+/// File name holding a directory's `svn:externals` definition in Git.
+pub const SVNEXTERNALS: &str = ".svnexternals";
+
+/// File property marking a file executable.
+pub const SVN_EXECUTABLE: &str = "svn:executable";
+
+/// File property marking a file as special; SVN uses it for symlinks.
+pub const SVN_SPECIAL: &str = "svn:special";
+
+/// Prefix of the content SVN stores for a symlink.
+const SVN_LINK_PREFIX: &str = "link ";
+
+/// File property selecting the line endings of a text file.
+pub const SVN_EOL_STYLE: &str = "svn:eol-style";
+
+/// File property listing the keywords SVN expands in a file.
+pub const SVN_KEYWORDS: &str = "svn:keywords";
+
+/// File name of the Git attributes file holding the `svn:eol-style` block.
+pub const GITATTRIBUTES: &str = ".gitattributes";
+
+/// Directory property recording the revisions merged into a branch.
+pub const SVN_MERGEINFO: &str = "svn:mergeinfo";
+
+/// Delimiters of the generated block in `.gitattributes`.
+const EOL_BLOCK_BEGIN: &str = "# BEGIN svn:eol-style (generated by reposync)";
+const EOL_BLOCK_END: &str = "# END svn:eol-style";
+
+/// Keyword names SVN understands; each group lists the aliases of one
+/// keyword.
+const KEYWORD_GROUPS: &[&[&str]] = &[
+    &["Date", "LastChangedDate"],
+    &["Revision", "LastChangedRevision", "Rev"],
+    &["Author", "LastChangedBy"],
+    &["HeadURL", "URL"],
+    &["Id"],
+    &["Header"],
+];
+
+/// Delimiters of the generated block in `.gitignore`.
+const IGNORE_BLOCK_BEGIN: &str = "# BEGIN svn:ignore (generated by reposync)";
+const IGNORE_BLOCK_END: &str = "# END svn:ignore";
+
+/// First lines of the `.gitignore` files generated before the block existed;
+/// such a file holds nothing but the properties.
+const LEGACY_GITIGNORE_HEADER: &str =
+    "# Generated from svn:ignore and svn:global-ignores by reposync.\n\
+     # Edits are synced back to SVN as property changes.\n";
+
+/// First lines of every generated `.svnexternals`.
+const SVNEXTERNALS_HEADER: &str = "# svn:externals of this directory, synced by reposync.\n\
+                                   # Edits are synced back to SVN as property changes.\n";
+
+/// The ignore properties of one SVN directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IgnoreProps {
+    /// `svn:ignore` patterns.
+    pub ignore: Vec<String>,
+    /// `svn:global-ignores` patterns.
+    pub global_ignores: Vec<String>,
+}
+
+impl IgnoreProps {
+    /// Build from raw property values. `svn:ignore` holds one pattern per
+    /// line; `svn:global-ignores` is whitespace-separated.
+    pub fn from_values(ignore: Option<&str>, global_ignores: Option<&str>) -> Self {
+        Self {
+            ignore: ignore
+                .unwrap_or_default()
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect(),
+            global_ignores: global_ignores
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ignore.is_empty() && self.global_ignores.is_empty()
+    }
+
+    /// Value for `svn:ignore` (empty when there are no patterns).
+    pub fn svn_ignore_value(&self) -> String {
+        self.ignore.iter().map(|p| format!("{}\n", p)).collect()
+    }
+
+    /// Value for `svn:global-ignores` (empty when there are no patterns).
+    pub fn global_ignores_value(&self) -> String {
+        self.global_ignores.iter().map(|p| format!("{}\n", p)).collect()
+    }
+
+    /// Render as the generated block of a `.gitignore` (empty when there
+    /// are no patterns).
+    pub fn to_gitignore(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut out = format!("{}\n", IGNORE_BLOCK_BEGIN);
+        for pattern in &self.ignore {
+            out.push_str(&format!("/{}\n", pattern));
+        }
+        for pattern in &self.global_ignores {
+            out.push_str(&format!("{}\n", pattern));
+        }
+        out.push_str(IGNORE_BLOCK_END);
+        out.push('\n');
+        out
+    }
+
+    /// Parse the generated block of `.gitignore` content; the lines outside
+    /// it are Git's own. Also returns the lines SVN cannot express:
+    /// negations and patterns that reach into subdirectories.
+    pub fn from_gitignore(content: &str) -> (Self, Vec<String>) {
+        let mut props = Self::default();
+        let mut unsupported = Vec::new();
+        let lines: Vec<&str> = if content.starts_with(LEGACY_GITIGNORE_HEADER) {
+            content.lines().collect()
+        } else {
+            content
+                .lines()
+                .skip_while(|line| *line != IGNORE_BLOCK_BEGIN)
+                .skip(1)
+                .take_while(|line| *line != IGNORE_BLOCK_END)
+                .collect()
+        };
+        for line in lines.into_iter().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // Git treats `dir/` as "directories only"; SVN cannot tell.
+            let pattern = line.trim_end_matches('/');
+            let (anchored, name) = match pattern.strip_prefix('/') {
+                Some(rest) => (true, rest),
+                None => (false, pattern.strip_prefix("**/").unwrap_or(pattern)),
+            };
+            if line.starts_with('!') || name.is_empty() || name.contains('/') {
+                unsupported.push(line.to_string());
+            } else if anchored {
+                props.ignore.push(name.to_string());
+            } else {
+                props.global_ignores.push(name.to_string());
+            }
+        }
+        (props, unsupported)
+    }
+}
+
+/// Whether a repo-relative path names a `.gitignore` file.
+pub fn is_gitignore(path: &str) -> bool {
+    path == GITIGNORE || path.ends_with(&format!("/{}", GITIGNORE))
+}
+
+/// Whether a repo-relative path names a `.svnexternals` file.
+pub fn is_svnexternals(path: &str) -> bool {
+    path == SVNEXTERNALS || path.ends_with(&format!("/{}", SVNEXTERNALS))
+}
+
+/// Join two repo-relative paths, either of which may be `""`.
+pub fn join_path(dir: &str, rest: &str) -> String {
+    match (dir, rest) {
+        ("", r) => r.to_string(),
+        (d, "") => d.to_string(),
+        (d, r) => format!("{}/{}", d, r),
+    }
+}
+
+/// Whether `path` is `root` or lies below it.
+pub fn is_under(path: &str, root: &str) -> bool {
+    path == root
+        || path
+            .strip_prefix(root)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Directory part of a repo-relative path (`""` for the root).
+pub fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+/// `.gitignore` content with its generated block replaced by `props`; every
+/// other line is kept. A file generated before the block existed is
+/// replaced as a whole.
+pub fn render_gitignore(existing: &str, props: &IgnoreProps) -> String {
+    let mut content = String::new();
+    if !existing.starts_with(LEGACY_GITIGNORE_HEADER) {
+        let mut in_block = false;
+        for line in existing.lines() {
+            match line {
+                IGNORE_BLOCK_BEGIN => in_block = true,
+                IGNORE_BLOCK_END if in_block => in_block = false,
+                _ if in_block => {}
+                _ => {
+                    content.push_str(line);
+                    content.push('\n');
+                }
+            }
+        }
+    }
+    content.push_str(&props.to_gitignore());
+    content
+}
+
+/// Read the ignore properties of the SVN directory `path` at `rev`, and with
+/// `recursive` of every directory below it that has any.
+///
+/// Keys are relative to `path`; `path` itself is always present.
+pub async fn fetch_ignore_props(
+    svn: &SvnClient,
+    path: &str,
+    rev: i64,
+    recursive: bool,
+) -> Result<BTreeMap<String, IgnoreProps>, SvnError> {
+    let mut raw: BTreeMap<String, (Option<String>, Option<String>)> = BTreeMap::new();
+    raw.insert(String::new(), (None, None));
+    for prop in svn.propget(path, SVN_IGNORE, rev, recursive).await? {
+        raw.entry(prop.path).or_default().0 = Some(prop.value);
+    }
+    for prop in svn.propget(path, SVN_GLOBAL_IGNORES, rev, recursive).await? {
+        raw.entry(prop.path).or_default().1 = Some(prop.value);
+    }
+    Ok(raw
+        .into_iter()
+        .map(|(dir, (ignore, global))| {
+            (dir, IgnoreProps::from_values(ignore.as_deref(), global.as_deref()))
+        })
+        .collect())
+}
+
+/// What [`write_gitignore`] or [`write_svnexternals`] did to the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileUpdate {
+    Unchanged,
+    Written,
+    Removed,
+}
+
+/// Replace the generated block of `<root>/<dir>/.gitignore` with `props`,
+/// keeping every other line. The file is removed when nothing else is left
+/// in it, so hand-written patterns are never lost.
+pub fn write_gitignore(
+    root: &Path,
+    dir: &str,
+    props: &IgnoreProps,
+) -> std::io::Result<FileUpdate> {
+    let path = root.join(dir).join(GITIGNORE);
+    let existing = std::fs::read_to_string(&path).ok();
+    let content = render_gitignore(existing.as_deref().unwrap_or(""), props);
+    if existing.as_deref() == Some(content.as_str()) {
+        return Ok(FileUpdate::Unchanged);
+    }
+    if content.trim().is_empty() {
+        if existing.is_none() {
+            return Ok(FileUpdate::Unchanged);
+        }
+        std::fs::remove_file(&path)?;
+        debug!(path = %path.display(), "removed generated .gitignore");
+        return Ok(FileUpdate::Removed);
+    }
+    std::fs::create_dir_all(root.join(dir))?;
+    std::fs::write(&path, content)?;
+    debug!(path = %path.display(), "wrote generated .gitignore");
+    Ok(FileUpdate::Written)
+}
+
+/// One `svn:externals` definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvnExternal {
+    /// Target path relative to the directory holding the property.
+    pub path: String,
+    /// URL as written, possibly relative (`^/`, `//`, `/` or `../`).
+    pub url: String,
+    /// Peg revision (`URL@REV`).
+    pub peg_rev: Option<i64>,
+    /// Operative revision (`-r REV`).
+    pub rev: Option<i64>,
+}
+
+impl SvnExternal {
+    /// Revision the external is pinned to, if any.
+    pub fn pinned_rev(&self) -> Option<i64> {
+        self.rev.or(self.peg_rev)
+    }
+
+    /// Absolute URL of the external, given the URL of the directory holding
+    /// the property and the repository root.
+    pub fn resolve_url(&self, dir_url: &str, repo_root: &str) -> String {
+        let dir_url = dir_url.trim_end_matches('/');
+        let (scheme, after_scheme) = dir_url.split_once("://").unwrap_or(("", dir_url));
+        let resolved = if let Some(rest) = self.url.strip_prefix("^/") {
+            format!("{}/{}", repo_root.trim_end_matches('/'), rest)
+        } else if let Some(rest) = self.url.strip_prefix("//") {
+            format!("{}://{}", scheme, rest)
+        } else if self.url.starts_with('/') {
+            let host = after_scheme.split('/').next().unwrap_or_default();
+            format!("{}://{}{}", scheme, host, self.url)
+        } else if self.url.starts_with("../") {
+            format!("{}/{}", dir_url, self.url)
+        } else {
+            return self.url.clone();
+        };
+        normalize_url(&resolved)
+    }
+}
+
+/// Collapse `.` and `..` segments in a URL path.
+fn normalize_url(url: &str) -> String {
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme), rest),
+        None => (None, url),
+    };
+    let mut segments: Vec<&str> = Vec::new();
+    for (i, segment) in rest.split('/').enumerate() {
+        match segment {
+            "." => {}
+            // Never climb above the host.
+            ".." if segments.len() > 1 => {
+                segments.pop();
+            }
+            ".." => {}
+            "" if i > 0 => {}
+            s => segments.push(s),
+        }
+    }
+    match scheme {
+        Some(scheme) => format!("{}://{}", scheme, segments.join("/")),
+        None => segments.join("/"),
+    }
+}
+
+/// Parse an `svn:externals` value, in either the `[-r REV] URL[@PEG] PATH`
+/// form or the pre-1.5 `PATH [-r REV] URL` form. Comments and lines that
+/// cannot be parsed are skipped.
+pub fn parse_externals(value: &str) -> Vec<SvnExternal> {
+    let looks_like_url = |s: &str| {
+        s.contains("://") || s.starts_with('^') || s.starts_with('/') || s.starts_with("../")
+    };
+    let mut externals = Vec::new();
+    for line in value.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut rev = None;
+        let mut words: Vec<&str> = Vec::new();
+        let mut tokens = line.split_whitespace();
+        while let Some(token) = tokens.next() {
+            match token.strip_prefix("-r") {
+                Some("") => rev = tokens.next().and_then(|r| r.parse().ok()),
+                Some(r) if r.parse::<i64>().is_ok() => rev = r.parse().ok(),
+                _ => words.push(token),
+            }
+        }
+        let (url, path) = match words.as_slice() {
+            [url, path] if looks_like_url(url) => (*url, *path),
+            [path, url] => (*url, *path),
+            _ => continue,
+        };
+        let (url, peg_rev) = match url.rsplit_once('@') {
+            Some((base, peg)) if peg.parse::<i64>().is_ok() => (base, peg.parse().ok()),
+            _ => (url, None),
+        };
+        externals.push(SvnExternal {
+            path: path.trim_matches('/').to_string(),
+            url: url.to_string(),
+            peg_rev,
+            rev,
+        });
+    }
+    externals
+}
+
+/// Render an `svn:externals` value as `.svnexternals` content.
+pub fn to_svnexternals(value: &str) -> String {
+    let mut out = SVNEXTERNALS_HEADER.to_string();
+    for line in value.lines() {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Recover the `svn:externals` value from `.svnexternals` content.
+pub fn from_svnexternals(content: &str) -> String {
+    let header: Vec<&str> = SVNEXTERNALS_HEADER.lines().collect();
+    content
+        .lines()
+        .filter(|line| !header.contains(line))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Read `svn:externals` of the SVN directory `path` at `rev`, and with
+/// `recursive` of every directory below it that has any.
+///
+/// Keys are relative to `path`; `path` itself is always present, with an
+/// empty value when it has no externals.
+pub async fn fetch_externals(
+    svn: &SvnClient,
+    path: &str,
+    rev: i64,
+    recursive: bool,
+) -> Result<BTreeMap<String, String>, SvnError> {
+    let mut values = BTreeMap::new();
+    values.insert(String::new(), String::new());
+    for prop in svn.propget(path, SVN_EXTERNALS, rev, recursive).await? {
+        values.insert(prop.path, prop.value);
+    }
+    Ok(values)
+}
+
+/// Bring `<root>/<dir>/.svnexternals` in line with an `svn:externals` value.
+/// An empty value removes the file.
+pub fn write_svnexternals(root: &Path, dir: &str, value: &str) -> std::io::Result<FileUpdate> {
+    let path = root.join(dir).join(SVNEXTERNALS);
+    let existing = std::fs::read_to_string(&path).ok();
+    if value.trim().is_empty() {
+        if existing.is_none() {
+            return Ok(FileUpdate::Unchanged);
+        }
+        std::fs::remove_file(&path)?;
+        return Ok(FileUpdate::Removed);
+    }
+    let content = to_svnexternals(value);
+    if existing.as_deref() == Some(content.as_str()) {
+        return Ok(FileUpdate::Unchanged);
+    }
+    std::fs::create_dir_all(root.join(dir))?;
+    std::fs::write(&path, content)?;
+    Ok(FileUpdate::Written)
+}
+
+/// Repo-relative target paths of the externals defined in `content`, the
+/// `.svnexternals` file of directory `dir`.
+pub fn external_targets(dir: &str, content: &str) -> Vec<String> {
+    parse_externals(&from_svnexternals(content))
+        .into_iter()
+        .map(|e| join_path(dir, &e.path))
+        .collect()
+}
+
+/// Target paths of the externals defined in any directory above `paths`.
+/// `read` returns the content of a repo-relative `.svnexternals` file.
+pub fn external_roots<'a>(
+    paths: impl IntoIterator<Item = &'a str>,
+    mut read: impl FnMut(&str) -> Option<String>,
+) -> Vec<String> {
+    let mut dirs: Vec<String> = Vec::new();
+    for path in paths {
+        let mut dir = parent_dir(path);
+        loop {
+            if !dirs.iter().any(|d| d == dir) {
+                dirs.push(dir.to_string());
+            }
+            if dir.is_empty() {
+                break;
+            }
+            dir = parent_dir(dir);
+        }
+    }
+    dirs.iter()
+        .filter_map(|dir| Some((dir, read(&join_path(dir, SVNEXTERNALS))?)))
+        .flat_map(|(dir, content)| external_targets(dir, &content))
+        .collect()
+}
+
+/// Set `svn:externals` on the working-copy directory `dir` (relative to
+/// `wc`), deleting it when `value` is empty.
+pub async fn apply_externals(
+    svn: &SvnClient,
+    wc: &Path,
+    dir: &str,
+    value: &str,
+) -> Result<(), SvnError> {
+    let target = if dir.is_empty() { "." } else { dir };
+    if value.trim().is_empty() {
+        svn.propdel(wc, SVN_EXTERNALS, target).await
+    } else {
+        svn.propset(wc, SVN_EXTERNALS, value, target).await
+    }
+}
+
+/// Set the ignore properties of the working-copy directory `dir` (relative
+/// to `wc`), deleting those with no patterns.
+pub async fn apply_ignore_props(
+    svn: &SvnClient,
+    wc: &Path,
+    dir: &str,
+    props: &IgnoreProps,
+) -> Result<(), SvnError> {
+    let target = if dir.is_empty() { "." } else { dir };
+    for (name, value) in [
+        (SVN_IGNORE, props.svn_ignore_value()),
+        (SVN_GLOBAL_IGNORES, props.global_ignores_value()),
+    ] {
+        if value.is_empty() {
+            svn.propdel(wc, name, target).await?;
+        } else {
+            svn.propset(wc, name, &value, target).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Values of the file property `name` at or below `path` at `rev`, keyed
+/// by path relative to `path`.
+pub async fn fetch_file_props(
+    svn: &SvnClient,
+    path: &str,
+    name: &str,
+    rev: i64,
+    recursive: bool,
+) -> Result<BTreeMap<String, String>, SvnError> {
+    let props = svn.propget(path, name, rev, recursive).await?;
+    Ok(props.into_iter().map(|p| (p.path, p.value.trim().to_string())).collect())
+}
+
+/// Where the properties of a revision may have changed below the synced
+/// tree, as paths relative to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PropTargets {
+    /// Files added, modified or replaced.
+    pub files: Vec<String>,
+    /// Directories added, modified or replaced, with whether they were
+    /// added or replaced: everything below those is new as well.
+    pub dirs: Vec<(String, bool)>,
+}
+
+impl PropTargets {
+    /// Targets of the changed paths of a log entry, for the tree at the
+    /// repository path `base` (`""` for the root). Adding or replacing a
+    /// directory above `base` replaces the whole tree.
+    pub fn from_log(changed_paths: &[SvnChangedPath], base: &str) -> Self {
+        let mut targets = Self::default();
+        for changed in changed_paths {
+            let path = changed.path.trim_matches('/');
+            let added = matches!(changed.action.as_str(), "A" | "R");
+            let rel = if base.is_empty() {
+                path
+            } else if is_under(path, base) {
+                path[base.len()..].trim_start_matches('/')
+            } else {
+                if added && is_under(base, path) {
+                    targets.dirs.push((String::new(), true));
+                }
+                continue;
+            };
+            if changed.action == "D" {
+                continue;
+            }
+            if changed.kind == "dir" || rel.is_empty() {
+                targets.dirs.push((rel.to_string(), added));
+            } else {
+                targets.files.push(rel.to_string());
+            }
+        }
+        targets
+    }
+
+    /// Whether `path` is a target file or lies in an added directory.
+    pub fn covers(&self, path: &str) -> bool {
+        self.files.iter().any(|f| f == path)
+            || self
+                .dirs
+                .iter()
+                .any(|(dir, added)| *added && (dir.is_empty() || is_under(path, dir)))
+    }
+}
+
+/// Every property at `rev` of the target files and of everything in the
+/// added directories of `targets`, keyed by path and name. Two lookups at
+/// most, whatever the size of the tree.
+pub async fn fetch_target_props(
+    svn: &SvnClient,
+    targets: &PropTargets,
+    rev: i64,
+) -> Result<BTreeMap<String, BTreeMap<String, String>>, SvnError> {
+    let added: Vec<String> =
+        targets.dirs.iter().filter(|(_, added)| *added).map(|(d, _)| d.clone()).collect();
+    let mut props = Vec::new();
+    if !targets.files.is_empty() {
+        props.extend(svn.proplist(&targets.files, rev, false).await?);
+    }
+    if !added.is_empty() {
+        props.extend(svn.proplist(&added, rev, true).await?);
+    }
+    let mut by_path: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    for prop in props {
+        by_path.entry(prop.path).or_default().insert(prop.name, prop.value.trim().to_string());
+    }
+    Ok(by_path)
+}
+
+/// Bring `entries` up to date with the `svn:eol-style` values in `props`,
+/// fetched for `targets`: entries of the paths they cover are replaced and
+/// those of files no longer in `root` dropped.
+pub fn update_eol_entries(
+    entries: &mut BTreeMap<String, String>,
+    root: &Path,
+    targets: &PropTargets,
+    props: &BTreeMap<String, BTreeMap<String, String>>,
+) {
+    entries.retain(|path, _| !targets.covers(path) && is_file_or_symlink(&root.join(path)));
+    for (path, values) in props {
+        if let Some(style) = values.get(SVN_EOL_STYLE) {
+            if is_file_or_symlink(&root.join(path)) {
+                entries.insert(path.clone(), style.clone());
+            }
+        }
+    }
+}
+
+/// Set the executable bits of the files `targets` covers in `root` from the
+/// `svn:executable` in `props`. Returns the number of files whose mode
+/// changed.
+pub fn update_executable_bits(
+    root: &Path,
+    targets: &PropTargets,
+    props: &BTreeMap<String, BTreeMap<String, String>>,
+) -> std::io::Result<usize> {
+    let executable = |path: &str| props.get(path).is_some_and(|v| v.contains_key(SVN_EXECUTABLE));
+    let mut changed = 0;
+    let paths = targets.files.iter().chain(props.keys().filter(|p| targets.covers(p)));
+    for path in paths.collect::<BTreeSet<_>>() {
+        if set_executable(&root.join(path), executable(path))? {
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// Git attributes matching an `svn:eol-style` value. `CR` has no Git
+/// equivalent.
+pub fn eol_attributes(style: &str) -> Option<&'static str> {
+    match style.trim() {
+        "native" => Some("text"),
+        "LF" => Some("text eol=lf"),
+        "CRLF" => Some("text eol=crlf"),
+        _ => None,
+    }
+}
+
+/// The `svn:eol-style` value matching Git attributes from [`eol_attributes`].
+pub fn eol_style_of(attributes: &str) -> Option<&'static str> {
+    match attributes.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["text"] => Some("native"),
+        ["text", "eol=lf"] => Some("LF"),
+        ["text", "eol=crlf"] => Some("CRLF"),
+        _ => None,
+    }
+}
+
+/// Anchored `.gitattributes` pattern matching exactly the file `path`.
+fn attribute_pattern(path: &str) -> String {
+    let mut pattern = String::from("/");
+    for c in path.chars() {
+        if matches!(c, '*' | '?' | '[' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    if !pattern.contains(|c: char| c.is_whitespace() || c == '"') {
+        return pattern;
+    }
+    // Patterns with whitespace are written C-style quoted.
+    let mut quoted = String::from("\"");
+    for c in pattern.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Split one `.gitattributes` line into its pattern, unquoted if it is
+/// written C-style quoted, and its attributes.
+fn split_attribute_line(line: &str) -> Option<(String, &str)> {
+    if let Some(rest) = line.strip_prefix('"') {
+        let mut pattern = String::new();
+        let mut chars = rest.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (i, '"') => break i + 1,
+                (_, '\\') => match chars.next()?.1 {
+                    't' => pattern.push('\t'),
+                    c => pattern.push(c),
+                },
+                (_, c) => pattern.push(c),
+            }
+        };
+        Some((pattern, &rest[end..]))
+    } else {
+        let (pattern, attributes) = line.split_once(char::is_whitespace)?;
+        Some((pattern.to_string(), attributes))
+    }
+}
+
+/// Split one `.gitattributes` line written by [`write_eol_entries`] into the
+/// file path and its attributes.
+fn parse_attribute_line(line: &str) -> Option<(String, &str)> {
+    let (pattern, attributes) = split_attribute_line(line)?;
+    let mut path = String::new();
+    let mut chars = pattern.strip_prefix('/')?.chars();
+    while let Some(c) = chars.next() {
+        path.push(if c == '\\' { chars.next()? } else { c });
+    }
+    Some((path, attributes.trim()))
+}
+
+/// The `svn:eol-style` of each file listed in the generated block of
+/// `.gitattributes` content.
+pub fn parse_eol_entries(content: &str) -> BTreeMap<String, String> {
+    content
+        .lines()
+        .skip_while(|line| *line != EOL_BLOCK_BEGIN)
+        .skip(1)
+        .take_while(|line| *line != EOL_BLOCK_END)
+        .filter_map(parse_attribute_line)
+        .filter_map(|(path, attributes)| Some((path, eol_style_of(attributes)?.to_string())))
+        .collect()
+}
+
+/// Whether the `.gitattributes` `pattern` matches the file `path`: patterns
+/// without a `/` match the file name at any depth, the others the path
+/// from the repository root.
+fn attribute_pattern_matches(pattern: &str, path: &str) -> bool {
+    if pattern.ends_with('/') {
+        return false;
+    }
+    match pattern.trim_start_matches('/') {
+        anchored if pattern.contains('/') => glob_match::glob_match(anchored, path),
+        name => glob_match::glob_match(name, path.rsplit('/').next().unwrap_or(path)),
+    }
+}
+
+/// The `svn:eol-style` that the `.gitattributes` `content` gives `path`:
+/// `native` for `text`, `LF`/`CRLF` for `eol=lf`/`eol=crlf`. `None` when no
+/// rule marks the path as text or a rule marks it `-text`/`binary`; Git
+/// leaves the line endings of such files alone, and so does SVN.
+pub fn gitattributes_eol_style(content: &str, path: &str) -> Option<&'static str> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Text {
+        Unspecified,
+        Set,
+        Unset,
+    }
+    let mut text = Text::Unspecified;
+    let mut eol = None;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("[attr]") {
+            continue;
+        }
+        let Some((pattern, attributes)) = split_attribute_line(line) else {
+            continue;
+        };
+        if !attribute_pattern_matches(&pattern, path) {
+            continue;
+        }
+        for attribute in attributes.split_whitespace() {
+            match attribute {
+                "text" => text = Text::Set,
+                "-text" | "binary" => text = Text::Unset,
+                "!text" => text = Text::Unspecified,
+                "eol=lf" => eol = Some("LF"),
+                "eol=crlf" => eol = Some("CRLF"),
+                "-eol" | "!eol" => eol = None,
+                _ if attribute.starts_with("text=") => text = Text::Unspecified,
+                _ => {}
+            }
+        }
+    }
+    match (text, eol) {
+        (Text::Unset, _) => None,
+        (_, Some(style)) => Some(style),
+        (Text::Set, None) => Some("native"),
+        (Text::Unspecified, None) => None,
+    }
+}
+
+/// The `svn:eol-style` entries of `<root>/.gitattributes`.
+pub fn read_eol_entries(root: &Path) -> BTreeMap<String, String> {
+    std::fs::read_to_string(root.join(GITATTRIBUTES))
+        .map(|content| parse_eol_entries(&content))
+        .unwrap_or_default()
+}
+
+/// `.gitattributes` content with its generated block replaced by `entries`
+/// (path to `svn:eol-style`); every other line is kept.
+pub fn render_eol_entries(existing: &str, entries: &BTreeMap<String, String>) -> String {
+    let mut content = String::new();
+    let mut in_block = false;
+    for line in existing.lines() {
+        match line {
+            EOL_BLOCK_BEGIN => in_block = true,
+            EOL_BLOCK_END if in_block => in_block = false,
+            _ if in_block => {}
+            _ => {
+                content.push_str(line);
+                content.push('\n');
+            }
+        }
+    }
+    let lines: Vec<String> = entries
+        .iter()
+        .filter_map(|(file, style)| {
+            Some(format!("{} {}\n", attribute_pattern(file), eol_attributes(style)?))
+        })
+        .collect();
+    if !lines.is_empty() {
+        content.push_str(EOL_BLOCK_BEGIN);
+        content.push('\n');
+        content.extend(lines);
+        content.push_str(EOL_BLOCK_END);
+        content.push('\n');
+    }
+    content
+}
+
+/// Replace the generated block of `<root>/.gitattributes` with `entries`
+/// (path to `svn:eol-style`), keeping every other line. The file is removed
+/// when nothing else is left in it.
+pub fn write_eol_entries(
+    root: &Path,
+    entries: &BTreeMap<String, String>,
+) -> std::io::Result<FileUpdate> {
+    let path = root.join(GITATTRIBUTES);
+    let existing = std::fs::read_to_string(&path).ok();
+    let content = render_eol_entries(existing.as_deref().unwrap_or(""), entries);
+    if existing.as_deref() == Some(content.as_str()) {
+        return Ok(FileUpdate::Unchanged);
+    }
+    if content.trim().is_empty() {
+        if existing.is_none() {
+            return Ok(FileUpdate::Unchanged);
+        }
+        std::fs::remove_file(&path)?;
+        debug!(path = %path.display(), "removed .gitattributes");
+        return Ok(FileUpdate::Removed);
+    }
+    std::fs::write(&path, content)?;
+    debug!(path = %path.display(), entries = entries.len(), "updated svn:eol-style attributes");
+    Ok(FileUpdate::Written)
+}
+
+/// Files whose `svn:eol-style` entry differs between `old` and `new`, with
+/// the new style (`None` if the entry was removed).
+pub fn eol_entry_changes(
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) -> Vec<(String, Option<String>)> {
+    let removed = old
+        .keys()
+        .filter(|path| !new.contains_key(*path))
+        .map(|path| (path.clone(), None));
+    let changed = new
+        .iter()
+        .filter(|(path, style)| old.get(*path) != Some(*style))
+        .map(|(path, style)| (path.clone(), Some(style.clone())));
+    removed.chain(changed).collect()
+}
+
+/// Convert CRLF line endings to LF.
+pub fn normalize_eol(text: &str) -> Cow<'_, str> {
+    if text.contains("\r\n") {
+        Cow::Owned(text.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Whether `content` uses a single line ending throughout, so SVN accepts
+/// an `svn:eol-style` on it.
+pub fn has_consistent_eol(content: &[u8]) -> bool {
+    let crlf = content.windows(2).filter(|w| w == b"\r\n").count();
+    let cr = content.iter().filter(|&&b| b == b'\r').count();
+    let lf = content.iter().filter(|&&b| b == b'\n').count();
+    cr == crlf && (crlf == 0 || crlf == lf)
+}
+
+/// Every keyword name enabled by an `svn:keywords` value, aliases included.
+fn keyword_names(keywords: &str) -> Vec<&'static str> {
+    keywords
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|k| !k.is_empty())
+        .filter_map(|k| {
+            // Custom keywords (`Name=format`) are not collapsed.
+            KEYWORD_GROUPS
+                .iter()
+                .find(|group| group.iter().any(|name| name.eq_ignore_ascii_case(k)))
+        })
+        .flat_map(|group| group.iter().copied())
+        .collect()
+}
+
+/// Collapse the keyword expansion starting at `s[0] == '$'`, returning the
+/// length of the expansion and its collapsed form.
+fn collapse_keyword_at(s: &[u8], names: &[&str]) -> Option<(usize, Vec<u8>)> {
+    let name = names.iter().find(|name| {
+        s[1..].starts_with(name.as_bytes()) && s.get(1 + name.len()) == Some(&b':')
+    })?;
+    let start = name.len() + 2;
+    let end = start + s[start..].iter().position(|&b| matches!(b, b'$' | b'\n' | b'\r'))?;
+    if s[end] != b'$' {
+        return None;
+    }
+    let value = &s[start..end];
+    let collapsed = if let Some(fixed) = value.strip_prefix(b":") {
+        // Fixed-width form `$Name:: value  $` keeps its width.
+        if !fixed.starts_with(b" ") {
+            return None;
+        }
+        let mut collapsed = format!("${}::", name).into_bytes();
+        collapsed.resize(collapsed.len() + fixed.len(), b' ');
+        collapsed.push(b'$');
+        collapsed
+    } else if value.starts_with(b" ") && value.ends_with(b" ") {
+        format!("${}$", name).into_bytes()
+    } else {
+        return None;
+    };
+    Some((end + 1, collapsed))
+}
+
+/// Collapse the expanded forms of the keywords in `keywords` (an
+/// `svn:keywords` value) back to `$Name$`. Returns `None` when nothing
+/// changed.
+pub fn collapse_keywords(content: &[u8], keywords: &str) -> Option<Vec<u8>> {
+    let names = keyword_names(keywords);
+    if names.is_empty() || !content.contains(&b'$') {
+        return None;
+    }
+    let mut out = Vec::with_capacity(content.len());
+    let mut changed = false;
+    let mut i = 0;
+    while i < content.len() {
+        if content[i] == b'$' {
+            if let Some((len, collapsed)) = collapse_keyword_at(&content[i..], &names) {
+                changed |= collapsed[..] != content[i..i + len];
+                out.extend_from_slice(&collapsed);
+                i += len;
+                continue;
+            }
+        }
+        out.push(content[i]);
+        i += 1;
+    }
+    changed.then_some(out)
+}
+
+/// Collapse every keyword SVN knows, whatever the file's `svn:keywords`.
+pub fn collapse_all_keywords(content: &[u8]) -> Option<Vec<u8>> {
+    collapse_keywords(content, "Date Revision Author HeadURL Id Header")
+}
+
+/// `text`, compared and merged with LF line endings and collapsed keywords,
+/// put back into the form of `original`: CRLF line endings if `original`
+/// uses them throughout, and each keyword expanded as it is in `original`.
+pub fn restore_text_form(text: &str, original: &[u8]) -> Vec<u8> {
+    let names = keyword_names("Date Revision Author HeadURL Id Header");
+    let mut expansions: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+    let mut i = 0;
+    while i < original.len() {
+        if original[i] == b'$' {
+            if let Some((len, collapsed)) = collapse_keyword_at(&original[i..], &names) {
+                let expanded = &original[i..i + len];
+                // A literal collapsed form in `original` must stay collapsed.
+                let literal = original.windows(collapsed.len()).any(|w| w == collapsed);
+                if collapsed[..] != *expanded
+                    && !literal
+                    && !expansions.iter().any(|(c, _)| *c == collapsed)
+                {
+                    expansions.push((collapsed, expanded.to_vec()));
+                }
+                i += len;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    let mut out = text.as_bytes().to_vec();
+    for (collapsed, expanded) in &expansions {
+        let mut replaced = Vec::with_capacity(out.len());
+        let mut i = 0;
+        while i < out.len() {
+            if out[i..].starts_with(collapsed) {
+                replaced.extend_from_slice(expanded);
+                i += collapsed.len();
+            } else {
+                replaced.push(out[i]);
+                i += 1;
+            }
+        }
+        out = replaced;
+    }
+
+    let crlf = original.windows(2).any(|w| w == b"\r\n");
+    if crlf && has_consistent_eol(original) {
+        let mut converted = Vec::with_capacity(out.len() + out.len() / 32);
+        for (i, &b) in out.iter().enumerate() {
+            if b == b'\n' && (i == 0 || out[i - 1] != b'\r') {
+                converted.push(b'\r');
+            }
+            converted.push(b);
+        }
+        out = converted;
+    }
+    out
+}
+
+/// An `svn:keywords` value for the collapsed keywords (`$Id$`, `$Rev::  $`)
+/// in `content`, or `None` if it has none.
+pub fn keywords_in(content: &[u8]) -> Option<String> {
+    let found: Vec<&str> = KEYWORD_GROUPS
+        .iter()
+        .flat_map(|group| group.iter().copied())
+        .filter(|name| {
+            let plain = format!("${}$", name);
+            let fixed = format!("${}:: ", name);
+            [plain, fixed]
+                .iter()
+                .any(|needle| content.windows(needle.len()).any(|w| w == needle.as_bytes()))
+        })
+        .collect();
+    (!found.is_empty()).then(|| found.join(" "))
+}
+
+/// `svn:eol-style` and `svn:keywords` for a text file newly added from Git:
+/// the style a `.gitattributes` rule gives it (none if no rule marks it as
+/// text, see [`gitattributes_eol_style`]), and the keywords it contains in
+/// collapsed form.
+pub fn new_file_props(
+    path: &str,
+    content: &[u8],
+    gitattributes: &str,
+    eol_style: bool,
+    keywords: bool,
+) -> Vec<(&'static str, String)> {
+    let mut props = Vec::new();
+    if crate::conflict::detector::is_binary_content(content) {
+        return props;
+    }
+    if eol_style && has_consistent_eol(content) {
+        if let Some(style) = gitattributes_eol_style(gitattributes, path) {
+            props.push((SVN_EOL_STYLE, style.to_string()));
+        }
+    }
+    if let Some(value) = keywords.then(|| keywords_in(content)).flatten() {
+        props.push((SVN_KEYWORDS, value));
+    }
+    props
+}
+
+/// Files at or below `path` with `svn:executable` set at `rev`, relative
+/// to `path` (`""` for `path` itself).
+pub async fn fetch_executables(
+    svn: &SvnClient,
+    path: &str,
+    rev: i64,
+    recursive: bool,
+) -> Result<BTreeSet<String>, SvnError> {
+    let props = svn.propget(path, SVN_EXECUTABLE, rev, recursive).await?;
+    Ok(props.into_iter().map(|p| p.path).collect())
+}
+
+/// Whether the file at `path` has any executable bit set.
+#[cfg(unix)]
+pub fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::symlink_metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+/// Whether the file at `path` has any executable bit set.
+#[cfg(not(unix))]
+pub fn is_executable(_path: &Path) -> bool {
+    false
+}
+
+/// Set or clear the executable bits of the file at `path`, following its
+/// read bits like `chmod +x` does. Returns whether the mode changed.
+#[cfg(unix)]
+pub fn set_executable(path: &Path, executable: bool) -> std::io::Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+    let meta = std::fs::symlink_metadata(path)?;
+    if !meta.is_file() {
+        return Ok(false);
+    }
+    let mode = meta.permissions().mode();
+    let new_mode = if executable {
+        mode | (mode & 0o444) >> 2
+    } else {
+        mode & !0o111
+    };
+    if new_mode == mode {
+        return Ok(false);
+    }
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(new_mode))?;
+    Ok(true)
+}
+
+/// Set or clear the executable bits of the file at `path`. File modes are
+/// not tracked on this platform, so this never changes anything.
+#[cfg(not(unix))]
+pub fn set_executable(_path: &Path, _executable: bool) -> std::io::Result<bool> {
+    Ok(false)
+}
+
+/// Make exactly the files listed in `executables` (relative to `root`)
+/// executable, walking the whole tree except `.git`. Returns the number of
+/// files whose mode changed.
+pub fn sync_executable_bits(
+    root: &Path,
+    executables: &BTreeSet<String>,
+) -> std::io::Result<usize> {
+    fn walk(
+        dir: &Path,
+        rel: &str,
+        executables: &BTreeSet<String>,
+        changed: &mut usize,
+    ) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if rel.is_empty() && name == ".git" {
+                continue;
+            }
+            let path = join_path(rel, &name);
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                walk(&entry.path(), &path, executables, changed)?;
+            } else if file_type.is_file()
+                && set_executable(&entry.path(), executables.contains(&path))?
+            {
+                *changed += 1;
+            }
+        }
+        Ok(())
+    }
+    let mut changed = 0;
+    walk(root, "", executables, &mut changed)?;
+    Ok(changed)
+}
+
+/// Set or delete `svn:executable` on the working-copy file `target`
+/// (relative to `wc`).
+pub async fn apply_executable(
+    svn: &SvnClient,
+    wc: &Path,
+    target: &str,
+    executable: bool,
+) -> Result<(), SvnError> {
+    if executable {
+        svn.propset(wc, SVN_EXECUTABLE, "*", target).await
+    } else {
+        svn.propdel(wc, SVN_EXECUTABLE, target).await
+    }
+}
+
+/// The `svn:mergeinfo` value of directory `path` at `rev`, empty when unset.
+pub async fn fetch_mergeinfo(svn: &SvnClient, path: &str, rev: i64) -> Result<String, SvnError> {
+    let props = svn.propget(path, SVN_MERGEINFO, rev, false).await?;
+    Ok(props.into_iter().find(|p| p.path.is_empty()).map(|p| p.value).unwrap_or_default())
+}
+
+/// Parse an `svn:mergeinfo` value into the newest merged revision of each
+/// source path (without the leading `/`).
+///
+/// Each line is `/path:ranges` where ranges look like `5-10*,12`; the `*`
+/// marks non-inheritable ranges and does not matter here.
+pub fn parse_mergeinfo(value: &str) -> BTreeMap<String, i64> {
+    let mut merged = BTreeMap::new();
+    for line in value.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Some((path, ranges)) = line.rsplit_once(':') else {
+            continue;
+        };
+        let newest = ranges
+            .split(',')
+            .filter_map(|range| {
+                let range = range.trim().trim_end_matches('*');
+                range.rsplit('-').next()?.parse::<i64>().ok()
+            })
+            .max();
+        if let Some(rev) = newest {
+            let path = path.trim_start_matches('/').to_string();
+            let entry = merged.entry(path).or_insert(rev);
+            *entry = (*entry).max(rev);
+        }
+    }
+    merged
+}
+
+/// Sources whose newest merged revision grew between the `old` and `new`
+/// `svn:mergeinfo` values, with that revision.
+pub fn new_merges(old: &str, new: &str) -> Vec<(String, i64)> {
+    let old = parse_mergeinfo(old);
+    parse_mergeinfo(new)
+        .into_iter()
+        .filter(|(path, rev)| old.get(path).is_none_or(|prev| rev > prev))
+        .collect()
+}
+
+/// Target of a symlink from the content SVN stores for it (`link <target>`,
+/// as returned by `svn cat` or `svn diff`).
+pub fn parse_special(content: &[u8]) -> Option<String> {
+    let content = std::str::from_utf8(content).ok()?;
+    let target = content.strip_prefix(SVN_LINK_PREFIX)?.trim_end_matches(['\r', '\n']);
+    (!target.is_empty() && !target.contains('\n')).then(|| target.to_string())
+}
+
+/// Whether `path` itself is a symlink (without following it).
+pub fn is_symlink(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// Whether `path` is a regular file or a symlink, dangling or not.
+pub fn is_file_or_symlink(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|m| !m.is_dir())
+}
+
+/// Replace whatever is at `path` with a symlink to `target`, creating
+/// parent directories as needed.
+pub fn write_symlink(path: &Path, target: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    remove_file_or_symlink(path)?;
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, path)
+    }
+    // Without symlinks, keep the target as file content like Git does.
+    #[cfg(not(unix))]
+    {
+        std::fs::write(path, target)
+    }
+}
+
+/// Write `content` to `path` as a regular file, replacing a symlink there
+/// instead of writing through it.
+pub fn write_regular_file(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    if is_symlink(path) {
+        std::fs::remove_file(path)?;
+    }
+    std::fs::write(path, content)
+}
+
+/// Copy the symlink at `src` to `dst` without following it.
+pub fn copy_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let target = std::fs::read_link(src)?;
+    write_symlink(dst, &target.to_string_lossy())
+}
+
+/// Remove a file or symlink at `path`, if any.
+fn remove_file_or_symlink(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Whether a symlink at `link_path` (relative to the repository root)
+/// pointing at `target` resolves outside the repository.
+pub fn symlink_escapes(link_path: &str, target: &str) -> bool {
+    if target.starts_with('/') || target.starts_with('\\') || Path::new(target).is_absolute() {
+        return true;
+    }
+    let mut depth = parent_dir(link_path).split('/').filter(|c| !c.is_empty()).count();
+    for component in target.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." if depth == 0 => return true,
+            ".." => depth -= 1,
+            _ => depth += 1,
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitignore_round_trip() {
+        let props = IgnoreProps::from_values(Some("build\n*.o\n\n"), Some("*.log  .cache"));
+        assert_eq!(props.ignore, vec!["build", "*.o"]);
+        assert_eq!(props.global_ignores, vec!["*.log", ".cache"]);
+
+        let gitignore = render_gitignore("*.swp\n", &props);
+        assert!(gitignore.starts_with("*.swp\n# BEGIN svn:ignore"));
+        assert!(gitignore.ends_with("/build\n/*.o\n*.log\n.cache\n# END svn:ignore\n"));
+
+        // Only the generated block maps to the properties.
+        let (parsed, unsupported) = IgnoreProps::from_gitignore(&gitignore);
+        assert_eq!(parsed, props);
+        assert!(unsupported.is_empty());
+        assert_eq!(parsed.svn_ignore_value(), "build\n*.o\n");
+        assert_eq!(render_gitignore(&gitignore, &IgnoreProps::default()), "*.swp\n");
+
+        // Files generated before the block are replaced as a whole.
+        let legacy = format!("{}/build\n", LEGACY_GITIGNORE_HEADER);
+        assert_eq!(IgnoreProps::from_gitignore(&legacy).0.ignore, vec!["build"]);
+        assert_eq!(render_gitignore(&legacy, &props), props.to_gitignore());
+    }
+
+    #[test]
+    fn test_from_gitignore_unsupported_lines() {
+        let content = format!(
+            "{}\n# comment\ntarget/\n**/*.tmp\n!keep.tmp\ndocs/out\n{}\n",
+            IGNORE_BLOCK_BEGIN, IGNORE_BLOCK_END
+        );
+        let (props, unsupported) = IgnoreProps::from_gitignore(&content);
+        assert!(props.ignore.is_empty());
+        assert_eq!(props.global_ignores, vec!["target", "*.tmp"]);
+        assert_eq!(unsupported, vec!["!keep.tmp", "docs/out"]);
+    }
+
+    #[test]
+    fn test_gitignore_paths() {
+        assert!(is_gitignore(".gitignore"));
+        assert!(is_gitignore("src/.gitignore"));
+        assert!(!is_gitignore("src/not.gitignore"));
+        assert_eq!(parent_dir("src/lib/.gitignore"), "src/lib");
+        assert_eq!(parent_dir(".gitignore"), "");
+    }
+
+    #[test]
+    fn test_parse_externals_both_formats() {
+        let value = "# shared code\n\
+                     -r 120 ^/shared/lib@100 third_party/lib\n\
+                     https://svn.example.com/other/trunk/util util\n\
+                     legacy -r5 http://svn.example.com/old/trunk\n\
+                     not-an-external\n";
+        let externals = parse_externals(value);
+        assert_eq!(externals.len(), 3);
+        assert_eq!(
+            externals[0],
+            SvnExternal {
+                path: "third_party/lib".into(),
+                url: "^/shared/lib".into(),
+                peg_rev: Some(100),
+                rev: Some(120),
+            }
+        );
+        assert_eq!(externals[0].pinned_rev(), Some(120));
+        assert_eq!(externals[1].path, "util");
+        assert_eq!(externals[1].pinned_rev(), None);
+        assert_eq!(externals[2].path, "legacy");
+        assert_eq!(externals[2].url, "http://svn.example.com/old/trunk");
+        assert_eq!(externals[2].rev, Some(5));
+    }
+
+    #[test]
+    fn test_resolve_external_urls() {
+        let dir_url = "https://svn.example.com/repos/app/trunk/src";
+        let root = "https://svn.example.com/repos/app";
+        let resolve = |url: &str| {
+            SvnExternal {
+                path: "x".into(),
+                url: url.into(),
+                peg_rev: None,
+                rev: None,
+            }
+            .resolve_url(dir_url, root)
+        };
+        assert_eq!(resolve("^/shared/lib"), "https://svn.example.com/repos/app/shared/lib");
+        assert_eq!(resolve("../../lib"), "https://svn.example.com/repos/app/lib");
+        assert_eq!(resolve("/repos/other"), "https://svn.example.com/repos/other");
+        assert_eq!(resolve("//mirror.example.com/x"), "https://mirror.example.com/x");
+        assert_eq!(resolve("svn://host/abs"), "svn://host/abs");
+    }
+
+    #[test]
+    fn test_svnexternals_round_trip() {
+        let value = "^/shared/lib third_party/lib\n-r 7 ^/util util\n";
+        let content = to_svnexternals(value);
+        assert_eq!(from_svnexternals(&content), value);
+        assert_eq!(
+            external_targets("src", &content),
+            vec!["src/third_party/lib", "src/util"]
+        );
+        assert!(is_svnexternals("src/.svnexternals"));
+        assert!(is_under("src/util/a.c", "src/util"));
+        assert!(!is_under("src/utility.c", "src/util"));
+
+        let roots = external_roots(["src/util/x.c", "README"], |path| {
+            (path == "src/.svnexternals").then(|| content.clone())
+        });
+        assert_eq!(roots, vec!["src/third_party/lib", "src/util"]);
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(write_svnexternals(dir.path(), "src", value).unwrap(), FileUpdate::Written);
+        assert_eq!(write_svnexternals(dir.path(), "src", value).unwrap(), FileUpdate::Unchanged);
+        assert_eq!(write_svnexternals(dir.path(), "src", "").unwrap(), FileUpdate::Removed);
+    }
+
+    #[test]
+    fn test_write_gitignore_keeps_hand_written_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let props = IgnoreProps::from_values(Some("out"), None);
+
+        assert_eq!(
+            write_gitignore(dir.path(), "sub", &props).unwrap(),
+            FileUpdate::Written
+        );
+        assert_eq!(
+            write_gitignore(dir.path(), "sub", &props).unwrap(),
+            FileUpdate::Unchanged
+        );
+        assert_eq!(
+            write_gitignore(dir.path(), "sub", &IgnoreProps::default()).unwrap(),
+            FileUpdate::Removed
+        );
+        assert!(!dir.path().join("sub/.gitignore").exists());
+
+        std::fs::write(dir.path().join(GITIGNORE), "*.swp\n").unwrap();
+        assert_eq!(
+            write_gitignore(dir.path(), "", &IgnoreProps::default()).unwrap(),
+            FileUpdate::Unchanged
+        );
+        assert_eq!(write_gitignore(dir.path(), "", &props).unwrap(), FileUpdate::Written);
+        assert_eq!(
+            write_gitignore(dir.path(), "", &IgnoreProps::default()).unwrap(),
+            FileUpdate::Written
+        );
+        let content = std::fs::read_to_string(dir.path().join(GITIGNORE)).unwrap();
+        assert_eq!(content, "*.swp\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sync_executable_bits() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("bin")).unwrap();
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        for file in ["bin/run.sh", "README", ".git/hook"] {
+            std::fs::write(dir.path().join(file), "x").unwrap();
+            std::fs::set_permissions(
+                dir.path().join(file),
+                std::fs::Permissions::from_mode(0o644),
+            )
+            .unwrap();
+        }
+        std::fs::set_permissions(
+            dir.path().join("README"),
+            std::fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+
+        let executables = BTreeSet::from(["bin/run.sh".to_string()]);
+        assert_eq!(sync_executable_bits(dir.path(), &executables).unwrap(), 2);
+        let mode = |f: &str| {
+            std::fs::metadata(dir.path().join(f)).unwrap().permissions().mode() & 0o777
+        };
+        assert_eq!(mode("bin/run.sh"), 0o755);
+        assert_eq!(mode("README"), 0o644);
+        assert_eq!(mode(".git/hook"), 0o644);
+        assert_eq!(sync_executable_bits(dir.path(), &executables).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_prop_targets_from_log() {
+        let changed = |action: &str, kind: &str, path: &str| SvnChangedPath {
+            action: action.into(),
+            path: path.into(),
+            kind: kind.into(),
+            copy_from_path: None,
+            copy_from_rev: None,
+        };
+        let targets = PropTargets::from_log(
+            &[
+                changed("M", "file", "/trunk/src/main.rs"),
+                changed("A", "dir", "/trunk/lib"),
+                changed("M", "dir", "/trunk"),
+                changed("D", "file", "/trunk/old.rs"),
+                changed("M", "file", "/branches/x/a.rs"),
+            ],
+            "trunk",
+        );
+        assert_eq!(targets.files, vec!["src/main.rs"]);
+        assert_eq!(targets.dirs, vec![("lib".to_string(), true), (String::new(), false)]);
+        assert!(targets.covers("src/main.rs"));
+        assert!(targets.covers("lib/x/y.rs"));
+        assert!(!targets.covers("README"));
+
+        let copied = PropTargets::from_log(&[changed("A", "dir", "/project")], "project/trunk");
+        assert_eq!(copied.dirs, vec![(String::new(), true)]);
+        assert!(copied.covers("README"));
+        assert!(PropTargets::from_log(&[changed("M", "dir", "/")], "trunk").dirs.is_empty());
+    }
+
+    #[test]
+    fn test_update_eol_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(dir.path().join(file), "x").unwrap();
+        }
+        let mut entries = BTreeMap::from([
+            ("a.txt".to_string(), "native".to_string()),
+            ("b.txt".to_string(), "CRLF".to_string()),
+            ("gone.txt".to_string(), "LF".to_string()),
+        ]);
+        let targets = PropTargets {
+            files: vec!["b.txt".into(), "c.txt".into()],
+            dirs: Vec::new(),
+        };
+        let props = BTreeMap::from([(
+            "c.txt".to_string(),
+            BTreeMap::from([(SVN_EOL_STYLE.to_string(), "LF".to_string())]),
+        )]);
+        update_eol_entries(&mut entries, dir.path(), &targets, &props);
+        assert_eq!(
+            entries,
+            BTreeMap::from([
+                ("a.txt".to_string(), "native".to_string()),
+                ("c.txt".to_string(), "LF".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_collapse_keywords() {
+        let content =
+            b"// $Id: a.c 12 2024-01-01 alice $\n// $Rev:: 12     $ $Author$ $Date: x $\n";
+        let collapsed = collapse_keywords(content, "Id Revision").unwrap();
+        assert_eq!(
+            String::from_utf8(collapsed).unwrap(),
+            "// $Id$\n// $Rev::        $ $Author$ $Date: x $\n"
+        );
+        assert_eq!(collapse_keywords(b"$Id$ costs $5: ok $", "Id"), None);
+        assert_eq!(collapse_keywords(content, "MyKw=%a"), None);
+        assert!(collapse_all_keywords(content).is_some());
+
+        assert_eq!(keywords_in(b"$Id$ and $Rev::   $").as_deref(), Some("Rev Id"));
+        assert_eq!(keywords_in(b"no keywords"), None);
+    }
+
+    #[test]
+    fn test_restore_text_form() {
+        let original = b"$Id: a.c 7 jdoe $\r\none\r\n";
+        assert_eq!(
+            restore_text_form("$Id$\none\ntwo\n", original),
+            b"$Id: a.c 7 jdoe $\r\none\r\ntwo\r\n".to_vec()
+        );
+        // Plain LF text with a literal collapsed keyword is left alone.
+        assert_eq!(restore_text_form("$Id$\nx\n", b"$Id$\n"), b"$Id$\nx\n".to_vec());
+        // Mixed line endings in the original are not imposed on the result.
+        assert_eq!(restore_text_form("a\nb\n", b"a\r\nb\n"), b"a\nb\n".to_vec());
+    }
+
+    #[test]
+    fn test_eol_entries_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(GITATTRIBUTES), "*.bin filter=lfs\n").unwrap();
+        let entries: BTreeMap<String, String> = [
+            ("src/a.c", "native"),
+            ("win/b c.bat", "CRLF"),
+            ("x[1]*.sh", "LF"),
+            ("old.mac", "CR"),
+        ]
+        .into_iter()
+        .map(|(p, s)| (p.to_string(), s.to_string()))
+        .collect();
+
+        assert_eq!(write_eol_entries(dir.path(), &entries).unwrap(), FileUpdate::Written);
+        let content = std::fs::read_to_string(dir.path().join(GITATTRIBUTES)).unwrap();
+        assert!(content.starts_with("*.bin filter=lfs\n"));
+        assert!(content.contains("/src/a.c text\n"));
+        assert!(content.contains("\"/win/b c.bat\" text eol=crlf\n"));
+        assert!(content.contains("/x\\[1]\\*.sh text eol=lf\n"));
+
+        let mut expected = entries.clone();
+        expected.remove("old.mac");
+        assert_eq!(read_eol_entries(dir.path()), expected);
+        let mut edited = expected.clone();
+        edited.insert("src/a.c".to_string(), "LF".to_string());
+        edited.remove("win/b c.bat");
+        assert_eq!(
+            eol_entry_changes(&expected, &edited),
+            vec![
+                ("win/b c.bat".to_string(), None),
+                ("src/a.c".to_string(), Some("LF".to_string())),
+            ]
+        );
+        assert_eq!(write_eol_entries(dir.path(), &entries).unwrap(), FileUpdate::Unchanged);
+
+        assert_eq!(
+            write_eol_entries(dir.path(), &BTreeMap::new()).unwrap(),
+            FileUpdate::Written
+        );
+        let content = std::fs::read_to_string(dir.path().join(GITATTRIBUTES)).unwrap();
+        assert_eq!(content, "*.bin filter=lfs\n");
+    }
+
+    #[test]
+    fn test_new_file_props() {
+        let entries = BTreeMap::from([("run.bat".to_string(), "CRLF".to_string())]);
+        let attributes = render_eol_entries("*.c text\n", &entries);
+        assert_eq!(
+            new_file_props("run.bat", b"@echo off\r\n", &attributes, true, true),
+            vec![(SVN_EOL_STYLE, "CRLF".to_string())]
+        );
+        assert_eq!(
+            new_file_props("src/a.c", b"/* $Id$ */\n", &attributes, true, true),
+            vec![(SVN_EOL_STYLE, "native".to_string()), (SVN_KEYWORDS, "Id".to_string())]
+        );
+        assert!(new_file_props("notes.txt", b"a\nb\n", &attributes, true, false).is_empty());
+        assert!(new_file_props("mixed.c", b"a\r\nb\n", &attributes, true, false).is_empty());
+        assert!(new_file_props("a.png", b"\x89PNG\0", &attributes, true, true).is_empty());
+        assert_eq!(normalize_eol("a\r\nb\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn test_gitattributes_eol_style() {
+        let attributes = "# line endings\n\
+                          *.sh text eol=lf\n\
+                          /docs/*.md text\n\
+                          *.bat eol=crlf\n\
+                          vendor/** -text\n\
+                          *.txt text=auto\n\
+                          \"/my file.sh\" -text\n";
+        assert_eq!(gitattributes_eol_style(attributes, "bin/run.sh"), Some("LF"));
+        assert_eq!(gitattributes_eol_style(attributes, "docs/a.md"), Some("native"));
+        assert_eq!(gitattributes_eol_style(attributes, "src/docs/a.md"), None);
+        assert_eq!(gitattributes_eol_style(attributes, "run.bat"), Some("CRLF"));
+        assert_eq!(gitattributes_eol_style(attributes, "vendor/lib/x.sh"), None);
+        assert_eq!(gitattributes_eol_style(attributes, "my file.sh"), None);
+        assert_eq!(gitattributes_eol_style(attributes, "readme.txt"), None);
+        assert_eq!(gitattributes_eol_style(attributes, "main.rs"), None);
+        assert_eq!(gitattributes_eol_style("", "main.rs"), None);
+    }
+
+    #[test]
+    fn test_parse_mergeinfo() {
+        let merged = parse_mergeinfo("/branches/feature:5-10*,12\n/trunk:3\n\n/bad:x\n");
+        assert_eq!(
+            merged,
+            BTreeMap::from([("branches/feature".to_string(), 12), ("trunk".to_string(), 3)])
+        );
+
+        let old = "/branches/feature:5-10\n/branches/other:4";
+        let new = "/branches/feature:5-14\n/branches/other:4\n/branches/new:20-21";
+        assert_eq!(
+            new_merges(old, new),
+            vec![("branches/feature".to_string(), 14), ("branches/new".to_string(), 21)]
+        );
+        assert!(new_merges(new, new).is_empty());
+    }
+
+    #[test]
+    fn test_symlinks() {
+        assert_eq!(parse_special(b"link ../shared/app.yml"), Some("../shared/app.yml".into()));
+        assert_eq!(parse_special(b"link target\n"), Some("target".into()));
+        assert_eq!(parse_special(b"plain text"), None);
+        assert_eq!(parse_special(b"link "), None);
+
+        assert!(!symlink_escapes("conf/app.yml", "../shared/app.yml"));
+        assert!(!symlink_escapes("a/b/link", "./c/../../d"));
+        assert!(symlink_escapes("conf/app.yml", "../../etc/passwd"));
+        assert!(symlink_escapes("link", "/etc/passwd"));
+        assert!(symlink_escapes("link", ".."));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_symlink_replaces_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let link = dir.path().join("sub/link");
+        std::fs::create_dir_all(dir.path().join("sub")).unwrap();
+        std::fs::write(&link, "link ../target").unwrap();
+        std::fs::write(dir.path().join("target"), "data").unwrap();
+
+        write_symlink(&link, "../target").unwrap();
+        assert!(is_symlink(&link));
+        assert_eq!(std::fs::read_link(&link).unwrap(), Path::new("../target"));
+
+        let copy = dir.path().join("copy");
+        copy_symlink(&link, &copy).unwrap();
+        assert_eq!(std::fs::read_link(&copy).unwrap(), Path::new("../target"));
+
+        // Writing a regular file replaces the link, not its target.
+        write_regular_file(&link, b"own content").unwrap();
+        assert!(!is_symlink(&link));
+        assert_eq!(std::fs::read_to_string(dir.path().join("target")).unwrap(), "data");
+        assert!(is_file_or_symlink(&copy) && !is_file_or_symlink(dir.path()));
+    }
+}
@@ -11,6 +11,7 @@
 //!
 //! A lock mechanism prevents concurrent sync cycles.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
@@ -18,16 +19,22 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
-use crate::config::{AppConfig, SvnLayout};
-use crate::conflict::detector::{ChangeKind, ConflictDetector, FileChange};
+use crate::config::{AppConfig, SvnLayout, SyncMode};
+use crate::conflict::detector::{is_binary_content, ChangeKind, ConflictDetector, FileChange};
 use crate::conflict::merger::Merger;
+use crate::conflict::resolver::{ConflictResolver, Resolution};
 use crate::conflict::Conflict;
 use crate::db::Database;
-use crate::errors::SyncError;
+use crate::errors::{ConflictError, GitError, SyncError, SvnError};
+use crate::file_policy::FilePolicy;
 use crate::git::client::GitClient;
+use crate::git::github::{ChecksState, GitHubClient, PullRequest};
 use crate::identity::IdentityMapper;
-use crate::models::AuditEntry;
+use crate::models::{AuditEntry, Repository};
+use crate::notify::Notifier;
+use crate::ref_map::{map_git_to_svn, map_svn_to_git, RefMapping};
 use crate::svn::client::SvnClient;
+use crate::svn_props::{self, FileUpdate, IgnoreProps, SvnExternal};
 
 // ---------------------------------------------------------------------------
 // Sync state machine
@@ -67,10 +74,21 @@ pub struct SyncStats {
     pub git_to_svn_count: usize,
     pub conflicts_detected: usize,
     pub conflicts_auto_resolved: usize,
+    /// Tags created on either side.
+    pub tags_synced: usize,
     pub started_at: String,
     pub completed_at: Option<String>,
 }
 
+/// Commits produced by applying a conflict resolution.
+///
+/// A side is `None` when it already held the chosen content.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResolutionOutcome {
+    pub svn_revision: Option<i64>,
+    pub git_sha: Option<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Engine
 // ---------------------------------------------------------------------------
@@ -81,7 +99,7 @@ const SYNC_MARKER: &str = "[reposync]";
 /// The bidirectional sync engine.
 pub struct SyncEngine {
     config: AppConfig,
-    db: Database,
+    db: Arc<Database>,
     svn_client: std::sync::Mutex<SvnClient>,
     git_client: Arc<std::sync::Mutex<GitClient>>,
     identity_mapper: Arc<IdentityMapper>,
@@ -90,6 +108,15 @@ pub struct SyncEngine {
     started_at: chrono::DateTime<Utc>,
     /// Optional repo ID for per-repo credential and watermark keys.
     repo_id: Option<String>,
+    /// Repo ID whose credentials a branch engine uses (its parent's).
+    credentials_repo_id: Option<String>,
+    /// Notifier for unresolved conflicts.
+    notifier: Option<Arc<Notifier>>,
+    /// Repository name used to route and label notifications.
+    repo_name: Option<String>,
+    /// For a branch engine, the SVN path of the mainline it belongs to and
+    /// the repo ID that mainline is synced under.
+    parent_mainline: Option<(String, Option<String>)>,
 }
 
 impl SyncEngine {
@@ -104,35 +131,145 @@ impl SyncEngine {
         info!("initializing sync engine");
         Self {
             config,
-            db,
+            db: Arc::new(db),
             svn_client: std::sync::Mutex::new(svn_client),
             git_client: Arc::new(std::sync::Mutex::new(git_client)),
             identity_mapper,
             running: Arc::new(AtomicBool::new(false)),
             started_at: Utc::now(),
             repo_id: None,
+            credentials_repo_id: None,
+            notifier: None,
+            repo_name: None,
+            parent_mainline: None,
         }
     }
 
+    /// Engine for a repository from the repositories table, set up like the
+    /// daemon's per-repo sync: the repository's SVN URL and Git host, its
+    /// stored credentials (falling back to its parent's, then the global
+    /// ones) and the working tree under `{data_dir}/repos/{id}/git-repo`,
+    /// which must already exist.
+    pub fn for_repository(
+        config: &AppConfig,
+        repo: &Repository,
+        db: Database,
+        identity_mapper: Arc<IdentityMapper>,
+    ) -> Result<Self, SyncError> {
+        let svn_password = repo_secret(&db, repo, "secret_svn_password");
+        let git_token = repo_secret(&db, repo, "secret_git_token");
+
+        let repo_config = config.for_repository(repo);
+        let svn_client = SvnClient::new(
+            repo.synced_svn_url(),
+            &repo.svn_username,
+            svn_password.as_deref().unwrap_or(""),
+        );
+        let git_repo_path = config.daemon.data_dir.join("repos").join(&repo.id).join("git-repo");
+        let mut git_client = GitClient::new(&git_repo_path)?;
+        git_client
+            .ensure_remote_credentials("origin", repo_config.github.git_auth(git_token.as_deref()))
+            .ok();
+
+        let mut engine = Self::new(repo_config, db, svn_client, git_client, identity_mapper);
+        engine.set_repo_id(repo.id.clone());
+        engine.repo_name = Some(repo.name.clone());
+        Ok(engine)
+    }
+
     /// Set the repository ID for per-repo credential and watermark keys.
     pub fn set_repo_id(&mut self, id: String) {
         self.repo_id = Some(id);
     }
 
+    /// Repository ID this engine syncs, if it was set.
+    pub fn repo_id(&self) -> Option<&str> {
+        self.repo_id.as_deref()
+    }
+
+    /// Claim this engine's repository against other engines of the process
+    /// syncing it; engines without a repo ID have nothing to share.
+    fn claim_repo(&self) -> Result<Option<RepoClaim>, SyncError> {
+        let Some(ref repo_id) = self.repo_id else {
+            return Ok(None);
+        };
+        match RepoClaim::acquire(repo_id) {
+            Some(claim) => Ok(Some(claim)),
+            None => Err(SyncError::AlreadyRunning {
+                started_at: self.started_at.to_rfc3339(),
+            }),
+        }
+    }
+
+    /// Send conflict notifications through `notifier`, routed and labelled
+    /// with `repo_name`.
+    pub fn set_notifier(&mut self, notifier: Arc<Notifier>, repo_name: Option<String>) {
+        self.notifier = Some(notifier);
+        self.repo_name = repo_name;
+    }
+
+    /// Repository name notifications are routed and labelled with.
+    pub fn repo_name(&self) -> Option<&str> {
+        self.repo_name.as_deref()
+    }
+
     /// Return the kv_state key for the last SVN revision watermark.
     /// Uses per-repo key if repo_id is set, otherwise global key.
     fn svn_rev_key(&self) -> String {
-        match &self.repo_id {
-            Some(rid) if !rid.is_empty() => format!("last_svn_rev_{}", rid),
-            _ => "last_svn_rev".to_string(),
+        svn_rev_key_for(self.effective_repo_id())
+    }
+
+    /// Return the kv_state key holding the number of the PR opened for
+    /// `sync.pr.branch`.
+    fn sync_pr_key(&self) -> String {
+        match self.effective_repo_id() {
+            Some(rid) => format!("sync_pr_number_{}", rid),
+            None => "sync_pr_number".to_string(),
+        }
+    }
+
+    /// Return the kv_state key for the last scanned revision of
+    /// `svn.tags_path`.
+    fn svn_tag_rev_key(&self) -> String {
+        svn_tag_rev_key_for(self.effective_repo_id())
+    }
+
+    /// Return the kv_state key listing the Git tags that already existed
+    /// when tag sync first ran.
+    fn git_tag_baseline_key(&self) -> String {
+        match self.effective_repo_id() {
+            Some(rid) => format!("git_tag_baseline_{}", rid),
+            None => "git_tag_baseline".to_string(),
         }
     }
 
+    /// Return the kv_state key recording why `svn:author`/`svn:date` could not
+    /// be set. Keyed by the repo whose SVN server is used.
+    fn svn_revprop_error_key(&self) -> String {
+        let cred_id = self.credentials_repo_id.as_deref().or(self.effective_repo_id());
+        svn_revprop_error_key_for(cred_id)
+    }
+
+    /// Return the kv_state key for the last Git SHA watermark.
+    /// Uses per-repo key if repo_id is set, otherwise global key.
+    fn git_sha_key(&self) -> String {
+        git_sha_key_for(self.effective_repo_id())
+    }
+
     /// Return the effective repo_id if set and non-empty, for repo-table watermark operations.
     fn effective_repo_id(&self) -> Option<&str> {
         self.repo_id.as_deref().filter(|id| !id.is_empty())
     }
 
+    /// Repo ID of the mainline this engine belongs to: its own for the
+    /// mainline engine, its parent's for a branch engine.
+    fn mainline_repo_id(&self) -> Option<&str> {
+        match &self.parent_mainline {
+            Some((_, rid)) => rid.as_deref().filter(|id| !id.is_empty()),
+            None => self.effective_repo_id(),
+        }
+    }
+
     /// Return a reference to the database.
     pub fn db(&self) -> &Database {
         &self.db
@@ -180,6 +317,7 @@ impl SyncEngine {
 
         // RAII guard that clears the running flag on drop (even on panic).
         let _guard = SyncLockGuard(self.running.clone());
+        let _repo_claim = self.claim_repo()?;
 
         // Hot-reload credentials from DB (changed via Setup Wizard).
         self.reload_credentials();
@@ -257,7 +395,7 @@ impl SyncEngine {
         };
         let last_git_hash = match self
             .db
-            .get_state("last_git_hash")
+            .get_state(&self.git_sha_key())
             .map_err(SyncError::DatabaseError)?
         {
             Some(s) if !s.is_empty() => Some(s),
@@ -290,43 +428,109 @@ impl SyncEngine {
 
     async fn do_sync_cycle(&self, stats: &mut SyncStats) -> Result<(), SyncError> {
         // 1. Fetch changes from both sides.
-        let svn_changes = self.fetch_svn_changes().await?;
-        let git_changes = self.fetch_git_changes().await?;
-
-        // 2. Detect conflicts.
-        let conflicts = self.detect_conflicts_internal(&svn_changes, &git_changes);
+        let mut svn_changes = self.fetch_svn_changes().await?;
+        let mut git_changes = self.fetch_git_changes().await?;
+
+        // 2. Detect conflicts. Paths touched on both sides get their content
+        //    and merge base loaded so overlapping edits can be auto-merged.
+        let overlaps = self
+            .load_overlap_contents(&mut svn_changes, &mut git_changes)
+            .await?;
+        let mut conflicts = self.detect_conflicts_internal(&svn_changes, &git_changes);
+        for conflict in &mut conflicts {
+            if let Some(overlap) = overlaps.get(&conflict.file_path) {
+                conflict.base_content = overlap.base_content.clone();
+                conflict.svn_rev = Some(overlap.svn_rev);
+                conflict.git_sha = Some(overlap.git_sha.clone());
+            }
+        }
         stats.conflicts_detected = conflicts.len();
 
+        // Clean merge results, written to both sides while applying changes.
+        // The merge runs on normalized text, so each side gets it back in
+        // its own line-ending and keyword form.
+        let mut merged_for_git: HashMap<String, Vec<u8>> = HashMap::new();
+        let mut merged_for_svn: HashMap<String, Vec<u8>> = HashMap::new();
+
         if !conflicts.is_empty() {
             info!(count = conflicts.len(), "conflicts detected");
             let _ = self.db.set_state("sync_state", "conflict_found");
 
             for conflict in &conflicts {
-                if self.config.sync.auto_merge && self.try_auto_merge(conflict) {
+                let merged = if self.config.sync.auto_merge {
+                    self.try_auto_merge(conflict)
+                } else {
+                    None
+                };
+                if let Some(content) = merged {
+                    let overlap = overlaps.get(&conflict.file_path);
+                    let in_form_of = |raw: Option<&Vec<u8>>| match raw {
+                        Some(raw) => svn_props::restore_text_form(&content, raw),
+                        None => content.clone().into_bytes(),
+                    };
+                    merged_for_git.insert(
+                        conflict.file_path.clone(),
+                        in_form_of(overlap.and_then(|o| o.git_raw.as_ref())),
+                    );
+                    merged_for_svn.insert(
+                        conflict.file_path.clone(),
+                        in_form_of(overlap.and_then(|o| o.svn_raw.as_ref())),
+                    );
                     stats.conflicts_auto_resolved += 1;
                 } else {
                     // Persist unresolved conflict
                     let mut db_conflict = crate::models::Conflict::new(conflict.file_path.clone());
+                    db_conflict.conflict_type = conflict.conflict_type.to_string();
+                    db_conflict.repo_id = self.effective_repo_id().map(|s| s.to_string());
                     db_conflict.svn_content = conflict.svn_content.clone();
                     db_conflict.git_content = conflict.git_content.clone();
                     db_conflict.base_content = conflict.base_content.clone();
                     db_conflict.svn_revision = conflict.svn_rev;
                     db_conflict.git_hash = conflict.git_sha.clone();
                     let _ = self.db.insert_conflict(&db_conflict);
+                    self.notify_conflict(conflict).await;
                 }
             }
         }
 
-        // 3. Apply SVN -> Git.
+        // 3. Apply SVN -> Git, in PR mode onto the sync branch.
         let _ = self.db.set_state("sync_state", "applying");
-        stats.svn_to_git_count = self.sync_svn_to_git(&svn_changes).await?;
+        let (target, open_pr) = match self.config.sync.mode {
+            SyncMode::Direct => (
+                GitTarget {
+                    client: self.git_client.clone(),
+                    branch: self.config.github.default_branch.clone(),
+                },
+                None,
+            ),
+            SyncMode::Pr => self.prepare_sync_branch().await?,
+        };
+        stats.svn_to_git_count = self
+            .sync_svn_to_git(&svn_changes, &merged_for_git, &target)
+            .await?;
+        if self.config.sync.mode == SyncMode::Pr {
+            self.update_sync_pull_request(&target, open_pr).await?;
+        }
 
         // 4. Apply Git -> SVN.
-        stats.git_to_svn_count = self.sync_git_to_svn(&git_changes).await?;
+        stats.git_to_svn_count = self.sync_git_to_svn(&git_changes, &merged_for_svn).await?;
+
+        // 5. Sync configured branches, each through its own child engine.
+        if !self.config.sync.sync_branches.is_empty() {
+            let (to_git, to_svn) = self.sync_branches().await?;
+            stats.svn_to_git_count += to_git;
+            stats.git_to_svn_count += to_svn;
+        }
+
+        // 6. Sync tags in both directions.
+        if self.config.sync.sync_tags {
+            stats.tags_synced = self.sync_tags().await?;
+        }
 
         info!(
             svn_to_git = stats.svn_to_git_count,
             git_to_svn = stats.git_to_svn_count,
+            tags = stats.tags_synced,
             "sync cycle completed"
         );
 
@@ -345,8 +549,18 @@ impl SyncEngine {
     /// 3. Commit with the mapped Git identity and a `[reposync]` marker.
     /// 4. Push to the remote.
     /// 5. Only then record the sync in the database.
-    async fn sync_svn_to_git(&self, svn_changes: &[SvnChangeSet]) -> Result<usize, SyncError> {
+    ///
+    /// Paths in `auto_merged` get the merged content instead of the SVN one.
+    /// Commits go to `target`, which in PR mode is the sync branch; the Git
+    /// watermark then stays put since the target branch is unchanged.
+    async fn sync_svn_to_git(
+        &self,
+        svn_changes: &[SvnChangeSet],
+        auto_merged: &HashMap<String, Vec<u8>>,
+        target: &GitTarget,
+    ) -> Result<usize, SyncError> {
         let mut count = 0;
+        let held_paths = self.held_conflict_paths()?;
 
         for change in svn_changes {
             if self.is_echo_commit(&change.message) {
@@ -354,6 +568,18 @@ impl SyncEngine {
                 continue;
             }
 
+            // Revisions that only touch paths outside the synced tree (e.g.
+            // another branch) produce no Git commit; just move past them.
+            let has_dir_props = (self.config.sync.sync_ignores
+                || self.config.sync.sync_externals
+                || self.config.sync.sync_merges)
+                && !change.changed_dirs.is_empty();
+            if change.changed_files.is_empty() && !has_dir_props {
+                debug!(rev = change.revision, "no changes under the synced path");
+                self.skip_svn_revision(change.revision);
+                continue;
+            }
+
             let git_identity = self
                 .identity_mapper
                 .svn_to_git(&change.author)
@@ -368,9 +594,16 @@ impl SyncEngine {
 
             // Get the git repo path before locking, for apply_diff_to_path.
             let repo_path = {
-                let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+                let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
                 git.repo_path().to_path_buf()
             };
+            // Taken before the revision's files land, which may replace
+            // `.gitattributes` with the SVN copy.
+            let eol_entries = if self.config.sync.normalize_line_endings {
+                svn_props::read_eol_entries(&repo_path)
+            } else {
+                BTreeMap::new()
+            };
 
             // 2. Apply the diff to the Git working tree.
             // Try git apply first; fall back to export-based copy if the diff
@@ -378,29 +611,22 @@ impl SyncEngine {
             // changes or initial adds).
             // When using standard layout, strip the trunk prefix from diff paths
             // so they match the git repository structure.
-            let processed_diff = if self.config.svn.layout == SvnLayout::Standard {
-                let tp = self.config.svn.trunk_path.trim_matches('/');
-                if !tp.is_empty() {
-                    diff.replace(
-                        &format!("a/{}/", tp),
-                        "a/",
-                    ).replace(
-                        &format!("b/{}/", tp),
-                        "b/",
-                    )
-                } else {
-                    diff
-                }
+            let diff_prefix = if self.config.svn.layout == SvnLayout::Standard {
+                self.config.svn.trunk_path.trim_matches('/')
             } else {
-                diff
+                ""
             };
-            let diff_applied = if !processed_diff.trim().is_empty() {
+            let processed_diff = rebase_svn_diff(&diff, diff_prefix);
+            // `svn diff` leaves out the content of copied paths, so copies and
+            // renames always go through the fallback below.
+            let has_copies = change.changed_files.iter().any(|f| f.copy_from.is_some());
+            let diff_applied = if !has_copies && !processed_diff.trim().is_empty() {
                 apply_diff_to_path(&repo_path, &processed_diff).await.is_ok()
             } else {
                 false
             };
 
-            if !diff_applied {
+            if !diff_applied && !change.changed_files.is_empty() {
                 // Fallback: fetch changed files individually when few files changed,
                 // or export the full tree when many files changed (P11 optimization).
                 let non_delete_count = change.changed_files.iter()
@@ -412,12 +638,22 @@ impl SyncEngine {
                     let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
                     for file in &change.changed_files {
                         let dst = repo_path.join(&file.path);
+                        self.remove_replaced_git_paths(target, file)?;
                         match file.action.as_str() {
-                            "D" => {
-                                if dst.exists() {
-                                    std::fs::remove_file(&dst).map_err(|e| {
-                                        SyncError::GitError(crate::errors::GitError::IoError(e))
-                                    })?;
+                            "D" => {}
+                            _ if file.is_dir => {
+                                // Copied directories arrive as a single entry;
+                                // export the whole subtree.
+                                if file.copy_from.is_some() {
+                                    if let Some(parent) = dst.parent() {
+                                        std::fs::create_dir_all(parent).map_err(|e| {
+                                            SyncError::GitError(crate::errors::GitError::IoError(e))
+                                        })?;
+                                    }
+                                    let svn_path = self.svn_repo_path(&file.path);
+                                    svn.export(&svn_path, change.revision, &dst)
+                                        .await
+                                        .map_err(SyncError::SvnError)?;
                                 }
                             }
                             _ => {
@@ -426,11 +662,12 @@ impl SyncEngine {
                                         SyncError::GitError(crate::errors::GitError::IoError(e))
                                     })?;
                                 }
-                                match svn.cat(&file.path, change.revision).await {
+                                match svn.cat(&self.svn_repo_path(&file.path), change.revision).await {
                                     Ok(content) => {
-                                        std::fs::write(&dst, content.as_bytes()).map_err(|e| {
-                                            SyncError::GitError(crate::errors::GitError::IoError(e))
-                                        })?;
+                                        svn_props::write_regular_file(&dst, content.as_bytes())
+                                            .map_err(|e| {
+                                                SyncError::GitError(GitError::IoError(e))
+                                            })?;
                                     }
                                     Err(e) => {
                                         warn!(file = %file.path, error = %e, "svn cat failed, skipping file");
@@ -458,10 +695,18 @@ impl SyncEngine {
                     for file in &change.changed_files {
                         let src = export_dir.path().join(&file.path);
                         let dst = repo_path.join(&file.path);
+                        self.remove_replaced_git_paths(target, file)?;
                         match file.action.as_str() {
-                            "D" => {
-                                if dst.exists() {
-                                    std::fs::remove_file(&dst).map_err(|e| {
+                            "D" => {}
+                            // Links stay links; `sync_svn_file_props` checks them.
+                            _ if svn_props::is_symlink(&src) => {
+                                svn_props::copy_symlink(&src, &dst).map_err(|e| {
+                                    SyncError::GitError(crate::errors::GitError::IoError(e))
+                                })?;
+                            }
+                            _ if src.is_dir() => {
+                                if file.copy_from.is_some() {
+                                    copy_dir_all(&src, &dst).map_err(|e| {
                                         SyncError::GitError(crate::errors::GitError::IoError(e))
                                     })?;
                                 }
@@ -472,7 +717,12 @@ impl SyncEngine {
                                         SyncError::GitError(crate::errors::GitError::IoError(e))
                                     })?;
                                 }
-                                if src.exists() && src.is_file() {
+                                if src.is_file() {
+                                    if svn_props::is_symlink(&dst) {
+                                        std::fs::remove_file(&dst).map_err(|e| {
+                                            SyncError::GitError(GitError::IoError(e))
+                                        })?;
+                                    }
                                     std::fs::copy(&src, &dst).map_err(|e| {
                                         SyncError::GitError(crate::errors::GitError::IoError(e))
                                     })?;
@@ -483,6 +733,57 @@ impl SyncEngine {
                 }
             }
 
+            let ignores_changed = if self.config.sync.sync_ignores {
+                self.sync_svn_ignores(change, target, &repo_path).await?
+            } else {
+                false
+            };
+            let externals_changed = if self.config.sync.sync_externals {
+                self.sync_svn_externals(change, target, &repo_path).await?
+            } else {
+                false
+            };
+            self.sync_svn_file_props(change, target, &repo_path, eol_entries).await?;
+            let merge_parents = if self.config.sync.sync_merges {
+                self.svn_merge_parents(change).await?
+            } else {
+                Vec::new()
+            };
+            // Property changes that leave no trace in Git need no commit.
+            if change.changed_files.is_empty()
+                && !ignores_changed
+                && !externals_changed
+                && merge_parents.is_empty()
+            {
+                debug!(rev = change.revision, "no Git-visible changes in revision");
+                self.skip_svn_revision(change.revision);
+                continue;
+            }
+
+            // Auto-merged paths take the merge result.
+            for file in change.changed_files.iter().filter(|f| f.action != "D") {
+                if let Some(merged) = auto_merged.get(&file.path) {
+                    std::fs::write(repo_path.join(&file.path), merged).map_err(|e| {
+                        SyncError::GitError(crate::errors::GitError::IoError(e))
+                    })?;
+                }
+            }
+
+            // Paths with an unresolved conflict keep their Git version until
+            // the conflict is resolved (see `apply_conflict_resolution`).
+            let held: Vec<String> = change
+                .changed_files
+                .iter()
+                .filter(|f| held_paths.contains(&f.path))
+                .map(|f| f.path.clone())
+                .collect();
+            if !held.is_empty() {
+                debug!(rev = change.revision, paths = ?held, "holding back conflicted paths");
+                let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+                git.restore_paths_from_head(&held)
+                    .map_err(SyncError::GitError)?;
+            }
+
             // 3. Commit with identity and sync marker.
             let commit_message = format!(
                 "{}\n\n{} synced from SVN r{}",
@@ -493,21 +794,30 @@ impl SyncEngine {
             // CLI call doesn't block the tokio async runtime (which would
             // make the web UI unresponsive during pushes).
             let git_sha = tokio::task::block_in_place(|| {
-                let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+                let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+                // Sources already in the branch history add nothing as parents.
+                let head = git.get_head_sha().ok();
+                let extra_parents: Vec<String> = merge_parents
+                    .into_iter()
+                    .filter(|sha| match &head {
+                        Some(head) => !git.is_ancestor(sha, head).unwrap_or(false),
+                        None => false,
+                    })
+                    .collect();
                 let oid = git
-                    .commit(
+                    .commit_with_parents(
                         &commit_message,
                         &git_identity.name,
                         &git_identity.email,
                         "reposync",
                         "sync@reposync.local",
+                        &extra_parents,
                     )
                     .map_err(SyncError::GitError)?;
 
                 // 4. Push to remote.
                 let token = self.config.github.token.as_deref();
-                let branch = &self.config.github.default_branch;
-                git.push("origin", branch, token)
+                git.push("origin", &target.branch, token)
                     .map_err(SyncError::GitError)?;
 
                 Ok::<_, SyncError>(oid.to_string())
@@ -529,13 +839,31 @@ impl SyncEngine {
             self.db
                 .insert_sync_record(&record)
                 .map_err(SyncError::DatabaseError)?;
+            self.db
+                .insert_commit_map_with_repo(
+                    change.revision,
+                    &git_sha,
+                    "svn_to_git",
+                    &change.author,
+                    &format!("{} <{}>", git_identity.name, git_identity.email),
+                    self.effective_repo_id(),
+                )
+                .map_err(SyncError::DatabaseError)?;
 
             // Update the SVN watermark (dual-write: kv_state + repo table).
             let _ = self
                 .db
                 .set_state(&self.svn_rev_key(), &change.revision.to_string());
             if let Some(rid) = self.effective_repo_id() {
-                let _ = self.db.update_repo_watermark(rid, change.revision, &git_sha);
+                let watermark_sha = match self.config.sync.mode {
+                    SyncMode::Direct => git_sha.clone(),
+                    SyncMode::Pr => self
+                        .db
+                        .get_repo_watermark(rid)
+                        .map(|(_, sha)| sha)
+                        .unwrap_or_default(),
+                };
+                let _ = self.db.update_repo_watermark(rid, change.revision, &watermark_sha);
                 let _ = self.db.increment_repo_sync_count(rid);
             }
 
@@ -582,8 +910,15 @@ impl SyncEngine {
     /// 3. Stage additions/deletions with `svn add`/`svn rm`.
     /// 4. Commit to SVN with a `[reposync]` marker.
     /// 5. Only then record the sync in the database.
-    async fn sync_git_to_svn(&self, git_changes: &[GitChangeSet]) -> Result<usize, SyncError> {
+    ///
+    /// Paths in `auto_merged` get the merged content instead of the Git one.
+    async fn sync_git_to_svn(
+        &self,
+        git_changes: &[GitChangeSet],
+        auto_merged: &HashMap<String, Vec<u8>>,
+    ) -> Result<usize, SyncError> {
         let mut count = 0;
+        let held_paths = self.held_conflict_paths()?;
 
         // Reuse a single SVN working copy across all commits (P4 optimization).
         // Create the tempdir once and use `svn update` between commits instead
@@ -591,6 +926,8 @@ impl SyncEngine {
         let svn_wc_dir = tempfile::tempdir()
             .map_err(|e| SyncError::SvnError(crate::errors::SvnError::IoError(e)))?;
         let mut svn_wc_initialized = false;
+        // Set once the server rejects a revprop change, for the rest of the cycle.
+        let mut revprops_blocked = false;
 
         for change in git_changes {
             if self.is_echo_commit(&change.message) {
@@ -606,27 +943,113 @@ impl SyncEngine {
             // 1. Get changed files and their contents from the Git commit.
             //    Lock is scoped in a block so the guard is dropped before any
             //    .await (std::sync::MutexGuard is !Send).
-            let file_contents = {
+            let (file_contents, external_roots, eol_entries, merge_sources) = {
                 let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+                let parent = git
+                    .commit_parents(&change.sha)
+                    .ok()
+                    .and_then(|parents| parents.into_iter().next());
                 // Use the pre-populated changed_files from fetch_git_changes
                 // instead of re-calling get_changed_files (P5 optimization).
-                let contents: Vec<(String, String, Option<Vec<u8>>)> = change
+                let contents: Vec<_> = change
                     .changed_files
                     .iter()
                     .map(|f| {
                         let (action, path) = (&f.action, &f.path);
-                        let content = if action != "D" {
+                        let content = if let (false, Some(merged)) =
+                            (action == "D", auto_merged.get(path))
+                        {
+                            Some(merged.clone())
+                        } else if action != "D" {
                             git.get_file_content_at_commit(&change.sha, path)
                                 .ok()
                                 .flatten()
                         } else {
                             None
                         };
-                        (action.clone(), path.clone(), content)
+                        let symlink = action != "D"
+                            && git.is_symlink_at_commit(&change.sha, path).ok().flatten()
+                                == Some(true);
+                        // The new executable state, if the file mode changed.
+                        let executable = if self.config.sync.sync_executable_bit
+                            && action != "D"
+                            && !symlink
+                        {
+                            let is_executable = |sha: &str, path: &str| {
+                                git.is_executable_at_commit(sha, path).ok().flatten()
+                            };
+                            let now = is_executable(&change.sha, path).unwrap_or(false);
+                            let source = f.copy_from.as_deref().unwrap_or(path);
+                            let before = parent
+                                .as_deref()
+                                .and_then(|p| is_executable(p, source))
+                                .unwrap_or(false);
+                            (now != before).then_some(now)
+                        } else {
+                            None
+                        };
+                        // The generated svn:eol-style block stays in Git.
+                        let (action, content) = if self.config.sync.normalize_line_endings
+                            && path == svn_props::GITATTRIBUTES
+                        {
+                            let content = content
+                                .map(|c| {
+                                    let c = String::from_utf8_lossy(&c);
+                                    svn_props::render_eol_entries(&c, &BTreeMap::new())
+                                })
+                                .filter(|c| !c.trim().is_empty())
+                                .map(String::into_bytes);
+                            let action = if content.is_none() { "D" } else { action };
+                            (action.to_string(), content)
+                        } else {
+                            (action.clone(), content)
+                        };
+                        (action, path.clone(), f.copy_from.clone(), content, executable, symlink)
                     })
                     .collect();
-                contents
+                // Externals defined before or after this commit, so files of
+                // a removed external are skipped too.
+                let external_roots = if self.config.sync.sync_externals {
+                    let mut shas = vec![change.sha.clone()];
+                    shas.extend(parent.clone());
+                    let paths = change.changed_files.iter().map(|f| f.path.as_str());
+                    svn_props::external_roots(paths, |path| {
+                        shas.iter().find_map(|sha| {
+                            git.get_file_content_at_commit(sha, path).ok().flatten()
+                        })
+                        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                    })
+                } else {
+                    Vec::new()
+                };
+                // .gitattributes after the commit and svn:eol-style entries
+                // after and before it.
+                let gitattributes = |sha: &str| {
+                    git.get_file_content_at_commit(sha, svn_props::GITATTRIBUTES)
+                        .ok()
+                        .flatten()
+                        .map(|c| String::from_utf8_lossy(&c).into_owned())
+                        .unwrap_or_default()
+                };
+                let eol_entries = if self.config.sync.normalize_line_endings {
+                    let after = gitattributes(&change.sha);
+                    let before = parent.as_deref().map(gitattributes).unwrap_or_default();
+                    (
+                        svn_props::parse_eol_entries(&after),
+                        svn_props::parse_eol_entries(&before),
+                        after,
+                    )
+                } else {
+                    Default::default()
+                };
+                let merge_sources = if self.config.sync.sync_merges {
+                    self.git_merge_sources(&git, &change.sha)?
+                } else {
+                    Vec::new()
+                };
+                (contents, external_roots, eol_entries, merge_sources)
             };
+            let (eol_entries, eol_entries_before, gitattributes) = eol_entries;
 
             // 2. Prepare SVN working copy: checkout on first use, update thereafter.
             let svn_url_for_log;
@@ -657,11 +1080,63 @@ impl SyncEngine {
             //    exist in the SVN working copy, treat it as an add so that
             //    `svn add` is called.  This handles the case where the SVN
             //    repo has fewer files than Git (e.g. freshly created repo).
-            let mut added_files = Vec::new();
-            let mut deleted_files = Vec::new();
+            let mut added_files: Vec<String> = Vec::new();
+            let mut deleted_files: Vec<String> = Vec::new();
+            // The generated block of `.gitignore` files becomes ignore
+            // properties on their directory (the file itself is committed
+            // too), `.svnexternals` files its `svn:externals`.
+            let mut ignore_updates: Vec<(String, Option<&Vec<u8>>)> = Vec::new();
+            let mut externals_updates: Vec<(String, Option<&Vec<u8>>)> = Vec::new();
+            // File mode changes become `svn:executable` once files are added.
+            let mut executable_updates: Vec<(String, bool)> = Vec::new();
+            // svn:eol-style and svn:keywords for files new to SVN.
+            let mut new_file_props: Vec<(String, Vec<(&str, String)>)> = Vec::new();
+            // Versioned files that turned into symlinks or back, re-added as
+            // the new kind.
+            let mut kind_changes: Vec<String> = Vec::new();
+            let link_policy = FilePolicy::new(0, Vec::new())
+                .reject_external_symlinks(self.config.sync.reject_external_symlinks);
+            // Symlinks are written as links, which `svn add` marks svn:special.
+            let write_wc_file = |dst: &std::path::Path, content: &[u8], symlink: bool| {
+                let written = if symlink {
+                    svn_props::write_symlink(dst, &String::from_utf8_lossy(content))
+                } else {
+                    svn_props::write_regular_file(dst, content)
+                };
+                written.map_err(|e| SyncError::GitError(crate::errors::GitError::IoError(e)))
+            };
 
-            for (action, file_path, content) in &file_contents {
-                let dst = svn_wc_dir.path().join(file_path);
+            for (action, file_path, copy_from, content, executable, symlink) in &file_contents {
+                if held_paths.contains(file_path) {
+                    debug!(
+                        sha = %change.sha,
+                        file_path = %file_path,
+                        "holding back conflicted path"
+                    );
+                    continue;
+                }
+                if self.config.sync.sync_ignores && svn_props::is_gitignore(file_path) {
+                    let dir = svn_props::parent_dir(file_path);
+                    let dir = self.svn_repo_path(dir).trim_end_matches('/').to_string();
+                    ignore_updates.push((dir, content.as_ref().filter(|_| action != "D")));
+                }
+                if self.config.sync.sync_externals {
+                    if svn_props::is_svnexternals(file_path) {
+                        let dir = svn_props::parent_dir(file_path);
+                        let dir = self.svn_repo_path(dir).trim_end_matches('/').to_string();
+                        externals_updates.push((dir, content.as_ref().filter(|_| action != "D")));
+                        continue;
+                    }
+                    // Submodules and vendored externals are SVN's own content.
+                    if file_path == ".gitmodules"
+                        || external_roots.iter().any(|r| svn_props::is_under(file_path, r))
+                    {
+                        debug!(file_path = %file_path, "skipping SVN external content");
+                        continue;
+                    }
+                }
+                let svn_path = self.svn_repo_path(file_path);
+                let dst = svn_wc_dir.path().join(&svn_path);
                 debug!(
                     sha = %change.sha,
                     action = %action,
@@ -670,10 +1145,54 @@ impl SyncEngine {
                     dst_exists = dst.exists(),
                     "processing file change"
                 );
+                if let Some(executable) = executable {
+                    executable_updates.push((svn_path.clone(), *executable));
+                }
+                if *symlink {
+                    let link_target =
+                        String::from_utf8_lossy(content.as_deref().unwrap_or_default());
+                    if link_policy.evaluate_symlink(file_path, &link_target).is_blocked() {
+                        continue;
+                    }
+                }
+                // Renames and copies keep their history in SVN. When the
+                // source is missing or held back, fall back to a plain add.
+                if let Some(from) = copy_from.as_deref().filter(|p| !held_paths.contains(*p)) {
+                    let from_svn_path = self.svn_repo_path(from);
+                    let src = svn_wc_dir.path().join(&from_svn_path);
+                    if src.exists() && !dst.exists() {
+                        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+                        let wc = svn_wc_dir.path();
+                        let result = if action == "R" {
+                            svn.mv(wc, &from_svn_path, &svn_path).await
+                        } else {
+                            svn.cp(wc, &from_svn_path, &svn_path).await
+                        };
+                        match result {
+                            Ok(()) => {
+                                if let Some(content) = content {
+                                    write_wc_file(&dst, content, *symlink)?;
+                                }
+                                continue;
+                            }
+                            Err(e) => {
+                                warn!(
+                                    from = %from,
+                                    file_path = %file_path,
+                                    error = %e,
+                                    "svn move/copy failed; adding without history"
+                                );
+                            }
+                        }
+                    }
+                    if action == "R" && src.exists() {
+                        deleted_files.push(from_svn_path);
+                    }
+                }
                 match action.as_str() {
                     "D" => {
-                        if dst.exists() {
-                            deleted_files.push(file_path.as_str());
+                        if svn_props::is_file_or_symlink(&dst) {
+                            deleted_files.push(svn_path);
                         } else {
                             debug!(
                                 file_path = %file_path,
@@ -688,24 +1207,35 @@ impl SyncEngine {
                                     SyncError::GitError(crate::errors::GitError::IoError(e))
                                 })?;
                             }
-                            std::fs::write(&dst, content).map_err(|e| {
-                                SyncError::GitError(crate::errors::GitError::IoError(e))
-                            })?;
-                            added_files.push(file_path.as_str());
+                            write_wc_file(&dst, content, *symlink)?;
+                            if !symlink {
+                                new_file_props.push((
+                                    svn_path.clone(),
+                                    svn_props::new_file_props(
+                                        file_path,
+                                        content,
+                                        &gitattributes,
+                                        self.config.sync.normalize_line_endings,
+                                        self.config.sync.collapse_keywords,
+                                    ),
+                                ));
+                            }
+                            added_files.push(svn_path);
                         }
                     }
                     _ => {
                         // Modified: overwrite content.
                         if let Some(content) = content {
-                            let file_is_new = !dst.exists();
+                            let file_is_new = !svn_props::is_file_or_symlink(&dst);
+                            if !file_is_new && svn_props::is_symlink(&dst) != *symlink {
+                                kind_changes.push(svn_path.clone());
+                            }
                             if let Some(parent) = dst.parent() {
                                 std::fs::create_dir_all(parent).map_err(|e| {
                                     SyncError::GitError(crate::errors::GitError::IoError(e))
                                 })?;
                             }
-                            std::fs::write(&dst, content).map_err(|e| {
-                                SyncError::GitError(crate::errors::GitError::IoError(e))
-                            })?;
+                            write_wc_file(&dst, content, *symlink)?;
                             // If the file didn't exist in the SVN working copy,
                             // it must be `svn add`ed even though Git says "M".
                             if file_is_new {
@@ -713,7 +1243,19 @@ impl SyncEngine {
                                     file_path = %file_path,
                                     "file marked as modified in Git but missing in SVN WC; treating as add"
                                 );
-                                added_files.push(file_path.as_str());
+                                if !symlink {
+                                    new_file_props.push((
+                                        svn_path.clone(),
+                                        svn_props::new_file_props(
+                                            file_path,
+                                            content,
+                                            &gitattributes,
+                                            self.config.sync.normalize_line_endings,
+                                            self.config.sync.collapse_keywords,
+                                        ),
+                                    ));
+                                }
+                                added_files.push(svn_path);
                             }
                         }
                     }
@@ -722,13 +1264,21 @@ impl SyncEngine {
 
             // 4. Stage changes in SVN.
             let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+            if !kind_changes.is_empty() {
+                let changed: Vec<&str> = kind_changes.iter().map(String::as_str).collect();
+                svn.rm_keep_local(svn_wc_dir.path(), &changed)
+                    .await
+                    .map_err(SyncError::SvnError)?;
+                added_files.append(&mut kind_changes);
+            }
             if !added_files.is_empty() {
                 debug!(
                     sha = %change.sha,
                     files = ?added_files,
                     "running svn add"
                 );
-                svn.add(svn_wc_dir.path(), &added_files)
+                let added: Vec<&str> = added_files.iter().map(String::as_str).collect();
+                svn.add(svn_wc_dir.path(), &added)
                     .await
                     .map_err(SyncError::SvnError)?;
             }
@@ -738,10 +1288,136 @@ impl SyncEngine {
                     files = ?deleted_files,
                     "running svn rm"
                 );
-                svn.rm(svn_wc_dir.path(), &deleted_files)
+                let deleted: Vec<&str> = deleted_files.iter().map(String::as_str).collect();
+                svn.rm(svn_wc_dir.path(), &deleted)
+                    .await
+                    .map_err(SyncError::SvnError)?;
+            }
+            for (svn_path, executable) in &executable_updates {
+                if svn_wc_dir.path().join(svn_path).is_file() {
+                    svn_props::apply_executable(&svn, svn_wc_dir.path(), svn_path, *executable)
+                        .await
+                        .map_err(SyncError::SvnError)?;
+                }
+            }
+            for (svn_path, props) in &new_file_props {
+                for (name, value) in props {
+                    svn.propset(svn_wc_dir.path(), name, value, svn_path)
+                        .await
+                        .map_err(SyncError::SvnError)?;
+                }
+            }
+            // Entries edited in the generated `.gitattributes` block.
+            for (file_path, style) in
+                svn_props::eol_entry_changes(&eol_entries_before, &eol_entries)
+            {
+                let svn_path = self.svn_repo_path(&file_path);
+                if held_paths.contains(&file_path)
+                    || new_file_props.iter().any(|(p, _)| *p == svn_path)
+                    || !svn_wc_dir.path().join(&svn_path).is_file()
+                {
+                    continue;
+                }
+                let wc = svn_wc_dir.path();
+                let name = svn_props::SVN_EOL_STYLE;
+                let result = match &style {
+                    Some(style) => svn.propset(wc, name, style, &svn_path).await,
+                    None => svn.propdel(wc, name, &svn_path).await,
+                };
+                // SVN refuses a style on a file with mixed line endings.
+                if let Err(e) = result {
+                    warn!(file_path = %file_path, error = %e, "could not update svn:eol-style");
+                }
+            }
+            for (svn_dir, content) in &ignore_updates {
+                let wc_dir = svn_wc_dir.path().join(svn_dir);
+                let props = match content {
+                    Some(content) => {
+                        let (props, unsupported) =
+                            IgnoreProps::from_gitignore(&String::from_utf8_lossy(content));
+                        if !unsupported.is_empty() {
+                            warn!(
+                                sha = %change.sha,
+                                dir = %svn_dir,
+                                patterns = ?unsupported,
+                                ".gitignore patterns have no SVN equivalent; not synced"
+                            );
+                        }
+                        props
+                    }
+                    // A removed .gitignore clears the properties, unless its
+                    // directory went with it.
+                    None if wc_dir.is_dir() => IgnoreProps::default(),
+                    None => continue,
+                };
+                if !wc_dir.is_dir() {
+                    std::fs::create_dir_all(&wc_dir).map_err(|e| {
+                        SyncError::GitError(crate::errors::GitError::IoError(e))
+                    })?;
+                    svn.add(svn_wc_dir.path(), &[svn_dir.as_str()])
+                        .await
+                        .map_err(SyncError::SvnError)?;
+                }
+                svn_props::apply_ignore_props(&svn, svn_wc_dir.path(), svn_dir, &props)
                     .await
                     .map_err(SyncError::SvnError)?;
             }
+            for (svn_dir, content) in &externals_updates {
+                let wc_dir = svn_wc_dir.path().join(svn_dir);
+                let value = match content {
+                    Some(content) => {
+                        svn_props::from_svnexternals(&String::from_utf8_lossy(content))
+                    }
+                    None if wc_dir.is_dir() => String::new(),
+                    None => continue,
+                };
+                if !wc_dir.is_dir() {
+                    std::fs::create_dir_all(&wc_dir).map_err(|e| {
+                        SyncError::GitError(crate::errors::GitError::IoError(e))
+                    })?;
+                    svn.add(svn_wc_dir.path(), &[svn_dir.as_str()])
+                        .await
+                        .map_err(SyncError::SvnError)?;
+                }
+                svn_props::apply_externals(&svn, svn_wc_dir.path(), svn_dir, &value)
+                    .await
+                    .map_err(SyncError::SvnError)?;
+            }
+
+            // 4a. Record the branches a Git merge brought in as svn:mergeinfo.
+            if !merge_sources.is_empty() {
+                let info = svn.info().await?;
+                let own_path = self.svn_mainline_path(&info.url, &info.root_url);
+                let branches_path =
+                    self.svn_layout_path(&own_path, &self.config.svn.branches_path);
+                let merge_target = match self.svn_repo_path("").trim_end_matches('/') {
+                    "" => ".".to_string(),
+                    path => path.to_string(),
+                };
+                for (branch, rev) in &merge_sources {
+                    let source = match (branch, &self.parent_mainline) {
+                        (Some(name), _) => format!("{}/{}", branches_path, name),
+                        (None, Some((mainline, _))) => mainline.clone(),
+                        (None, None) => own_path.clone(),
+                    };
+                    if source == own_path {
+                        continue;
+                    }
+                    let source_url = format!("^/{}", source);
+                    if let Err(e) = svn
+                        .merge_record_only(svn_wc_dir.path(), &source_url, *rev, &merge_target)
+                        .await
+                    {
+                        warn!(
+                            sha = %change.sha,
+                            source = %source,
+                            rev,
+                            error = %e,
+                            "failed to record merge in svn:mergeinfo"
+                        );
+                    }
+                }
+            }
 
             // 4b. Check `svn status` to verify there are actual pending changes.
             //     If SVN sees no modifications, skip this commit gracefully
@@ -769,9 +1445,8 @@ impl SyncEngine {
                 );
                 // Still advance the Git watermark so we don't retry this
                 // commit on the next cycle.
-                let _ = self.db.set_state("last_git_hash", &change.sha);
+                let _ = self.db.set_state(&self.git_sha_key(), &change.sha);
                 if let Some(rid) = self.effective_repo_id() {
-                    let _ = self.db.set_state(&format!("last_git_sha_{}", rid), &change.sha);
                     // Preserve existing SVN watermark — only update git_sha
                     let current_svn_rev = self.db.get_repo_watermark(rid)
                         .map(|(rev, _)| rev).unwrap_or(0);
@@ -793,10 +1468,50 @@ impl SyncEngine {
                 SYNC_MARKER,
                 &change.sha[..8.min(change.sha.len())]
             );
-            let svn_rev = svn
+            let as_author = if self.config.sync.commit_as_author {
+                match self.author_svn_client(&svn, &change.sha).await {
+                    Ok(found) => Some(found),
+                    Err(reason) => {
+                        warn!(
+                            sha = %change.sha,
+                            author = %change.author_email,
+                            reason = %reason,
+                            "committing as the service account"
+                        );
+                        let _ = self.db.insert_audit_log_with_repo(
+                            "commit_as_author",
+                            Some("git_to_svn"),
+                            None,
+                            Some(&change.sha),
+                            Some(&change.author_name),
+                            Some(&format!(
+                                "committed Git {} as the service account: {}",
+                                &change.sha[..8.min(change.sha.len())],
+                                reason
+                            )),
+                            true,
+                            self.repo_id.as_deref(),
+                        );
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            // A revision committed as the author already carries their name.
+            let (committer, svn_username, author_prop) = match as_author {
+                Some((client, username)) => (client, username, None),
+                None => (svn.clone(), svn_username.clone(), Some(svn_username)),
+            };
+            let svn_rev = committer
                 .commit(svn_wc_dir.path(), &commit_message, &svn_username)
                 .await
                 .map_err(SyncError::SvnError)?;
+            if !revprops_blocked {
+                revprops_blocked = !self
+                    .set_svn_revprops(&svn, svn_rev, author_prop.as_deref(), change.author_time)
+                    .await;
+            }
 
             // 6. Record the sync only after successful write.
             let record = crate::models::SyncRecord {
@@ -814,11 +1529,20 @@ impl SyncEngine {
             self.db
                 .insert_sync_record(&record)
                 .map_err(SyncError::DatabaseError)?;
+            self.db
+                .insert_commit_map_with_repo(
+                    svn_rev,
+                    &change.sha,
+                    "git_to_svn",
+                    &svn_username,
+                    &format!("{} <{}>", change.author_name, change.author_email),
+                    self.effective_repo_id(),
+                )
+                .map_err(SyncError::DatabaseError)?;
 
             // Update the Git watermark (dual-write: kv_state + repo table).
-            let _ = self.db.set_state("last_git_hash", &change.sha);
+            let _ = self.db.set_state(&self.git_sha_key(), &change.sha);
             if let Some(rid) = self.effective_repo_id() {
-                let _ = self.db.set_state(&format!("last_git_sha_{}", rid), &change.sha);
                 let _ = self.db.update_repo_watermark(rid, svn_rev, &change.sha);
                 let _ = self.db.increment_repo_sync_count(rid);
             }
@@ -853,6 +1577,105 @@ impl SyncEngine {
         Ok(count)
     }
 
+    /// Set `svn:author` to `author` (and, if configured, `svn:date`) on a
+    /// revision committed from Git.
+    ///
+    /// Returns `false` if the server rejected the change, typically because
+    /// no `pre-revprop-change` hook allows it. The revision keeps the service
+    /// account as author and the error is recorded for `reposync doctor`.
+    async fn set_svn_revprops(
+        &self,
+        svn: &SvnClient,
+        rev: i64,
+        author: Option<&str>,
+        author_time: i64,
+    ) -> bool {
+        let sync = &self.config.sync;
+        let mut props = Vec::new();
+        if let Some(author) = author.filter(|_| sync.preserve_svn_author) {
+            props.push(("svn:author", author.to_string()));
+        }
+        if sync.preserve_svn_date {
+            if let Some(date) = chrono::DateTime::from_timestamp(author_time, 0) {
+                props.push(("svn:date", date.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string()));
+            }
+        }
+        if props.is_empty() {
+            return true;
+        }
+
+        for (name, value) in &props {
+            if let Err(e) = svn.set_rev_prop(rev, name, value).await {
+                warn!(
+                    svn_rev = rev,
+                    prop = name,
+                    error = %e,
+                    "could not set SVN revision property; check the pre-revprop-change hook"
+                );
+                let _ = self.db.set_state(
+                    &self.svn_revprop_error_key(),
+                    &format!("r{}: {}: {}", rev, name, e),
+                );
+                return false;
+            }
+        }
+        let _ = self.db.set_state(&self.svn_revprop_error_key(), "");
+        true
+    }
+
+    /// Build a client that commits with the Git author's own SVN credential
+    /// (`sync.commit_as_author`), together with the SVN username it uses.
+    ///
+    /// The author and committer emails of a commit are whatever the pusher
+    /// set, so the credential is only chosen by an identity the Git host
+    /// vouches for: the committer of a commit whose signature it verified.
+    /// That is the enabled RepoSync user with the committer's email.
+    /// Returns the reason when no usable credential exists so the caller can
+    /// fall back to the service account.
+    async fn author_svn_client(
+        &self,
+        svn: &SvnClient,
+        sha: &str,
+    ) -> Result<(SvnClient, String), String> {
+        let email = self
+            .github_client()
+            .verified_committer_email(&self.config.github.repo, sha)
+            .await
+            .map_err(|e| format!("could not check the signature: {}", e))?
+            .ok_or_else(|| "the commit has no signature verified by the Git host".to_string())?;
+        let user = self
+            .db
+            .get_user_by_email(&email)
+            .map_err(|e| e.to_string())?
+            .filter(|u| u.enabled)
+            .ok_or_else(|| format!("no RepoSync user for {}", email))?;
+
+        let creds = self
+            .db
+            .list_user_credentials(&user.id)
+            .map_err(|e| e.to_string())?;
+        let cred = select_svn_credential(&creds, svn.url()).ok_or_else(|| {
+            format!("user '{}' has no SVN credential for {}", user.username, svn.url())
+        })?;
+
+        let key =
+            crate::crypto::get_or_create_encryption_key(&self.db).map_err(|e| e.to_string())?;
+        let password = crate::crypto::decrypt_credential(&cred.encrypted_value, &cred.nonce, &key)
+            .map_err(|e| {
+                format!("could not decrypt SVN credential of '{}': {}", user.username, e)
+            })?;
+
+        let username = if cred.username.is_empty() {
+            user.username.clone()
+        } else {
+            cred.username.clone()
+        };
+        let mut client = svn.clone();
+        client.set_username(username.clone());
+        client.set_password(password);
+        Ok((client, username))
+    }
+
     // -----------------------------------------------------------------------
     // Change fetching
     // -----------------------------------------------------------------------
@@ -934,6 +1757,28 @@ impl SyncEngine {
             None
         };
 
+        // When using standard layout, only sync files under trunk/ and strip
+        // the trunk prefix so git paths are repo-relative. The bare trunk/
+        // entry and non-trunk paths (branches/, tags/) are skipped.
+        let map_path = |path: &str| {
+            let raw = path.strip_prefix('/').unwrap_or(path);
+            match trunk_prefix {
+                Some(ref prefix) => raw
+                    .strip_prefix(prefix.as_str())
+                    .filter(|rest| !rest.is_empty())
+                    .map(str::to_string),
+                None => Some(raw.to_string()),
+            }
+        };
+        // Directories keep the root of the synced tree, as "".
+        let map_dir = |path: &str| {
+            let raw = path.strip_prefix('/').unwrap_or(path);
+            match trunk_prefix {
+                Some(ref prefix) if raw == prefix.trim_end_matches('/') => Some(String::new()),
+                _ => map_path(path),
+            }
+        };
+
         let change_sets: Vec<SvnChangeSet> = entries
             .into_iter()
             .filter(|e| !self.is_echo_commit(&e.message))
@@ -942,32 +1787,30 @@ impl SyncEngine {
                 author: e.author,
                 date: e.date,
                 message: e.message,
-                changed_files: e
+                changed_files: pair_svn_renames(
+                    e.changed_paths
+                        .iter()
+                        .filter_map(|p| {
+                            // SVN "R" (replaced) rewrites the path in place; Git
+                            // sees it as a modification.
+                            let action = if p.action == "R" { "M" } else { p.action.as_str() };
+                            Some(ChangedFile {
+                                path: map_path(&p.path)?,
+                                action: action.to_string(),
+                                content: None,
+                                is_binary: false,
+                                // Copies from outside the synced tree are plain adds.
+                                copy_from: p.copy_from_path.as_deref().and_then(map_path),
+                                is_dir: p.kind == "dir",
+                            })
+                        })
+                        .collect(),
+                ),
+                changed_dirs: e
                     .changed_paths
                     .iter()
-                    .filter_map(|p| {
-                        let raw = p.path.strip_prefix('/').unwrap_or(&p.path);
-                        // When using standard layout, only sync files under trunk/
-                        // and strip the trunk prefix so git paths are repo-relative.
-                        let mapped_path = if let Some(ref prefix) = trunk_prefix {
-                            if let Some(rest) = raw.strip_prefix(prefix.as_str()) {
-                                if rest.is_empty() {
-                                    return None; // skip bare trunk/ directory entry
-                                }
-                                rest.to_string()
-                            } else {
-                                return None; // skip non-trunk paths (branches/, tags/)
-                            }
-                        } else {
-                            raw.to_string()
-                        };
-                        Some(ChangedFile {
-                            path: mapped_path,
-                            action: p.action.clone(),
-                            content: None,
-                            is_binary: false,
-                        })
-                    })
+                    .filter(|p| p.kind == "dir" && p.action != "D")
+                    .filter_map(|p| Some((map_dir(&p.path)?, p.action != "M")))
                     .collect(),
                 diff_content: None,
             })
@@ -990,8 +1833,7 @@ impl SyncEngine {
         })?;
 
         // Check per-repo key first, then global key, then commit_map fallback.
-        let per_repo_key = self.effective_repo_id()
-            .map(|rid| format!("last_git_sha_{}", rid));
+        let per_repo_key = self.effective_repo_id().map(|_| self.git_sha_key());
         let repo_table_sha = self.effective_repo_id()
             .and_then(|rid| self.db.get_repo_watermark(rid).ok())
             .map(|(_, sha)| sha)
@@ -1028,21 +1870,38 @@ impl SyncEngine {
             if self.is_echo_commit(&c.message) {
                 continue;
             }
+            // Merging the PR-mode sync branch only brings in SVN revisions.
+            if self.config.sync.mode == SyncMode::Pr {
+                let parents = git.commit_parents(&c.sha).map_err(SyncError::GitError)?;
+                let merges_synced = parents
+                    .iter()
+                    .skip(1)
+                    .any(|p| self.db.is_git_sha_synced(p).unwrap_or(false));
+                if merges_synced {
+                    debug!(sha = %c.sha, "skipping merge of the sync branch");
+                    continue;
+                }
+            }
             // Populate changed_files from the commit's diff.
-            let files = git.get_changed_files(&c.sha).map_err(SyncError::GitError)?;
+            let files = git
+                .get_changed_files_with_renames(&c.sha)
+                .map_err(SyncError::GitError)?;
             let changed_files: Vec<ChangedFile> = files
                 .into_iter()
-                .map(|(action, path)| ChangedFile {
+                .map(|(action, path, copy_from)| ChangedFile {
                     path,
                     action,
                     content: None,
                     is_binary: false,
+                    copy_from,
+                    is_dir: false,
                 })
                 .collect();
             change_sets.push(GitChangeSet {
                 sha: c.sha,
                 author_name: c.author_name,
                 author_email: c.author_email,
+                author_time: c.author_time,
                 message: c.message,
                 changed_files,
             });
@@ -1062,46 +1921,1794 @@ impl SyncEngine {
     // Conflict detection
     // -----------------------------------------------------------------------
 
-    fn detect_conflicts_internal(
+    /// SVN revisions merged in by the extra parents of Git commit `sha`: for
+    /// each, the newest synced commit on its first-parent history, as the
+    /// branch it was synced on (`None` for the mainline) and its revision.
+    fn git_merge_sources(
         &self,
-        svn_changes: &[SvnChangeSet],
-        git_changes: &[GitChangeSet],
-    ) -> Vec<Conflict> {
-        let svn_file_changes: Vec<FileChange> = svn_changes
-            .iter()
-            .flat_map(|cs| {
-                cs.changed_files.iter().map(|f| FileChange {
-                    path: f.path.clone(),
-                    change_kind: match f.action.as_str() {
-                        "A" => ChangeKind::Added,
-                        "D" => ChangeKind::Deleted,
-                        "M" => ChangeKind::Modified,
-                        _ => ChangeKind::Modified,
-                    },
-                    content: f.content.clone(),
-                    is_binary: f.is_binary,
-                })
-            })
-            .collect();
-
-        let git_file_changes: Vec<FileChange> = git_changes
-            .iter()
-            .flat_map(|cs| {
-                cs.changed_files.iter().map(|f| FileChange {
-                    path: f.path.clone(),
-                    change_kind: match f.action.as_str() {
-                        "A" => ChangeKind::Added,
-                        "D" => ChangeKind::Deleted,
-                        "M" => ChangeKind::Modified,
-                        _ => ChangeKind::Modified,
-                    },
+        git: &GitClient,
+        sha: &str,
+    ) -> Result<Vec<(Option<String>, i64)>, SyncError> {
+        let branch_prefix = match self.mainline_repo_id() {
+            Some(rid) => format!("{}:branch:", rid),
+            None => "branch:".to_string(),
+        };
+        let mut sources = Vec::new();
+        for parent in git.commit_parents(sha)?.iter().skip(1) {
+            for commit in git.first_parent_chain(parent, 1000)? {
+                let mainline = self
+                    .db
+                    .get_svn_rev_for_git_sha_in_repo(&commit, self.mainline_repo_id())?;
+                if let Some(rev) = mainline {
+                    sources.push((None, rev));
+                    break;
+                }
+                let branch = self
+                    .db
+                    .find_svn_rev_for_git_sha_by_repo_prefix(&commit, &branch_prefix)?;
+                if let Some((rid, rev)) = branch {
+                    sources.push((rid.strip_prefix(&branch_prefix).map(str::to_string), rev));
+                    break;
+                }
+            }
+        }
+        Ok(sources)
+    }
+
+    /// Move the SVN watermark past a revision that produces no Git commit.
+    fn skip_svn_revision(&self, rev: i64) {
+        let _ = self.db.set_state(&self.svn_rev_key(), &rev.to_string());
+        if let Some(rid) = self.effective_repo_id() {
+            let current_sha = self
+                .db
+                .get_repo_watermark(rid)
+                .map(|(_, sha)| sha)
+                .unwrap_or_default();
+            let _ = self.db.update_repo_watermark(rid, rev, &current_sha);
+        }
+    }
+
+    /// Regenerate the `.gitignore` of every directory whose ignore properties
+    /// may have changed in `change`. Returns whether any file changed.
+    async fn sync_svn_ignores(
+        &self,
+        change: &SvnChangeSet,
+        target: &GitTarget,
+        repo_path: &std::path::Path,
+    ) -> Result<bool, SyncError> {
+        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        let mut changed = false;
+        for (dir, added) in &change.changed_dirs {
+            let svn_dir = self.svn_repo_path(dir).trim_end_matches('/').to_string();
+            let props =
+                svn_props::fetch_ignore_props(&svn, &svn_dir, change.revision, *added).await?;
+            for (sub, props) in props {
+                let git_dir = match (dir.as_str(), sub.as_str()) {
+                    (d, "") => d.to_string(),
+                    ("", s) => s.to_string(),
+                    (d, s) => format!("{}/{}", d, s),
+                };
+                let update = svn_props::write_gitignore(repo_path, &git_dir, &props)
+                    .map_err(|e| SyncError::GitError(GitError::IoError(e)))?;
+                if update == FileUpdate::Removed {
+                    let path = if git_dir.is_empty() {
+                        svn_props::GITIGNORE.to_string()
+                    } else {
+                        format!("{}/{}", git_dir, svn_props::GITIGNORE)
+                    };
+                    let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+                    git.remove_path(&path).map_err(SyncError::GitError)?;
+                }
+                changed |= update != FileUpdate::Unchanged;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Carry the file properties of what `change` adds or modifies over to
+    /// Git: `svn:special` as a symlink, `svn:executable` as the file mode,
+    /// `svn:eol-style` as entries in `.gitattributes` (starting from
+    /// `eol_entries`) and `svn:keywords` by collapsing expanded keywords.
+    async fn sync_svn_file_props(
+        &self,
+        change: &SvnChangeSet,
+        target: &GitTarget,
+        repo_path: &std::path::Path,
+        mut eol_entries: BTreeMap<String, String>,
+    ) -> Result<(), SyncError> {
+        let sync = &self.config.sync;
+        let files: Vec<String> = change
+            .changed_files
+            .iter()
+            .filter(|f| {
+                f.action != "D"
+                    && !f.is_dir
+                    && svn_props::is_file_or_symlink(&repo_path.join(&f.path))
+            })
+            .map(|f| f.path.clone())
+            .collect();
+        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        // One lookup of every property of exactly the changed files.
+        let svn_paths: Vec<String> = files
+            .iter()
+            .map(|path| self.svn_repo_path(path).trim_end_matches('/').to_string())
+            .collect();
+        let props = if files.is_empty() {
+            Vec::new()
+        } else {
+            svn.proplist(&svn_paths, change.revision, false).await?
+        };
+        let mut by_path: HashMap<&str, BTreeMap<&str, &str>> = HashMap::new();
+        for prop in &props {
+            let values = by_path.entry(prop.path.as_str()).or_default();
+            values.insert(prop.name.as_str(), prop.value.trim());
+        }
+        let io_err = |e| SyncError::GitError(GitError::IoError(e));
+
+        for (path, svn_path) in files.iter().zip(&svn_paths) {
+            let path = path.as_str();
+            let full = repo_path.join(path);
+            let values = by_path.get(svn_path.as_str());
+            let prop = |name: &str| values.and_then(|v| v.get(name).copied());
+            if prop(svn_props::SVN_SPECIAL).is_some() {
+                self.link_svn_special(target, path, &full)?;
+                continue;
+            }
+            if svn_props::is_symlink(&full) {
+                // No longer special: the file has content of its own again.
+                let content = svn.cat(&self.svn_repo_path(path), change.revision).await?;
+                svn_props::write_regular_file(&full, content.as_bytes()).map_err(io_err)?;
+            }
+            if sync.sync_executable_bit {
+                let executable = prop(svn_props::SVN_EXECUTABLE).is_some();
+                if svn_props::set_executable(&full, executable).map_err(io_err)? {
+                    debug!(path, executable, "updated file mode from svn:executable");
+                }
+            }
+            let keywords = prop(svn_props::SVN_KEYWORDS).filter(|_| sync.collapse_keywords);
+            if let Some(value) = keywords {
+                let content = std::fs::read(&full).map_err(io_err)?;
+                if let Some(collapsed) = svn_props::collapse_keywords(&content, value) {
+                    std::fs::write(&full, collapsed).map_err(io_err)?;
+                }
+            }
+            if sync.normalize_line_endings {
+                match prop(svn_props::SVN_EOL_STYLE) {
+                    Some(style) => eol_entries.insert(path.to_string(), style.to_string()),
+                    None => eol_entries.remove(path),
+                };
+            }
+        }
+
+        if sync.normalize_line_endings {
+            for file in &change.changed_files {
+                match file.action.as_str() {
+                    "D" => eol_entries.retain(|p, _| !svn_props::is_under(p, &file.path)),
+                    "R" => eol_entries.retain(|p, _| {
+                        !file.copy_from.as_deref().is_some_and(|from| svn_props::is_under(p, from))
+                    }),
+                    _ => {}
+                }
+            }
+            let update = svn_props::write_eol_entries(repo_path, &eol_entries).map_err(io_err)?;
+            if update == FileUpdate::Removed {
+                let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+                git.remove_path(svn_props::GITATTRIBUTES).map_err(SyncError::GitError)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Git commits of the branches an SVN revision merged into the synced
+    /// tree, from the sources whose newest revision grew in the root's
+    /// `svn:mergeinfo`.
+    ///
+    /// Sources without a synced Git commit are left out, so the revision
+    /// still lands, as an ordinary commit.
+    async fn svn_merge_parents(&self, change: &SvnChangeSet) -> Result<Vec<String>, SyncError> {
+        if !change.changed_dirs.iter().any(|(dir, added)| dir.is_empty() && !added) {
+            return Ok(Vec::new());
+        }
+        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        let root = self.svn_repo_path("").trim_end_matches('/').to_string();
+        let new = svn_props::fetch_mergeinfo(&svn, &root, change.revision).await?;
+        let old = match svn_props::fetch_mergeinfo(&svn, &root, change.revision - 1).await {
+            Ok(value) => value,
+            Err(e) if is_missing_path_error(&e) => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let merges = svn_props::new_merges(&old, &new);
+        if merges.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let info = svn.info().await?;
+        let root_svn = svn.with_url(info.root_url.clone());
+        let own_path = self.svn_mainline_path(&info.url, &info.root_url);
+        let mainline = match &self.parent_mainline {
+            Some((path, _)) => path.clone(),
+            None => own_path.clone(),
+        };
+        let mut parents = Vec::new();
+        for (source, source_rev) in merges.into_iter().filter(|(path, _)| *path != own_path) {
+            match self
+                .git_commit_for_svn_copy(&root_svn, &mainline, &source, source_rev)
+                .await?
+            {
+                CopySourceCommit::Synced(sha) if !parents.contains(&sha) => parents.push(sha),
+                CopySourceCommit::Synced(_) => {}
+                CopySourceCommit::Pending | CopySourceCommit::Unknown => warn!(
+                    rev = change.revision,
+                    source = %source,
+                    source_rev,
+                    "no synced Git commit for merge source, committing without it"
+                ),
+            }
+        }
+        Ok(parents)
+    }
+
+    /// Turn the file SVN stores for a symlink (`link <target>`) at `path`
+    /// into a real symlink, or drop it when the policy rejects its target.
+    fn link_svn_special(
+        &self,
+        target: &GitTarget,
+        path: &str,
+        full: &std::path::Path,
+    ) -> Result<(), SyncError> {
+        let io_err = |e| SyncError::GitError(GitError::IoError(e));
+        let link_target = match std::fs::read_link(full) {
+            Ok(link_target) => link_target.to_string_lossy().into_owned(),
+            Err(_) => match svn_props::parse_special(&std::fs::read(full).map_err(io_err)?) {
+                Some(link_target) => link_target,
+                None => {
+                    warn!(path, "file with svn:special is not a symlink, keeping it as is");
+                    return Ok(());
+                }
+            },
+        };
+        let policy = FilePolicy::new(0, Vec::new())
+            .reject_external_symlinks(self.config.sync.reject_external_symlinks);
+        if policy.evaluate_symlink(path, &link_target).is_blocked() {
+            let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+            return git.remove_path(path).map_err(SyncError::GitError);
+        }
+        if !svn_props::is_symlink(full) {
+            svn_props::write_symlink(full, &link_target).map_err(io_err)?;
+        }
+        Ok(())
+    }
+
+    /// Mirror the `svn:externals` changes in `change`: rewrite each
+    /// `.svnexternals` file, materialize new or changed externals and drop
+    /// removed ones. Returns whether anything changed.
+    async fn sync_svn_externals(
+        &self,
+        change: &SvnChangeSet,
+        target: &GitTarget,
+        repo_path: &std::path::Path,
+    ) -> Result<bool, SyncError> {
+        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        let mut repo_root: Option<String> = None;
+        let mut changed = false;
+        for (dir, added) in &change.changed_dirs {
+            let svn_dir = self.svn_repo_path(dir).trim_end_matches('/').to_string();
+            let values =
+                svn_props::fetch_externals(&svn, &svn_dir, change.revision, *added).await?;
+            for (sub, value) in values {
+                let git_dir = svn_props::join_path(dir, &sub);
+                let file = svn_props::join_path(&git_dir, svn_props::SVNEXTERNALS);
+                let old = std::fs::read_to_string(repo_path.join(&file))
+                    .map(|c| svn_props::parse_externals(&svn_props::from_svnexternals(&c)))
+                    .unwrap_or_default();
+                let new = svn_props::parse_externals(&value);
+
+                for ext in old.iter().filter(|e| !new.contains(e)) {
+                    let path = svn_props::join_path(&git_dir, &ext.path);
+                    debug!(path = %path, "dropping SVN external");
+                    let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+                    git.remove_submodule(&path).map_err(SyncError::GitError)?;
+                    changed = true;
+                }
+                for ext in new.iter().filter(|e| !old.contains(e)) {
+                    let root = match &repo_root {
+                        Some(root) => root.clone(),
+                        None => svn.info().await?.root_url,
+                    };
+                    repo_root = Some(root.clone());
+                    let dir_url = match self.svn_repo_path(&git_dir).trim_end_matches('/') {
+                        "" => svn.url().trim_end_matches('/').to_string(),
+                        d => format!("{}/{}", svn.url().trim_end_matches('/'), d),
+                    };
+                    let url = ext.resolve_url(&dir_url, &root);
+                    let path = svn_props::join_path(&git_dir, &ext.path);
+                    self.materialize_external(&svn, ext, &url, &path, target, repo_path)
+                        .await?;
+                    changed = true;
+                }
+
+                let update = svn_props::write_svnexternals(repo_path, &git_dir, &value)
+                    .map_err(|e| SyncError::GitError(GitError::IoError(e)))?;
+                if update == FileUpdate::Removed {
+                    let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+                    git.remove_path(&file).map_err(SyncError::GitError)?;
+                }
+                changed |= update != FileUpdate::Unchanged;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Put one SVN external at `path` in the Git tree: a submodule when `url`
+    /// is another synced repository, otherwise an export of the external.
+    async fn materialize_external(
+        &self,
+        svn: &SvnClient,
+        ext: &SvnExternal,
+        url: &str,
+        path: &str,
+        target: &GitTarget,
+        repo_path: &std::path::Path,
+    ) -> Result<(), SyncError> {
+        if let Some((git_url, sha)) = self.external_submodule(url, ext.pinned_rev()) {
+            info!(path, url, git_url = %git_url, sha = %sha, "mapping SVN external to submodule");
+            let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+            git.set_submodule(path, &git_url, &sha)
+                .map_err(SyncError::GitError)?;
+            return Ok(());
+        }
+
+        {
+            let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+            git.remove_submodule(path).map_err(SyncError::GitError)?;
+        }
+        let ext_svn = match ext.peg_rev {
+            Some(peg) => svn.with_url(format!("{}@{}", url, peg)),
+            None => svn.with_url(url),
+        };
+        let rev = match ext.pinned_rev() {
+            Some(rev) => rev,
+            None => ext_svn.info().await?.latest_rev,
+        };
+        let dst = repo_path.join(path);
+        if let Some(parent) = dst.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| SyncError::GitError(GitError::IoError(e)))?;
+        }
+        ext_svn.export("", rev, &dst).await?;
+        info!(path, url, rev, "vendored SVN external");
+        Ok(())
+    }
+
+    /// The Git remote and commit for an external pointing at the tree of
+    /// another synced repository, as of `rev` (latest synced if unpinned).
+    fn external_submodule(&self, url: &str, rev: Option<i64>) -> Option<(String, String)> {
+        let url = url.trim_end_matches('/');
+        let repos = self.db.list_repositories().ok()?;
+        let repo = repos.iter().find(|r| {
+            Some(r.id.as_str()) != self.effective_repo_id()
+                && !r.git_repo.is_empty()
+                && r.synced_svn_url().trim_end_matches('/') == url
+        })?;
+        let pair = match rev {
+            Some(rev) => self.db.get_commit_pair_at_or_before(rev, Some(&repo.id)),
+            None => self.db.get_last_commit_pair(Some(&repo.id)),
+        };
+        let (_, sha) = pair.ok()??;
+        let git_url = crate::git::remote_url::derive_git_remote_url(
+            &repo.git_api_url,
+            None,
+            &repo.git_repo,
+        );
+        Some((git_url, sha))
+    }
+
+    /// Stage the removal of whatever an SVN change takes away in Git: deleted
+    /// paths, the old path of a rename, and a directory replaced by a copy.
+    fn remove_replaced_git_paths(
+        &self,
+        target: &GitTarget,
+        file: &ChangedFile,
+    ) -> Result<(), SyncError> {
+        let mut paths: Vec<&str> = Vec::new();
+        match file.action.as_str() {
+            "D" => paths.push(&file.path),
+            "R" => paths.extend(file.copy_from.as_deref()),
+            _ => {}
+        }
+        if file.is_dir && file.copy_from.is_some() {
+            paths.push(&file.path);
+        }
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+        for path in paths {
+            git.remove_path(path).map_err(SyncError::GitError)?;
+        }
+        Ok(())
+    }
+
+    /// Report an unresolved conflict through the notifier, if one is set.
+    async fn notify_conflict(&self, conflict: &Conflict) {
+        let Some(notifier) = &self.notifier else {
+            return;
+        };
+        if let Err(e) = notifier
+            .notify_conflict(self.repo_name.as_deref(), conflict)
+            .await
+        {
+            warn!(file = %conflict.file_path, error = %e, "conflict notification failed");
+        }
+    }
+
+    fn detect_conflicts_internal(
+        &self,
+        svn_changes: &[SvnChangeSet],
+        git_changes: &[GitChangeSet],
+    ) -> Vec<Conflict> {
+        let svn_file_changes: Vec<FileChange> = svn_changes
+            .iter()
+            .flat_map(|cs| {
+                cs.changed_files.iter().map(|f| FileChange {
+                    path: f.path.clone(),
+                    change_kind: change_kind_of(f),
                     content: f.content.clone(),
                     is_binary: f.is_binary,
                 })
             })
             .collect();
+        // Keep only the latest change per path, matching how the detector
+        // indexes the Git side.
+        let mut seen = HashSet::new();
+        let mut svn_file_changes: Vec<FileChange> = svn_file_changes
+            .into_iter()
+            .rev()
+            .filter(|c| seen.insert(c.path.clone()))
+            .collect();
+        svn_file_changes.reverse();
+
+        let git_file_changes: Vec<FileChange> = git_changes
+            .iter()
+            .flat_map(|cs| {
+                cs.changed_files.iter().map(|f| FileChange {
+                    path: f.path.clone(),
+                    change_kind: change_kind_of(f),
+                    content: f.content.clone(),
+                    is_binary: f.is_binary,
+                })
+            })
+            .collect();
+
+        ConflictDetector::detect(&svn_file_changes, &git_file_changes)
+    }
+
+    /// Load both sides' content and the merge base for every path changed on
+    /// both sides in this cycle, so the detector and merger can work on real
+    /// text. Only the latest change per path and side is loaded. The loaded
+    /// text is normalized for comparison; the raw bytes are kept alongside.
+    ///
+    /// The base is the file as of the last `commit_map` pairing, read from
+    /// Git and falling back to SVN when the Git commit is not available.
+    async fn load_overlap_contents(
+        &self,
+        svn_changes: &mut [SvnChangeSet],
+        git_changes: &mut [GitChangeSet],
+    ) -> Result<HashMap<String, OverlapInfo>, SyncError> {
+        let git_paths: HashSet<&str> = git_changes
+            .iter()
+            .flat_map(|cs| cs.changed_files.iter().map(|f| f.path.as_str()))
+            .collect();
+        let overlap: HashSet<String> = svn_changes
+            .iter()
+            .flat_map(|cs| cs.changed_files.iter().map(|f| f.path.as_str()))
+            .filter(|p| git_paths.contains(p))
+            .map(str::to_string)
+            .collect();
+        if overlap.is_empty() {
+            return Ok(HashMap::new());
+        }
+        debug!(count = overlap.len(), "loading contents for paths changed on both sides");
+
+        // SVN side: latest revision touching each path.
+        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        let mut svn_revs: HashMap<String, i64> = HashMap::new();
+        let mut svn_raws: HashMap<String, Vec<u8>> = HashMap::new();
+        for cs in svn_changes.iter_mut().rev() {
+            for file in cs.changed_files.iter_mut() {
+                if !overlap.contains(&file.path) || svn_revs.contains_key(&file.path) {
+                    continue;
+                }
+                svn_revs.insert(file.path.clone(), cs.revision);
+                if file.action == "D" {
+                    continue;
+                }
+                match svn.cat_bytes(&self.svn_repo_path(&file.path), cs.revision).await {
+                    Ok(bytes) => {
+                        svn_raws.insert(file.path.clone(), bytes.clone());
+                        set_changed_file_content(file, bytes);
+                        file.content = file.content.take().map(|c| self.comparable_text(c));
+                    }
+                    Err(e) => warn!(file = %file.path, rev = cs.revision, error = %e, "svn cat failed"),
+                }
+            }
+        }
+
+        let base_pair = self
+            .db
+            .get_last_commit_pair(self.effective_repo_id())
+            .map_err(SyncError::DatabaseError)?;
+
+        // Git side and base: local object lookups only.
+        let mut git_shas: HashMap<String, String> = HashMap::new();
+        let mut git_raws: HashMap<String, Vec<u8>> = HashMap::new();
+        // Paths missing here could not be read from Git and fall back to SVN.
+        let mut git_bases: HashMap<String, Option<Vec<u8>>> = HashMap::new();
+        {
+            let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+            for cs in git_changes.iter_mut().rev() {
+                for file in cs.changed_files.iter_mut() {
+                    if !overlap.contains(&file.path) || git_shas.contains_key(&file.path) {
+                        continue;
+                    }
+                    git_shas.insert(file.path.clone(), cs.sha.clone());
+                    if file.action == "D" {
+                        continue;
+                    }
+                    match git.get_file_content_at_commit(&cs.sha, &file.path) {
+                        Ok(Some(bytes)) => {
+                            git_raws.insert(file.path.clone(), bytes.clone());
+                            set_changed_file_content(file, bytes);
+                            file.content = file.content.take().map(|c| self.comparable_text(c));
+                        }
+                        Ok(None) => {}
+                        Err(e) => warn!(file = %file.path, sha = %cs.sha, error = %e, "failed to read Git content"),
+                    }
+                }
+            }
+            if let Some((_, ref base_sha)) = base_pair {
+                for path in &overlap {
+                    if let Ok(base) = git.get_file_content_at_commit(base_sha, path) {
+                        git_bases.insert(path.clone(), base);
+                    }
+                }
+            }
+        }
+
+        let mut result = HashMap::new();
+        for path in overlap {
+            let base_bytes = match (&base_pair, git_bases.remove(&path)) {
+                (_, Some(bytes)) => bytes,
+                (Some((base_rev, _)), None) => svn
+                    .cat_bytes(&self.svn_repo_path(&path), *base_rev)
+                    .await
+                    .ok(),
+                (None, None) => None,
+            };
+            let base_content = base_bytes
+                .filter(|b| !is_binary_content(b))
+                .and_then(|b| String::from_utf8(b).ok())
+                .map(|c| self.comparable_text(c));
+            let (Some(svn_rev), Some(git_sha)) = (svn_revs.get(&path), git_shas.remove(&path))
+            else {
+                continue;
+            };
+            let info = OverlapInfo {
+                svn_rev: *svn_rev,
+                git_sha,
+                base_content,
+                svn_raw: svn_raws.remove(&path),
+                git_raw: git_raws.remove(&path),
+            };
+            result.insert(path, info);
+        }
+        Ok(result)
+    }
+
+    /// Text as compared and merged across both sides, so that line-ending
+    /// and keyword-expansion differences do not show up as edits.
+    fn comparable_text(&self, mut text: String) -> String {
+        if self.config.sync.collapse_keywords {
+            if let Some(collapsed) = svn_props::collapse_all_keywords(text.as_bytes()) {
+                text = String::from_utf8(collapsed).unwrap_or(text);
+            }
+        }
+        if self.config.sync.normalize_line_endings {
+            text = svn_props::normalize_eol(&text).into_owned();
+        }
+        text
+    }
+
+    // -----------------------------------------------------------------------
+    // Conflict resolution
+    // -----------------------------------------------------------------------
+
+    /// Apply a manual conflict resolution to the repositories.
+    ///
+    /// The chosen content is committed to the losing side -- Git for
+    /// `AcceptSvn`, SVN for `AcceptGit`, both for `AcceptMerged` -- with the
+    /// sync marker so the next cycle does not echo it back. Git commits go to
+    /// the default branch, or in PR mode to the sync branch. The new commit is
+    /// recorded in `commit_map` and the conflict is marked resolved, which
+    /// lets the sync pick up further changes to the path again.
+    ///
+    /// Takes the sync lock, so it fails with [`SyncError::AlreadyRunning`]
+    /// while a cycle is in progress.
+    pub async fn apply_conflict_resolution(
+        &self,
+        conflict_id: &str,
+        resolution: &Resolution,
+        resolved_by: &str,
+    ) -> Result<ResolutionOutcome, SyncError> {
+        if *resolution == Resolution::Deferred {
+            ConflictResolver::defer(conflict_id, resolved_by, &self.db)?;
+            return Ok(ResolutionOutcome::default());
+        }
+
+        if self
+            .running
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err(SyncError::AlreadyRunning {
+                started_at: self.started_at.to_rfc3339(),
+            });
+        }
+        let _guard = SyncLockGuard(self.running.clone());
+
+        let conflict = self
+            .db
+            .get_conflict(conflict_id)?
+            .ok_or_else(|| ConflictError::NotFound(conflict_id.to_string()))?;
+        if conflict.status == "resolved" {
+            return Err(ConflictError::AlreadyResolved(conflict_id.to_string()).into());
+        }
+        // The resolution is committed to this engine's SVN URL and working
+        // copy, so it must be the engine that recorded the conflict.
+        if let Some(ref repo_id) = conflict.repo_id {
+            if self.repo_id.as_ref() != Some(repo_id) {
+                return Err(ConflictError::WrongRepository {
+                    id: conflict_id.to_string(),
+                    repo_id: repo_id.clone(),
+                }
+                .into());
+            }
+        }
+        let _repo_claim = self.claim_repo()?;
+
+        self.reload_credentials();
+
+        // Bring the Git working tree up to date before reading or writing it,
+        // then pick the branch resolutions are committed to: the default
+        // branch, or in PR mode the sync branch, as in `do_sync_cycle`.
+        let git_head_content = tokio::task::block_in_place(|| {
+            let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+            git.pull("origin", &self.config.github.default_branch, None)?;
+            let head = git.get_head_sha()?;
+            git.get_file_content_at_commit(&head, &conflict.file_path)
+        })?;
+        let (target, open_pr) = match self.config.sync.mode {
+            SyncMode::Direct => (
+                GitTarget {
+                    client: self.git_client.clone(),
+                    branch: self.config.github.default_branch.clone(),
+                },
+                None,
+            ),
+            SyncMode::Pr => self.prepare_sync_branch().await?,
+        };
+        let target_content = match self.config.sync.mode {
+            SyncMode::Direct => git_head_content.clone(),
+            SyncMode::Pr => tokio::task::block_in_place(|| {
+                let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+                let head = git.get_head_sha()?;
+                git.get_file_content_at_commit(&head, &conflict.file_path)
+            })?,
+        };
+
+        // The stored conflict text is normalized for comparison and display
+        // only, so the winning side's raw bytes are read again here. `None`
+        // means the winning side deleted the file.
+        let (svn_head_rev, svn_head_content) = self.svn_head_content(&conflict.file_path).await?;
+        let (content, label, write_svn, write_git) = match resolution {
+            Resolution::AcceptSvn => (svn_head_content, "accept_svn", false, true),
+            Resolution::AcceptGit => (git_head_content, "accept_git", true, false),
+            Resolution::AcceptMerged(merged) => {
+                (Some(merged.clone().into_bytes()), "accept_merged", true, true)
+            }
+            Resolution::Deferred => unreachable!("deferred handled above"),
+        };
+
+        let message = format!(
+            "Resolve conflict on {} ({})\n\n{} conflict {} resolved by {}",
+            conflict.file_path, label, SYNC_MARKER, conflict_id, resolved_by
+        );
+
+        let mut outcome = ResolutionOutcome::default();
+        if write_svn {
+            outcome.svn_revision = self
+                .commit_resolution_to_svn(&conflict.file_path, content.as_deref(), &message)
+                .await?;
+        }
+        if write_git && target_content != content {
+            outcome.git_sha = Some(self.commit_resolution_to_git(
+                &target,
+                &conflict.file_path,
+                content.as_deref(),
+                &message,
+            )?);
+            if self.config.sync.mode == SyncMode::Pr {
+                self.update_sync_pull_request(&target, open_pr).await?;
+            }
+        }
+
+        // Pair the new commit with the revision that holds the same content
+        // on the other side.
+        if outcome.svn_revision.is_some() || outcome.git_sha.is_some() {
+            let (svn_rev, git_sha, direction) = match (&outcome.svn_revision, &outcome.git_sha) {
+                (Some(rev), Some(sha)) => (*rev, sha.clone(), "svn_to_git"),
+                (None, Some(sha)) => (svn_head_rev, sha.clone(), "svn_to_git"),
+                (Some(rev), None) => {
+                    let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+                    (*rev, git.get_head_sha()?, "git_to_svn")
+                }
+                (None, None) => unreachable!(),
+            };
+            self.db.insert_commit_map_with_repo(
+                svn_rev,
+                &git_sha,
+                direction,
+                resolved_by,
+                resolved_by,
+                self.effective_repo_id(),
+            )?;
+            let _ = self.db.insert_audit_log_with_repo(
+                "conflict_applied",
+                Some(direction),
+                Some(svn_rev),
+                Some(&git_sha),
+                Some(resolved_by),
+                Some(&format!(
+                    "applied {} for '{}' -> SVN r{}, Git {}",
+                    label,
+                    conflict.file_path,
+                    svn_rev,
+                    &git_sha[..8.min(git_sha.len())]
+                )),
+                true,
+                self.repo_id.as_deref(),
+            );
+        }
+
+        match resolution {
+            Resolution::AcceptSvn => ConflictResolver::accept_svn(conflict_id, resolved_by, &self.db)?,
+            Resolution::AcceptGit => ConflictResolver::accept_git(conflict_id, resolved_by, &self.db)?,
+            Resolution::AcceptMerged(merged) => {
+                ConflictResolver::accept_merged(conflict_id, merged, resolved_by, &self.db)?
+            }
+            Resolution::Deferred => unreachable!("deferred handled above"),
+        }
+
+        info!(
+            conflict_id,
+            file = %conflict.file_path,
+            svn_rev = ?outcome.svn_revision,
+            git_sha = ?outcome.git_sha,
+            "applied conflict resolution"
+        );
+        Ok(outcome)
+    }
+
+    /// Paths whose changes are held back until their conflict is resolved.
+    fn held_conflict_paths(&self) -> Result<std::collections::HashSet<String>, SyncError> {
+        let paths = self
+            .db
+            .list_active_conflict_paths(self.effective_repo_id())
+            .map_err(SyncError::DatabaseError)?;
+        Ok(paths.into_iter().collect())
+    }
+
+    /// Map a Git-relative path to its location under the SVN URL.
+    fn svn_repo_path(&self, file_path: &str) -> String {
+        let trunk = self.config.svn.trunk_path.trim_matches('/');
+        if self.config.svn.layout == SvnLayout::Standard && !trunk.is_empty() {
+            format!("{}/{}", trunk, file_path)
+        } else {
+            file_path.to_string()
+        }
+    }
+
+    /// Read the raw bytes of a file at SVN HEAD, returning `None` if it does
+    /// not exist, together with the revision that holds that state: the last
+    /// one changing the file, or HEAD when it is absent.
+    async fn svn_head_content(
+        &self,
+        file_path: &str,
+    ) -> Result<(i64, Option<Vec<u8>>), SyncError> {
+        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        let head = svn.info().await?.latest_rev;
+        let svn_path = self.svn_repo_path(file_path);
+        match svn.cat_bytes(&svn_path, head).await {
+            Ok(content) => {
+                let rev = svn.last_changed_rev(&svn_path, head).await?.unwrap_or(head);
+                Ok((rev, Some(content)))
+            }
+            Err(e) if is_missing_path_error(&e) => Ok((head, None)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write resolved content into a fresh SVN working copy and commit it.
+    ///
+    /// The working copy is a sparse checkout of the synced trunk or branch
+    /// holding just the file and its parent directories.  Returns `None` if
+    /// SVN already holds the content.
+    async fn commit_resolution_to_svn(
+        &self,
+        file_path: &str,
+        content: Option<&[u8]>,
+        message: &str,
+    ) -> Result<Option<i64>, SyncError> {
+        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        let svn = match self.svn_repo_path("").trim_end_matches('/') {
+            "" => svn,
+            root => svn.with_url(format!("{}/{}", svn.url().trim_end_matches('/'), root)),
+        };
+        let wc_dir = tempfile::tempdir().map_err(SvnError::IoError)?;
+        svn.checkout_head_empty(wc_dir.path()).await?;
+        // Bring in the parent directories that exist in SVN, then the file.
+        let mut parents_exist = true;
+        for (end, _) in file_path.match_indices('/') {
+            let dir = &file_path[..end];
+            svn.update_target(wc_dir.path(), dir, Some("empty")).await?;
+            if !wc_dir.path().join(dir).is_dir() {
+                parents_exist = false;
+                break;
+            }
+        }
+        if parents_exist {
+            svn.update_target(wc_dir.path(), file_path, None).await?;
+        }
+
+        let dst = wc_dir.path().join(file_path);
+        match content {
+            Some(bytes) => {
+                let is_new = !dst.exists();
+                if !is_new && std::fs::read(&dst).map_err(SvnError::IoError)? == bytes {
+                    debug!(file = %file_path, "SVN already holds resolved content");
+                    return Ok(None);
+                }
+                if let Some(parent) = dst.parent() {
+                    std::fs::create_dir_all(parent).map_err(SvnError::IoError)?;
+                }
+                std::fs::write(&dst, bytes).map_err(SvnError::IoError)?;
+                if is_new {
+                    svn.add(wc_dir.path(), &[file_path]).await?;
+                }
+            }
+            None => {
+                if !dst.exists() {
+                    debug!(file = %file_path, "file already absent from SVN");
+                    return Ok(None);
+                }
+                svn.rm(wc_dir.path(), &[file_path]).await?;
+            }
+        }
+
+        let rev = svn.commit(wc_dir.path(), message, "reposync").await?;
+        Ok(Some(rev))
+    }
+
+    /// Write resolved content into the working tree of `target`, commit and
+    /// push it with the client's configured credentials.
+    fn commit_resolution_to_git(
+        &self,
+        target: &GitTarget,
+        file_path: &str,
+        content: Option<&[u8]>,
+        message: &str,
+    ) -> Result<String, SyncError> {
+        tokio::task::block_in_place(|| {
+            let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+            match content {
+                Some(bytes) => {
+                    let dst = git.repo_path().join(file_path);
+                    if let Some(parent) = dst.parent() {
+                        std::fs::create_dir_all(parent).map_err(crate::errors::GitError::IoError)?;
+                    }
+                    std::fs::write(&dst, bytes).map_err(crate::errors::GitError::IoError)?;
+                }
+                None => git.remove_path(file_path)?,
+            }
+
+            let oid = git.commit(
+                message,
+                "reposync",
+                "sync@reposync.local",
+                "reposync",
+                "sync@reposync.local",
+            )?;
+            git.push("origin", &target.branch, None)?;
+            Ok(oid.to_string())
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Branch synchronization
+    // -----------------------------------------------------------------------
+
+    /// Synchronize the SVN branches and Git branches selected by
+    /// `sync.sync_branches`.
+    ///
+    /// Branches are discovered under `svn.branches_path` and among the remote
+    /// Git branches, paired by name mapping (see [`crate::ref_map`]), created
+    /// on whichever side is missing, and then synced by a child engine with
+    /// its own Git worktree and watermarks. A failing pair does not stop the
+    /// others; the first failure is returned after all pairs have run.
+    ///
+    /// Returns the `(svn_to_git, git_to_svn)` commit counts.
+    async fn sync_branches(&self) -> Result<(usize, usize), SyncError> {
+        let mappings = RefMapping::parse_all(&self.config.sync.sync_branches);
+        let default_branch = self.config.github.default_branch.clone();
+
+        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        let svn_info = svn.info().await?;
+        let root_svn = svn.with_url(svn_info.root_url.trim_end_matches('/'));
+        let trunk_svn_path = self.svn_mainline_path(&svn_info.url, &svn_info.root_url);
+        let branches_path = self.svn_layout_path(&trunk_svn_path, &self.config.svn.branches_path);
+
+        let svn_branches = match root_svn.list_branches(&branches_path).await {
+            Ok(names) => names,
+            Err(e) if is_missing_path_error(&e) => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let git_branches = {
+            let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+            git.list_remote_branches("origin")?
+        };
+
+        // Branch pairs created by hand as child repositories sync themselves.
+        let mut manual: HashSet<String> = HashSet::new();
+        if let Some(rid) = self.effective_repo_id() {
+            let root_url = svn_info.root_url.trim_end_matches('/');
+            for child in self.db.list_child_repositories(rid)? {
+                let base = child.svn_url.trim_end_matches('/').strip_prefix(root_url);
+                let base = base.unwrap_or_default().trim_matches('/');
+                manual.insert(svn_props::join_path(base, child.svn_branch.trim_matches('/')));
+                manual.insert(child.git_branch);
+            }
+        }
+
+        let pairs: Vec<BranchPair> =
+            plan_branch_pairs(&mappings, &svn_branches, &git_branches, &default_branch)
+                .into_iter()
+                .filter(|pair| {
+                    !manual.contains(&pair.git_name)
+                        && !manual.contains(&format!("{}/{}", branches_path, pair.svn_name))
+                })
+                .collect();
+        debug!(count = pairs.len(), "branch pairs to sync");
+
+        let mut counts = (0, 0);
+        let mut first_error: Option<SyncError> = None;
+        for pair in &pairs {
+            match self.sync_branch_pair(&root_svn, &trunk_svn_path, pair).await {
+                Ok((to_git, to_svn)) => {
+                    counts.0 += to_git;
+                    counts.1 += to_svn;
+                }
+                Err(e) => {
+                    warn!(
+                        svn_branch = %pair.svn_name,
+                        git_branch = %pair.git_name,
+                        error = %e,
+                        "branch sync failed"
+                    );
+                    let _ = self.db.insert_audit_log_with_repo(
+                        "branch_sync",
+                        None,
+                        None,
+                        None,
+                        None,
+                        Some(&format!("{} <-> {}: {}", pair.svn_name, pair.git_name, e)),
+                        false,
+                        self.repo_id.as_deref(),
+                    );
+                    first_error.get_or_insert(SyncError::BranchSyncFailed {
+                        branch: pair.svn_name.clone(),
+                        detail: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(counts),
+        }
+    }
+
+    /// Create the missing side of a branch pair if needed, then run one sync
+    /// cycle for it.
+    async fn sync_branch_pair(
+        &self,
+        root_svn: &SvnClient,
+        trunk_svn_path: &str,
+        pair: &BranchPair,
+    ) -> Result<(usize, usize), SyncError> {
+        let branches_path = self.svn_layout_path(trunk_svn_path, &self.config.svn.branches_path);
+        let svn_path = format!("{}/{}", branches_path, pair.svn_name);
+        let child_id = self.branch_repo_id(&pair.svn_name);
+
+        // Pairing of the new branch's first SVN revision and Git commit.
+        let mut seed: Option<(i64, String, &str)> = None;
+        if !pair.git_exists {
+            let (rev, sha) = self
+                .create_git_branch(root_svn, trunk_svn_path, &svn_path, pair)
+                .await?;
+            seed = Some((rev, sha, "svn_to_git"));
+        } else if !pair.svn_exists {
+            let (rev, sha) = self.create_svn_branch(root_svn, trunk_svn_path, pair).await?;
+            seed = Some((rev, sha, "git_to_svn"));
+        }
+
+        let mut config = self.config.clone();
+        config.svn.layout = SvnLayout::Standard;
+        config.svn.trunk_path = svn_path;
+        config.svn.branches_path = branches_path;
+        config.svn.tags_path = self.svn_layout_path(trunk_svn_path, &self.config.svn.tags_path);
+        config.github.default_branch = pair.git_name.clone();
+        config.sync.sync_branches.clear();
+        config.sync.sync_tags = false;
+        config.sync.mode = SyncMode::Direct;
+
+        let worktree = tokio::task::block_in_place(|| {
+            let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+            let dir = git
+                .repo_path()
+                .with_file_name("branches")
+                .join(pair.git_name.replace('/', "--"));
+            git.open_worktree(&pair.git_name, &dir)
+        })?;
+
+        let mut child = SyncEngine {
+            config,
+            db: self.db.clone(),
+            svn_client: std::sync::Mutex::new(root_svn.clone()),
+            git_client: Arc::new(std::sync::Mutex::new(worktree)),
+            identity_mapper: self.identity_mapper.clone(),
+            running: Arc::new(AtomicBool::new(false)),
+            started_at: Utc::now(),
+            repo_id: None,
+            credentials_repo_id: self.effective_repo_id().map(str::to_string),
+            notifier: self.notifier.clone(),
+            repo_name: self.repo_name.clone(),
+            parent_mainline: Some((
+                trunk_svn_path.to_string(),
+                self.effective_repo_id().map(str::to_string),
+            )),
+        };
+        child.set_repo_id(child_id);
+
+        match seed {
+            Some((rev, sha, direction)) => child.seed_watermarks(rev, &sha, direction)?,
+            None if child.db.get_state(&child.svn_rev_key())?.is_none() => {
+                // Both branches predate the pairing: assume they match now.
+                let head = root_svn.info().await?.latest_rev;
+                let tip = {
+                    let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+                    git.remote_branch_sha("origin", &pair.git_name)?
+                };
+                if let Some(tip) = tip {
+                    info!(
+                        svn_branch = %pair.svn_name,
+                        git_branch = %pair.git_name,
+                        svn_rev = head,
+                        "pairing existing branches at their current heads"
+                    );
+                    child.seed_watermarks(head, &tip, "svn_to_git")?;
+                }
+            }
+            None => {}
+        }
+
+        if !pair.git_exists {
+            tokio::task::block_in_place(|| {
+                let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+                let token = self.config.github.token.as_deref();
+                git.push("origin", &pair.git_name, token)
+            })?;
+        }
+
+        let stats = child.boxed_sync_cycle().await?;
+        Ok((stats.svn_to_git_count, stats.git_to_svn_count))
+    }
+
+    /// [`run_sync_cycle`](Self::run_sync_cycle) behind a boxed future, for
+    /// branch engines started from within a cycle.
+    fn boxed_sync_cycle(&self) -> BoxedSyncCycle<'_> {
+        Box::pin(self.run_sync_cycle())
+    }
+
+    /// Create the local Git branch for an SVN branch, starting from the Git
+    /// commit that matches the SVN copy source.
+    ///
+    /// Returns the branch's creating SVN revision and the start commit.
+    async fn create_git_branch(
+        &self,
+        root_svn: &SvnClient,
+        trunk_svn_path: &str,
+        svn_path: &str,
+        pair: &BranchPair,
+    ) -> Result<(i64, String), SyncError> {
+        let created = root_svn.creation_entry(svn_path).await?;
+        let created_rev = created.as_ref().map(|e| e.revision).unwrap_or(0);
+        let copy_source = created.as_ref().and_then(|entry| {
+            entry
+                .changed_paths
+                .iter()
+                .find(|p| p.path.trim_matches('/') == svn_path)
+                .and_then(|p| Some((p.copy_from_path.clone()?, p.copy_from_rev?)))
+        });
+
+        let fork = match &copy_source {
+            Some((path, rev)) => {
+                self.git_commit_for_svn_copy(root_svn, trunk_svn_path, path, *rev)
+                    .await?
+            }
+            None => CopySourceCommit::Unknown,
+        };
+
+        let start_sha = match fork {
+            CopySourceCommit::Synced(sha) => sha,
+            CopySourceCommit::Pending | CopySourceCommit::Unknown => {
+                warn!(
+                    svn_branch = %pair.svn_name,
+                    copy_source = ?copy_source,
+                    "no synced commit for the branch point, starting from the default branch"
+                );
+                let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+                git.remote_branch_sha("origin", &self.config.github.default_branch)?
+                    .ok_or_else(|| SyncError::BranchSyncFailed {
+                        branch: pair.svn_name.clone(),
+                        detail: "default branch not found on origin".into(),
+                    })?
+            }
+        };
+
+        let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+        if !git.list_branches()?.contains(&pair.git_name) {
+            git.create_branch(&pair.git_name, &start_sha)?;
+        }
+        info!(
+            svn_branch = %pair.svn_name,
+            git_branch = %pair.git_name,
+            from = %start_sha,
+            "created Git branch for SVN branch"
+        );
+        Ok((created_rev, start_sha))
+    }
+
+    /// Find the Git commit matching the content of an SVN copy source, which
+    /// is either the mainline or a synced branch.
+    async fn git_commit_for_svn_copy(
+        &self,
+        root_svn: &SvnClient,
+        trunk_svn_path: &str,
+        source_path: &str,
+        source_rev: i64,
+    ) -> Result<CopySourceCommit, SyncError> {
+        let source_path = source_path.trim_matches('/');
+        let branches_prefix = format!(
+            "{}/",
+            self.svn_layout_path(trunk_svn_path, &self.config.svn.branches_path)
+        );
+        let scope = if source_path == trunk_svn_path {
+            self.mainline_repo_id().map(str::to_string)
+        } else if let Some(name) = source_path.strip_prefix(&branches_prefix) {
+            Some(self.branch_repo_id(name))
+        } else {
+            return Ok(CopySourceCommit::Unknown);
+        };
+
+        // The source content is that of the last revision that changed it.
+        let changed_rev = match root_svn.last_changed_rev(source_path, source_rev).await? {
+            Some(rev) => rev,
+            None => return Ok(CopySourceCommit::Unknown),
+        };
+        let synced_rev = self
+            .db
+            .get_state(&svn_rev_key_for(scope.as_deref()))?
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(0);
+
+        Ok(
+            match self.db.get_commit_pair_at_or_before(source_rev, scope.as_deref())? {
+                Some((rev, sha)) if rev >= changed_rev => CopySourceCommit::Synced(sha),
+                _ if changed_rev > synced_rev => CopySourceCommit::Pending,
+                _ => CopySourceCommit::Unknown,
+            },
+        )
+    }
+
+    /// Copy the SVN mainline to a new branch for a Git branch, at the newest
+    /// synced revision on the branch's first-parent history from its merge
+    /// base with the default branch.
+    ///
+    /// Returns the copy revision and the Git commit it corresponds to.
+    async fn create_svn_branch(
+        &self,
+        root_svn: &SvnClient,
+        trunk_svn_path: &str,
+        pair: &BranchPair,
+    ) -> Result<(i64, String), SyncError> {
+        let chain = {
+            let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+            let branch_tip = git.remote_branch_sha("origin", &pair.git_name)?;
+            let main_tip = git.remote_branch_sha("origin", &self.config.github.default_branch)?;
+            match (branch_tip, main_tip) {
+                (Some(b), Some(m)) => git.first_parent_chain(&git.merge_base(&b, &m)?, 1000)?,
+                _ => Vec::new(),
+            }
+        };
+
+        let mut fork = None;
+        for sha in chain {
+            if let Some(rev) = self
+                .db
+                .get_svn_rev_for_git_sha_in_repo(&sha, self.effective_repo_id())?
+            {
+                fork = Some((rev, sha));
+                break;
+            }
+        }
+        let (source_rev, sha) = fork.ok_or_else(|| SyncError::BranchSyncFailed {
+            branch: pair.git_name.clone(),
+            detail: "no synced commit found in the branch history".into(),
+        })?;
+
+        let branches_path = self.svn_layout_path(trunk_svn_path, &self.config.svn.branches_path);
+        let rev = root_svn
+            .create_branch(&pair.svn_name, trunk_svn_path, &branches_path, source_rev)
+            .await?;
+        info!(
+            svn_branch = %pair.svn_name,
+            git_branch = %pair.git_name,
+            source_rev,
+            rev,
+            "created SVN branch for Git branch"
+        );
+        Ok((rev, sha))
+    }
+
+    /// Record the starting point of a newly paired branch so its first cycle
+    /// only replays changes made after it.
+    fn seed_watermarks(&self, svn_rev: i64, git_sha: &str, direction: &str) -> Result<(), SyncError> {
+        self.db.set_state(&self.svn_rev_key(), &svn_rev.to_string())?;
+        self.db.set_state(&self.git_sha_key(), git_sha)?;
+        self.db.insert_commit_map_with_repo(
+            svn_rev,
+            git_sha,
+            direction,
+            "reposync",
+            "reposync <sync@reposync.local>",
+            self.effective_repo_id(),
+        )?;
+        Ok(())
+    }
+
+    /// Repo ID under which a branch pair keeps its watermarks and conflicts.
+    fn branch_repo_id(&self, svn_branch: &str) -> String {
+        branch_repo_id_for(self.mainline_repo_id(), svn_branch)
+    }
+
+    /// Repository path of the tree this engine syncs (e.g. `trunk`), derived
+    /// from the client URL and, for the standard layout, `svn.trunk_path`.
+    fn svn_mainline_path(&self, url: &str, root_url: &str) -> String {
+        let base = url
+            .strip_prefix(root_url)
+            .unwrap_or_default()
+            .trim_matches('/');
+        let trunk = self.config.svn.trunk_path.trim_matches('/');
+        match (base.is_empty(), self.config.svn.layout == SvnLayout::Standard && !trunk.is_empty()) {
+            (_, false) => base.to_string(),
+            (true, true) => trunk.to_string(),
+            (false, true) => format!("{}/{}", base, trunk),
+        }
+    }
+
+    /// Repository path of `path` (`svn.branches_path` or `svn.tags_path`),
+    /// which is relative to the project holding the mainline
+    /// `trunk_svn_path`: the configured URL for the standard layout, the
+    /// mainline's parent otherwise (`project` for `project/trunk`). Branch
+    /// engines get these paths resolved in their config.
+    fn svn_layout_path(&self, trunk_svn_path: &str, path: &str) -> String {
+        let trunk = self.config.svn.trunk_path.trim_matches('/');
+        let project = if self.parent_mainline.is_some() {
+            ""
+        } else if self.config.svn.layout == SvnLayout::Standard && !trunk.is_empty() {
+            trunk_svn_path
+                .strip_suffix(trunk)
+                .unwrap_or_default()
+                .trim_end_matches('/')
+        } else {
+            svn_props::parent_dir(trunk_svn_path)
+        };
+        svn_props::join_path(project, path.trim_matches('/'))
+    }
+
+    // -----------------------------------------------------------------------
+    // PR mode
+    // -----------------------------------------------------------------------
+
+    /// Open the worktree of `sync.pr.branch` and return it with the open
+    /// sync PR, if any.
+    ///
+    /// Once the previous PR has been merged, the sync branch restarts from
+    /// the target branch so the next PR only carries new revisions. A PR
+    /// closed without merging leaves the branch as is; its commits go into
+    /// the next PR.
+    async fn prepare_sync_branch(&self) -> Result<(GitTarget, Option<PullRequest>), SyncError> {
+        let branch = self.config.sync.pr.branch.clone();
+        let default_branch = &self.config.github.default_branch;
+
+        let (worktree, main_tip) = tokio::task::block_in_place(|| {
+            let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+            let main_tip = git
+                .remote_branch_sha("origin", default_branch)?
+                .ok_or_else(|| GitError::RefNotFound(format!("origin/{}", default_branch)))?;
+            if !git.list_branches()?.contains(&branch) {
+                let start = git.remote_branch_sha("origin", &branch)?.unwrap_or(main_tip.clone());
+                git.create_branch(&branch, &start)?;
+            }
+            let dir = git.repo_path().with_file_name("pr-sync");
+            Ok::<_, GitError>((git.open_worktree(&branch, &dir)?, main_tip))
+        })?;
+
+        let github = self.github_client();
+        let repo = &self.config.github.repo;
+        let pr = match self.db.get_state(&self.sync_pr_key())? {
+            Some(number) => match number.parse::<u64>() {
+                Ok(number) => Some(github.get_pull_request(repo, number).await?),
+                Err(_) => None,
+            },
+            None => None,
+        };
+
+        let open_pr = match pr {
+            Some(pr) if pr.state == "open" => Some(pr),
+            Some(pr) => {
+                self.db.set_state(&self.sync_pr_key(), "")?;
+                let tip = worktree.get_head_sha()?;
+                let merged = pr.merged == Some(true) && pr.head.sha == tip;
+                if merged || worktree.is_ancestor(&tip, &main_tip)? {
+                    info!(pr = pr.number, branch = %branch, "restarting sync branch after merge");
+                    tokio::task::block_in_place(|| {
+                        worktree.reset_to(&main_tip)?;
+                        worktree.push_force("origin", &branch, None)
+                    })?;
+                } else {
+                    warn!(pr = pr.number, "sync PR closed without merging, keeping its commits");
+                }
+                None
+            }
+            None => None,
+        };
+
+        let target = GitTarget {
+            client: Arc::new(std::sync::Mutex::new(worktree)),
+            branch,
+        };
+        Ok((target, open_pr))
+    }
+
+    /// Open a PR for unmerged commits on the sync branch and, with
+    /// `sync.pr.auto_merge`, merge the open PR once its checks pass.
+    async fn update_sync_pull_request(
+        &self,
+        target: &GitTarget,
+        open_pr: Option<PullRequest>,
+    ) -> Result<(), SyncError> {
+        let pr_config = &self.config.sync.pr;
+        let default_branch = &self.config.github.default_branch;
+        let repo = &self.config.github.repo;
+        let github = self.github_client();
+
+        let pr = match open_pr {
+            Some(pr) => pr,
+            None => {
+                let pending = {
+                    let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+                    let tip = git.get_head_sha()?;
+                    match git.remote_branch_sha("origin", default_branch)? {
+                        Some(main_tip) => {
+                            let base = git.merge_base(&tip, &main_tip)?;
+                            git.first_parent_chain(&tip, 1000)?
+                                .into_iter()
+                                .take_while(|sha| *sha != base)
+                                .collect::<Vec<_>>()
+                        }
+                        None => Vec::new(),
+                    }
+                };
+                if pending.is_empty() {
+                    return Ok(());
+                }
+
+                let revisions: Vec<i64> = pending
+                    .iter()
+                    .rev()
+                    .filter_map(|sha| {
+                        self.db
+                            .get_svn_rev_for_git_sha_in_repo(sha, self.effective_repo_id())
+                            .ok()
+                            .flatten()
+                    })
+                    .collect();
+                let (title, body) = sync_pr_text(&pr_config.title_prefix, &revisions);
+                let pr = github
+                    .create_pull_request(repo, &title, &body, &target.branch, default_branch)
+                    .await?;
+                self.db.set_state(&self.sync_pr_key(), &pr.number.to_string())?;
+                if !pr_config.labels.is_empty() {
+                    if let Err(e) = github.add_labels(repo, pr.number, &pr_config.labels).await {
+                        warn!(pr = pr.number, error = %e, "failed to label sync PR");
+                    }
+                }
+                if !pr_config.reviewers.is_empty() {
+                    if let Err(e) = github
+                        .request_reviewers(repo, pr.number, &pr_config.reviewers)
+                        .await
+                    {
+                        warn!(pr = pr.number, error = %e, "failed to request reviewers");
+                    }
+                }
+                info!(pr = pr.number, url = %pr.html_url, "opened sync PR");
+                pr
+            }
+        };
 
-        ConflictDetector::detect(&svn_file_changes, &git_file_changes)
+        if !pr_config.auto_merge {
+            return Ok(());
+        }
+        let head = {
+            let git = target.client.lock().unwrap_or_else(|p| p.into_inner());
+            git.get_head_sha()?
+        };
+        let checks = match github.checks_state(repo, &head).await? {
+            // Only with `merge_without_checks`: give CI one cycle to register
+            // checks for a new head; if there are still none, it passes.
+            ChecksState::Unreported if pr_config.merge_without_checks => {
+                let key = format!("{}_unchecked_head", self.sync_pr_key());
+                if self.db.get_state(&key)?.as_deref() == Some(head.as_str()) {
+                    ChecksState::Success
+                } else {
+                    self.db.set_state(&key, &head)?;
+                    ChecksState::Pending
+                }
+            }
+            checks => checks,
+        };
+        match checks {
+            ChecksState::Success => match github.merge_pull_request(repo, pr.number).await {
+                Ok(()) => info!(pr = pr.number, "merged sync PR"),
+                // Branch protection may still require reviews; retry next cycle.
+                Err(e) => warn!(pr = pr.number, error = %e, "sync PR not merged"),
+            },
+            ChecksState::Failure => warn!(pr = pr.number, "sync PR checks failed"),
+            ChecksState::Pending | ChecksState::Unreported => {
+                debug!(pr = pr.number, "waiting for sync PR checks")
+            }
+        }
+        Ok(())
+    }
+
+    /// API client for the configured Git host.
+    fn github_client(&self) -> GitHubClient {
+        let token = self
+            .stored_git_token()
+            .or_else(|| self.config.github.token.clone())
+            .unwrap_or_default();
+        GitHubClient::new(
+            &self.config.github.api_url,
+            token,
+            self.config.github.provider.clone(),
+        )
+        .with_app_auth(self.config.github.github_app_auth())
+    }
+
+    // -----------------------------------------------------------------------
+    // Tag synchronization
+    // -----------------------------------------------------------------------
+
+    /// Synchronize tags between `svn.tags_path` and Git.
+    ///
+    /// SVN tag copies become annotated Git tags on the commit synced from the
+    /// copy source, and Git tags on synced commits become SVN copies. Names
+    /// are translated with `sync.tag_mappings` (see [`crate::ref_map`]).
+    /// Synced tags are recorded in `tag_map`; deleting or moving a tag
+    /// afterwards is not propagated.
+    ///
+    /// The first run only records where both sides stand: tags that exist
+    /// before tag sync is enabled (or before an upgrade that turned it on)
+    /// are left alone, and only tags created afterwards are copied.
+    ///
+    /// Returns the number of tags created on either side.
+    async fn sync_tags(&self) -> Result<usize, SyncError> {
+        let mappings = tag_mappings(&self.config.sync.tag_mappings);
+        let svn = self.svn_client.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        let svn_info = svn.info().await?;
+
+        let baseline_key = self.git_tag_baseline_key();
+        if self.db.get_state(&self.svn_tag_rev_key())?.is_none() {
+            let git_tags: Vec<String> = {
+                let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+                git.list_tags()?.into_iter().map(|(name, _)| name).collect()
+            };
+            self.db.set_state(&baseline_key, &git_tags.join("\n"))?;
+            self.db
+                .set_state(&self.svn_tag_rev_key(), &svn_info.latest_rev.to_string())?;
+            info!(
+                svn_rev = svn_info.latest_rev,
+                git_tags = git_tags.len(),
+                "tag sync starts here; existing tags are left alone"
+            );
+            return Ok(0);
+        }
+
+        let root_svn = svn.with_url(svn_info.root_url.trim_end_matches('/'));
+        let trunk_svn_path = self.svn_mainline_path(&svn_info.url, &svn_info.root_url);
+
+        let synced = self.db.list_tag_map(self.effective_repo_id())?;
+        let mut svn_done: HashSet<String> = synced.iter().map(|t| t.svn_tag.clone()).collect();
+        let mut git_done: HashSet<String> = synced.into_iter().map(|t| t.git_tag).collect();
+        if let Some(baseline) = self.db.get_state(&baseline_key)? {
+            git_done.extend(baseline.lines().map(str::to_string));
+        }
+
+        let to_git = self
+            .sync_svn_tags_to_git(
+                &root_svn,
+                &trunk_svn_path,
+                svn_info.latest_rev,
+                &mappings,
+                &mut svn_done,
+                &mut git_done,
+            )
+            .await?;
+        let to_svn = self
+            .sync_git_tags_to_svn(&root_svn, &trunk_svn_path, &mappings, &git_done)
+            .await?;
+        Ok(to_git + to_svn)
+    }
+
+    /// Create Git tags for the tag copies committed to SVN since the last
+    /// scanned revision. Stops at the first tag whose source has not been
+    /// synced yet so it is retried next cycle.
+    async fn sync_svn_tags_to_git(
+        &self,
+        root_svn: &SvnClient,
+        trunk_svn_path: &str,
+        head_rev: i64,
+        mappings: &[RefMapping],
+        svn_done: &mut HashSet<String>,
+        git_done: &mut HashSet<String>,
+    ) -> Result<usize, SyncError> {
+        let tags_path = self.svn_layout_path(trunk_svn_path, &self.config.svn.tags_path);
+        let tags_path = tags_path.as_str();
+        let key = self.svn_tag_rev_key();
+        let since = self
+            .db
+            .get_state(&key)?
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(0);
+        if since >= head_rev {
+            return Ok(0);
+        }
+
+        let entries = match root_svn.log_path(tags_path, since + 1, head_rev).await {
+            Ok(entries) => entries,
+            Err(e) if is_missing_path_error(&e) => {
+                debug!(tags_path, "no SVN tags directory");
+                self.db.set_state(&key, &head_rev.to_string())?;
+                return Ok(0);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let existing: HashSet<String> = {
+            let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+            git.list_tags()?.into_iter().map(|(name, _)| name).collect()
+        };
+
+        let mut count = 0;
+        for entry in &entries {
+            if self.is_echo_commit(&entry.message) {
+                self.db.set_state(&key, &entry.revision.to_string())?;
+                continue;
+            }
+
+            for changed in &entry.changed_paths {
+                let Some(name) = svn_tag_name(tags_path, &changed.path) else {
+                    continue;
+                };
+                let (Some(source_path), Some(source_rev)) =
+                    (&changed.copy_from_path, changed.copy_from_rev)
+                else {
+                    continue;
+                };
+                if changed.action != "A" || svn_done.contains(name) {
+                    continue;
+                }
+                let Some(git_name) = map_svn_to_git(mappings, name) else {
+                    continue;
+                };
+
+                let sha = match self
+                    .git_commit_for_svn_copy(root_svn, trunk_svn_path, source_path, source_rev)
+                    .await?
+                {
+                    CopySourceCommit::Synced(sha) => sha,
+                    CopySourceCommit::Pending => {
+                        debug!(tag = name, source_rev, "tag source not synced yet");
+                        return Ok(count);
+                    }
+                    CopySourceCommit::Unknown => {
+                        warn!(
+                            tag = name,
+                            source = %source_path,
+                            source_rev,
+                            "no synced Git commit for SVN tag, skipping"
+                        );
+                        continue;
+                    }
+                };
+
+                if existing.contains(&git_name) {
+                    info!(svn_tag = name, git_tag = %git_name, "pairing existing tags");
+                } else {
+                    let tagger = self
+                        .identity_mapper
+                        .svn_to_git(&entry.author)
+                        .map_err(SyncError::IdentityError)?;
+                    let message = format!(
+                        "{}\n\n{} synced from SVN {}/{} r{}",
+                        entry.message, SYNC_MARKER, tags_path, name, entry.revision
+                    );
+                    tokio::task::block_in_place(|| {
+                        let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+                        git.create_annotated_tag(
+                            &git_name,
+                            &sha,
+                            &message,
+                            &tagger.name,
+                            &tagger.email,
+                        )?;
+                        git.push_tag("origin", &git_name)
+                    })?;
+                    info!(svn_tag = name, git_tag = %git_name, target = %sha, "created Git tag");
+                    count += 1;
+                }
+
+                self.db.insert_tag_map(
+                    name,
+                    &git_name,
+                    entry.revision,
+                    &sha,
+                    "svn_to_git",
+                    self.effective_repo_id(),
+                )?;
+                svn_done.insert(name.to_string());
+                git_done.insert(git_name);
+            }
+            self.db.set_state(&key, &entry.revision.to_string())?;
+        }
+        Ok(count)
+    }
+
+    /// Copy the SVN source of every new Git tag on a synced commit into
+    /// `svn.tags_path`. Tags on commits that were never synced are left
+    /// alone.
+    async fn sync_git_tags_to_svn(
+        &self,
+        root_svn: &SvnClient,
+        trunk_svn_path: &str,
+        mappings: &[RefMapping],
+        git_done: &HashSet<String>,
+    ) -> Result<usize, SyncError> {
+        let tags_path = self.svn_layout_path(trunk_svn_path, &self.config.svn.tags_path);
+        let tags_path = tags_path.as_str();
+        let svn_tags = match root_svn.list_branches(tags_path).await {
+            Ok(names) => names,
+            Err(e) if is_missing_path_error(&e) => {
+                debug!(tags_path, "no SVN tags directory, not exporting Git tags");
+                return Ok(0);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let git_tags = {
+            let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+            git.list_tags()?
+        };
+
+        let mut count = 0;
+        for (git_name, sha) in git_tags {
+            if git_done.contains(&git_name) {
+                continue;
+            }
+            let Some(svn_name) = map_git_to_svn(mappings, &git_name) else {
+                continue;
+            };
+            if svn_name.contains('/') {
+                warn!(
+                    git_tag = %git_name,
+                    svn_tag = %svn_name,
+                    "SVN tag name must not contain '/'"
+                );
+                continue;
+            }
+
+            if svn_tags.contains(&svn_name) {
+                let created = root_svn
+                    .creation_entry(&format!("{}/{}", tags_path, svn_name))
+                    .await?;
+                info!(svn_tag = %svn_name, git_tag = %git_name, "pairing existing tags");
+                self.db.insert_tag_map(
+                    &svn_name,
+                    &git_name,
+                    created.map(|e| e.revision).unwrap_or(0),
+                    &sha,
+                    "git_to_svn",
+                    self.effective_repo_id(),
+                )?;
+                continue;
+            }
+
+            // The tagged commit is on the mainline or on a synced branch.
+            let branch_prefix = self.branch_repo_id("");
+            let source = match self
+                .db
+                .get_svn_rev_for_git_sha_in_repo(&sha, self.effective_repo_id())?
+            {
+                Some(rev) => Some((trunk_svn_path.to_string(), rev)),
+                None => self
+                    .db
+                    .find_svn_rev_for_git_sha_by_repo_prefix(&sha, &branch_prefix)?
+                    .and_then(|(rid, rev)| {
+                        let name = rid.strip_prefix(&branch_prefix)?;
+                        let branches_path = self
+                            .svn_layout_path(trunk_svn_path, &self.config.svn.branches_path);
+                        Some((format!("{}/{}", branches_path, name), rev))
+                    }),
+            };
+            let Some((source_path, source_rev)) = source else {
+                debug!(git_tag = %git_name, sha = %sha, "tagged commit not synced to SVN");
+                continue;
+            };
+
+            let message = format!(
+                "Create tag {} from {} at r{}\n\n{} synced from Git tag {}",
+                svn_name, source_path, source_rev, SYNC_MARKER, git_name
+            );
+            let rev = root_svn
+                .copy(
+                    &source_path,
+                    source_rev,
+                    &format!("{}/{}", tags_path, svn_name),
+                    &message,
+                )
+                .await?;
+            info!(git_tag = %git_name, svn_tag = %svn_name, rev, "created SVN tag");
+            self.db.insert_tag_map(
+                &svn_name,
+                &git_name,
+                rev,
+                &sha,
+                "git_to_svn",
+                self.effective_repo_id(),
+            )?;
+            count += 1;
+        }
+        Ok(count)
     }
 
     // -----------------------------------------------------------------------
@@ -1114,9 +3721,8 @@ impl SyncEngine {
     /// back to global keys for backward compatibility.
     fn reload_credentials(&self) {
         // SVN password — per-repo key first, then global
-        let svn_pw = self
-            .repo_id
-            .as_ref()
+        let cred_id = self.credentials_repo_id.as_ref().or(self.repo_id.as_ref());
+        let svn_pw = cred_id
             .and_then(|rid| {
                 self.db
                     .get_state(&format!("secret_svn_password_{}", rid))
@@ -1138,10 +3744,17 @@ impl SyncEngine {
             debug!("reloaded SVN password from database");
         }
 
-        // Git token — per-repo key first, then global
-        let git_tok = self
-            .repo_id
-            .as_ref()
+        if let Some(token) = self.stored_git_token() {
+            let mut git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
+            git.set_auth(self.config.github.git_auth(Some(&token)));
+            debug!("reloaded Git token from database");
+        }
+    }
+
+    /// Git token saved in the DB — per-repo key first, then global.
+    fn stored_git_token(&self) -> Option<String> {
+        let cred_id = self.credentials_repo_id.as_ref().or(self.repo_id.as_ref());
+        cred_id
             .and_then(|rid| {
                 self.db
                     .get_state(&format!("secret_git_token_{}", rid))
@@ -1155,13 +3768,7 @@ impl SyncEngine {
                     .ok()
                     .flatten()
                     .filter(|v| !v.is_empty())
-            });
-
-        if let Some(token) = git_tok {
-            let git = self.git_client.lock().unwrap_or_else(|p| p.into_inner());
-            let _ = git.ensure_remote_credentials("origin", Some(&token));
-            debug!("reloaded Git token from database");
-        }
+            })
     }
 
     // -----------------------------------------------------------------------
@@ -1209,28 +3816,146 @@ impl SyncEngine {
         message.contains(SYNC_MARKER)
     }
 
-    fn try_auto_merge(&self, conflict: &Conflict) -> bool {
+    /// Three-way merge a conflict, returning the merged content if clean.
+    fn try_auto_merge(&self, conflict: &Conflict) -> Option<String> {
         let (base, ours, theirs) = match (
             &conflict.base_content,
             &conflict.svn_content,
             &conflict.git_content,
         ) {
             (Some(b), Some(o), Some(t)) => (b.as_str(), o.as_str(), t.as_str()),
-            _ => return false,
+            _ => return None,
         };
 
         if Merger::can_auto_merge(base, ours, theirs) {
             match Merger::three_way_merge(base, ours, theirs) {
                 Ok(result) if !result.has_conflicts => {
                     info!(file = %conflict.file_path, "auto-merged conflict");
-                    true
+                    Some(result.merged_content)
                 }
-                _ => false,
+                _ => None,
             }
         } else {
-            false
+            None
+        }
+    }
+}
+
+/// Future of a sync cycle; boxing breaks the recursion between a cycle and
+/// the branch cycles it starts.
+type BoxedSyncCycle<'a> =
+    std::pin::Pin<Box<dyn std::future::Future<Output = Result<SyncStats, SyncError>> + Send + 'a>>;
+
+/// One SVN branch paired with one Git branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BranchPair {
+    /// Directory name under `svn.branches_path`.
+    svn_name: String,
+    git_name: String,
+    svn_exists: bool,
+    git_exists: bool,
+}
+
+/// Pair existing SVN and Git branches through `mappings`.
+///
+/// SVN branches are considered first, so when both sides exist the SVN
+/// name decides the Git name. Git branches with no SVN counterpart yet are
+/// added after them; the default branch is never paired since it tracks the
+/// mainline.
+fn plan_branch_pairs(
+    mappings: &[RefMapping],
+    svn_branches: &[String],
+    git_branches: &[String],
+    default_branch: &str,
+) -> Vec<BranchPair> {
+    let mut pairs: Vec<BranchPair> = Vec::new();
+    for svn_name in svn_branches {
+        let Some(git_name) = map_svn_to_git(mappings, svn_name) else {
+            continue;
+        };
+        if git_name == default_branch {
+            continue;
+        }
+        pairs.push(BranchPair {
+            svn_name: svn_name.clone(),
+            git_exists: git_branches.contains(&git_name),
+            git_name,
+            svn_exists: true,
+        });
+    }
+    for git_name in git_branches {
+        if git_name == default_branch || pairs.iter().any(|p| &p.git_name == git_name) {
+            continue;
+        }
+        let Some(svn_name) = map_git_to_svn(mappings, git_name) else {
+            continue;
+        };
+        if pairs.iter().any(|p| p.svn_name == svn_name) {
+            continue;
+        }
+        pairs.push(BranchPair {
+            svn_name,
+            git_name: git_name.clone(),
+            svn_exists: false,
+            git_exists: true,
+        });
+    }
+    pairs
+}
+
+/// Rewrite `svn diff` output so its paths are relative to `prefix` and use
+/// the `a/` / `b/` form `git apply` expects.
+///
+/// File sections outside `prefix` are dropped. Headers already in Git form
+/// (`svn diff --git`) are handled as well.
+fn rebase_svn_diff(diff: &str, prefix: &str) -> String {
+    let relative = |path: &str| -> Option<String> {
+        if prefix.is_empty() {
+            Some(path.to_string())
+        } else {
+            path.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .map(|rest| rest.to_string())
+        }
+    };
+
+    let mut out = String::with_capacity(diff.len());
+    let mut index_path: Option<String> = None;
+    let mut keep = true;
+    for line in diff.split_inclusive('\n') {
+        if let Some(path) = line.strip_prefix("Index: ") {
+            let path = path.trim_end();
+            keep = relative(path).is_some();
+            index_path = Some(path.to_string());
+        }
+        if !keep {
+            continue;
+        }
+        let header = [("--- ", "a/"), ("+++ ", "b/")]
+            .into_iter()
+            .find_map(|(marker, side)| line.strip_prefix(marker).map(|rest| (marker, side, rest)));
+        let Some((marker, side, rest)) = header else {
+            out.push_str(line);
+            continue;
+        };
+        let end = rest.find(['\t', '\n']).unwrap_or(rest.len());
+        let (path, tail) = rest.split_at(end);
+        // Git-form headers carry the side prefix in front of the Index path.
+        let path = match (&index_path, path.strip_prefix(side)) {
+            (Some(index), Some(stripped)) if path != index && stripped == index => stripped,
+            _ => path,
+        };
+        match relative(path) {
+            Some(rel) if path != "/dev/null" => {
+                out.push_str(marker);
+                out.push_str(side);
+                out.push_str(&rel);
+                out.push_str(tail);
+            }
+            _ => out.push_str(line),
         }
     }
+    out
 }
 
 // ---------------------------------------------------------------------------
@@ -1273,6 +3998,217 @@ pub async fn apply_diff_to_path(
     Ok(())
 }
 
+/// Recursively copy a directory tree.
+fn copy_dir_all(src: &std::path::Path, dst: &std::path::Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let to = dst.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            svn_props::copy_symlink(&entry.path(), &to)?;
+        } else if file_type.is_dir() {
+            copy_dir_all(&entry.path(), &to)?;
+        } else {
+            std::fs::copy(entry.path(), &to)?;
+        }
+    }
+    Ok(())
+}
+
+/// Map a changed file onto the detector's change kind.
+fn change_kind_of(file: &ChangedFile) -> ChangeKind {
+    match (file.action.as_str(), &file.copy_from) {
+        ("R", Some(from)) => ChangeKind::Renamed { from: from.clone() },
+        ("A", _) => ChangeKind::Added,
+        ("D", _) => ChangeKind::Deleted,
+        _ => ChangeKind::Modified,
+    }
+}
+
+/// Fold SVN copy-with-delete pairs into renames.
+///
+/// SVN records a move as an add with history plus a delete of the source in
+/// the same revision. Each such pair becomes a single "R" entry whose
+/// `copy_from` is the old path, and the delete is dropped.
+fn pair_svn_renames(files: Vec<ChangedFile>) -> Vec<ChangedFile> {
+    let deleted: HashSet<String> = files
+        .iter()
+        .filter(|f| f.action == "D")
+        .map(|f| f.path.clone())
+        .collect();
+    let mut moved: HashSet<String> = HashSet::new();
+    let mut files: Vec<ChangedFile> = files
+        .into_iter()
+        .map(|mut f| {
+            if f.action == "A" {
+                if let Some(from) = f.copy_from.as_ref().filter(|p| deleted.contains(*p)) {
+                    // A source copied to several places is renamed only once.
+                    if moved.insert(from.clone()) {
+                        f.action = "R".to_string();
+                    }
+                }
+            }
+            f
+        })
+        .collect();
+    files.retain(|f| f.action != "D" || !moved.contains(&f.path));
+    files
+}
+
+/// Store fetched content on a changed file, flagging binaries instead. Text
+/// that is not UTF-8 is stored without content, so it is not auto-merged.
+fn set_changed_file_content(file: &mut ChangedFile, bytes: Vec<u8>) {
+    if is_binary_content(&bytes) {
+        file.is_binary = true;
+        file.content = None;
+    } else {
+        file.content = String::from_utf8(bytes).ok();
+    }
+}
+
+/// Branch that SVN revisions are committed to and pushed.
+struct GitTarget {
+    client: Arc<std::sync::Mutex<GitClient>>,
+    branch: String,
+}
+
+/// Title and body of a sync PR carrying the given SVN revisions.
+fn sync_pr_text(title_prefix: &str, revisions: &[i64]) -> (String, String) {
+    let range = match (revisions.first(), revisions.last()) {
+        (Some(first), Some(last)) if first != last => format!("r{}-r{}", first, last),
+        (Some(rev), _) => format!("r{}", rev),
+        _ => "new revisions".to_string(),
+    };
+    let title = format!("{} Sync SVN {}", title_prefix, range).trim().to_string();
+    let mut body = String::from("Revisions synchronized from SVN by RepoSync:\n\n");
+    for rev in revisions {
+        body.push_str(&format!("- r{}\n", rev));
+    }
+    (title, body)
+}
+
+/// Pick the user credential to commit to the SVN repository at `svn_url`
+/// with: an `svn_password` or `svn_token` whose `server_url` is the longest
+/// prefix of `svn_url` (an empty `server_url` matches any server), with
+/// passwords preferred over tokens.
+fn select_svn_credential<'a>(
+    creds: &'a [crate::models::UserCredential],
+    svn_url: &str,
+) -> Option<&'a crate::models::UserCredential> {
+    let url = svn_url.trim_end_matches('/');
+    creds
+        .iter()
+        .filter(|c| c.service == "svn_password" || c.service == "svn_token")
+        .filter(|c| {
+            let server = c.server_url.trim_end_matches('/');
+            url == server
+                || (url.starts_with(server) && url[server.len()..].starts_with('/'))
+                || server.is_empty()
+        })
+        .max_by_key(|c| {
+            (
+                c.server_url.trim_end_matches('/').len(),
+                c.service == "svn_password",
+            )
+        })
+}
+
+/// Return the kv_state key holding the last failure to set `svn:author` or
+/// `svn:date` for `repo_id` (empty once an attempt succeeds).
+pub fn svn_revprop_error_key_for(repo_id: Option<&str>) -> String {
+    match repo_id {
+        Some(rid) => format!("svn_revprop_error_{}", rid),
+        None => "svn_revprop_error".to_string(),
+    }
+}
+
+/// Secret `name` (e.g. `secret_git_token`) stored for `repo`, falling back
+/// to its parent's and then the global one.
+pub fn repo_secret(db: &Database, repo: &Repository, name: &str) -> Option<String> {
+    [Some(&repo.id), repo.parent_id.as_ref()]
+        .into_iter()
+        .flatten()
+        .map(|id| format!("{}_{}", name, id))
+        .chain(std::iter::once(name.to_string()))
+        .find_map(|key| db.get_state(&key).ok().flatten().filter(|v| !v.is_empty()))
+}
+
+/// Return the kv_state key for the last SVN revision synced by the engine
+/// with `repo_id` (`None` = the global engine).
+pub fn svn_rev_key_for(repo_id: Option<&str>) -> String {
+    match repo_id {
+        Some(rid) => format!("last_svn_rev_{}", rid),
+        None => "last_svn_rev".to_string(),
+    }
+}
+
+/// Return the kv_state key for the last Git SHA synced by the engine with
+/// `repo_id` (`None` = the global engine).
+pub fn git_sha_key_for(repo_id: Option<&str>) -> String {
+    match repo_id {
+        Some(rid) => format!("last_git_sha_{}", rid),
+        None => "last_git_hash".to_string(),
+    }
+}
+
+/// Return the kv_state key for the last scanned tags revision of the engine
+/// with `repo_id` (`None` = the global engine).
+pub fn svn_tag_rev_key_for(repo_id: Option<&str>) -> String {
+    match repo_id {
+        Some(rid) => format!("last_svn_tag_rev_{}", rid),
+        None => "last_svn_tag_rev".to_string(),
+    }
+}
+
+/// Repo ID under which the branch pair of `svn_branch` keeps its watermarks,
+/// conflicts and `commit_map` rows, below the mainline with `mainline_repo_id`.
+pub fn branch_repo_id_for(mainline_repo_id: Option<&str>, svn_branch: &str) -> String {
+    match mainline_repo_id {
+        Some(rid) => format!("{}:branch:{}", rid, svn_branch),
+        None => format!("branch:{}", svn_branch),
+    }
+}
+
+/// Where the content of an SVN copy source stands in the synced history.
+enum CopySourceCommit {
+    /// The Git commit holding the source content.
+    Synced(String),
+    /// The source revision has not been synced yet.
+    Pending,
+    /// The source is outside the synced paths or has no recorded commit.
+    Unknown,
+}
+
+/// Tag name mappings from `sync.tag_mappings`, with unmatched names kept
+/// as they are.
+fn tag_mappings(entries: &[String]) -> Vec<RefMapping> {
+    let mut mappings = RefMapping::parse_all(entries);
+    mappings.push(RefMapping::parse("*"));
+    mappings
+}
+
+/// Name of the tag created by a changed path directly below `tags_path`,
+/// e.g. `1.0` for `/tags/1.0`.
+fn svn_tag_name<'a>(tags_path: &str, changed_path: &'a str) -> Option<&'a str> {
+    let rest = changed_path.trim_matches('/').strip_prefix(tags_path)?;
+    let name = rest.strip_prefix('/')?;
+    (!name.is_empty() && !name.contains('/')).then_some(name)
+}
+
+/// Whether an svn error means the requested path does not exist.
+fn is_missing_path_error(err: &SvnError) -> bool {
+    match err {
+        SvnError::CommandFailed { stderr, .. } => {
+            stderr.contains("E160013")
+                || stderr.contains("W160013")
+                || stderr.contains("E200009")
+                || stderr.contains("path not found")
+        }
+        _ => false,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Sync lock RAII guard
 // ---------------------------------------------------------------------------
@@ -1288,6 +4224,31 @@ impl Drop for SyncLockGuard {
     }
 }
 
+/// Repo IDs some engine in this process is currently syncing.  The daemon's
+/// global engine, the scheduler's per-repo engines and conflict resolution
+/// each hold their own `running` flag, so they serialize on this instead.
+fn active_repos() -> &'static std::sync::Mutex<HashSet<String>> {
+    static ACTIVE: std::sync::OnceLock<std::sync::Mutex<HashSet<String>>> =
+        std::sync::OnceLock::new();
+    ACTIVE.get_or_init(Default::default)
+}
+
+/// Claim on a repo ID in [`active_repos`], released on drop.
+struct RepoClaim(String);
+
+impl RepoClaim {
+    fn acquire(repo_id: &str) -> Option<Self> {
+        let mut active = active_repos().lock().unwrap_or_else(|p| p.into_inner());
+        active.insert(repo_id.to_string()).then(|| Self(repo_id.to_string()))
+    }
+}
+
+impl Drop for RepoClaim {
+    fn drop(&mut self) {
+        active_repos().lock().unwrap_or_else(|p| p.into_inner()).remove(&self.0);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Internal change-set types
 // ---------------------------------------------------------------------------
@@ -1300,6 +4261,10 @@ pub struct SvnChangeSet {
     pub date: String,
     pub message: String,
     pub changed_files: Vec<ChangedFile>,
+    /// Directories added or modified in the revision, whose properties may
+    /// have changed, relative to the synced tree (`""` for its root). The
+    /// flag is set for added directories, whose whole subtree is new.
+    pub changed_dirs: Vec<(String, bool)>,
     pub diff_content: Option<String>,
 }
 
@@ -1309,17 +4274,41 @@ pub struct GitChangeSet {
     pub sha: String,
     pub author_name: String,
     pub author_email: String,
+    /// Author timestamp (seconds since the Unix epoch).
+    pub author_time: i64,
     pub message: String,
     pub changed_files: Vec<ChangedFile>,
 }
 
+/// Sync state of a path changed on both sides within one cycle.
+#[derive(Debug, Clone)]
+struct OverlapInfo {
+    /// Latest SVN revision touching the path.
+    svn_rev: i64,
+    /// Latest Git commit touching the path.
+    git_sha: String,
+    /// Content at the last synced pairing, if it was text.
+    base_content: Option<String>,
+    /// Raw bytes of the SVN and Git versions, whose line endings and keyword
+    /// expansions a merge result is written back in.
+    svn_raw: Option<Vec<u8>>,
+    git_raw: Option<Vec<u8>>,
+}
+
 /// A single file changed in a commit.
+///
+/// `action` is "A", "M" or "D", or "R" for a rename whose old path is in
+/// `copy_from`. An "A" with `copy_from` set is a copy.
 #[derive(Debug, Clone)]
 pub struct ChangedFile {
     pub path: String,
     pub action: String,
     pub content: Option<String>,
     pub is_binary: bool,
+    /// Source path of a copy or rename, relative to the synced tree.
+    pub copy_from: Option<String>,
+    /// Whether the path is a directory (only known for SVN changes).
+    pub is_dir: bool,
 }
 
 #[cfg(test)]
@@ -1335,6 +4324,15 @@ mod tests {
         assert!(!normal_message.contains(SYNC_MARKER));
     }
 
+    #[test]
+    fn test_repo_claim_is_exclusive() {
+        let claim = RepoClaim::acquire("claim-test-repo").expect("first claim");
+        assert!(RepoClaim::acquire("claim-test-repo").is_none());
+        assert!(RepoClaim::acquire("claim-test-repo:branch:dev").is_some());
+        drop(claim);
+        assert!(RepoClaim::acquire("claim-test-repo").is_some());
+    }
+
     #[test]
     fn test_sync_state_display() {
         assert_eq!(SyncState::Idle.to_string(), "idle");
@@ -1351,4 +4349,189 @@ mod tests {
             "resolution_applied"
         );
     }
+
+    #[test]
+    fn test_plan_branch_pairs() {
+        let mappings = RefMapping::parse_all(&["release-*:release/*".to_string()]);
+        let svn = vec!["release-1.0".to_string(), "release-2.0".to_string(), "exp".to_string()];
+        let git = vec![
+            "main".to_string(),
+            "release/2.0".to_string(),
+            "release/3.0".to_string(),
+            "feature/x".to_string(),
+        ];
+        let pairs = plan_branch_pairs(&mappings, &svn, &git, "main");
+        let summary: Vec<(&str, &str, bool, bool)> = pairs
+            .iter()
+            .map(|p| (p.svn_name.as_str(), p.git_name.as_str(), p.svn_exists, p.git_exists))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("release-1.0", "release/1.0", true, false),
+                ("release-2.0", "release/2.0", true, true),
+                ("release-3.0", "release/3.0", false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_branch_pairs_skips_default_branch() {
+        let mappings = RefMapping::parse_all(&["*".to_string()]);
+        let pairs = plan_branch_pairs(
+            &mappings,
+            &["main".to_string()],
+            &["main".to_string()],
+            "main",
+        );
+        assert!(pairs.is_empty());
+    }
+
+    #[test]
+    fn test_sync_pr_text() {
+        let (title, body) = sync_pr_text("[svn-sync]", &[12, 13, 15]);
+        assert_eq!(title, "[svn-sync] Sync SVN r12-r15");
+        assert!(body.contains("- r12\n- r13\n- r15\n"));
+
+        let (title, _) = sync_pr_text("", &[7]);
+        assert_eq!(title, "Sync SVN r7");
+    }
+
+    #[test]
+    fn test_select_svn_credential() {
+        let cred = |id: &str, service: &str, server_url: &str| crate::models::UserCredential {
+            id: id.into(),
+            user_id: "u1".into(),
+            service: service.into(),
+            server_url: server_url.into(),
+            username: "alice".into(),
+            encrypted_value: String::new(),
+            nonce: String::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let creds = vec![
+            cred("git", "git_token", "https://svn.example.com"),
+            cred("any", "svn_token", ""),
+            cred("other", "svn_password", "https://svn.example.com/other"),
+            cred("host", "svn_token", "https://svn.example.com/"),
+            cred("repo", "svn_password", "https://svn.example.com/repo"),
+        ];
+
+        let pick = |url: &str| select_svn_credential(&creds, url).map(|c| c.id.as_str());
+        assert_eq!(pick("https://svn.example.com/repo/trunk"), Some("repo"));
+        assert_eq!(pick("https://svn.example.com/repository"), Some("host"));
+        assert_eq!(pick("https://svn.other.org/repo"), Some("any"));
+        assert!(select_svn_credential(&creds[..1], "https://svn.example.com").is_none());
+    }
+
+    #[test]
+    fn test_svn_tag_name() {
+        assert_eq!(svn_tag_name("tags", "/tags/1.0"), Some("1.0"));
+        assert_eq!(svn_tag_name("tags", "/tags/1.0/src/a.txt"), None);
+        assert_eq!(svn_tag_name("tags", "/tags"), None);
+        assert_eq!(svn_tag_name("tags", "/tagsx/1.0"), None);
+        assert_eq!(svn_tag_name("tags", "/trunk/tags/1.0"), None);
+    }
+
+    #[test]
+    fn test_tag_mappings_keep_unmatched_names() {
+        let mappings = tag_mappings(&["REL_*:v*".to_string()]);
+        assert_eq!(map_svn_to_git(&mappings, "REL_1.2").as_deref(), Some("v1.2"));
+        assert_eq!(map_git_to_svn(&mappings, "v1.2").as_deref(), Some("REL_1.2"));
+        assert_eq!(map_svn_to_git(&mappings, "nightly").as_deref(), Some("nightly"));
+    }
+
+    fn changed(action: &str, path: &str, copy_from: Option<&str>) -> ChangedFile {
+        ChangedFile {
+            path: path.to_string(),
+            action: action.to_string(),
+            content: None,
+            is_binary: false,
+            copy_from: copy_from.map(str::to_string),
+            is_dir: false,
+        }
+    }
+
+    #[test]
+    fn test_set_changed_file_content() {
+        let mut text = changed("M", "a.c", None);
+        set_changed_file_content(&mut text, b"int a;\n".to_vec());
+        assert_eq!(text.content.as_deref(), Some("int a;\n"));
+        assert!(!text.is_binary);
+
+        // Latin-1 text is not merged, but not binary either.
+        let mut latin1 = changed("M", "b.c", None);
+        set_changed_file_content(&mut latin1, b"/* caf\xe9 */\n".to_vec());
+        assert_eq!(latin1.content, None);
+        assert!(!latin1.is_binary);
+
+        let mut image = changed("M", "c.png", None);
+        set_changed_file_content(&mut image, b"\x89PNG\0".to_vec());
+        assert!(image.is_binary);
+    }
+
+    #[test]
+    fn test_pair_svn_renames() {
+        let files = pair_svn_renames(vec![
+            changed("A", "lib/new.rs", Some("src/old.rs")),
+            changed("D", "src/old.rs", None),
+            changed("A", "copy.rs", Some("kept.rs")),
+            changed("D", "gone.rs", None),
+        ]);
+        let summary: Vec<(&str, &str, Option<&str>)> = files
+            .iter()
+            .map(|f| (f.action.as_str(), f.path.as_str(), f.copy_from.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("R", "lib/new.rs", Some("src/old.rs")),
+                ("A", "copy.rs", Some("kept.rs")),
+                ("D", "gone.rs", None),
+            ]
+        );
+        assert!(matches!(
+            change_kind_of(&files[0]),
+            ChangeKind::Renamed { ref from } if from == "src/old.rs"
+        ));
+        assert!(matches!(change_kind_of(&files[1]), ChangeKind::Added));
+    }
+
+    #[test]
+    fn test_rebase_svn_diff_strips_prefix_and_drops_other_paths() {
+        let diff = "Index: branches/rel/src/a.txt\n\
+===================================================================\n\
+--- branches/rel/src/a.txt\t(revision 4)\n\
++++ branches/rel/src/a.txt\t(revision 5)\n\
+@@ -1 +1 @@\n\
+-old\n\
++new\n\
+Index: trunk/b.txt\n\
+===================================================================\n\
+--- trunk/b.txt\t(revision 4)\n\
++++ trunk/b.txt\t(revision 5)\n\
+@@ -1 +1 @@\n\
+-x\n\
++y\n";
+        let rebased = rebase_svn_diff(diff, "branches/rel");
+        assert!(rebased.contains("--- a/src/a.txt\t(revision 4)\n"));
+        assert!(rebased.contains("+++ b/src/a.txt\t(revision 5)\n"));
+        assert!(rebased.contains("+new\n"));
+        assert!(!rebased.contains("b.txt"));
+    }
+
+    #[test]
+    fn test_rebase_svn_diff_git_format_without_prefix() {
+        let diff = "Index: dir/a.txt\n\
+===================================================================\n\
+--- a/dir/a.txt\t(revision 1)\n\
++++ b/dir/a.txt\t(revision 2)\n\
+@@ -1 +1 @@\n\
+-old\n\
++new\n";
+        let rebased = rebase_svn_diff(diff, "");
+        assert!(rebased.contains("--- a/dir/a.txt\t(revision 1)\n"));
+        assert!(rebased.contains("+++ b/dir/a.txt\t(revision 2)\n"));
+    }
 }
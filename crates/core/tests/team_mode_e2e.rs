@@ -16,7 +16,7 @@ use std::sync::Arc;
 
 use tempfile::TempDir;
 
-use reposync_core::config::{AppConfig, IdentityConfig};
+use reposync_core::config::{AppConfig, GitProvider, IdentityConfig, SvnLayout};
 use reposync_core::db::Database;
 use reposync_core::git::GitClient;
 use reposync_core::identity::IdentityMapper;
@@ -393,6 +393,24 @@ async fn test_team_mode_git_to_svn_sync() {
         std::fs::read_to_string(verify_dir.join("app.js")).unwrap(),
         "console.log('hello');\n"
     );
+
+    // The revision is attributed to the Git author's SVN user.
+    let author = Command::new("svn")
+        .args(["propget", "--revprop", "-r", "2", "svn:author", &svn_url])
+        .output()
+        .unwrap();
+    let expected = make_identity_mapper()
+        .git_to_svn("Dev User", "dev@example.com")
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&author.stdout).trim(), expected);
+    assert_eq!(
+        engine
+            .db()
+            .get_state(&reposync_core::sync_engine::svn_revprop_error_key_for(None))
+            .unwrap()
+            .as_deref(),
+        Some("")
+    );
 }
 
 // ===========================================================================
@@ -883,3 +901,591 @@ async fn test_team_mode_forced_failure_persists_audit_entry() {
         "sync_state should be 'error' after failed cycle"
     );
 }
+
+// ===========================================================================
+// Test 9: Applying conflict resolutions
+// ===========================================================================
+
+/// Accepting the SVN side of a conflict commits the raw SVN content at HEAD
+/// to Git, pairs it in commit_map with the revision holding that content and
+/// marks the conflict resolved.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_team_mode_resolve_accept_svn_commits_to_git() {
+    use reposync_core::conflict::resolver::Resolution;
+
+    if !svn_available() {
+        eprintln!("SKIPPED: svn/svnadmin not found in PATH");
+        return;
+    }
+
+    let tmp = TempDir::new().unwrap();
+    let svn_url = create_svn_repo(tmp.path());
+    let wc_path = tmp.path().join("wc");
+    svn_checkout(&svn_url, &wc_path);
+    let detected_rev = svn_commit_file(&wc_path, "shared.txt", "SVN version\r\n", "SVN edits");
+    let content_rev =
+        svn_commit_file(&wc_path, "shared.txt", "SVN version 2\r\n", "More SVN edits");
+    svn_commit_file(&wc_path, "other.txt", "unrelated", "Unrelated SVN edits");
+
+    let git_work_dir = tmp.path().join("git_work");
+    let bare_dir = tmp.path().join("origin.git");
+    let git_client = setup_git_with_bare_origin(&git_work_dir, &bare_dir);
+
+    std::fs::write(git_work_dir.join("shared.txt"), "Git version").unwrap();
+    git_client
+        .commit("Git edits", "G", "g@example.com", "G", "g@example.com")
+        .unwrap();
+    git_client.push("origin", "main", None).unwrap();
+
+    // The stored SVN text is normalized and from detection time; neither
+    // may end up in Git.
+    let db = setup_db(&tmp.path().join("sync.db"));
+    let conflict_id = db
+        .insert_conflict_entry(
+            "shared.txt",
+            "content",
+            Some("SVN version\n"),
+            Some("Git version"),
+            Some("base"),
+            Some(detected_rev),
+            None,
+        )
+        .unwrap();
+
+    let config = make_app_config(&svn_url, tmp.path());
+    let svn_client = SvnClient::new(&svn_url, "", "");
+    let mapper = Arc::new(make_identity_mapper());
+    let engine = SyncEngine::new(config, db, svn_client, git_client, mapper);
+
+    let outcome = engine
+        .apply_conflict_resolution(&conflict_id, &Resolution::AcceptSvn, "admin")
+        .await
+        .expect("resolution failed");
+
+    assert!(outcome.svn_revision.is_none());
+    let git_sha = outcome.git_sha.expect("expected a Git commit");
+
+    // The commit reached origin with the SVN content and the sync marker.
+    let origin = git2::Repository::open_bare(&bare_dir).unwrap();
+    let head = origin
+        .find_reference("refs/heads/main")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    assert_eq!(head.id().to_string(), git_sha);
+    assert!(head.message().unwrap().contains("[reposync]"));
+    let blob = head
+        .tree()
+        .unwrap()
+        .get_path(Path::new("shared.txt"))
+        .unwrap()
+        .to_object(&origin)
+        .unwrap()
+        .peel_to_blob()
+        .unwrap();
+    assert_eq!(blob.content(), b"SVN version 2\r\n");
+
+    // commit_map pairs the new Git commit with the revision holding the
+    // content, not the one the conflict was detected at.
+    assert_eq!(
+        engine.db().get_svn_rev_for_git_sha(&git_sha).unwrap(),
+        Some(content_rev)
+    );
+
+    // The conflict no longer holds the path back.
+    let conflict = engine.db().get_conflict(&conflict_id).unwrap().unwrap();
+    assert_eq!(conflict.status, "resolved");
+    assert_eq!(conflict.resolution.as_deref(), Some("accept_svn"));
+    assert!(engine
+        .db()
+        .list_active_conflict_paths(None)
+        .unwrap()
+        .is_empty());
+}
+
+/// Accepting the Git side commits the Git content to SVN.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_team_mode_resolve_accept_git_commits_to_svn() {
+    use reposync_core::conflict::resolver::Resolution;
+
+    if !svn_available() {
+        eprintln!("SKIPPED: svn/svnadmin not found in PATH");
+        return;
+    }
+
+    let tmp = TempDir::new().unwrap();
+    let svn_url = create_svn_repo(tmp.path());
+    let wc_path = tmp.path().join("wc");
+    svn_checkout(&svn_url, &wc_path);
+    let svn_rev = svn_commit_file(&wc_path, "shared.txt", "SVN version", "SVN edits");
+
+    let git_work_dir = tmp.path().join("git_work");
+    let bare_dir = tmp.path().join("origin.git");
+    let git_client = setup_git_with_bare_origin(&git_work_dir, &bare_dir);
+
+    let db = setup_db(&tmp.path().join("sync.db"));
+    let conflict_id = db
+        .insert_conflict_entry(
+            "shared.txt",
+            "content",
+            Some("SVN version"),
+            Some("Git version"),
+            None,
+            Some(svn_rev),
+            None,
+        )
+        .unwrap();
+
+    let config = make_app_config(&svn_url, tmp.path());
+    let svn_client = SvnClient::new(&svn_url, "", "");
+    let mapper = Arc::new(make_identity_mapper());
+    let engine = SyncEngine::new(config, db, svn_client, git_client, mapper);
+
+    let outcome = engine
+        .apply_conflict_resolution(&conflict_id, &Resolution::AcceptGit, "admin")
+        .await
+        .expect("resolution failed");
+
+    let new_rev = outcome.svn_revision.expect("expected an SVN commit");
+    assert!(new_rev > svn_rev);
+    assert!(outcome.git_sha.is_none());
+
+    let output = Command::new("svn")
+        .args(["cat", &format!("{}/shared.txt", svn_url), "--non-interactive"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "Git version");
+
+    assert_eq!(
+        engine
+            .db()
+            .get_git_sha_for_svn_rev(new_rev)
+            .unwrap()
+            .as_deref(),
+        Some(get_head_sha(&git_work_dir).as_str())
+    );
+}
+
+// ===========================================================================
+// Test 10: Auto-merge of overlapping edits
+// ===========================================================================
+
+/// Non-overlapping edits to the same file on both sides are three-way merged
+/// against the last commit_map pairing, and the merge lands on both sides.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_team_mode_auto_merges_overlapping_edits() {
+    if !svn_available() {
+        eprintln!("SKIPPED: svn/svnadmin not found in PATH");
+        return;
+    }
+
+    let tmp = TempDir::new().unwrap();
+    let svn_url = create_svn_repo(tmp.path());
+    let wc_path = tmp.path().join("wc");
+    svn_checkout(&svn_url, &wc_path);
+    let base = "one\ntwo\nthree\nfour\nfive\n";
+    let base_rev = svn_commit_file(&wc_path, "shared.txt", base, "Add shared file");
+
+    let git_work_dir = tmp.path().join("git_work");
+    let bare_dir = tmp.path().join("origin.git");
+    let git_client = setup_git_with_bare_origin(&git_work_dir, &bare_dir);
+    std::fs::write(git_work_dir.join("shared.txt"), base).unwrap();
+    git_client
+        .commit("Add shared file", "T", "t@example.com", "T", "t@example.com")
+        .unwrap();
+    git_client.push("origin", "main", None).unwrap();
+    let base_sha = get_head_sha(&git_work_dir);
+
+    // Both sides start from the same paired state.
+    let db = setup_db(&tmp.path().join("sync.db"));
+    db.insert_commit_map(base_rev, &base_sha, "svn_to_git", "t", "T <t@example.com>")
+        .unwrap();
+    let _ = db.set_state("last_svn_rev", &base_rev.to_string());
+    let _ = db.set_state("last_git_hash", &base_sha);
+
+    // SVN edits the first line, Git edits the last one.
+    svn_commit_file(
+        &wc_path,
+        "shared.txt",
+        "ONE\ntwo\nthree\nfour\nfive\n",
+        "SVN edits top",
+    );
+    std::fs::write(
+        git_work_dir.join("shared.txt"),
+        "one\ntwo\nthree\nfour\nFIVE\n",
+    )
+    .unwrap();
+    git_client
+        .commit("Git edits bottom", "G", "g@example.com", "G", "g@example.com")
+        .unwrap();
+    git_client.push("origin", "main", None).unwrap();
+
+    let mut config = make_app_config(&svn_url, tmp.path());
+    config.svn.layout = reposync_core::config::SvnLayout::Custom;
+    let svn_client = SvnClient::new(&svn_url, "", "");
+    let mapper = Arc::new(make_identity_mapper());
+    let engine = SyncEngine::new(config, db, svn_client, git_client, mapper);
+
+    let stats = engine.run_sync_cycle().await.expect("sync cycle failed");
+    assert_eq!(stats.conflicts_detected, 1);
+    assert_eq!(stats.conflicts_auto_resolved, 1);
+
+    let merged = "ONE\ntwo\nthree\nfour\nFIVE\n";
+    assert_eq!(
+        std::fs::read_to_string(git_work_dir.join("shared.txt")).unwrap(),
+        merged
+    );
+    let output = Command::new("svn")
+        .args(["cat", &format!("{}/shared.txt", svn_url), "--non-interactive"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), merged);
+    assert!(engine
+        .db()
+        .list_active_conflict_paths(None)
+        .unwrap()
+        .is_empty());
+}
+
+// ===========================================================================
+// Test 11: Branch synchronization from sync_branches
+// ===========================================================================
+
+fn read_branch_file(bare_dir: &Path, branch: &str, file: &str) -> Option<String> {
+    let repo = git2::Repository::open_bare(bare_dir).unwrap();
+    let reference = repo
+        .find_reference(&format!("refs/heads/{}", branch))
+        .ok()?;
+    let tree = reference.peel_to_tree().unwrap();
+    let entry = tree.get_path(Path::new(file)).ok()?;
+    let blob = repo.find_blob(entry.id()).unwrap();
+    Some(String::from_utf8_lossy(blob.content()).to_string())
+}
+
+/// SVN branches and Git branches matching `sync_branches` are paired, the
+/// missing side is created from the matching point, and later commits on a
+/// branch reach the other side without touching the mainline.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_team_mode_syncs_configured_branches() {
+    if !svn_available() {
+        eprintln!("SKIPPED: svn/svnadmin not found in PATH");
+        return;
+    }
+
+    let tmp = TempDir::new().unwrap();
+    let svn_url = create_svn_repo(tmp.path());
+    let wc_path = tmp.path().join("wc");
+    svn_checkout(&svn_url, &wc_path);
+    let base_rev = svn_commit_file(&wc_path, "trunk/app.txt", "v1\n", "Add app");
+    for args in [
+        vec!["mkdir", "-m", "Add branches", &format!("{}/branches", svn_url)],
+        vec![
+            "copy",
+            "-m",
+            "Branch rel-1",
+            &format!("{}/trunk", svn_url),
+            &format!("{}/branches/rel-1", svn_url),
+        ],
+    ] {
+        let status = Command::new("svn")
+            .args(&args)
+            .arg("--non-interactive")
+            .stdout(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "svn {:?} failed", args);
+    }
+
+    let git_work_dir = tmp.path().join("git_work");
+    let bare_dir = tmp.path().join("origin.git");
+    let git_client = setup_git_with_bare_origin(&git_work_dir, &bare_dir);
+    std::fs::write(git_work_dir.join("app.txt"), "v1\n").unwrap();
+    git_client
+        .commit("Add app", "T", "t@example.com", "T", "t@example.com")
+        .unwrap();
+    git_client.push("origin", "main", None).unwrap();
+    let base_sha = get_head_sha(&git_work_dir);
+    // A Git-only release branch that SVN does not have yet.
+    git_client.create_branch("release/2", &base_sha).unwrap();
+    git_client.push("origin", "release/2", None).unwrap();
+
+    let db = setup_db(&tmp.path().join("sync.db"));
+    db.insert_commit_map(base_rev, &base_sha, "svn_to_git", "t", "T <t@example.com>")
+        .unwrap();
+    let _ = db.set_state("last_svn_rev", &base_rev.to_string());
+    let _ = db.set_state("last_git_hash", &base_sha);
+
+    let mut config = make_app_config(&svn_url, tmp.path());
+    config.sync.sync_branches = vec!["rel-*:release/*".to_string()];
+    let svn_client = SvnClient::new(&svn_url, "", "");
+    let mapper = Arc::new(make_identity_mapper());
+    let engine = SyncEngine::new(config, db, svn_client, git_client, mapper);
+
+    engine.run_sync_cycle().await.expect("first sync cycle failed");
+
+    // SVN rel-1 became Git release/1; Git release/2 became SVN rel-2.
+    assert_eq!(
+        read_branch_file(&bare_dir, "release/1", "app.txt").as_deref(),
+        Some("v1\n")
+    );
+    let output = Command::new("svn")
+        .args(["cat", &format!("{}/branches/rel-2/app.txt", svn_url), "--non-interactive"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "v1\n");
+
+    // A commit on the SVN branch lands on the Git branch only.
+    let status = Command::new("svn")
+        .args(["update", wc_path.to_str().unwrap(), "--non-interactive"])
+        .stdout(std::process::Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    svn_commit_file(&wc_path, "branches/rel-1/app.txt", "v2\n", "Fix on rel-1");
+
+    let stats = engine.run_sync_cycle().await.expect("second sync cycle failed");
+    assert_eq!(stats.svn_to_git_count, 1);
+    assert_eq!(
+        read_branch_file(&bare_dir, "release/1", "app.txt").as_deref(),
+        Some("v2\n")
+    );
+    assert_eq!(
+        read_branch_file(&bare_dir, "main", "app.txt").as_deref(),
+        Some("v1\n")
+    );
+}
+
+/// An engine pointed at the trunk of a project below the repository root,
+/// as the scheduler configures one per repository, finds the project's
+/// branches next to that trunk.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_team_mode_syncs_branches_of_nested_project() {
+    if !svn_available() {
+        eprintln!("SKIPPED: svn/svnadmin not found in PATH");
+        return;
+    }
+
+    let tmp = TempDir::new().unwrap();
+    let svn_url = create_svn_repo(tmp.path());
+    let wc_path = tmp.path().join("wc");
+    svn_checkout(&svn_url, &wc_path);
+    let base_rev = svn_commit_file(&wc_path, "app/trunk/app.txt", "v1\n", "Add app");
+    for args in [
+        vec!["mkdir", "-m", "Add branches", &format!("{}/app/branches", svn_url)],
+        vec![
+            "copy",
+            "-m",
+            "Branch rel-1",
+            &format!("{}/app/trunk", svn_url),
+            &format!("{}/app/branches/rel-1", svn_url),
+        ],
+    ] {
+        let status = Command::new("svn")
+            .args(&args)
+            .arg("--non-interactive")
+            .stdout(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "svn {:?} failed", args);
+    }
+
+    let git_work_dir = tmp.path().join("git_work");
+    let bare_dir = tmp.path().join("origin.git");
+    let git_client = setup_git_with_bare_origin(&git_work_dir, &bare_dir);
+    std::fs::write(git_work_dir.join("app.txt"), "v1\n").unwrap();
+    git_client
+        .commit("Add app", "T", "t@example.com", "T", "t@example.com")
+        .unwrap();
+    git_client.push("origin", "main", None).unwrap();
+    let base_sha = get_head_sha(&git_work_dir);
+
+    let db = setup_db(&tmp.path().join("sync.db"));
+    db.insert_commit_map(base_rev, &base_sha, "svn_to_git", "t", "T <t@example.com>")
+        .unwrap();
+    let _ = db.set_state("last_svn_rev", &base_rev.to_string());
+    let _ = db.set_state("last_git_hash", &base_sha);
+
+    let trunk_url = format!("{}/app/trunk", svn_url);
+    let mut config = make_app_config(&trunk_url, tmp.path());
+    config.svn.layout = SvnLayout::Custom;
+    config.svn.trunk_path = String::new();
+    config.sync.sync_branches = vec!["rel-*:release/*".to_string()];
+    let svn_client = SvnClient::new(&trunk_url, "", "");
+    let mapper = Arc::new(make_identity_mapper());
+    let engine = SyncEngine::new(config, db, svn_client, git_client, mapper);
+
+    engine.run_sync_cycle().await.expect("sync cycle failed");
+    assert_eq!(
+        read_branch_file(&bare_dir, "release/1", "app.txt").as_deref(),
+        Some("v1\n")
+    );
+}
+
+// ===========================================================================
+// Test 12: Tag synchronization
+// ===========================================================================
+
+/// An SVN tag copy becomes an annotated Git tag on the synced commit, and a
+/// Git tag on a synced commit becomes an SVN tag, with names translated by
+/// `tag_mappings`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_team_mode_syncs_tags() {
+    if !svn_available() {
+        eprintln!("SKIPPED: svn/svnadmin not found in PATH");
+        return;
+    }
+
+    let tmp = TempDir::new().unwrap();
+    let svn_url = create_svn_repo(tmp.path());
+    let wc_path = tmp.path().join("wc");
+    svn_checkout(&svn_url, &wc_path);
+    let base_rev = svn_commit_file(&wc_path, "trunk/app.txt", "v1\n", "Add app");
+
+    let git_work_dir = tmp.path().join("git_work");
+    let bare_dir = tmp.path().join("origin.git");
+    let git_client = setup_git_with_bare_origin(&git_work_dir, &bare_dir);
+    std::fs::write(git_work_dir.join("app.txt"), "v1\n").unwrap();
+    git_client
+        .commit("Add app", "T", "t@example.com", "T", "t@example.com")
+        .unwrap();
+    git_client.push("origin", "main", None).unwrap();
+    let base_sha = get_head_sha(&git_work_dir);
+
+    let db = setup_db(&tmp.path().join("sync.db"));
+    db.insert_commit_map(base_rev, &base_sha, "svn_to_git", "t", "T <t@example.com>")
+        .unwrap();
+    let _ = db.set_state("last_svn_rev", &base_rev.to_string());
+    let _ = db.set_state("last_git_hash", &base_sha);
+
+    let svn_tag = |name: &str, message: &str| {
+        let status = Command::new("svn")
+            .args([
+                "copy",
+                "-m",
+                message,
+                &format!("{}/trunk", svn_url),
+                &format!("{}/tags/{}", svn_url, name),
+                "--non-interactive",
+            ])
+            .stdout(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "svn copy to tags/{} failed", name);
+    };
+    let tagger = GitClient::new(&git_work_dir).unwrap();
+    let git_tag = |name: &str, message: &str| {
+        tagger
+            .create_annotated_tag(name, &base_sha, message, "T", "t@example.com")
+            .unwrap();
+        let status = Command::new("git")
+            .args(["push", "origin", &format!("refs/tags/{}", name)])
+            .current_dir(&git_work_dir)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+
+    // Tags from before tag sync first runs stay where they are.
+    let status = Command::new("svn")
+        .args(["mkdir", "-m", "Add tags", &format!("{}/tags", svn_url), "--non-interactive"])
+        .stdout(std::process::Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success());
+    svn_tag("0.9", "Tag 0.9");
+    git_tag("v1", "Release 1");
+
+    let mut config = make_app_config(&svn_url, tmp.path());
+    config.sync.tag_mappings = vec!["*:v*".to_string()];
+    let svn_client = SvnClient::new(&svn_url, "", "");
+    let mapper = Arc::new(make_identity_mapper());
+    let engine = SyncEngine::new(config, db, svn_client, git_client, mapper);
+
+    let stats = engine.run_sync_cycle().await.expect("first sync cycle failed");
+    assert_eq!(stats.tags_synced, 0);
+
+    // SVN tag 1.0 and Git tag v2, both on the synced base.
+    svn_tag("1.0", "Tag 1.0");
+    git_tag("v2", "Release 2");
+
+    let stats = engine.run_sync_cycle().await.expect("second sync cycle failed");
+    assert_eq!(stats.tags_synced, 2);
+
+    let repo = git2::Repository::open_bare(&bare_dir).unwrap();
+    let tag_ref = repo.find_reference("refs/tags/v1.0").expect("Git tag v1.0 missing");
+    assert!(tag_ref.peel_to_tag().is_ok(), "v1.0 should be an annotated tag");
+    assert_eq!(tag_ref.peel_to_commit().unwrap().id().to_string(), base_sha);
+
+    let output = Command::new("svn")
+        .args(["cat", &format!("{}/tags/2/app.txt", svn_url), "--non-interactive"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "v1\n");
+    assert!(repo.find_reference("refs/tags/v0.9").is_err());
+    let output = Command::new("svn")
+        .args(["ls", &format!("{}/tags", svn_url), "--non-interactive"])
+        .output()
+        .unwrap();
+    assert!(!String::from_utf8_lossy(&output.stdout).lines().any(|l| l == "1/"));
+
+    // Nothing left to do on the next cycle.
+    let stats = engine.run_sync_cycle().await.expect("third sync cycle failed");
+    assert_eq!(stats.tags_synced, 0);
+}
+
+// ===========================================================================
+// Test 13: Plain Git remote without a REST API
+// ===========================================================================
+
+/// With the `git` provider the bare repository path is the whole Git-side
+/// configuration: it validates without an API URL, is cloned from as is,
+/// and SVN revisions are pushed to it.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_team_mode_plain_git_remote() {
+    if !svn_available() {
+        eprintln!("SKIPPED: svn/svnadmin not found in PATH");
+        return;
+    }
+
+    let tmp = TempDir::new().unwrap();
+    let svn_url = create_svn_repo(tmp.path());
+    let wc_path = tmp.path().join("wc");
+    svn_checkout(&svn_url, &wc_path);
+    svn_commit_file(&wc_path, "readme.txt", "Hello from SVN", "Add readme");
+
+    // Seed the bare remote, then work from a fresh clone of its path.
+    let bare_dir = tmp.path().join("origin.git");
+    let seed = setup_git_with_bare_origin(&tmp.path().join("seed"), &bare_dir);
+    let head_sha = get_head_sha(seed.repo_path());
+
+    let mut config = make_app_config(&svn_url, tmp.path());
+    config.github.provider = GitProvider::Plain;
+    config.github.repo = bare_dir.display().to_string();
+    config.github.api_url = String::new();
+    config.github.token = None;
+    config.validate().expect("plain Git remote config should validate");
+    assert_eq!(config.github.clone_url(), bare_dir.display().to_string());
+
+    let auth = config.github.git_auth(None);
+    let refs = GitClient::list_remote_refs(&config.github.clone_url(), &auth).unwrap();
+    assert!(refs.contains(&"refs/heads/main".to_string()));
+
+    let git_work_dir = tmp.path().join("git_work");
+    let git_client = GitClient::clone_repo(&config.github.clone_url(), &git_work_dir, &auth)
+        .expect("failed to clone the plain remote");
+    let db = setup_db(&tmp.path().join("sync.db"));
+    let _ = db.set_state("last_git_hash", &head_sha);
+
+    let svn_client = SvnClient::new(&svn_url, "", "");
+    let mapper = Arc::new(make_identity_mapper());
+    let engine = SyncEngine::new(config, db, svn_client, git_client, mapper);
+    let stats = engine.run_sync_cycle().await.expect("sync cycle failed");
+    assert_eq!(stats.svn_to_git_count, 1);
+
+    assert_eq!(
+        read_branch_file(&bare_dir, "main", "readme.txt").as_deref(),
+        Some("Hello from SVN")
+    );
+}
@@ -18,6 +18,7 @@ use reposync_core::config::AppConfig;
 use reposync_core::db::Database;
 use reposync_core::git::GitClient;
 use reposync_core::identity::IdentityMapper;
+use reposync_core::notify::Notifier;
 use reposync_core::svn::SvnClient;
 use reposync_core::sync_engine::SyncEngine;
 use reposync_web::WebServer;
@@ -84,6 +85,9 @@ async fn main() -> Result<()> {
     info!("SVN URL       : {}", config.svn.url);
     info!("GitHub repo   : {}", config.github.repo);
     info!("Poll interval : {}s", config.daemon.poll_interval_secs);
+    if !config.github.provider.has_api() {
+        info!("Git remote    : plain, no API (Git changes are polled)");
+    }
     info!("Web listen    : {}", config.web.listen);
     info!("Data dir      : {}", config.daemon.data_dir.display());
     info!("Log level     : {}", log_level);
@@ -147,6 +151,9 @@ async fn main() -> Result<()> {
             let provider = match config.github.provider {
                 reposync_core::config::GitProvider::GitHub => "github",
                 reposync_core::config::GitProvider::Gitea => "gitea",
+                reposync_core::config::GitProvider::GitLab => "gitlab",
+                reposync_core::config::GitProvider::BitbucketServer => "bitbucket-server",
+                reposync_core::config::GitProvider::Plain => "git",
             };
             let sync_mode = match config.sync.mode {
                 reposync_core::config::SyncMode::Direct => "direct",
@@ -261,12 +268,12 @@ async fn main() -> Result<()> {
 
     // Initialize Git client (graceful: create empty repo if clone fails)
     let git_repo_path = config.daemon.data_dir.join("git-repo");
-    let git_client = if git_repo_path.join(".git").exists() {
+    let git_auth = config.github.git_auth(config.github.token.as_deref());
+    let mut git_client = if git_repo_path.join(".git").exists() {
         GitClient::new(&git_repo_path).context("failed to open existing Git repository")?
     } else {
         let clone_url = config.github.clone_url();
-        let token = config.github.token.as_deref();
-        match GitClient::clone_repo(&clone_url, &git_repo_path, token) {
+        match GitClient::clone_repo(&clone_url, &git_repo_path, &git_auth) {
             Ok(client) => {
                 info!("Git client cloned at {}", git_repo_path.display());
                 client
@@ -300,9 +307,10 @@ async fn main() -> Result<()> {
             }
         }
     };
-    // Ensure the remote URL has embedded credentials for reliable HTTP auth.
+    // Hand the credentials to the client; they are never written to the
+    // remote URL.
     git_client
-        .ensure_remote_credentials("origin", config.github.token.as_deref())
+        .ensure_remote_credentials("origin", git_auth)
         .ok(); // Don't crash if this fails
 
     // Initialize identity mapper
@@ -311,6 +319,12 @@ async fn main() -> Result<()> {
     );
     info!("Identity mapper initialized");
 
+    // Initialize notifications (Slack / email)
+    let notifier = Arc::new(Notifier::new(&config.notifications));
+    if !notifier.is_configured() {
+        info!("No notification channels configured");
+    }
+
     // Initialize sync engine
     let mut engine = SyncEngine::new(
         config.clone(),
@@ -320,11 +334,14 @@ async fn main() -> Result<()> {
         identity_mapper,
     );
     // Set repo_id from the first enabled repository for per-repo keys
+    let mut repo_name = None;
     if let Ok(repos) = engine.db().list_repositories() {
         if let Some(repo) = repos.into_iter().find(|r| r.enabled) {
             engine.set_repo_id(repo.id);
+            repo_name = Some(repo.name);
         }
     }
+    engine.set_notifier(notifier.clone(), repo_name);
 
     // Auto-detect watermarks for repos where last_svn_rev == 0.
     // This recovers watermark state from existing git history after a
@@ -476,6 +493,18 @@ async fn main() -> Result<()> {
     let listen_addr = config.web.listen.clone();
     let app_state_for_cleanup = web_server.app_state();
 
+    // Resume a full import that was still running when the daemon stopped.
+    {
+        let app_state = web_server.app_state();
+        tokio::spawn(async move {
+            match reposync_web::api::setup::resume_interrupted_import(&app_state).await {
+                Ok(true) => info!("Resumed the interrupted SVN import"),
+                Ok(false) => {}
+                Err(e) => warn!("Failed to resume the interrupted SVN import: {}", e),
+            }
+        });
+    }
+
     // Start web server — runs on the main tokio runtime directly
     // (not spawned) to ensure it gets immediate access to worker threads.
     let web_handle = tokio::spawn(async move {
@@ -520,6 +549,7 @@ async fn main() -> Result<()> {
         import_progress,
         scheduler_db,
         config.clone(),
+        notifier,
     );
 
     // Capture sync handles for graceful shutdown before moving sched
@@ -6,7 +6,7 @@
 //! 2. Per-repo sync cycles for every enabled repository in the database,
 //!    each honoring its own `poll_interval_secs` and `last_sync_at`.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -16,13 +16,15 @@ use tokio::sync::{broadcast, mpsc, Notify, RwLock};
 use tokio::time;
 use tracing::{debug, error, info, warn};
 
-use reposync_core::config::AppConfig;
+use reposync_core::config::{AppConfig, NotificationEvent};
 use reposync_core::db::Database;
+use reposync_core::errors::SyncError;
 use reposync_core::git::GitClient;
 use reposync_core::identity::IdentityMapper;
 use reposync_core::import::{ImportPhase, ImportProgress};
+use reposync_core::notify::Notifier;
 use reposync_core::svn::SvnClient;
-use reposync_core::sync_engine::SyncEngine;
+use reposync_core::sync_engine::{SyncEngine, SyncStats};
 
 /// Tracks aggregate statistics across sync cycles.
 #[allow(dead_code)]
@@ -68,9 +70,14 @@ pub struct Scheduler {
     pub sync_handles: Arc<tokio::sync::Mutex<Vec<tokio::task::JoinHandle<()>>>>,
     /// Cached identity mapper (shared across all repo sync cycles).
     cached_identity_mapper: std::sync::OnceLock<Arc<IdentityMapper>>,
+    /// Slack/email notifier for failed cycles and conflicts.
+    notifier: Arc<Notifier>,
+    /// Consecutive failed cycles per repo ID, reset by a successful cycle.
+    repo_error_streaks: Arc<std::sync::Mutex<HashMap<String, u64>>>,
 }
 
 impl Scheduler {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         sync_engine: Arc<SyncEngine>,
         poll_interval: Duration,
@@ -79,6 +86,7 @@ impl Scheduler {
         import_progress: Arc<RwLock<ImportProgress>>,
         db: Database,
         app_config: AppConfig,
+        notifier: Arc<Notifier>,
     ) -> Self {
         Self {
             sync_engine,
@@ -92,6 +100,8 @@ impl Scheduler {
             running_repos: Arc::new(tokio::sync::Mutex::new(HashSet::new())),
             sync_handles: Arc::new(tokio::sync::Mutex::new(Vec::new())),
             cached_identity_mapper: std::sync::OnceLock::new(),
+            notifier,
+            repo_error_streaks: Arc::new(std::sync::Mutex::new(HashMap::new())),
         }
     }
 
@@ -111,7 +121,7 @@ impl Scheduler {
         interval.tick().await;
 
         // Run maintenance (audit pruning, retention) every ~10 minutes.
-        let maintenance_ticks = (600 / self.poll_interval.as_secs().max(1)) as u64;
+        let maintenance_ticks = 600 / self.poll_interval.as_secs().max(1);
         let mut tick_count: u64 = 0;
 
         loop {
@@ -127,7 +137,7 @@ impl Scheduler {
                     // Per-repo scheduler handles all repos from the DB.
                     self.maybe_run_repo_cycles().await;
                     // Periodic maintenance (every ~10 minutes)
-                    if tick_count % maintenance_ticks == 0 {
+                    if tick_count.is_multiple_of(maintenance_ticks) {
                         if let Err(e) = self.db.run_maintenance(90) {
                             warn!("periodic maintenance failed: {}", e);
                         }
@@ -193,9 +203,20 @@ impl Scheduler {
         let engine = self.sync_engine.clone();
         let sched_stats = self.stats.clone();
         let ws = self.ws_broadcast.clone();
+        let notifier = self.notifier.clone();
 
         tokio::spawn(async move {
-            match engine.run_sync_cycle().await {
+            let result = engine.run_sync_cycle().await;
+            let streak = match &result {
+                Ok(_) => 0,
+                Err(SyncError::AlreadyRunning { .. }) => {
+                    sched_stats.consecutive_errors.load(Ordering::SeqCst)
+                }
+                Err(_) => sched_stats.consecutive_errors.load(Ordering::SeqCst) + 1,
+            };
+            notify_cycle_result(&notifier, engine.repo_name(), streak, &result).await;
+
+            match result {
                 Ok(sync_stats) => {
                     sched_stats.consecutive_errors.store(0, Ordering::SeqCst);
                     sched_stats
@@ -222,7 +243,13 @@ impl Scheduler {
                 }
                 Err(e) => {
                     let errors = sched_stats.total_errors.fetch_add(1, Ordering::SeqCst) + 1;
-                    let consecutive = sched_stats.consecutive_errors.fetch_add(1, Ordering::SeqCst) + 1;
+                    // A cycle skipped for a running one does not extend the
+                    // streak.
+                    let consecutive = if matches!(e, SyncError::AlreadyRunning { .. }) {
+                        sched_stats.consecutive_errors.load(Ordering::SeqCst)
+                    } else {
+                        sched_stats.consecutive_errors.fetch_add(1, Ordering::SeqCst) + 1
+                    };
                     error!(
                         cycle = cycle_num,
                         error = %e,
@@ -356,15 +383,7 @@ impl Scheduler {
                 });
 
             // Build SVN URL: repo.svn_url + repo.svn_branch
-            let svn_url = if repo.svn_branch.is_empty() {
-                repo.svn_url.clone()
-            } else {
-                format!(
-                    "{}/{}",
-                    repo.svn_url.trim_end_matches('/'),
-                    repo.svn_branch.trim_start_matches('/')
-                )
-            };
+            let svn_url = repo.synced_svn_url();
 
             let svn_client = SvnClient::new(
                 &svn_url,
@@ -388,7 +407,11 @@ impl Scheduler {
                 &repo.git_repo,
             );
 
-            let git_client = if git_repo_path.join(".git").exists() {
+            // The repository's own Git host, which may differ from the
+            // configured one (and from the GitHub App's).
+            let repo_github = self.app_config.github.for_repository(&repo);
+            let git_auth = repo_github.git_auth(git_token.as_deref());
+            let mut git_client = if git_repo_path.join(".git").exists() {
                 match GitClient::new(&git_repo_path) {
                     Ok(c) => c,
                     Err(e) => {
@@ -402,7 +425,7 @@ impl Scheduler {
                     error!(repo_name = %repo.name, error = %e, "failed to create git repo dir");
                     continue;
                 }
-                match GitClient::clone_repo(&clone_url, &git_repo_path, git_token.as_deref()) {
+                match GitClient::clone_repo(&clone_url, &git_repo_path, &git_auth) {
                     Ok(c) => c,
                     Err(e) => {
                         warn!(
@@ -435,10 +458,8 @@ impl Scheduler {
                 }
             };
 
-            // Ensure remote has credentials embedded.
-            git_client
-                .ensure_remote_credentials("origin", git_token.as_deref())
-                .ok();
+            // Hand the credentials to the client (kept in memory only).
+            git_client.ensure_remote_credentials("origin", git_auth).ok();
 
             // Reuse cached identity mapper when possible (P7 optimization).
             let identity_mapper = match self.cached_identity_mapper.get() {
@@ -469,11 +490,7 @@ impl Scheduler {
             };
 
             // Override global config with per-repo settings.
-            // The trunk_path must be empty because the branch path is already
-            // baked into the SVN URL (svn_url + svn_branch).
-            let mut repo_config = self.app_config.clone();
-            repo_config.svn.trunk_path = String::new();
-            repo_config.svn.layout = reposync_core::config::SvnLayout::Custom;
+            let repo_config = self.app_config.for_repository(&repo);
 
             let mut engine = SyncEngine::new(
                 repo_config,
@@ -483,11 +500,14 @@ impl Scheduler {
                 identity_mapper,
             );
             engine.set_repo_id(repo.id.clone());
+            engine.set_notifier(self.notifier.clone(), Some(repo.name.clone()));
 
             let repo_id = repo.id.clone();
             let repo_name = repo.name.clone();
             let running_repos = self.running_repos.clone();
             let ws = self.ws_broadcast.clone();
+            let notifier = self.notifier.clone();
+            let error_streaks = self.repo_error_streaks.clone();
 
             // Mark this repo as running.
             {
@@ -501,6 +521,25 @@ impl Scheduler {
             let handle = tokio::spawn(async move {
                 let result = engine.run_sync_cycle().await;
 
+                let streak = {
+                    let mut streaks = error_streaks.lock().unwrap_or_else(|p| p.into_inner());
+                    match &result {
+                        Ok(_) => {
+                            streaks.remove(&repo_id);
+                            0
+                        }
+                        Err(SyncError::AlreadyRunning { .. }) => {
+                            streaks.get(&repo_id).copied().unwrap_or(0)
+                        }
+                        Err(_) => {
+                            let streak = streaks.entry(repo_id.clone()).or_insert(0);
+                            *streak += 1;
+                            *streak
+                        }
+                    }
+                };
+                notify_cycle_result(&notifier, Some(&repo_name), streak, &result).await;
+
                 match &result {
                     Ok(sync_stats) => {
                         info!(
@@ -553,3 +592,47 @@ impl Scheduler {
         }
     }
 }
+
+/// Send the notifications routed for the outcome of one sync cycle.
+///
+/// `streak` is the number of consecutive failed cycles including this one;
+/// the error-threshold alert fires when it reaches the threshold and again
+/// at every multiple of it, so a repository that keeps failing is not
+/// forgotten after one alert.
+/// A cycle that found another one running did not fail and sends nothing.
+async fn notify_cycle_result(
+    notifier: &Notifier,
+    repo_name: Option<&str>,
+    streak: u64,
+    result: &Result<SyncStats, SyncError>,
+) {
+    let sent = match result {
+        Ok(stats) => {
+            if !notifier.is_routed(NotificationEvent::SyncComplete, repo_name) {
+                return;
+            }
+            notifier.notify_sync_complete(repo_name, stats).await
+        }
+        Err(SyncError::AlreadyRunning { .. }) => return,
+        Err(e) => {
+            let error = e.to_string();
+            let mut sent = Ok(());
+            if notifier.is_routed(NotificationEvent::SyncFailed, repo_name) {
+                sent = notifier.notify_sync_error(repo_name, &error).await;
+            }
+            let threshold = u64::from(notifier.error_threshold());
+            if threshold > 0 && streak.is_multiple_of(threshold) {
+                warn!(
+                    repo_name = repo_name.unwrap_or_default(),
+                    consecutive_errors = streak,
+                    "sync error threshold reached"
+                );
+                sent = sent.and(notifier.notify_error_threshold(repo_name, streak, &error).await);
+            }
+            sent
+        }
+    };
+    if let Err(e) = sent {
+        warn!(repo_name = repo_name.unwrap_or_default(), error = %e, "notification failed");
+    }
+}
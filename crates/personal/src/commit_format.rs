@@ -35,7 +35,9 @@ impl CommitFormatter {
             .replace("{svn_date}", svn_date)
     }
 
-    /// Format a commit message for Git→SVN direction.
+    /// Format a commit message for Git→SVN direction.  A `pr_number` of 0
+    /// stands for a commit pushed without a pull request, and the template
+    /// lines naming the PR number are left out.
     pub fn format_git_to_svn(
         &self,
         original_message: &str,
@@ -43,7 +45,17 @@ impl CommitFormatter {
         pr_number: u64,
         pr_branch: &str,
     ) -> String {
-        self.git_to_svn_template
+        let template = if pr_number == 0 {
+            let lines: Vec<&str> = self
+                .git_to_svn_template
+                .lines()
+                .filter(|line| !line.contains("{pr_number}"))
+                .collect();
+            lines.join("\n")
+        } else {
+            self.git_to_svn_template.clone()
+        };
+        template
             .replace("{original_message}", original_message.trim())
             .replace("{git_sha}", git_sha)
             .replace("{pr_number}", &pr_number.to_string())
@@ -133,6 +145,12 @@ mod tests {
         assert!(result.contains("PR-Number: #42"));
         assert!(result.contains("PR-Branch: feature/search"));
         assert!(result.contains("[reposync]"));
+
+        // A directly pushed commit has no PR number.
+        let result = fmt.format_git_to_svn("Fix typo", "abc123def", 0, "main");
+        assert!(result.contains("Git-SHA: abc123def"));
+        assert!(!result.contains("PR-Number"));
+        assert!(result.contains("PR-Branch: main"));
     }
 
     #[test]
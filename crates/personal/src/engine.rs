@@ -96,6 +96,13 @@ impl PersonalSyncEngine {
             }
         }
 
+        if !config.github.provider.has_api() {
+            info!(
+                "plain Git remote has no pull requests; commits pushed to the default \
+                 branch are synced to SVN instead of merged PRs"
+            );
+        }
+
         Self {
             config,
             db: Arc::new(db),
@@ -209,8 +216,23 @@ impl PersonalSyncEngine {
         Ok(count as u64)
     }
 
-    /// Git → SVN sync phase (via merged PRs).
+    /// Git → SVN sync phase (via merged PRs, or the pushed commits of a
+    /// plain Git remote).
     async fn sync_git_to_svn(&self) -> Result<(u64, u64)> {
+        if !self.config.github.provider.has_api() {
+            self.set_state(PersonalSyncState::ApplyingGitToSvn);
+            let syncer = GitToSvnSync::new(
+                self.svn_client.clone(),
+                (*self.github_client).clone(),
+                self.db.clone(),
+                &self.config,
+                self.svn_wc_path.clone(),
+                self.git_repo_path.clone(),
+            );
+            let result = syncer.sync_pushed_commits().await?;
+            return Ok((0, result.commits_synced));
+        }
+
         // First, detect merged PRs
         let monitor = PrMonitor::new(&self.github_client, &self.db, &self.config);
         let merged_prs = monitor.check_for_merged_prs().await?;
@@ -3,9 +3,11 @@
 //! Replays merged pull request commits from a GitHub repository back into an
 //! SVN working copy. Each PR's commits are applied in order and committed to
 //! SVN with metadata trailers (Git SHA, PR number, branch) for traceability
-//! and echo suppression.
+//! and echo suppression.  A plain Git remote has no pull requests, so the
+//! commits pushed to its default branch are replayed instead.
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -13,13 +15,20 @@ use tracing::{debug, error, info, instrument, warn};
 
 use reposync_core::db::Database;
 use reposync_core::file_policy::{FilePolicy, FilePolicyDecision};
-use reposync_core::git::github::{GitHubClient, GitHubCommit, PullRequest};
-use reposync_core::git::GitClient;
+use reposync_core::git::client::GitCommitInfo;
+use reposync_core::git::github::{
+    GitHubClient, GitHubCommit, GitHubCommitDetail, GitHubGitActor, PullRequest,
+};
+use reposync_core::git::{GitAuth, GitClient};
 use reposync_core::personal_config::PersonalConfig;
 use reposync_core::svn::SvnClient;
+use reposync_core::svn_props::{self, IgnoreProps};
 
 use crate::commit_format::CommitFormatter;
 
+/// Watermark of the last commit of a plain Git remote replayed to SVN.
+const GIT_WATERMARK_KEY: &str = "git_sha";
+
 // ---------------------------------------------------------------------------
 // Public types
 // ---------------------------------------------------------------------------
@@ -48,8 +57,16 @@ pub struct GitToSvnSync {
     git_repo_path: PathBuf,
     github_repo: String,
     default_branch: String,
+    /// Credentials for fetching a plain Git remote: token, SSH key and
+    /// known_hosts file.
+    git_auth: GitAuth,
     svn_author: String,
     svn_url: String,
+    sync_ignores: bool,
+    sync_externals: bool,
+    sync_executable_bit: bool,
+    normalize_line_endings: bool,
+    collapse_keywords: bool,
 }
 
 impl GitToSvnSync {
@@ -84,8 +101,14 @@ impl GitToSvnSync {
             git_repo_path,
             github_repo: config.github.repo.clone(),
             default_branch: config.github.default_branch.clone(),
+            git_auth: config.github.git_auth(),
             svn_author: config.developer.svn_username.clone(),
             svn_url: config.svn.url.clone(),
+            sync_ignores: config.options.sync_ignores,
+            sync_externals: config.options.sync_externals,
+            sync_executable_bit: config.options.sync_executable_bit,
+            normalize_line_endings: config.options.normalize_line_endings,
+            collapse_keywords: config.options.collapse_keywords,
         }
     }
 
@@ -236,6 +259,89 @@ impl GitToSvnSync {
         Ok(result)
     }
 
+    /// Replay the commits pushed to the default branch of a plain Git
+    /// remote, which has no pull requests to watch.
+    ///
+    /// Fetches the remote, fast-forwards the local branch and replays the
+    /// commits since the last one replayed, oldest first.  Commits already in
+    /// `commit_map` (those SVN→Git created) are skipped.
+    #[instrument(skip(self), fields(repo = %self.github_repo))]
+    pub async fn sync_pushed_commits(&self) -> Result<GitToSvnResult> {
+        info!("starting git-to-svn sync cycle of pushed commits");
+        let mut result = GitToSvnResult::default();
+
+        self.ensure_svn_working_copy()
+            .await
+            .context("failed to ensure SVN working copy")?;
+
+        let since = self
+            .db
+            .get_watermark(GIT_WATERMARK_KEY)
+            .context("failed to read Git watermark from database")?;
+        let repo_path = self.git_repo_path.clone();
+        let branch = self.default_branch.clone();
+        let auth = self.git_auth.clone();
+        let commits = tokio::task::spawn_blocking(move || -> Result<_> {
+            let git = open_clone(&repo_path, auth)?;
+            let Some(old_tip) = fast_forward_to_remote(&git, &branch)? else {
+                return Ok((None, Vec::new()));
+            };
+            let since = since.unwrap_or(old_tip);
+            let mut commits = git.get_commits_since(Some(&since), None)?;
+            commits.reverse();
+            Ok((Some(git.get_head_sha()?), commits))
+        })
+        .await
+        .context("git task panicked")??;
+        let (Some(tip), commits) = commits else {
+            debug!("remote branch does not exist yet");
+            return Ok(result);
+        };
+
+        for commit in commits {
+            if CommitFormatter::is_sync_marker(&commit.message)
+                || self.db.is_git_sha_synced(&commit.sha)?
+            {
+                debug!(git_sha = %commit.sha, "skipping commit already in SVN");
+                self.db.set_watermark(GIT_WATERMARK_KEY, &commit.sha)?;
+                continue;
+            }
+            let commit = pushed_commit(commit);
+            let svn_rev = self
+                .replay_commit(&commit, 0, &self.default_branch)
+                .await
+                .with_context(|| format!("failed to replay commit {}", commit.sha))?;
+            self.db
+                .insert_commit_map(
+                    svn_rev,
+                    &commit.sha,
+                    "git_to_svn",
+                    &self.svn_author,
+                    &commit.commit.author.name,
+                )
+                .context("failed to record commit mapping")?;
+            let _ = self.db.insert_audit_log(
+                "git_to_svn_commit",
+                Some("git_to_svn"),
+                Some(svn_rev),
+                Some(&commit.sha),
+                Some(&self.svn_author),
+                Some(&format!(
+                    "replayed pushed commit {} as r{}",
+                    &commit.sha[..8.min(commit.sha.len())],
+                    svn_rev
+                )),
+                true,
+            );
+            self.db.set_watermark(GIT_WATERMARK_KEY, &commit.sha)?;
+            result.commits_synced += 1;
+        }
+        self.db.set_watermark(GIT_WATERMARK_KEY, &tip)?;
+
+        info!(commits = result.commits_synced, "git-to-svn sync of pushed commits complete");
+        Ok(result)
+    }
+
     /// Sync a single merged PR's commits to SVN.
     ///
     /// Returns the number of commits successfully replayed.
@@ -483,14 +589,163 @@ impl GitToSvnSync {
             return Ok(());
         }
 
+        // The generated block of `.gitignore` files becomes ignore properties
+        // on their directory, applied once the Git repo is no longer borrowed;
+        // the file itself is committed too.
+        let mut ignore_updates: Vec<(String, Option<Vec<u8>>)> = Vec::new();
+        let mut externals_updates: Vec<(String, Option<Vec<u8>>)> = Vec::new();
+        // File mode changes become `svn:executable` on the written files.
+        let mut executable_updates: Vec<(String, bool)> = Vec::new();
+        let parent = git_client.commit_parents(&commit.sha)?.into_iter().next();
+        // Files new to SVN get svn:eol-style / svn:keywords once written.
+        let mut new_files: Vec<String> = Vec::new();
+        // Versioned files that turned into symlinks or back, re-added as
+        // the new kind.
+        let mut kind_changes: Vec<String> = Vec::new();
+        let gitattributes_at = |sha: &str| -> Result<String> {
+            let content = git_client.get_file_content_at_commit(sha, svn_props::GITATTRIBUTES)?;
+            Ok(content
+                .map(|c| String::from_utf8_lossy(&c).into_owned())
+                .unwrap_or_default())
+        };
+        let (eol_entries, eol_entries_before, gitattributes) = if self.normalize_line_endings {
+            let before = match &parent {
+                Some(parent) => gitattributes_at(parent)?,
+                None => String::new(),
+            };
+            let after = gitattributes_at(&commit.sha)?;
+            (
+                svn_props::parse_eol_entries(&after),
+                svn_props::parse_eol_entries(&before),
+                after,
+            )
+        } else {
+            Default::default()
+        };
+        // Content under an external target belongs to the external's source.
+        let external_roots = if self.sync_externals {
+            let mut shas = vec![commit.sha.clone()];
+            shas.extend(git_client.commit_parents(&commit.sha)?.into_iter().take(1));
+            let paths = changed_files.iter().map(|(_, path)| path.as_str());
+            svn_props::external_roots(paths, |path| {
+                shas.iter()
+                    .find_map(|sha| git_client.get_file_content_at_commit(sha, path).ok().flatten())
+                    .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            })
+        } else {
+            Vec::new()
+        };
+
         for (action, file_path) in &changed_files {
             let dst = self.svn_wc_path.join(file_path);
 
+            if self.sync_ignores && svn_props::is_gitignore(file_path) {
+                let content = if action == "D" {
+                    None
+                } else {
+                    git_client.get_file_content_at_commit(&commit.sha, file_path)?
+                };
+                ignore_updates.push((svn_props::parent_dir(file_path).to_string(), content));
+            }
+            if self.sync_externals {
+                if svn_props::is_svnexternals(file_path) {
+                    let content = if action == "D" {
+                        None
+                    } else {
+                        git_client.get_file_content_at_commit(&commit.sha, file_path)?
+                    };
+                    externals_updates
+                        .push((svn_props::parent_dir(file_path).to_string(), content));
+                    continue;
+                }
+                if external_roots.iter().any(|r| svn_props::is_under(file_path, r)) {
+                    debug!(path = file_path, "skipping SVN external content");
+                    continue;
+                }
+            }
+
+            // The generated svn:eol-style block stays in Git.
+            if self.normalize_line_endings && file_path == svn_props::GITATTRIBUTES {
+                let content = match action.as_str() {
+                    "D" => None,
+                    _ => git_client.get_file_content_at_commit(&commit.sha, file_path)?,
+                };
+                let content = content
+                    .map(|c| {
+                        let c = String::from_utf8_lossy(&c);
+                        svn_props::render_eol_entries(&c, &BTreeMap::new())
+                    })
+                    .filter(|c| !c.trim().is_empty());
+                match content {
+                    Some(content) => std::fs::write(&dst, content).with_context(|| {
+                        format!("failed to write file: {}", dst.display())
+                    })?,
+                    None if dst.exists() => std::fs::remove_file(&dst).with_context(|| {
+                        format!("failed to remove deleted file: {}", dst.display())
+                    })?,
+                    None => {}
+                }
+                continue;
+            }
+            // Symlinks become svn:special files; `svn add` marks them.
+            let symlink = action != "D"
+                && git_client.is_symlink_at_commit(&commit.sha, file_path)? == Some(true);
+            if symlink {
+                let target = git_client
+                    .get_file_content_at_commit(&commit.sha, file_path)?
+                    .map(|c| String::from_utf8_lossy(&c).into_owned())
+                    .unwrap_or_default();
+                let decision = self.policy.evaluate_symlink(file_path, &target);
+                if decision.is_blocked() {
+                    warn!(
+                        path = file_path,
+                        target = %target,
+                        reason = decision.label(),
+                        git_sha = %commit.sha,
+                        "Git→SVN: symlink blocked by policy — not replayed"
+                    );
+                    let _ = self.db.insert_audit_log(
+                        "file_policy_skip",
+                        Some("git_to_svn"),
+                        None,
+                        Some(&commit.sha),
+                        None,
+                        Some(&format!("Skipped symlink '{}' -> '{}'", file_path, target)),
+                        true,
+                    );
+                    continue;
+                }
+                if svn_props::is_file_or_symlink(&dst) && !svn_props::is_symlink(&dst) {
+                    kind_changes.push(file_path.clone());
+                }
+                svn_props::write_symlink(&dst, &target)
+                    .with_context(|| format!("failed to create symlink: {}", dst.display()))?;
+                continue;
+            }
+            if action != "D" && svn_props::is_symlink(&dst) {
+                kind_changes.push(file_path.clone());
+            }
+            if action != "D" && !dst.exists() {
+                new_files.push(file_path.clone());
+            }
+
+            if self.sync_executable_bit && action != "D" {
+                let now = git_client.is_executable_at_commit(&commit.sha, file_path)?;
+                let before = match &parent {
+                    Some(parent) => git_client.is_executable_at_commit(parent, file_path)?,
+                    None => None,
+                };
+                let now = now.unwrap_or(false);
+                if now != before.unwrap_or(false) {
+                    executable_updates.push((file_path.clone(), now));
+                }
+            }
+
             match action.as_str() {
                 "D" => {
                     // File was deleted in this commit: remove it from SVN WC
                     // so `svn status` picks it up as missing.
-                    if dst.exists() {
+                    if svn_props::is_file_or_symlink(&dst) {
                         std::fs::remove_file(&dst).with_context(|| {
                             format!("failed to remove deleted file: {}", dst.display())
                         })?;
@@ -570,9 +825,10 @@ impl GitToSvnSync {
                                         })?;
                                     }
                                 }
-                                std::fs::write(&dst, &write_content).with_context(|| {
-                                    format!("failed to write file: {}", dst.display())
-                                })?;
+                                svn_props::write_regular_file(&dst, &write_content)
+                                    .with_context(|| {
+                                        format!("failed to write file: {}", dst.display())
+                                    })?;
                             }
                             FilePolicyDecision::LfsTrack { .. } => {
                                 // File exceeds LFS threshold — same LFS pointer
@@ -630,9 +886,10 @@ impl GitToSvnSync {
                                         })?;
                                     }
                                 }
-                                std::fs::write(&dst, &write_content).with_context(|| {
-                                    format!("failed to write file: {}", dst.display())
-                                })?;
+                                svn_props::write_regular_file(&dst, &write_content)
+                                    .with_context(|| {
+                                        format!("failed to write file: {}", dst.display())
+                                    })?;
                             }
                             FilePolicyDecision::Ignored { pattern } => {
                                 warn!(
@@ -655,6 +912,8 @@ impl GitToSvnSync {
                                 );
                                 continue;
                             }
+                            // Only symlinks get this decision; they are handled above.
+                            FilePolicyDecision::ExternalSymlink { .. } => continue,
                             FilePolicyDecision::Oversize { size, limit } => {
                                 warn!(
                                     path = file_path,
@@ -683,6 +942,80 @@ impl GitToSvnSync {
             }
         }
 
+        drop(git_client);
+        if !kind_changes.is_empty() {
+            let paths: Vec<&str> = kind_changes.iter().map(String::as_str).collect();
+            self.svn.rm_keep_local(&self.svn_wc_path, &paths).await?;
+            self.svn.add(&self.svn_wc_path, &paths).await?;
+        }
+        for (dir, content) in &ignore_updates {
+            self.apply_ignore_update(&commit.sha, dir, content.as_deref())
+                .await
+                .with_context(|| format!("failed to set ignore properties on '{}'", dir))?;
+        }
+        for (dir, content) in &externals_updates {
+            self.apply_externals_update(dir, content.as_deref())
+                .await
+                .with_context(|| format!("failed to set svn:externals on '{}'", dir))?;
+        }
+        for (path, executable) in &executable_updates {
+            if !self.svn_wc_path.join(path).is_file() {
+                continue;
+            }
+            // New files must be versioned before they can carry a property.
+            if *executable {
+                self.svn.add(&self.svn_wc_path, &[path.as_str()]).await?;
+            }
+            svn_props::apply_executable(&self.svn, &self.svn_wc_path, path, *executable)
+                .await
+                .with_context(|| format!("failed to update svn:executable on '{}'", path))?;
+        }
+        for path in &new_files {
+            let dst = self.svn_wc_path.join(path);
+            let Ok(content) = std::fs::read(&dst) else {
+                continue;
+            };
+            let props = svn_props::new_file_props(
+                path,
+                &content,
+                &gitattributes,
+                self.normalize_line_endings,
+                self.collapse_keywords,
+            );
+            if props.is_empty() {
+                continue;
+            }
+            self.svn.add(&self.svn_wc_path, &[path.as_str()]).await?;
+            for (name, value) in &props {
+                self.svn
+                    .propset(&self.svn_wc_path, name, value, path)
+                    .await
+                    .with_context(|| format!("failed to set {} on '{}'", name, path))?;
+            }
+        }
+        // Entries edited in the generated `.gitattributes` block.
+        for (path, style) in svn_props::eol_entry_changes(&eol_entries_before, &eol_entries) {
+            if new_files.contains(&path) || !self.svn_wc_path.join(&path).is_file() {
+                continue;
+            }
+            let result = match &style {
+                Some(style) => {
+                    self.svn
+                        .propset(&self.svn_wc_path, svn_props::SVN_EOL_STYLE, style, &path)
+                        .await
+                }
+                None => {
+                    self.svn
+                        .propdel(&self.svn_wc_path, svn_props::SVN_EOL_STYLE, &path)
+                        .await
+                }
+            };
+            // SVN refuses a style on a file with mixed line endings.
+            if let Err(e) = result {
+                warn!(path, error = %e, "could not update svn:eol-style");
+            }
+        }
+
         debug!(
             git_sha = %commit.sha,
             file_count = changed_files.len(),
@@ -692,6 +1025,55 @@ impl GitToSvnSync {
         Ok(())
     }
 
+    /// Set the ignore properties of `dir` from its `.gitignore` content;
+    /// `None` means the file was deleted.
+    async fn apply_ignore_update(&self, sha: &str, dir: &str, content: Option<&[u8]>) -> Result<()> {
+        let wc_dir = self.svn_wc_path.join(dir);
+        let props = match content {
+            Some(content) => {
+                let (props, unsupported) =
+                    IgnoreProps::from_gitignore(&String::from_utf8_lossy(content));
+                if !unsupported.is_empty() {
+                    warn!(
+                        git_sha = %sha,
+                        dir,
+                        patterns = ?unsupported,
+                        ".gitignore patterns have no SVN equivalent; not synced"
+                    );
+                }
+                props
+            }
+            // The directory went away along with its .gitignore.
+            None if !wc_dir.is_dir() => return Ok(()),
+            None => IgnoreProps::default(),
+        };
+        if !wc_dir.is_dir() {
+            std::fs::create_dir_all(&wc_dir)
+                .with_context(|| format!("failed to create directory: {}", wc_dir.display()))?;
+            self.svn.add(&self.svn_wc_path, &[dir]).await?;
+        }
+        svn_props::apply_ignore_props(&self.svn, &self.svn_wc_path, dir, &props).await?;
+        Ok(())
+    }
+
+    /// Set `svn:externals` on `dir` from its `.svnexternals` content; `None`
+    /// means the file was deleted.
+    async fn apply_externals_update(&self, dir: &str, content: Option<&[u8]>) -> Result<()> {
+        let wc_dir = self.svn_wc_path.join(dir);
+        let value = match content {
+            Some(content) => svn_props::from_svnexternals(&String::from_utf8_lossy(content)),
+            None if !wc_dir.is_dir() => return Ok(()),
+            None => String::new(),
+        };
+        if !wc_dir.is_dir() {
+            std::fs::create_dir_all(&wc_dir)
+                .with_context(|| format!("failed to create directory: {}", wc_dir.display()))?;
+            self.svn.add(&self.svn_wc_path, &[dir]).await?;
+        }
+        svn_props::apply_externals(&self.svn, &self.svn_wc_path, dir, &value).await?;
+        Ok(())
+    }
+
     /// Detect the merge strategy used for a PR by inspecting the merge commit.
     async fn detect_merge_strategy(&self, pr: &PullRequest, commits: &[GitHubCommit]) -> String {
         let merge_sha = match &pr.merge_commit_sha {
@@ -719,6 +1101,57 @@ impl GitToSvnSync {
     }
 }
 
+/// Open the local clone at `path` with `auth` for its transfers.
+fn open_clone(path: &Path, auth: GitAuth) -> Result<GitClient> {
+    let mut git = GitClient::new(path).context("failed to open local git repo")?;
+    git.set_auth(auth);
+    Ok(git)
+}
+
+/// Fetch `origin` and fast-forward the checked-out `branch` to it.  Returns
+/// the commit the branch was at, or `None` when the remote has no such
+/// branch.  Fails when the remote branch no longer contains that commit.
+fn fast_forward_to_remote(git: &GitClient, branch: &str) -> Result<Option<String>> {
+    git.fetch("origin", None).context("git fetch failed")?;
+    let Some(remote_tip) = git.remote_branch_sha("origin", branch)? else {
+        return Ok(None);
+    };
+    let local_tip = git.get_head_sha()?;
+    if local_tip != remote_tip {
+        if !git.is_ancestor(&local_tip, &remote_tip)? {
+            anyhow::bail!(
+                "origin/{} does not contain the local commit {}; was its history rewritten?",
+                branch,
+                &local_tip[..8.min(local_tip.len())]
+            );
+        }
+        git.reset_to(&remote_tip)?;
+    }
+    Ok(Some(local_tip))
+}
+
+/// A commit of the local clone in the shape pull request commits have.
+fn pushed_commit(info: GitCommitInfo) -> GitHubCommit {
+    GitHubCommit {
+        sha: info.sha,
+        commit: GitHubCommitDetail {
+            message: info.message,
+            author: GitHubGitActor {
+                name: info.author_name,
+                email: info.author_email,
+                date: None,
+            },
+            committer: GitHubGitActor {
+                name: info.committer_name,
+                email: info.committer_email,
+                date: None,
+            },
+            verification: None,
+        },
+        author: None,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // File-level helpers (retained for tests)
 // ---------------------------------------------------------------------------
@@ -850,6 +1283,86 @@ fn parse_svn_status(output: &str) -> (Vec<String>, Vec<String>) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fast_forward_to_remote() {
+        let origin_dir = tempfile::tempdir().unwrap();
+        let mut opts = git2::RepositoryInitOptions::new();
+        opts.initial_head("main");
+        git2::Repository::init_opts(origin_dir.path(), &opts).unwrap();
+        let origin = GitClient::new(origin_dir.path()).unwrap();
+        std::fs::write(origin_dir.path().join("a.txt"), "a\n").unwrap();
+        let first = origin.commit("first", "T", "t@t.com", "T", "t@t.com").unwrap().to_string();
+
+        let clone_dir = tempfile::tempdir().unwrap();
+        git2::Repository::clone(origin_dir.path().to_str().unwrap(), clone_dir.path()).unwrap();
+        let git = GitClient::new(clone_dir.path()).unwrap();
+        assert_eq!(fast_forward_to_remote(&git, "main").unwrap(), Some(first.clone()));
+        assert_eq!(fast_forward_to_remote(&git, "develop").unwrap(), None);
+
+        // A pushed commit is fetched and checked out.
+        std::fs::write(origin_dir.path().join("b.txt"), "b\n").unwrap();
+        let second = origin.commit("second", "T", "t@t.com", "T", "t@t.com").unwrap().to_string();
+        assert_eq!(fast_forward_to_remote(&git, "main").unwrap(), Some(first.clone()));
+        assert_eq!(git.get_head_sha().unwrap(), second);
+        assert!(clone_dir.path().join("b.txt").exists());
+        let pushed = git.get_commits_since(Some(&first), None).unwrap();
+        assert_eq!(pushed.len(), 1);
+        assert_eq!(pushed_commit(pushed[0].clone()).commit.message, "second");
+
+        // The remote dropped a commit the clone has.
+        origin.reset_to(&first).unwrap();
+        std::fs::write(origin_dir.path().join("c.txt"), "c\n").unwrap();
+        origin.commit("rewritten", "T", "t@t.com", "T", "t@t.com").unwrap();
+        assert!(fast_forward_to_remote(&git, "main").is_err());
+        assert_eq!(git.get_head_sha().unwrap(), second);
+    }
+
+    #[test]
+    fn test_fast_forward_to_remote_uses_configured_auth() {
+        use std::io::{BufRead, BufReader, Write};
+
+        // An HTTP remote that asks for credentials and reports what it got.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut authorization = None;
+                for line in BufReader::new(&stream).lines() {
+                    let line = line.unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("authorization") {
+                            authorization = Some(value.trim().to_string());
+                        }
+                    }
+                }
+                let status = match authorization {
+                    Some(_) => "404 Not Found",
+                    None => "401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"git\"",
+                };
+                let _ = tx.send(authorization);
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        let clone_dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(clone_dir.path()).unwrap();
+        repo.remote("origin", &format!("http://127.0.0.1:{}/repo.git", port)).unwrap();
+        let git = open_clone(clone_dir.path(), GitAuth::from_token(Some("push-secret"))).unwrap();
+        assert!(fast_forward_to_remote(&git, "main").is_err());
+        let sent: Vec<String> = rx.try_iter().flatten().collect();
+        assert_eq!(sent, vec!["Basic eC1hY2Nlc3MtdG9rZW46cHVzaC1zZWNyZXQ=".to_string()]);
+    }
+
     #[test]
     fn test_parse_svn_status_added_and_deleted() {
         let output = "\
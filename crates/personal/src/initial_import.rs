@@ -2,8 +2,15 @@
 //!
 //! Supports two modes:
 //! - **Snapshot**: Export SVN HEAD as a single Git commit.
-//! - **Full history**: Replay all SVN revisions as individual Git commits.
-
+//! - **Full history**: Replay all SVN revisions as individual Git commits,
+//!   optionally only a revision range that starts with a baseline snapshot.
+//! - **Layout**: Replay the trunk together with the `branches` and `tags`
+//!   beside it, as Git branches and tags.
+//! - **Dump**: Replay the revisions of an `svnadmin dump` / `svnrdump` file,
+//!   without contacting the SVN server.
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -11,12 +18,16 @@ use std::sync::Mutex;
 use tracing::{debug, error, info, warn};
 
 use reposync_core::db::Database;
+use reposync_core::dump_import::DumpReplayer;
 use reposync_core::file_policy::FilePolicy;
 use reposync_core::git::github::GitHubClient;
-use reposync_core::git::GitClient;
+use reposync_core::git::{FastImport, FastImportCommit, GitClient, TreeManifest};
+use reposync_core::import::{is_imported_path, stage_export, svn_date_to_epoch, ImportFilter};
 use reposync_core::identity::mapper::IdentityMapper;
+use reposync_core::layout_import::{ImportLayout, LayoutReplayer};
 use reposync_core::personal_config::PersonalConfig;
-use reposync_core::svn::SvnClient;
+use reposync_core::svn::{SvnClient, SvnInfo, SvnLogEntry};
+use reposync_core::svn_props;
 
 use crate::commit_format::CommitFormatter;
 use crate::svn_to_git::SvnToGitSync;
@@ -28,6 +39,9 @@ pub enum ImportMode {
     Snapshot,
     /// Replay every SVN revision as an individual Git commit.
     Full,
+    /// Replay the trunk and every `branches/*` and `tags/*` beside it into
+    /// Git branches and tags.
+    Layout,
 }
 
 /// Handles the initial import of SVN history into Git.
@@ -38,6 +52,8 @@ pub struct InitialImport<'a> {
     pub db: &'a Database,
     pub config: &'a PersonalConfig,
     pub formatter: &'a CommitFormatter,
+    /// Revision range (full mode only) and path filters of the import.
+    pub filter: ImportFilter,
 }
 
 impl<'a> InitialImport<'a> {
@@ -45,6 +61,36 @@ impl<'a> InitialImport<'a> {
     ///
     /// Returns the number of commits created.
     pub async fn import(&self, mode: ImportMode) -> Result<u64> {
+        if mode != ImportMode::Full && self.filter.has_range() {
+            anyhow::bail!("a revision range is only supported by the full history import");
+        }
+        self.prepare().await?;
+
+        match mode {
+            ImportMode::Snapshot => self.import_snapshot().await,
+            ImportMode::Full => self.import_full().await,
+            ImportMode::Layout => self.import_layout().await,
+        }
+    }
+
+    /// Import the history from an SVN dump stream instead of the server.
+    ///
+    /// `root` is the repository path the configured SVN URL points at (e.g.
+    /// `trunk`; empty for the repository root).  Returns the number of
+    /// commits created.
+    pub async fn import_dump(&self, dump: impl BufRead, root: &str) -> Result<u64> {
+        if self.filter.has_range() {
+            anyhow::bail!("a revision range is only supported by the full history import");
+        }
+        self.prepare().await?;
+        self.replay_dump(dump, root)
+    }
+
+    /// Checks shared by every import mode: the filter, LFS availability and
+    /// the GitHub repository.
+    async fn prepare(&self) -> Result<()> {
+        self.filter.validate()?;
+
         // LFS preflight: if LFS is configured, verify git-lfs is available.
         if self.config.options.lfs_threshold > 0 {
             match reposync_core::lfs::preflight_check() {
@@ -66,18 +112,25 @@ impl<'a> InitialImport<'a> {
         }
 
         // Ensure the GitHub repo exists (auto-create if configured)
-        self.ensure_github_repo().await?;
+        self.ensure_github_repo().await
+    }
 
-        match mode {
-            ImportMode::Snapshot => self.import_snapshot().await,
-            ImportMode::Full => self.import_full().await,
-        }
+    /// File policy of the `[options]` config narrowed by the path filters.
+    fn file_policy(&self) -> FilePolicy {
+        self.filter.policy(&FilePolicy::from(&self.config.options))
     }
 
     /// Auto-create the GitHub repo if it doesn't exist and auto_create is enabled.
     async fn ensure_github_repo(&self) -> Result<()> {
         let repo = &self.config.github.repo;
 
+        // A plain Git remote has no API to check or create it; the push
+        // reports a missing remote.
+        if !self.config.github.provider.has_api() {
+            info!(repo, "plain Git remote, skipping repository check");
+            return Ok(());
+        }
+
         let exists = self
             .github_client
             .repo_exists(repo)
@@ -96,11 +149,9 @@ impl<'a> InitialImport<'a> {
             );
         }
 
-        // Extract repo name from "owner/name" format
-        let name = repo
-            .split('/')
-            .nth(1)
-            .context("invalid repo format, expected 'owner/repo'")?;
+        if !repo.contains('/') {
+            anyhow::bail!("invalid repo format '{}', expected 'owner/repo'", repo);
+        }
 
         info!(
             repo,
@@ -109,7 +160,7 @@ impl<'a> InitialImport<'a> {
         );
         self.github_client
             .create_repo(
-                name,
+                repo,
                 self.config.github.private,
                 &format!(
                     "SVN mirror managed by RepoSync (source: {})",
@@ -137,7 +188,7 @@ impl<'a> InitialImport<'a> {
         info!(head_rev, "SVN HEAD revision");
 
         // Build file policy from config.
-        let policy = FilePolicy::from(&self.config.options);
+        let policy = self.file_policy();
         if policy.has_constraints() {
             info!(
                 max_file_size = policy.max_file_size(),
@@ -239,29 +290,10 @@ impl<'a> InitialImport<'a> {
     /// Full history import: replay every SVN revision as a Git commit.
     async fn import_full(&self) -> Result<u64> {
         info!("starting full history import");
-
-        // Build identity mapper if configured — allows preserving original SVN authors.
-        let identity_mapper = match &self.config.identity {
-            Some(identity_config) => {
-                match IdentityMapper::new(identity_config) {
-                    Ok(mapper) => {
-                        info!("identity mapper enabled — original SVN authors will be preserved");
-                        Some(mapper)
-                    }
-                    Err(e) => {
-                        warn!(error = %e, "failed to initialize identity mapper, falling back to developer identity");
-                        None
-                    }
-                }
-            }
-            None => {
-                info!("no identity mapping configured — all commits will use developer identity");
-                None
-            }
-        };
+        let identity_mapper = self.identity_mapper();
 
         // Build file policy from config.
-        let policy = FilePolicy::from(&self.config.options);
+        let policy = self.file_policy();
         if policy.has_constraints() {
             info!(
                 max_file_size = policy.max_file_size(),
@@ -279,25 +311,96 @@ impl<'a> InitialImport<'a> {
             .await
             .context("failed to get SVN info")?;
         let head_rev = svn_info.latest_rev;
-        info!(head_rev, "SVN HEAD revision — will import all revisions");
-
-        let mut count = 0u64;
+        let (start_rev, end_rev) = self.filter.revisions(self.svn_client, head_rev).await?;
+        info!(head_rev, start_rev, end_rev, "SVN HEAD revision — will import the range");
 
-        // Iterate through all revisions
+        // Iterate through the revisions of the range
         let log_entries = self
             .svn_client
-            .log(1, head_rev)
+            .log(start_rev, end_rev)
             .await
             .context("failed to get SVN log")?;
 
-        let git_client_guard = self.git_client.lock().unwrap();
-        let repo_path = git_client_guard.repo_path().to_path_buf();
-        drop(git_client_guard);
+        let branch = &self.config.github.default_branch;
+        let (repo_path, parent, mut manifest, mut gitattributes) = {
+            let git_client = self.git_client.lock().unwrap();
+            let parent = git_client.branch_sha(branch)?;
+            let (manifest, gitattributes) = match &parent {
+                Some(sha) => (
+                    TreeManifest::from_commit(git_client.repo(), sha)?,
+                    git_client.get_file_content_at_commit(sha, svn_props::GITATTRIBUTES)?,
+                ),
+                None => (TreeManifest::new(), None),
+            };
+            (git_client.repo_path().to_path_buf(), parent, manifest, gitattributes)
+        };
+        // LFS objects are stored locally as pointers are staged; `prepare`
+        // has checked that git-lfs is available.
+        let lfs_repo = policy.lfs_enabled().then_some(repo_path.as_path());
+
+        // Stream the commits to `git fast-import`, one mark per revision.
+        let mut fast_import =
+            FastImport::start(&repo_path, &format!("refs/heads/{}", branch), parent.as_deref())
+                .context("failed to start git fast-import")?;
+        let mut identities = BTreeMap::new();
+
+        // Starting mid-history, a snapshot of the revision before the range
+        // stands in for the history left out.
+        let baseline_rev = (start_rev > 1).then_some(start_rev - 1);
+        if let Some(rev) = baseline_rev {
+            let export_dir = tempfile::tempdir().context("failed to create temp dir")?;
+            match self.svn_client.export("", rev, export_dir.path()).await {
+                Ok(()) => {
+                    stage_export(
+                        export_dir.path(),
+                        &policy,
+                        gitattributes.as_deref(),
+                        lfs_repo,
+                        self.db,
+                    )
+                    .with_context(|| format!("failed to stage the baseline at r{}", rev))?;
+                    let changes = manifest
+                        .update_from_dir(export_dir.path(), is_imported_path)
+                        .with_context(|| format!("failed to read the baseline tree of r{}", rev))?;
+                    gitattributes =
+                        std::fs::read(export_dir.path().join(svn_props::GITATTRIBUTES)).ok();
+                    if !changes.is_empty() {
+                        let developer = &self.config.developer;
+                        let now = chrono::Utc::now();
+                        let message = self.formatter.format_svn_to_git(
+                            &format!("Baseline import from SVN (snapshot at r{})", rev),
+                            rev,
+                            &developer.svn_username,
+                            &now.to_rfc3339(),
+                        );
+                        fast_import
+                            .commit(&FastImportCommit {
+                                mark: rev,
+                                author_name: &developer.name,
+                                author_email: &developer.email,
+                                author_time: now.timestamp(),
+                                committer_name: &developer.name,
+                                committer_email: &developer.email,
+                                committer_time: now.timestamp(),
+                                message: &message,
+                                changes: &changes,
+                            })
+                            .with_context(|| format!("failed to write the baseline at r{}", rev))?;
+                        let git_author = format!("{} <{}>", developer.name, developer.email);
+                        identities.insert(rev, (developer.svn_username.clone(), git_author));
+                        info!(rev, files = changes.len(), "streamed baseline snapshot");
+                    }
+                }
+                // The path may not exist before the range; the first
+                // imported revision then carries the whole tree.
+                Err(e) => warn!(rev, error = %e, "no baseline snapshot before the range"),
+            }
+        }
 
         for entry in &log_entries {
             let rev = entry.revision;
 
-            // Export this revision to a temp directory, then copy with policy.
+            // Export this revision to a temp directory, then stage it with policy.
             let export_dir = match tempfile::tempdir() {
                 Ok(d) => d,
                 Err(e) => {
@@ -311,12 +414,13 @@ impl<'a> InitialImport<'a> {
                 continue;
             }
 
-            // Copy with policy enforcement — propagate hard I/O errors
+            // Stage with policy enforcement — propagate hard I/O errors
             // instead of silently swallowing them.
-            let skipped = match SvnToGitSync::copy_tree_with_policy(
+            let stats = match stage_export(
                 export_dir.path(),
-                &repo_path,
                 &policy,
+                gitattributes.as_deref(),
+                lfs_repo,
                 self.db,
             ) {
                 Ok(s) => s,
@@ -328,82 +432,78 @@ impl<'a> InitialImport<'a> {
                         Some(rev),
                         None,
                         Some(&self.config.developer.svn_username),
-                        Some(&format!("copy_tree_with_policy failed at r{}: {}", rev, e)),
+                        Some(&format!("stage_export failed at r{}: {}", rev, e)),
                         false,
                     );
-                    anyhow::bail!("copy_tree_with_policy failed for revision r{}: {}", rev, e);
+                    anyhow::bail!("stage_export failed for revision r{}: {}", rev, e);
                 }
             };
 
-            if skipped > 0 {
-                debug!(rev, skipped, "files skipped by policy during import");
+            if stats.skipped > 0 {
+                debug!(rev, skipped = stats.skipped, "files skipped by policy during import");
+            }
+
+            let changes = manifest
+                .update_from_dir(export_dir.path(), is_imported_path)
+                .with_context(|| format!("failed to read the staged tree of r{}", rev))?;
+            gitattributes = std::fs::read(export_dir.path().join(svn_props::GITATTRIBUTES)).ok();
+            if changes.is_empty() {
+                // Property-only revisions leave the tree unchanged.
+                debug!(rev, "no changes to commit");
+                continue;
             }
 
             let message =
                 self.formatter
                     .format_svn_to_git(&entry.message, rev, &entry.author, &entry.date);
+            let (author_name, author_email) = self.author_of(identity_mapper.as_ref(), entry);
+
+            fast_import
+                .commit(&FastImportCommit {
+                    mark: rev,
+                    author_name: &author_name,
+                    author_email: &author_email,
+                    author_time: svn_date_to_epoch(&entry.date),
+                    committer_name: &self.config.developer.name,
+                    committer_email: &self.config.developer.email,
+                    committer_time: chrono::Utc::now().timestamp(),
+                    message: &message,
+                    changes: &changes,
+                })
+                .with_context(|| format!("failed to write r{} to git fast-import", rev))?;
+            debug!(rev, changes = changes.len(), author = %author_name, "streamed revision");
+            let git_author = format!("{} <{}>", author_name, author_email);
+            identities.insert(rev, (entry.author.clone(), git_author));
+        }
 
-            // Resolve the Git identity for this commit's author.
-            let (author_name, author_email) = match &identity_mapper {
-                Some(mapper) => match mapper.svn_to_git(&entry.author) {
-                    Ok(identity) => {
-                        debug!(rev, svn_author = %entry.author, git_name = %identity.name, git_email = %identity.email, "mapped SVN author to Git identity");
-                        (identity.name, identity.email)
-                    }
-                    Err(e) => {
-                        debug!(rev, svn_author = %entry.author, error = %e, "identity mapping failed, using developer identity");
-                        (self.config.developer.name.clone(), self.config.developer.email.clone())
-                    }
-                },
-                None => (self.config.developer.name.clone(), self.config.developer.email.clone()),
-            };
-
-            let git_client = self.git_client.lock().unwrap();
-            match git_client.commit(
-                &message,
-                &author_name,
-                &author_email,
-                &self.config.developer.name,
-                &self.config.developer.email,
-            ) {
-                Ok(oid) => {
-                    let sha = oid.to_string();
-                    debug!(rev, sha = %sha, author = %author_name, "committed revision");
-
-                    self.db
-                        .insert_commit_map(
-                            rev,
-                            &sha,
-                            "svn_to_git",
-                            &entry.author,
-                            &format!("{} <{}>", author_name, author_email),
-                        )
-                        .ok();
-
-                    count += 1;
-                }
-                Err(e) => {
-                    // Empty commits (no file changes) are expected for property-only revisions
-                    debug!(rev, error = %e, "commit failed (possibly empty revision)");
-                }
+        // The mark of each commit is its SVN revision.
+        let marks = fast_import.finish().context("git fast-import failed")?;
+        for (rev, sha) in &marks {
+            if let Some((svn_author, git_author)) = identities.get(rev) {
+                self.db
+                    .insert_commit_map(*rev, sha, "svn_to_git", svn_author, git_author)
+                    .ok();
             }
-            drop(git_client);
         }
+        let count = marks.len() as u64;
 
         // Push all at once
         if count > 0 {
             let git_client = self.git_client.lock().unwrap();
+            git_client
+                .checkout_branch(branch)
+                .context("failed to check out the imported branch")?;
             let token = self.config.github.token.as_deref();
             git_client
-                .push("origin", &self.config.github.default_branch, token)
+                .push("origin", branch, token)
                 .context("failed to push to GitHub")?;
             drop(git_client);
         }
 
         // Set watermarks
-        if let Some(last) = log_entries.last() {
+        if let Some(last) = log_entries.last().map(|e| e.revision).or(baseline_rev) {
             self.db
-                .set_watermark("svn_rev", &last.revision.to_string())
+                .set_watermark("svn_rev", &last.to_string())
                 .ok();
         }
 
@@ -436,4 +536,272 @@ impl<'a> InitialImport<'a> {
         );
         Ok(count)
     }
+
+    /// Layout import: replay the trunk the SVN URL points at together with
+    /// the `branches` and `tags` directories beside it.  Branches and tags
+    /// keep their SVN names.
+    async fn import_layout(&self) -> Result<u64> {
+        info!("starting layout import of trunk, branches and tags");
+        let identity_mapper = self.identity_mapper();
+        let policy = self.file_policy();
+
+        let svn_info = self
+            .svn_client
+            .info()
+            .await
+            .context("failed to get SVN info")?;
+        let root_url = svn_info.root_url.trim_end_matches('/');
+        let (base, trunk) = svn_info
+            .url
+            .trim_end_matches('/')
+            .rsplit_once('/')
+            .filter(|(base, _)| base.starts_with(root_url))
+            .context("the SVN URL must point at a trunk directory below the repository root")?;
+        let base_info = SvnInfo { url: base.to_string(), ..svn_info.clone() };
+
+        let branch = &self.config.github.default_branch;
+        let mut replayer = {
+            let git_client = self.git_client.lock().unwrap();
+            let layout = ImportLayout::standard(trunk, branch);
+            LayoutReplayer::new(self.svn_client, &base_info, layout, &git_client, &policy, self.db)?
+        }
+        .lfs(policy.lfs_enabled());
+
+        let log_entries = replayer.history(svn_info.latest_rev).await?;
+        for entry in &log_entries {
+            let rev = entry.revision;
+            let message =
+                self.formatter
+                    .format_svn_to_git(&entry.message, rev, &entry.author, &entry.date);
+            let (author_name, author_email) = self.author_of(identity_mapper.as_ref(), entry);
+            let template = FastImportCommit {
+                mark: 0,
+                author_name: &author_name,
+                author_email: &author_email,
+                author_time: svn_date_to_epoch(&entry.date),
+                committer_name: &self.config.developer.name,
+                committer_email: &self.config.developer.email,
+                committer_time: chrono::Utc::now().timestamp(),
+                message: &message,
+                changes: &[],
+            };
+            let commits = replayer
+                .replay(entry, &template)
+                .await
+                .with_context(|| format!("failed to import r{}", rev))?;
+            for commit in commits {
+                debug!(
+                    rev,
+                    line = %commit.svn_path,
+                    git = %commit.git_name,
+                    changes = commit.changes,
+                    "streamed revision"
+                );
+            }
+        }
+
+        let summary = replayer.finish()?;
+        let refs = summary.refs();
+        if !refs.is_empty() {
+            let git_client = self.git_client.lock().unwrap();
+            if summary.branches.contains(branch) {
+                git_client
+                    .checkout_branch(branch)
+                    .context("failed to check out the imported branch")?;
+            }
+            git_client
+                .push_refs("origin", &refs)
+                .context("failed to push to GitHub")?;
+        }
+
+        if let Some(last) = log_entries.last() {
+            self.db
+                .set_watermark("svn_rev", &last.revision.to_string())
+                .ok();
+        }
+        let git_client = self.git_client.lock().unwrap();
+        if let Ok(sha) = git_client.get_head_sha() {
+            self.db.set_watermark("git_sha", &sha).ok();
+        }
+        drop(git_client);
+
+        self.db
+            .insert_audit_log(
+                "import_full",
+                Some("svn_to_git"),
+                Some(svn_info.latest_rev),
+                None,
+                Some(&self.config.developer.svn_username),
+                Some(&format!(
+                    "Layout import: {} commits on {} branches, {} tags from {} revisions",
+                    summary.commits,
+                    summary.branches.len(),
+                    summary.tags.len(),
+                    log_entries.len()
+                )),
+                true,
+            )
+            .ok();
+
+        info!(
+            count = summary.commits,
+            branches = summary.branches.len(),
+            tags = summary.tags.len(),
+            "layout import completed"
+        );
+        Ok(summary.commits)
+    }
+
+    /// Identity mapper for imported commits, if configured — allows
+    /// preserving original SVN authors.
+    fn identity_mapper(&self) -> Option<IdentityMapper> {
+        match &self.config.identity {
+            Some(identity_config) => {
+                match IdentityMapper::new(identity_config) {
+                    Ok(mapper) => {
+                        info!("identity mapper enabled — original SVN authors will be preserved");
+                        Some(mapper)
+                    }
+                    Err(e) => {
+                        warn!(error = %e, "failed to initialize identity mapper, falling back to developer identity");
+                        None
+                    }
+                }
+            }
+            None => {
+                info!("no identity mapping configured — all commits will use developer identity");
+                None
+            }
+        }
+    }
+
+    /// Git author of an imported revision: the mapped SVN author, or the
+    /// developer.
+    fn author_of(
+        &self,
+        identity_mapper: Option<&IdentityMapper>,
+        entry: &SvnLogEntry,
+    ) -> (String, String) {
+        let rev = entry.revision;
+        match identity_mapper {
+            Some(mapper) => match mapper.svn_to_git(&entry.author) {
+                Ok(identity) => {
+                    debug!(rev, svn_author = %entry.author, git_name = %identity.name, git_email = %identity.email, "mapped SVN author to Git identity");
+                    (identity.name, identity.email)
+                }
+                Err(e) => {
+                    debug!(rev, svn_author = %entry.author, error = %e, "identity mapping failed, using developer identity");
+                    (self.config.developer.name.clone(), self.config.developer.email.clone())
+                }
+            },
+            None => (self.config.developer.name.clone(), self.config.developer.email.clone()),
+        }
+    }
+
+    /// Dump import: replay every revision of `dump` that changes `root`.
+    fn replay_dump(&self, dump: impl BufRead, root: &str) -> Result<u64> {
+        info!(root, "starting dump import");
+
+        let identity_mapper = self
+            .config
+            .identity
+            .as_ref()
+            .and_then(|identity_config| match IdentityMapper::new(identity_config) {
+                Ok(mapper) => Some(mapper),
+                Err(e) => {
+                    warn!(error = %e, "identity mapper unavailable, using developer identity");
+                    None
+                }
+            });
+        let policy = self.file_policy();
+        let repo_path = self.git_client.lock().unwrap().repo_path().to_path_buf();
+        let mut replayer = DumpReplayer::new(dump, root, &repo_path, &policy)?
+            .sync_executable_bit(self.config.options.sync_executable_bit)
+            .normalize_line_endings(self.config.options.normalize_line_endings);
+
+        let mut count = 0u64;
+        let mut last_rev = None;
+        let git_client = self.git_client.lock().unwrap();
+        while let Some(replayed) = replayer.next_revision(&git_client)? {
+            let entry = &replayed.revision;
+            let rev = entry.revision;
+            last_rev = Some(rev);
+            if replayed.stats.skipped > 0 {
+                let skipped = replayed.stats.skipped;
+                debug!(rev, skipped, "files skipped by policy during import");
+            }
+            if !replayed.changed || !git_client.has_changes()? {
+                debug!(rev, "no changes under the import root");
+                continue;
+            }
+
+            let message = self.formatter.format_svn_to_git(
+                entry.message(),
+                rev,
+                entry.author(),
+                entry.date(),
+            );
+            let (author_name, author_email) = match identity_mapper
+                .as_ref()
+                .and_then(|mapper| mapper.svn_to_git(entry.author()).ok())
+            {
+                Some(identity) => (identity.name, identity.email),
+                None => (self.config.developer.name.clone(), self.config.developer.email.clone()),
+            };
+            let sha = git_client
+                .commit(
+                    &message,
+                    &author_name,
+                    &author_email,
+                    &self.config.developer.name,
+                    &self.config.developer.email,
+                )
+                .with_context(|| format!("failed to commit r{}", rev))?
+                .to_string();
+            debug!(rev, sha = %sha, author = %author_name, "committed revision");
+            self.db
+                .insert_commit_map(
+                    rev,
+                    &sha,
+                    "svn_to_git",
+                    entry.author(),
+                    &format!("{} <{}>", author_name, author_email),
+                )
+                .ok();
+            count += 1;
+        }
+
+        if count > 0 {
+            let token = self.config.github.token.as_deref();
+            git_client
+                .push("origin", &self.config.github.default_branch, token)
+                .context("failed to push to GitHub")?;
+        }
+        if let Some(rev) = last_rev {
+            self.db.set_watermark("svn_rev", &rev.to_string()).ok();
+        }
+        if let Ok(sha) = git_client.get_head_sha() {
+            self.db.set_watermark("git_sha", &sha).ok();
+        }
+        drop(git_client);
+
+        self.db
+            .insert_audit_log(
+                "import_dump",
+                Some("svn_to_git"),
+                last_rev,
+                None,
+                Some(&self.config.developer.svn_username),
+                Some(&format!(
+                    "Dump import: {} commits up to r{}",
+                    count,
+                    last_rev.unwrap_or(0)
+                )),
+                true,
+            )
+            .ok();
+
+        info!(count, last_rev, "dump import completed");
+        Ok(count)
+    }
 }
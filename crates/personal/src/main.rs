@@ -14,6 +14,7 @@ mod scheduler;
 mod signals;
 mod svn_to_git;
 
+use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
@@ -24,9 +25,10 @@ use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::EnvFilter;
 
 use reposync_core::db::Database;
-use reposync_core::config::GitProvider;
+use reposync_core::dump_import::open_dump;
 use reposync_core::git::github::GitHubClient;
 use reposync_core::git::GitClient;
+use reposync_core::import::ImportFilter;
 use reposync_core::personal_config::PersonalConfig;
 use reposync_core::svn::SvnClient;
 
@@ -72,6 +74,46 @@ enum Commands {
         /// Import full SVN history (one commit per revision).
         #[arg(long, conflicts_with = "snapshot")]
         full: bool,
+
+        /// Also import the `branches/*` and `tags/*` beside the trunk URL as
+        /// Git branches and tags, forked where they were copied.
+        #[arg(long, conflicts_with_all = ["snapshot", "dump"])]
+        branches: bool,
+
+        /// Import full history from an `svnadmin dump` / `svnrdump` file
+        /// (`-` for stdin) instead of the SVN server.
+        #[arg(long, value_name = "FILE", conflicts_with = "snapshot")]
+        dump: Option<PathBuf>,
+
+        /// Repository path in the dump that the SVN URL points at.
+        #[arg(long, value_name = "PATH", default_value = "trunk", requires = "dump")]
+        dump_root: String,
+
+        /// First SVN revision to import; the tree before it becomes a single
+        /// baseline commit.
+        #[arg(long, value_name = "REV", conflicts_with_all = ["snapshot", "branches", "dump"])]
+        from_rev: Option<i64>,
+
+        /// Import the revisions committed after this date (YYYY-MM-DD or
+        /// RFC 3339) instead of a start revision.
+        #[arg(
+            long,
+            value_name = "DATE",
+            conflicts_with_all = ["from_rev", "snapshot", "branches", "dump"]
+        )]
+        from_date: Option<String>,
+
+        /// Last SVN revision to import (default: HEAD).
+        #[arg(long, value_name = "REV", conflicts_with_all = ["snapshot", "branches", "dump"])]
+        to_rev: Option<i64>,
+
+        /// Only import paths matching this glob, e.g. `src/**` (repeatable).
+        #[arg(long, value_name = "GLOB")]
+        include: Vec<String>,
+
+        /// Leave out paths matching this glob (repeatable).
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
     },
 
     /// Emit log messages at every level and exit.  Used by integration tests
@@ -101,14 +143,34 @@ async fn main() -> Result<()> {
         Commands::Stop => cmd_stop(&config_path),
         Commands::Status => cmd_status(&config_path),
         Commands::Sync => cmd_sync(&config_path).await,
-        Commands::Import { snapshot, full: _ } => {
+        Commands::Import {
+            snapshot,
+            full: _,
+            branches,
+            dump,
+            dump_root,
+            from_rev,
+            from_date,
+            to_rev,
+            include,
+            exclude,
+        } => {
+            let filter = ImportFilter {
+                from_rev,
+                from_date,
+                to_rev,
+                include_paths: include,
+                exclude_paths: exclude,
+            };
             let mode = if snapshot {
                 ImportMode::Snapshot
+            } else if branches {
+                ImportMode::Layout
             } else {
                 // Default to full if neither flag specified
                 ImportMode::Full
             };
-            cmd_import(&config_path, mode).await
+            cmd_import(&config_path, mode, dump.map(|path| (path, dump_root)), filter).await
         }
         Commands::LogProbe => {
             cmd_log_probe();
@@ -195,18 +257,24 @@ async fn build_engine(config_path: &str) -> Result<(PersonalSyncEngine, Personal
 
     // Set up Git repository.
     let git_repo_path = data_dir.join("git-repo");
-    let git_client = if git_repo_path.exists() {
+    let git_auth = config.github.git_auth();
+    let mut git_client = if git_repo_path.exists() {
         GitClient::new(&git_repo_path).context("failed to open git repository")?
     } else {
         std::fs::create_dir_all(&git_repo_path).context("failed to create git repo directory")?;
         let remote_url = config.github.clone_url();
-        GitClient::clone_repo(&remote_url, &git_repo_path, config.github.token.as_deref())
+        GitClient::clone_repo(&remote_url, &git_repo_path, &git_auth)
             .context("failed to clone git repository")?
     };
+    // Credentials stay in memory; tokens embedded in the remote URL by older
+    // versions are removed.
+    let _ = git_client.ensure_remote_credentials("origin", git_auth);
 
     // Create GitHub client.
     let github_token = config.github.token.as_deref().unwrap_or("");
-    let github_client = GitHubClient::new(&config.github.api_url, github_token, GitProvider::default());
+    let github_client =
+        GitHubClient::new(&config.github.api_url, github_token, config.github.provider.clone())
+            .with_app_auth(config.github.app_auth.clone());
 
     let engine = PersonalSyncEngine::new(config.clone(), db, svn_client, git_client, github_client);
     Ok((engine, config))
@@ -297,8 +365,14 @@ async fn cmd_sync(config_path: &str) -> Result<()> {
     Ok(())
 }
 
-/// Import SVN history into Git.
-async fn cmd_import(config_path: &str, mode: ImportMode) -> Result<()> {
+/// Import SVN history into Git, from the server or from a dump file and
+/// the repository path it is rooted at, narrowed by `filter`.
+async fn cmd_import(
+    config_path: &str,
+    mode: ImportMode,
+    dump: Option<(PathBuf, String)>,
+    filter: ImportFilter,
+) -> Result<()> {
     let config =
         PersonalConfig::load_and_resolve(config_path).context("failed to load personal config")?;
     config.validate().context("invalid personal config")?;
@@ -320,17 +394,20 @@ async fn cmd_import(config_path: &str, mode: ImportMode) -> Result<()> {
 
     // GitHub client.
     let github_token = config.github.token.as_deref().unwrap_or("");
-    let github_client = GitHubClient::new(&config.github.api_url, github_token, GitProvider::default());
+    let github_client =
+        GitHubClient::new(&config.github.api_url, github_token, config.github.provider.clone())
+            .with_app_auth(config.github.app_auth.clone());
 
     // Initialize Git repo for import.
     let git_repo_path = data_dir.join("git-repo");
-    let git_client = if git_repo_path.exists() {
+    let git_auth = config.github.git_auth();
+    let mut git_client = if git_repo_path.exists() {
         GitClient::new(&git_repo_path).context("failed to open git repository")?
     } else {
         std::fs::create_dir_all(&git_repo_path).context("failed to create git repo directory")?;
         // For import, try to clone first; if repo doesn't exist yet, init locally
         let remote_url = config.github.clone_url();
-        match GitClient::clone_repo(&remote_url, &git_repo_path, config.github.token.as_deref()) {
+        match GitClient::clone_repo(&remote_url, &git_repo_path, &git_auth) {
             Ok(client) => client,
             Err(_) => {
                 // Repo might not exist yet (auto-create will handle it)
@@ -338,6 +415,8 @@ async fn cmd_import(config_path: &str, mode: ImportMode) -> Result<()> {
             }
         }
     };
+    // A fresh repository has no origin yet; the credentials are kept anyway.
+    let _ = git_client.ensure_remote_credentials("origin", git_auth);
     let git_client = std::sync::Arc::new(std::sync::Mutex::new(git_client));
 
     let commit_format = crate::commit_format::CommitFormatter::new(&config.commit_format);
@@ -349,9 +428,13 @@ async fn cmd_import(config_path: &str, mode: ImportMode) -> Result<()> {
         db: &db,
         config: &config,
         formatter: &commit_format,
+        filter,
     };
 
-    let count = importer.import(mode).await?;
+    let count = match dump {
+        Some((path, root)) => importer.import_dump(open_dump(&path)?, &root).await?,
+        None => importer.import(mode).await?,
+    };
     println!("✓ Import complete! {} commits created.", count);
     Ok(())
 }
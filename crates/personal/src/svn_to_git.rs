@@ -5,6 +5,7 @@
 //! Echo suppression prevents re-syncing commits that originated from the
 //! Git side (identified by the `[reposync]` marker in the commit message).
 
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -16,6 +17,7 @@ use reposync_core::file_policy::{FilePolicy, FilePolicyDecision};
 use reposync_core::git::GitClient;
 use reposync_core::personal_config::PersonalConfig;
 use reposync_core::svn::SvnClient;
+use reposync_core::svn_props::{self, FileUpdate, PropTargets};
 
 use crate::commit_format::CommitFormatter;
 
@@ -112,6 +114,7 @@ impl SvnToGitSync {
             .context("failed to fetch SVN log entries")?;
 
         let mut synced_count: usize = 0;
+        let repo_base = svn_info.repo_path();
 
         for entry in &log_entries {
             let rev = entry.revision;
@@ -138,15 +141,17 @@ impl SvnToGitSync {
             let export_dir = tempfile::tempdir()
                 .context("failed to create temporary directory for SVN export")?;
 
-            self.svn_client
-                .export("", rev, export_dir.path())
-                .await
-                .with_context(|| format!("failed to export SVN revision r{}", rev))?;
+            // Keywords stay collapsed in Git, as stored in SVN.
+            let export = if self.config.options.collapse_keywords {
+                self.svn_client.export_unexpanded("", rev, export_dir.path()).await
+            } else {
+                self.svn_client.export("", rev, export_dir.path()).await
+            };
+            export.with_context(|| format!("failed to export SVN revision r{}", rev))?;
 
             // 6. Copy exported files into the Git working tree (with policy).
-            let git_client = self.git_client.lock().unwrap();
-            let repo_path = git_client.repo_path().to_path_buf();
-            drop(git_client); // Release lock before blocking I/O.
+            // The guard is a temporary, released before the blocking I/O below.
+            let repo_path = self.git_client.lock().unwrap().repo_path().to_path_buf();
 
             let skipped =
                 Self::copy_tree_with_policy(export_dir.path(), &repo_path, &self.policy, &self.db)
@@ -160,6 +165,43 @@ impl SvnToGitSync {
             Self::remove_stale_files(export_dir.path(), &repo_path)
                 .with_context(|| format!("failed to remove stale files for r{}", rev))?;
 
+            // Properties are only looked up where the revision changed
+            // something.
+            let targets = PropTargets::from_log(&entry.changed_paths, &repo_base);
+
+            // 6c. Mirror svn:ignore / svn:global-ignores as .gitignore files.
+            if self.config.options.sync_ignores {
+                self.write_gitignores(rev, &targets, &repo_path)
+                    .await
+                    .with_context(|| format!("failed to sync ignore properties for r{}", rev))?;
+            }
+
+            // 6d. Give files the Git mode matching svn:executable, and mirror
+            // svn:eol-style in .gitattributes.
+            let options = &self.config.options;
+            if options.sync_executable_bit || options.normalize_line_endings {
+                let props = svn_props::fetch_target_props(&self.svn_client, &targets, rev)
+                    .await
+                    .with_context(|| format!("failed to read file properties of r{}", rev))?;
+                if options.sync_executable_bit {
+                    let changed = svn_props::update_executable_bits(&repo_path, &targets, &props)
+                        .with_context(|| format!("failed to set file modes for r{}", rev))?;
+                    debug!(rev, changed, "synced svn:executable to file modes");
+                }
+                if options.normalize_line_endings {
+                    self.write_eol_attributes(&targets, &props, &repo_path)
+                        .with_context(|| format!("failed to sync svn:eol-style for r{}", rev))?;
+                }
+            }
+
+            // 6e. Record svn:externals definitions in .svnexternals files; the
+            // externals' content already came with the export.
+            if self.config.options.sync_externals {
+                self.write_svnexternals(rev, &targets, &repo_path)
+                    .await
+                    .with_context(|| format!("failed to sync externals for r{}", rev))?;
+            }
+
             // 7. Format the commit message with metadata trailers.
             let commit_message =
                 self.formatter
@@ -248,6 +290,86 @@ impl SvnToGitSync {
         Ok(synced_count)
     }
 
+    /// Rewrite the generated `.gitignore` of every directory `targets`
+    /// changed at `rev` (and of everything below the added ones) from its
+    /// ignore properties, dropping those left empty.
+    async fn write_gitignores(
+        &self,
+        rev: i64,
+        targets: &PropTargets,
+        repo_path: &Path,
+    ) -> Result<()> {
+        let mut dirs = 0;
+        for (changed, added) in &targets.dirs {
+            let props =
+                svn_props::fetch_ignore_props(&self.svn_client, changed, rev, *added).await?;
+            for (sub, props) in &props {
+                let dir = svn_props::join_path(changed, sub);
+                let update = svn_props::write_gitignore(repo_path, &dir, props)?;
+                if update == FileUpdate::Removed {
+                    let path = Path::new(&dir).join(svn_props::GITIGNORE);
+                    self.git_client
+                        .lock()
+                        .unwrap()
+                        .remove_path(&path.to_string_lossy())?;
+                }
+            }
+            dirs += props.len();
+        }
+        debug!(rev, dirs, "synced ignore properties to .gitignore");
+        Ok(())
+    }
+
+    /// Bring the generated block of the root `.gitattributes` up to date
+    /// with the `svn:eol-style` values in `props`, fetched for `targets`.
+    fn write_eol_attributes(
+        &self,
+        targets: &PropTargets,
+        props: &BTreeMap<String, BTreeMap<String, String>>,
+        repo_path: &Path,
+    ) -> Result<()> {
+        let mut entries = svn_props::read_eol_entries(repo_path);
+        svn_props::update_eol_entries(&mut entries, repo_path, targets, props);
+        if svn_props::write_eol_entries(repo_path, &entries)? == FileUpdate::Removed {
+            self.git_client
+                .lock()
+                .unwrap()
+                .remove_path(svn_props::GITATTRIBUTES)?;
+        }
+        debug!(files = entries.len(), "synced svn:eol-style to .gitattributes");
+        Ok(())
+    }
+
+    /// Rewrite the `.svnexternals` file of every directory `targets` changed
+    /// at `rev` (and of everything below the added ones), removing
+    /// generated files whose property went away.
+    async fn write_svnexternals(
+        &self,
+        rev: i64,
+        targets: &PropTargets,
+        repo_path: &Path,
+    ) -> Result<()> {
+        let mut dirs = 0;
+        for (changed, added) in &targets.dirs {
+            let externals =
+                svn_props::fetch_externals(&self.svn_client, changed, rev, *added).await?;
+            for (sub, value) in &externals {
+                let dir = svn_props::join_path(changed, sub);
+                let update = svn_props::write_svnexternals(repo_path, &dir, value)?;
+                if update == FileUpdate::Removed {
+                    let path = Path::new(&dir).join(svn_props::SVNEXTERNALS);
+                    self.git_client
+                        .lock()
+                        .unwrap()
+                        .remove_path(&path.to_string_lossy())?;
+                }
+            }
+            dirs += externals.len();
+        }
+        debug!(rev, dirs, "synced svn:externals to .svnexternals");
+        Ok(())
+    }
+
     /// Advance the SVN watermark to the given revision.
     fn advance_watermark(&self, rev: i64) -> Result<()> {
         self.db
@@ -324,6 +446,22 @@ impl SvnToGitSync {
             let src_path = entry.path();
             let dst_path = dst.join(&file_name);
 
+            if entry.file_type()?.is_symlink() {
+                let rel = src_path
+                    .strip_prefix(export_root)
+                    .unwrap_or(&src_path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                Self::copy_symlink_with_policy(&src_path, &dst_path, &rel, policy, skipped)?;
+                continue;
+            }
+            // A symlink replaced by a file or directory: never write through it.
+            if svn_props::is_symlink(&dst_path) {
+                std::fs::remove_file(&dst_path).with_context(|| {
+                    format!("failed to remove symlink: {}", dst_path.display())
+                })?;
+            }
+
             if src_path.is_dir() {
                 // Check if the entire directory matches an ignore pattern.
                 let rel = src_path
@@ -418,6 +556,8 @@ impl SvnToGitSync {
                         );
                         *skipped += 1;
                     }
+                    // Only symlinks get this decision; they are handled above.
+                    FilePolicyDecision::ExternalSymlink { .. } => *skipped += 1,
                 }
             }
         }
@@ -425,6 +565,37 @@ impl SvnToGitSync {
         Ok(())
     }
 
+    /// Recreate the symlink `src` (SVN export) at `dst` (Git working tree)
+    /// with the same target, unless the policy blocks it.
+    fn copy_symlink_with_policy(
+        src: &Path,
+        dst: &Path,
+        rel: &str,
+        policy: &FilePolicy,
+        skipped: &mut usize,
+    ) -> Result<()> {
+        let target = std::fs::read_link(src)
+            .with_context(|| format!("failed to read symlink: {}", src.display()))?;
+        let target = target.to_string_lossy();
+        let decision = policy.evaluate_symlink(rel, &target);
+        if decision.is_blocked() {
+            warn!(
+                path = rel,
+                target = %target,
+                reason = decision.label(),
+                "symlink blocked by policy — not copied to Git"
+            );
+            *skipped += 1;
+            return Ok(());
+        }
+        if dst.is_dir() && !svn_props::is_symlink(dst) {
+            std::fs::remove_dir_all(dst)
+                .with_context(|| format!("failed to remove directory: {}", dst.display()))?;
+        }
+        svn_props::write_symlink(dst, &target)
+            .with_context(|| format!("failed to create symlink: {}", dst.display()))
+    }
+
     /// Remove files and directories from `dst` (Git working tree) that do not
     /// exist in `src` (SVN export). Hidden entries (starting with `.`) at the
     /// root level are always skipped so `.git/` is never touched.
@@ -454,9 +625,11 @@ impl SvnToGitSync {
 
             let src_path = src.join(&file_name);
             let dst_path = entry.path();
+            // Symlinks are compared as entries of their own, never followed.
+            let src_meta = std::fs::symlink_metadata(&src_path).ok();
 
-            if dst_path.is_dir() {
-                if src_path.is_dir() {
+            if entry.file_type()?.is_dir() {
+                if src_meta.as_ref().is_some_and(|m| m.is_dir()) {
                     // Both exist — recurse.
                     Self::remove_stale_inner(&src_path, &dst_path, false)?;
                 } else {
@@ -466,7 +639,7 @@ impl SvnToGitSync {
                     })?;
                     debug!(path = %dst_path.display(), "removed stale directory");
                 }
-            } else if !src_path.exists() {
+            } else if src_meta.is_none() {
                 // File exists in Git but not in SVN export — remove it.
                 std::fs::remove_file(&dst_path).with_context(|| {
                     format!("failed to remove stale file: {}", dst_path.display())
@@ -602,6 +775,46 @@ mod tests {
         assert!(!dst.path().join("old_dir").exists());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_tree_keeps_symlinks() {
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+
+        std::fs::create_dir(src.path().join("conf")).unwrap();
+        std::fs::write(src.path().join("shared.yml"), "a: 1").unwrap();
+        std::os::unix::fs::symlink("../shared.yml", src.path().join("conf/app.yml")).unwrap();
+        std::os::unix::fs::symlink("/etc/hosts", src.path().join("hosts")).unwrap();
+        // A file in Git that became a symlink must not be written through.
+        std::fs::create_dir(dst.path().join("conf")).unwrap();
+        std::fs::write(dst.path().join("conf/app.yml"), "old").unwrap();
+
+        let policy = FilePolicy::new(0, vec![]).reject_external_symlinks(true);
+        let mut skipped = 0;
+        SvnToGitSync::copy_tree_policy_inner(
+            src.path(),
+            dst.path(),
+            dst.path(),
+            src.path(),
+            true,
+            &policy,
+            &mut skipped,
+        )
+        .unwrap();
+
+        let link = dst.path().join("conf/app.yml");
+        assert_eq!(std::fs::read_link(&link).unwrap(), Path::new("../shared.yml"));
+        assert_eq!(std::fs::read_to_string(&link).unwrap(), "a: 1");
+        assert!(std::fs::symlink_metadata(dst.path().join("hosts")).is_err());
+        assert_eq!(skipped, 1);
+
+        // Dangling links count as present; stale ones go without following.
+        std::fs::remove_file(src.path().join("shared.yml")).unwrap();
+        std::fs::remove_file(dst.path().join("shared.yml")).unwrap();
+        SvnToGitSync::remove_stale_files(src.path(), dst.path()).unwrap();
+        assert!(svn_props::is_symlink(&link));
+    }
+
     #[test]
     fn test_watermark_key_constant() {
         assert_eq!(WATERMARK_KEY, "svn_rev");
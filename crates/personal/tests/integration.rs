@@ -263,6 +263,13 @@ fn make_test_config(svn_url: &str, data_dir: &Path) -> PersonalConfig {
             private: true,
             token: None,
             git_base_url: None,
+            provider: Default::default(),
+            ssh_key_path: None,
+            ssh_key_passphrase_env: None,
+            ssh_known_hosts: None,
+            ssh_key_passphrase: None,
+            app: None,
+            app_auth: None,
         },
         developer: DeveloperConfig {
             name: "Test User".into(),
@@ -2541,6 +2548,13 @@ async fn test_replay_path_lfs_pointer_skipped_not_committed() {
             auto_create: false,
             private: false,
             token: Some("unused".into()),
+            provider: Default::default(),
+            ssh_key_path: None,
+            ssh_key_passphrase_env: None,
+            ssh_known_hosts: None,
+            ssh_key_passphrase: None,
+            app: None,
+            app_auth: None,
         },
         developer: DeveloperConfig {
             name: "Test User".into(),
@@ -2580,6 +2594,7 @@ async fn test_replay_path_lfs_pointer_skipped_not_committed() {
                 email: "test@example.com".into(),
                 date: None,
             },
+            verification: None,
         },
         author: None,
     };
@@ -2690,6 +2705,13 @@ async fn test_replay_path_normal_content_written_to_svn() {
             auto_create: false,
             private: false,
             token: Some("unused".into()),
+            provider: Default::default(),
+            ssh_key_path: None,
+            ssh_key_passphrase_env: None,
+            ssh_known_hosts: None,
+            ssh_key_passphrase: None,
+            app: None,
+            app_auth: None,
         },
         developer: DeveloperConfig {
             name: "Test User".into(),
@@ -2728,6 +2750,7 @@ async fn test_replay_path_normal_content_written_to_svn() {
                 email: "test@example.com".into(),
                 date: None,
             },
+            verification: None,
         },
         author: None,
     };
@@ -8,6 +8,12 @@ use axum::routing::{get, post};
 use axum::{Json, Router};
 use serde::{Deserialize, Serialize};
 
+use reposync_core::conflict::resolver::Resolution;
+use reposync_core::db::Database;
+use reposync_core::errors::{ConflictError, SyncError};
+use reposync_core::identity::IdentityMapper;
+use reposync_core::sync_engine::SyncEngine;
+
 use crate::api::auth::validate_session;
 use crate::api::status::AppError;
 use crate::AppState;
@@ -54,6 +60,9 @@ struct ConflictDetail {
 #[derive(Deserialize)]
 pub struct ResolveConflictRequest {
     pub resolution: String,
+    /// Merged content for a `custom` resolution. Falls back to content
+    /// previously stored on the conflict.
+    pub content: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -150,8 +159,23 @@ async fn resolve_conflict(
     )
     .await?;
 
+    let db = &state.db;
+    let conflict = db
+        .get_conflict(&id)
+        .map_err(|e| AppError::Internal(format!("database error: {}", e)))?
+        .ok_or_else(|| AppError::NotFound(format!("conflict '{}' not found", id)))?;
+
     let resolution = match body.resolution.as_str() {
-        "accept_svn" | "accept_git" | "custom" => body.resolution.as_str(),
+        "accept_svn" => Resolution::AcceptSvn,
+        "accept_git" => Resolution::AcceptGit,
+        "custom" => match body.content.clone().or(conflict.resolved_content) {
+            Some(content) => Resolution::AcceptMerged(content),
+            None => {
+                return Err(AppError::BadRequest(
+                    "custom resolution requires 'content'".into(),
+                ));
+            }
+        },
         other => {
             return Err(AppError::BadRequest(format!(
                 "invalid resolution '{}': must be accept_svn, accept_git, or custom",
@@ -160,9 +184,54 @@ async fn resolve_conflict(
         }
     };
 
-    let db = &state.db;
-    db.resolve_conflict(&id, "resolved", resolution, "api")
-        .map_err(|e| AppError::Internal(format!("failed to resolve conflict: {}", e)))?;
+    // The daemon's engine syncs a single repository; conflicts recorded by
+    // the scheduler's per-repo engines are resolved through an engine for
+    // their own repository.
+    let repo_engine;
+    let engine = match conflict.repo_id {
+        Some(ref repo_id) if state.sync_engine.repo_id() != Some(repo_id.as_str()) => {
+            let repo = db
+                .get_repository(repo_id)
+                .map_err(|e| AppError::Internal(format!("database error: {}", e)))?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("repository '{}' not found", repo_id))
+                })?;
+            let identity_mapper = IdentityMapper::new(&state.config.identity)
+                .map_err(|e| AppError::Internal(format!("identity mapper: {}", e)))?;
+            let engine_db = Database::new(state.config.daemon.data_dir.join("reposync.db"))
+                .map_err(|e| AppError::Internal(format!("database error: {}", e)))?;
+            repo_engine = SyncEngine::for_repository(
+                &state.config,
+                &repo,
+                engine_db,
+                Arc::new(identity_mapper),
+            )
+            .map_err(|e| AppError::Internal(format!("failed to set up sync engine: {}", e)))?;
+            &repo_engine
+        }
+        _ => state.sync_engine.as_ref(),
+    };
+
+    // Commit the chosen content to the losing side before marking the
+    // conflict resolved.
+    let outcome = engine
+        .apply_conflict_resolution(&id, &resolution, "api")
+        .await
+        .map_err(|e| match e {
+            SyncError::ConflictError(ConflictError::AlreadyResolved(_)) => {
+                AppError::BadRequest(e.to_string())
+            }
+            SyncError::AlreadyRunning { .. } => AppError::Conflict(e.to_string()),
+            SyncError::ConflictError(ConflictError::NotFound(_)) => {
+                AppError::NotFound(e.to_string())
+            }
+            // The engine must not commit another repository's file to its
+            // SVN URL.
+            SyncError::ConflictError(ConflictError::WrongRepository { .. }) => {
+                AppError::Conflict(e.to_string())
+            }
+            other => AppError::Internal(format!("failed to apply resolution: {}", other)),
+        })?;
 
     // Broadcast update via WebSocket
     let update = serde_json::json!({
@@ -175,6 +244,8 @@ async fn resolve_conflict(
     Ok(Json(serde_json::json!({
         "ok": true,
         "message": format!("conflict {} resolved", id),
+        "svn_revision": outcome.svn_revision,
+        "git_sha": outcome.git_sha,
     })))
 }
 
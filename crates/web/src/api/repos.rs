@@ -14,7 +14,7 @@ use reposync_core::db::Database;
 use reposync_core::file_policy::FilePolicy;
 use reposync_core::git::GitClient;
 use reposync_core::identity::IdentityMapper;
-use reposync_core::import::{self, ImportConfig, ImportPhase, ImportProgress};
+use reposync_core::import::{self, ImportConfig, ImportFilter, ImportPhase, ImportProgress};
 use reposync_core::svn::SvnClient;
 
 use crate::api::auth::{validate_session, validate_session_with_role};
@@ -464,6 +464,37 @@ async fn trigger_sync(
 struct ImportQuery {
     #[serde(default)]
     reset: bool,
+    /// First revision to import; earlier history becomes a baseline commit.
+    from_rev: Option<i64>,
+    /// Last revision to import.
+    to_rev: Option<i64>,
+    /// Import the revisions after this date instead of `from_rev`.
+    from_date: Option<String>,
+    /// Comma-separated globs of the paths to import.
+    #[serde(default)]
+    include: String,
+    /// Comma-separated globs of the paths to leave out.
+    #[serde(default)]
+    exclude: String,
+}
+
+impl ImportQuery {
+    fn filter(&self) -> ImportFilter {
+        let globs = |list: &str| -> Vec<String> {
+            list.split(',')
+                .map(str::trim)
+                .filter(|glob| !glob.is_empty())
+                .map(String::from)
+                .collect()
+        };
+        ImportFilter {
+            from_rev: self.from_rev,
+            from_date: self.from_date.clone(),
+            to_rev: self.to_rev,
+            include_paths: globs(&self.include),
+            exclude_paths: globs(&self.exclude),
+        }
+    }
 }
 
 async fn start_repo_import(
@@ -490,6 +521,11 @@ async fn start_repo_import(
         .map_err(|e| AppError::Internal(format!("database error: {}", e)))?
         .ok_or_else(|| AppError::NotFound("repository not found".into()))?;
 
+    let filter = import_query.filter();
+    filter
+        .validate()
+        .map_err(|e| AppError::BadRequest(format!("{:#}", e)))?;
+
     // 2. Check if an import is already running for this repo
     let progress = state.get_repo_import_progress(&id).await;
     {
@@ -561,21 +597,18 @@ async fn start_repo_import(
         std::fs::create_dir_all(&git_repo_path)
             .map_err(|e| AppError::Internal(format!("mkdir failed: {}", e)))?;
 
-        let base_clone_url = reposync_core::git::remote_url::derive_git_remote_url(
+        let clone_url = reposync_core::git::remote_url::derive_git_remote_url(
             &repo.git_api_url,
             None,
             &repo.git_repo,
         );
-        // Embed token in URL for the force-push
-        let clone_url = if let Some(ref tok) = git_token {
-            if let Some(rest) = base_clone_url.strip_prefix("https://") {
-                format!("https://x-access-token:{}@{}", tok, rest)
-            } else {
-                base_clone_url.clone()
-            }
-        } else {
-            base_clone_url.clone()
-        };
+        // Credentials reach the force-push through its environment.
+        let push_env = state
+            .config
+            .github
+            .for_repository(&repo)
+            .git_auth(git_token.as_deref())
+            .command_env(&clone_url);
         let branch = if repo.git_branch.is_empty() { "main" } else { &repo.git_branch };
 
         let init_cmds: Vec<Vec<&str>> = vec![
@@ -588,6 +621,8 @@ async fn start_repo_import(
             let output = std::process::Command::new("git")
                 .args(args)
                 .current_dir(&git_repo_path)
+                .env("GIT_TERMINAL_PROMPT", "0")
+                .envs(push_env.clone())
                 .output()
                 .map_err(|e| AppError::Internal(format!("git {:?} failed: {}", args[0], e)))?;
             if !output.status.success() && args[0] != "push" {
@@ -596,8 +631,9 @@ async fn start_repo_import(
             }
         }
 
-        // Reset watermark to 0
+        // Reset watermark to 0 and forget the previous import
         let _ = db.update_repo_watermark(&id, 0, "");
+        let _ = db.clear_repo_import_data(&id);
         {
             let mut p = progress.write().await;
             p.push_log("[info] Reset: remote wiped, starting fresh import...".into());
@@ -615,11 +651,12 @@ async fn start_repo_import(
         &repo.git_repo,
     );
 
-    let git_client = if git_repo_path.join(".git").exists() {
+    let git_auth = state.config.github.for_repository(&repo).git_auth(git_token.as_deref());
+    let mut git_client = if git_repo_path.join(".git").exists() {
         GitClient::new(&git_repo_path)
             .map_err(|e| AppError::Internal(format!("failed to open git repo: {}", e)))?
     } else {
-        match GitClient::clone_repo(&clone_url, &git_repo_path, git_token.as_deref()) {
+        match GitClient::clone_repo(&clone_url, &git_repo_path, &git_auth) {
             Ok(client) => client,
             Err(_) => {
                 info!("Clone failed, initializing empty repo with remote");
@@ -646,7 +683,7 @@ async fn start_repo_import(
 
     // 8. Configure git remote credentials
     git_client
-        .ensure_remote_credentials("origin", git_token.as_deref())
+        .ensure_remote_credentials("origin", git_auth)
         .map_err(|e| AppError::Internal(format!("failed to set git credentials: {}", e)))?;
 
     // Install git-lfs hooks if available
@@ -682,6 +719,16 @@ async fn start_repo_import(
         branch: repo.git_branch.clone(),
         push_token: git_token,
         message_prefix: None,
+        sync_executable_bit: state.config.sync.sync_executable_bit,
+        normalize_line_endings: state.config.sync.normalize_line_endings,
+        collapse_keywords: state.config.sync.collapse_keywords,
+        // Without a reset, an interrupted import of this repository
+        // continues after its last checkpoint.
+        resume: !import_query.reset,
+        layout: None,
+        filter,
+        repo_id: Some(repo.id.clone()),
+        per_repo_progress: true,
     };
 
     let ws_broadcast = Some(state.ws_broadcast.clone());
@@ -703,41 +750,22 @@ async fn start_repo_import(
 
         let mut p = progress.write().await;
         match result {
+            // A cancelled import leaves the repository's watermarks alone, so
+            // the scheduler does not sync on top of a half-imported branch
+            // and the next start resumes the import.
+            Ok(count) if p.phase == ImportPhase::Cancelled => {
+                p.completed_at = Some(chrono::Utc::now().to_rfc3339());
+                info!(repo_id = %repo_id_clone, count, "per-repo import cancelled");
+            }
+            // `run_full_import` recorded the repository's watermarks.
             Ok(count) => {
-                if p.phase != ImportPhase::Cancelled {
-                    p.phase = ImportPhase::Completed;
-                }
+                p.phase = ImportPhase::Completed;
                 p.completed_at = Some(chrono::Utc::now().to_rfc3339());
                 p.push_log(format!(
                     "[info] Import complete: {} commits created",
                     count
                 ));
                 info!(repo_id = %repo_id_clone, count, "per-repo import completed successfully");
-
-                // Update repo watermark so scheduler knows where import ended.
-                // Read from the watermarks table (where run_full_import writes)
-                // or fall back to the import progress total_revs.
-                let last_rev = import_db.get_watermark("svn_rev")
-                    .ok().flatten()
-                    .and_then(|v| v.parse::<i64>().ok())
-                    .unwrap_or_else(|| p.total_revs); // fallback to total revisions
-                let head_sha = {
-                    let g = git_client.lock().unwrap_or_else(|p| p.into_inner());
-                    g.get_head_sha().unwrap_or_default()
-                };
-                if last_rev > 0 {
-                    let _ = import_db.update_repo_watermark(&repo_id_clone, last_rev, &head_sha);
-                    // Also write per-repo kv_state keys for backward compat
-                    let _ = import_db.set_state(
-                        &format!("last_svn_rev_{}", repo_id_clone),
-                        &last_rev.to_string(),
-                    );
-                    let _ = import_db.set_state(
-                        &format!("last_git_sha_{}", repo_id_clone),
-                        &head_sha,
-                    );
-                    info!(repo_id = %repo_id_clone, last_rev, %head_sha, "updated repo watermark after import");
-                }
             }
             Err(e) => {
                 p.phase = ImportPhase::Failed;
@@ -749,7 +777,7 @@ async fn start_repo_import(
             }
         }
 
-        if let Err(e) = import_db.persist_import_progress(&p) {
+        if let Err(e) = import_db.persist_import_progress_for(Some(&repo_id_clone), &p) {
             tracing::warn!("failed to persist import progress for repo {}: {}", repo_id_clone, e);
         }
 
@@ -1156,7 +1184,24 @@ async fn test_repo_git(
         ))
         .or_else(|| db.get_state("secret_git_token").ok().flatten().filter(|v| !v.is_empty()));
 
-    let check_url = format!("{}/repos/{}", repo.git_api_url.trim_end_matches('/'), repo.git_repo);
+    if repo.git_provider == "git" {
+        let remote = reposync_core::git::derive_git_remote_url(
+            &repo.git_api_url,
+            None,
+            &repo.git_repo,
+        );
+        let auth = state.config.github.for_repository(&repo).git_auth(token.as_deref());
+        let result = crate::api::setup::test_plain_remote(remote, auth).await;
+        return Ok(Json(serde_json::json!({"ok": result.ok, "message": result.message})));
+    }
+
+    let check_url = match repo.git_provider.as_str() {
+        "gitlab" => reposync_core::git::gitlab::project_url(&repo.git_api_url, &repo.git_repo),
+        "bitbucket-server" => {
+            reposync_core::git::bitbucket::repo_url(&repo.git_api_url, &repo.git_repo)
+        }
+        _ => format!("{}/repos/{}", repo.git_api_url.trim_end_matches('/'), repo.git_repo),
+    };
 
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
@@ -1165,13 +1210,18 @@ async fn test_repo_git(
 
     let mut req = client.get(&check_url);
     if let Some(ref tok) = token {
-        req = req.header("Authorization", format!("token {}", tok));
+        req = match repo.git_provider.as_str() {
+            "gitlab" => req.header("PRIVATE-TOKEN", tok),
+            "bitbucket-server" => req.bearer_auth(tok),
+            _ => req.header("Authorization", format!("token {}", tok)),
+        };
     }
 
     match req.send().await {
         Ok(resp) if resp.status().is_success() => {
             if let Ok(json) = resp.json::<serde_json::Value>().await {
-                let name = json.get("full_name").or_else(|| json.get("name"))
+                let name = json.get("full_name").or_else(|| json.get("path_with_namespace"))
+                    .or_else(|| json.get("name"))
                     .and_then(|v| v.as_str()).unwrap_or(&repo.git_repo);
                 Ok(Json(serde_json::json!({"ok": true, "message": format!("Repository found: {}", name)})))
             } else {
@@ -13,12 +13,13 @@ use axum::{Json, Router};
 use serde::{Deserialize, Serialize};
 use tracing::{error, info, warn};
 
-use reposync_core::config::AppConfig;
+use reposync_core::config::{AppConfig, SvnLayout};
 use reposync_core::db::Database;
 use reposync_core::file_policy::FilePolicy;
-use reposync_core::git::GitClient;
+use reposync_core::git::{GitAuth, GitClient};
 use reposync_core::identity::IdentityMapper;
 use reposync_core::import::{self, ImportConfig, ImportProgress, ImportPhase};
+use reposync_core::layout_import::ImportLayout;
 use reposync_core::svn::SvnClient;
 
 use crate::api::auth::validate_session_with_role;
@@ -70,6 +71,9 @@ pub struct ApplyConfigRequest {
     pub git_token_env: Option<String>,
     /// Actual Git token (stored securely in DB, not in TOML).
     pub git_token: Option<String>,
+    /// Passphrase of the SSH key set as `github.ssh_key_path` (stored
+    /// securely in DB, not in TOML).
+    pub git_ssh_passphrase: Option<String>,
     pub git_default_branch: Option<String>,
 
     // Sync
@@ -205,7 +209,8 @@ async fn get_setup_config(
         .flatten()
         .map(|v| !v.is_empty())
         .unwrap_or(false)
-        || cfg.github.token.is_some();
+        || cfg.github.token.is_some()
+        || cfg.github.app_auth.is_some();
 
     let admin_password_set = db
         .get_state("secret_admin_password")
@@ -334,17 +339,27 @@ async fn test_git_connection(
     let api_url = body.api_url.trim().trim_end_matches('/').to_string();
     let repo = body.repo.trim().to_string();
 
-    if api_url.is_empty() || repo.is_empty() {
+    if (api_url.is_empty() && body.provider != "git") || repo.is_empty() {
         return Ok(Json(TestConnectionResponse {
             ok: false,
             message: "API URL and repository are required".into(),
         }));
     }
 
-    let check_url = if body.provider == "gitea" {
-        format!("{}/repos/{}", api_url, repo)
-    } else {
-        format!("{}/repos/{}", api_url, repo)
+    // A plain Git remote has no API; check that it answers `ls-remote`.
+    if body.provider == "git" {
+        // The entered token, never the GitHub App's, is tried on the remote.
+        let auth = state.config.github.git_auth(body.token.as_deref()).with_app(None);
+        return Ok(Json(test_plain_remote(repo, auth).await));
+    }
+
+    // GitHub and Gitea share the same repository endpoint shape; GitLab
+    // addresses projects by their encoded path, Bitbucket Server by project
+    // key and slug.
+    let check_url = match body.provider.as_str() {
+        "gitlab" => reposync_core::git::gitlab::project_url(&api_url, &repo),
+        "bitbucket-server" => reposync_core::git::bitbucket::repo_url(&api_url, &repo),
+        _ => format!("{}/repos/{}", api_url, repo),
     };
 
     let client = reqwest::Client::builder()
@@ -355,7 +370,11 @@ async fn test_git_connection(
     let mut req = client.get(&check_url);
     if let Some(ref token) = body.token {
         if !token.is_empty() {
-            req = req.header("Authorization", format!("token {}", token));
+            req = match body.provider.as_str() {
+                "gitlab" => req.header("PRIVATE-TOKEN", token),
+                "bitbucket-server" => req.bearer_auth(token),
+                _ => req.header("Authorization", format!("token {}", token)),
+            };
         }
     }
     match req.send().await {
@@ -365,6 +384,7 @@ async fn test_git_connection(
                 if let Ok(json) = resp.json::<serde_json::Value>().await {
                     let name = json
                         .get("full_name")
+                        .or_else(|| json.get("path_with_namespace"))
                         .or_else(|| json.get("name"))
                         .and_then(|v| v.as_str())
                         .unwrap_or(&repo);
@@ -418,6 +438,29 @@ async fn test_git_connection(
     }
 }
 
+/// Check that a plain Git remote answers, listing its refs the way
+/// `git ls-remote` does.
+pub(crate) async fn test_plain_remote(url: String, auth: GitAuth) -> TestConnectionResponse {
+    let listed = tokio::task::spawn_blocking(move || {
+        reposync_core::git::GitClient::list_remote_refs(&url, &auth)
+    })
+    .await;
+    match listed {
+        Ok(Ok(refs)) => TestConnectionResponse {
+            ok: true,
+            message: format!("Git remote reachable ({} refs)", refs.len()),
+        },
+        Ok(Err(e)) => TestConnectionResponse {
+            ok: false,
+            message: format!("Git remote not reachable: {}", e),
+        },
+        Err(e) => TestConnectionResponse {
+            ok: false,
+            message: format!("Git remote check failed: {}", e),
+        },
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Apply configuration
 // ---------------------------------------------------------------------------
@@ -521,6 +564,21 @@ async fn apply_config(
             }
         }
 
+        if let Some(ref passphrase) = body.git_ssh_passphrase {
+            if !passphrase.is_empty() {
+                match reposync_core::crypto::get_or_create_encryption_key(db) {
+                    Ok(key) => match reposync_core::crypto::encrypt_credential(passphrase, &key) {
+                        Ok((ct, nonce)) => {
+                            let _ = db.store_encrypted_secret("git_ssh_passphrase", &ct, &nonce);
+                            info!("SSH key passphrase encrypted and stored in database");
+                        }
+                        Err(e) => warn!("Failed to encrypt SSH key passphrase: {}", e),
+                    },
+                    Err(e) => warn!("Failed to get encryption key: {}", e),
+                }
+            }
+        }
+
         if let Some(ref password) = body.web_admin_password {
             if !password.is_empty() {
                 match reposync_core::crypto::hash_password(password) {
@@ -722,6 +780,57 @@ async fn cancel_import(
     }
 }
 
+/// Restart the full import a daemon restart interrupted; it continues from
+/// its last checkpoint.  Cancelled and failed imports are left for the user
+/// to start again, and so are branches and tags imports, which cannot be
+/// resumed.  Returns whether an import was restarted.
+pub async fn resume_interrupted_import(state: &Arc<AppState>) -> anyhow::Result<bool> {
+    let Some(saved) = state.db.load_import_progress()? else {
+        return Ok(false);
+    };
+    if !matches!(
+        saved.phase,
+        ImportPhase::Connecting
+            | ImportPhase::Importing
+            | ImportPhase::Verifying
+            | ImportPhase::FinalPush
+    ) {
+        return Ok(false);
+    }
+    let config = &state.config;
+    if config.sync.import_branches && config.svn.layout == SvnLayout::Standard {
+        warn!("not restarting the interrupted branches and tags import");
+        let mut p = state.import_progress.write().await;
+        *p = saved;
+        p.phase = ImportPhase::Failed;
+        p.push_log(
+            "[error] A branches and tags import cannot be resumed after a restart; \
+             use Reset & Reimport to start it over"
+                .into(),
+        );
+        if let Err(e) = state.db.persist_import_progress(&p) {
+            warn!("failed to persist import progress: {}", e);
+        }
+        return Ok(false);
+    }
+
+    {
+        let mut p = state.import_progress.write().await;
+        *p = ImportProgress::default();
+        p.phase = ImportPhase::Importing;
+        p.started_at = saved.started_at;
+        p.push_log("[info] Resuming the import interrupted by a restart...".into());
+    }
+    if let Err(AppError::Internal(msg)) = spawn_import_task(state).await {
+        // Leave the importing phase, which pauses the scheduler.
+        let mut p = state.import_progress.write().await;
+        p.phase = ImportPhase::Failed;
+        p.push_log(format!("[error] Could not resume the import: {}", msg));
+        anyhow::bail!(msg);
+    }
+    Ok(true)
+}
+
 // ---------------------------------------------------------------------------
 // Shared import helper
 // ---------------------------------------------------------------------------
@@ -737,6 +846,7 @@ async fn spawn_import_task(state: &Arc<AppState>) -> Result<(), AppError> {
     config
         .resolve_env_vars()
         .map_err(|e| AppError::Internal(format!("failed to resolve env vars: {}", e)))?;
+    config.resolve_secrets_from_db(&state.db);
 
     // Load secrets from DB
     let (db_svn_password, db_git_token) = {
@@ -766,6 +876,9 @@ async fn spawn_import_task(state: &Arc<AppState>) -> Result<(), AppError> {
         .clone()
         .or(db_svn_password)
         .unwrap_or_default();
+    // A layout import reads trunk, branches and tags below the configured URL.
+    let layout = (config.sync.import_branches && config.svn.layout == SvnLayout::Standard)
+        .then(|| ImportLayout::from_config(&config));
     let svn_import_url = {
         let base = config.svn.url.trim_end_matches('/');
         let trunk = if config.svn.trunk_path.is_empty() {
@@ -773,7 +886,7 @@ async fn spawn_import_task(state: &Arc<AppState>) -> Result<(), AppError> {
         } else {
             &config.svn.trunk_path
         };
-        if trunk.is_empty() || trunk == "/" {
+        if layout.is_some() || trunk.is_empty() || trunk == "/" {
             base.to_string()
         } else {
             format!("{}/{}", base, trunk.trim_start_matches('/'))
@@ -788,12 +901,13 @@ async fn spawn_import_task(state: &Arc<AppState>) -> Result<(), AppError> {
     std::fs::create_dir_all(&config.daemon.data_dir)
         .map_err(|e| AppError::Internal(format!("failed to create data dir: {}", e)))?;
 
-    let git_client = if git_repo_path.join(".git").exists() {
+    let git_auth = config.github.git_auth(git_token.as_deref());
+    let mut git_client = if git_repo_path.join(".git").exists() {
         GitClient::new(&git_repo_path)
             .map_err(|e| AppError::Internal(format!("failed to open git repo: {}", e)))?
     } else {
         let clone_url = config.github.clone_url();
-        match GitClient::clone_repo(&clone_url, &git_repo_path, git_token.as_deref()) {
+        match GitClient::clone_repo(&clone_url, &git_repo_path, &git_auth) {
             Ok(client) => client,
             Err(_) => {
                 info!("Clone failed, initializing empty repo with remote");
@@ -821,7 +935,7 @@ async fn spawn_import_task(state: &Arc<AppState>) -> Result<(), AppError> {
     };
 
     git_client
-        .ensure_remote_credentials("origin", git_token.as_deref())
+        .ensure_remote_credentials("origin", git_auth)
         .map_err(|e| AppError::Internal(format!("failed to set git credentials: {}", e)))?;
 
     let git_client = Arc::new(std::sync::Mutex::new(git_client));
@@ -839,6 +953,21 @@ async fn spawn_import_task(state: &Arc<AppState>) -> Result<(), AppError> {
         branch: config.github.default_branch.clone(),
         push_token: git_token,
         message_prefix: None,
+        sync_executable_bit: config.sync.sync_executable_bit,
+        normalize_line_endings: config.sync.normalize_line_endings,
+        collapse_keywords: config.sync.collapse_keywords,
+        resume: true,
+        layout,
+        filter: Default::default(),
+        // The daemon runs the engine of the first enabled repository.
+        repo_id: state
+            .db
+            .list_repositories()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|r| r.enabled)
+            .map(|r| r.id),
+        per_repo_progress: false,
     };
 
     let progress = state.import_progress.clone();
@@ -979,6 +1108,7 @@ async fn reset_and_reimport(
     config
         .resolve_env_vars()
         .map_err(|e| AppError::Internal(format!("failed to resolve env vars: {}", e)))?;
+    config.resolve_secrets_from_db(&state.db);
 
     let git_repo_path = config.daemon.data_dir.join("git-repo");
     let git_token = {
@@ -1018,14 +1148,9 @@ async fn reset_and_reimport(
     std::fs::create_dir_all(&git_repo_path)
         .map_err(|e| AppError::Internal(format!("mkdir failed: {}", e)))?;
     let branch = &config.github.default_branch;
-    let clone_url = format!(
-        "https://x-access-token:{}@{}",
-        git_token,
-        config
-            .github
-            .clone_url()
-            .trim_start_matches("https://")
-    );
+    let clone_url = config.github.clone_url();
+    // Credentials reach the force-push through its environment.
+    let push_env = config.github.git_auth(Some(&git_token)).command_env(&clone_url);
 
     // git init + empty commit + force push
     let init_cmds = [
@@ -1038,6 +1163,8 @@ async fn reset_and_reimport(
         let output = std::process::Command::new("git")
             .args(args)
             .current_dir(&git_repo_path)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .envs(push_env.clone())
             .output()
             .map_err(|e| AppError::Internal(format!("git {} failed: {}", args[0], e)))?;
         if !output.status.success() {
@@ -287,7 +287,10 @@ fn disk_usage(path: &Path) -> (u64, u64) {
         let mut buf = MaybeUninit::<libc::statvfs>::uninit();
         if libc::statvfs(c_path.as_ptr(), buf.as_mut_ptr()) == 0 {
             let stat = buf.assume_init();
+            // The statvfs field widths differ between platforms.
+            #[allow(clippy::unnecessary_cast)]
             let total = stat.f_blocks as u64 * stat.f_frsize as u64;
+            #[allow(clippy::unnecessary_cast)]
             let free = stat.f_bavail as u64 * stat.f_frsize as u64;
             (free, total)
         } else {
@@ -530,6 +533,7 @@ pub enum AppError {
     NotFound(String),
     Unauthorized(String),
     Forbidden(String),
+    Conflict(String),
     Internal(String),
 }
 
@@ -540,6 +544,7 @@ impl axum::response::IntoResponse for AppError {
             AppError::NotFound(_) => (axum::http::StatusCode::NOT_FOUND, String::new()),
             AppError::Unauthorized(_) => (axum::http::StatusCode::UNAUTHORIZED, String::new()),
             AppError::Forbidden(_) => (axum::http::StatusCode::FORBIDDEN, String::new()),
+            AppError::Conflict(_) => (axum::http::StatusCode::CONFLICT, String::new()),
             AppError::Internal(msg) => {
                 // Log the full error server-side but return a generic message to clients
                 tracing::error!(detail = %msg, "internal server error");
@@ -550,7 +555,8 @@ impl axum::response::IntoResponse for AppError {
         let client_message = match self {
             AppError::Internal(_) => "internal server error".to_string(),
             AppError::BadRequest(msg) | AppError::NotFound(msg) |
-            AppError::Unauthorized(msg) | AppError::Forbidden(msg) => msg,
+            AppError::Unauthorized(msg) | AppError::Forbidden(msg) |
+            AppError::Conflict(msg) => msg,
         };
 
         let body = serde_json::json!({ "error": client_message });
@@ -1,4 +1,5 @@
-//! Webhook receiver endpoints for GitHub and SVN push notifications.
+//! Webhook receiver endpoints for GitHub (and Gitea/GitLab/Bitbucket Server)
+//! and SVN push notifications.
 
 use std::sync::Arc;
 
@@ -21,10 +22,14 @@ use reposync_core::config::GitProvider;
 
 #[derive(Debug, Deserialize)]
 struct GitHubPushPayload {
-    #[serde(rename = "ref")]
+    /// Bitbucket Server lists the pushed refs in `changes` instead.
+    #[serde(rename = "ref", default)]
     git_ref: String,
     commits: Option<Vec<GitHubCommitPayload>>,
     repository: Option<GitHubRepoPayload>,
+    /// GitLab names the repository in `project` instead.
+    project: Option<GitLabProjectPayload>,
+    changes: Option<Vec<BitbucketChangePayload>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,7 +49,26 @@ struct GitHubAuthorPayload {
 
 #[derive(Debug, Deserialize)]
 struct GitHubRepoPayload {
-    full_name: String,
+    full_name: Option<String>,
+    /// Bitbucket Server names the repository by slug and project key.
+    slug: Option<String>,
+    project: Option<BitbucketProjectPayload>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabProjectPayload {
+    path_with_namespace: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BitbucketChangePayload {
+    ref_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketProjectPayload {
+    key: String,
 }
 
 // ---------------------------------------------------------------------------
@@ -81,41 +105,59 @@ async fn github_webhook(
     body: Bytes,
 ) -> Result<Json<WebhookResponse>, AppError> {
     // Determine provider from headers
-    let is_gitea = headers.contains_key("x-gitea-event") || headers.contains_key("x-gitea-signature");
+    let provider = if headers.contains_key("x-gitlab-event")
+        || headers.contains_key("x-gitlab-token")
+    {
+        GitProvider::GitLab
+    } else if headers.contains_key("x-gitea-event") || headers.contains_key("x-gitea-signature") {
+        GitProvider::Gitea
+    } else if headers.contains_key("x-event-key") {
+        GitProvider::BitbucketServer
+    } else {
+        GitProvider::GitHub
+    };
 
     // Verify webhook signature if a secret is configured
     if state.config.github.webhook_secret.is_none() {
         tracing::warn!("GitHub webhook secret not configured - webhook payloads are not authenticated");
     }
     if state.config.github.webhook_secret.is_some() {
-        let (signature, provider) = if is_gitea {
-            let sig = headers.get("x-gitea-signature")
-                .and_then(|v| v.to_str().ok())
-                .ok_or_else(|| AppError::Unauthorized("missing X-Gitea-Signature header".into()))?;
-            (sig.to_string(), GitProvider::Gitea)
-        } else {
-            let sig = headers.get("x-hub-signature-256")
-                .and_then(|v| v.to_str().ok())
-                .ok_or_else(|| AppError::Unauthorized("missing X-Hub-Signature-256 header".into()))?;
-            (sig.to_string(), GitProvider::GitHub)
+        // GitLab sends the secret itself rather than a signature.  Plain Git
+        // remotes send no webhooks and are never detected above.
+        let header = match provider {
+            GitProvider::GitHub | GitProvider::Plain => "X-Hub-Signature-256",
+            GitProvider::Gitea => "X-Gitea-Signature",
+            GitProvider::GitLab => "X-Gitlab-Token",
+            GitProvider::BitbucketServer => "X-Hub-Signature",
         };
+        let signature = headers.get(header)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| AppError::Unauthorized(format!("missing {} header", header)))?;
 
         let secret = state.config.github.webhook_secret.as_deref()
             .ok_or_else(|| AppError::Unauthorized("webhook secret not configured".into()))?;
 
         if !reposync_core::git::github::GitHubClient::verify_webhook_signature(
-            &body, &signature, secret, &provider,
+            &body, signature, secret, &provider,
         ) {
             return Err(AppError::Unauthorized("webhook signature verification failed".into()));
         }
     }
 
     // Parse the event type
-    let event_type = headers
-        .get("x-gitea-event")
-        .or_else(|| headers.get("x-github-event"))
+    let event_type = match headers
+        .get("x-gitlab-event")
+        .or_else(|| headers.get("x-event-key"))
         .and_then(|v| v.to_str().ok())
-        .unwrap_or("unknown");
+    {
+        Some("Push Hook" | "repo:refs_changed") => "push",
+        Some(event) => event,
+        None => headers
+            .get("x-gitea-event")
+            .or_else(|| headers.get("x-github-event"))
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("unknown"),
+    };
 
     if event_type != "push" {
         info!(event_type, "ignoring non-push GitHub webhook event");
@@ -129,17 +171,28 @@ async fn github_webhook(
     let payload: GitHubPushPayload = serde_json::from_slice(&body)
         .map_err(|e| AppError::BadRequest(format!("invalid JSON payload: {}", e)))?;
 
+    let bitbucket_repo = payload.repository.as_ref().and_then(|r| {
+        Some(format!("{}/{}", r.project.as_ref()?.key, r.slug.as_deref()?))
+    });
     let repo_name = payload
         .repository
         .as_ref()
-        .map(|r| r.full_name.as_str())
+        .and_then(|r| r.full_name.as_deref())
+        .or_else(|| payload.project.as_ref().map(|p| p.path_with_namespace.as_str()))
+        .or(bitbucket_repo.as_deref())
         .unwrap_or("unknown");
+    let git_ref = match &payload.changes {
+        Some(changes) if payload.git_ref.is_empty() => {
+            changes.iter().map(|c| c.ref_id.as_str()).collect::<Vec<_>>().join(",")
+        }
+        _ => payload.git_ref.clone(),
+    };
 
     let commit_count = payload.commits.as_ref().map(|c| c.len()).unwrap_or(0);
 
     info!(
         repo = repo_name,
-        git_ref = %payload.git_ref,
+        git_ref = %git_ref,
         commits = commit_count,
         "received GitHub push webhook"
     );
@@ -153,7 +206,7 @@ async fn github_webhook(
     let update = serde_json::json!({
         "type": "webhook_received",
         "source": "github",
-        "ref": payload.git_ref,
+        "ref": git_ref,
         "commits": commit_count,
     });
     let _ = state.ws_broadcast.send(update.to_string());
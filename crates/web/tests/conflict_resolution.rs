@@ -0,0 +1,224 @@
+//! Integration test for resolving conflicts of repositories the daemon's own
+//! engine does not sync: the scheduler records them under their repo ID, and
+//! the resolve endpoint must commit through an engine for that repository.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+
+use axum::Router;
+use reposync_core::config::{AppConfig, IdentityConfig};
+use reposync_core::db::Database;
+use reposync_core::identity::IdentityMapper;
+use reposync_core::import::ImportProgress;
+use reposync_core::models::{Conflict, Repository};
+use reposync_core::sync_engine::SyncEngine;
+use reposync_web::api;
+use reposync_web::AppState;
+
+const TEST_TOKEN: &str = "test-session-token-for-conflict-tests";
+
+fn config(data_dir: &Path) -> AppConfig {
+    let toml_str = format!(
+        r#"
+[daemon]
+data_dir = "{}"
+
+[svn]
+url = "https://svn.test.invalid/repo"
+username = "testuser"
+password_env = ""
+
+[github]
+repo = "test/repo"
+token_env = ""
+default_branch = "main"
+"#,
+        data_dir.display().to_string().replace('\\', "/")
+    );
+    let mut config: AppConfig = toml::from_str(&toml_str).expect("parse test config");
+    config.web.admin_password = Some("test-admin-pass".to_string());
+    config
+}
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("run git");
+    assert!(out.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&out.stderr));
+    String::from_utf8(out.stdout).unwrap()
+}
+
+fn svn_available() -> bool {
+    ["svn", "svnadmin"].iter().all(|bin| {
+        Command::new(bin)
+            .arg("--version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+/// Create a local SVN repository holding `a.txt` and return its URL.
+fn create_svn_repo(tmp: &Path, id: &str) -> String {
+    let repo_dir = tmp.join(format!("{}-svn", id));
+    let status = Command::new("svnadmin")
+        .args(["create", repo_dir.to_str().unwrap()])
+        .status()
+        .expect("run svnadmin");
+    assert!(status.success(), "svnadmin create failed");
+    let url = format!("file://{}", repo_dir.display());
+
+    let import = tmp.join(format!("{}-svn-import", id));
+    std::fs::create_dir_all(&import).unwrap();
+    std::fs::write(import.join("a.txt"), format!("{} svn\n", id)).unwrap();
+    let status = Command::new("svn")
+        .args(["import", import.to_str().unwrap(), &url, "-m", "initial", "--non-interactive"])
+        .stdout(std::process::Stdio::null())
+        .status()
+        .expect("run svn import");
+    assert!(status.success(), "svn import failed");
+    url
+}
+
+/// Add a repository with an SVN repository and a bare origin, each holding
+/// their own `a.txt`, and the Git working tree cloned where the scheduler
+/// keeps it.
+fn add_repository(db: &Database, tmp: &Path, data_dir: &Path, id: &str) -> Repository {
+    let svn_url = create_svn_repo(tmp, id);
+
+    let origin = tmp.join(format!("{}.git", id));
+    std::fs::create_dir_all(&origin).unwrap();
+    git(&origin, &["init", "--bare", "--initial-branch", "main"]);
+
+    let seed = tmp.join(format!("{}-seed", id));
+    std::fs::create_dir_all(&seed).unwrap();
+    git(&seed, &["init", "--initial-branch", "main"]);
+    std::fs::write(seed.join("a.txt"), format!("{} git\n", id)).unwrap();
+    git(&seed, &["add", "a.txt"]);
+    git(&seed, &["commit", "-m", "initial"]);
+    git(&seed, &["push", origin.to_str().unwrap(), "main"]);
+
+    let worktree = data_dir.join("repos").join(id).join("git-repo");
+    std::fs::create_dir_all(worktree.parent().unwrap()).unwrap();
+    git(data_dir, &["clone", origin.to_str().unwrap(), worktree.to_str().unwrap()]);
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let repo = Repository {
+        id: id.to_string(),
+        name: id.to_string(),
+        svn_url,
+        svn_branch: String::new(),
+        svn_username: "testuser".into(),
+        git_provider: "github".into(),
+        git_api_url: String::new(),
+        git_repo: format!("test/{}", id),
+        git_branch: "main".into(),
+        sync_mode: "direct".into(),
+        poll_interval_secs: 60,
+        lfs_threshold_mb: 50,
+        auto_merge: false,
+        enabled: true,
+        created_by: None,
+        parent_id: None,
+        created_at: now.clone(),
+        updated_at: now,
+        last_svn_rev: 0,
+        last_git_sha: String::new(),
+        last_sync_at: None,
+        sync_status: "idle".into(),
+        total_syncs: 0,
+        total_errors: 0,
+    };
+    db.insert_repository(&repo).expect("insert repository");
+    repo
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resolve_conflict_of_second_repository() {
+    if !svn_available() {
+        eprintln!("SKIPPED: svn/svnadmin not found in PATH");
+        return;
+    }
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let data_dir = tmp.path().join("data");
+    std::fs::create_dir_all(&data_dir).unwrap();
+    let config = config(&data_dir);
+
+    let db_path = data_dir.join("reposync.db");
+    let web_db = Database::new(&db_path).expect("web db");
+    web_db.initialize().expect("web db init");
+    let first = add_repository(&web_db, tmp.path(), &data_dir, "first");
+    let second = add_repository(&web_db, tmp.path(), &data_dir, "second");
+
+    let mut conflict = Conflict::new("a.txt".into());
+    conflict.svn_content = Some("second svn\n".into());
+    conflict.git_content = Some("second git\n".into());
+    conflict.svn_revision = Some(1);
+    conflict.repo_id = Some(second.id.clone());
+    let conflict_id = web_db.insert_conflict(&conflict).expect("insert conflict");
+
+    // Like the daemon, the shared engine belongs to the first repository.
+    let identity_mapper =
+        Arc::new(IdentityMapper::new(&IdentityConfig::default()).expect("identity mapper"));
+    let engine_db = Database::new(&db_path).expect("engine db");
+    let sync_engine = Arc::new(
+        SyncEngine::for_repository(&config, &first, engine_db, identity_mapper)
+            .expect("engine for first repository"),
+    );
+
+    let (sync_tx, _sync_rx) = tokio::sync::mpsc::channel(1);
+    let (ws_tx, _) = tokio::sync::broadcast::channel(16);
+    let state = Arc::new(AppState {
+        db: web_db,
+        sync_engine,
+        config,
+        sync_trigger: sync_tx,
+        ws_broadcast: ws_tx,
+        sessions: tokio::sync::RwLock::new(HashMap::from([(
+            TEST_TOKEN.to_string(),
+            chrono::Utc::now() + chrono::Duration::hours(1),
+        )])),
+        import_progress: Arc::new(tokio::sync::RwLock::new(ImportProgress::default())),
+        config_path: tmp.path().join("config.toml"),
+        prev_net_snapshot: std::sync::Mutex::new(None),
+        repo_import_progress: tokio::sync::RwLock::new(HashMap::new()),
+        login_attempts: std::sync::Mutex::new(HashMap::new()),
+    });
+
+    let app = Router::new().merge(api::conflicts::routes()).with_state(state.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("local_addr");
+    tokio::spawn(async move {
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await.ok();
+    });
+
+    let resp = reqwest::Client::new()
+        .post(format!("http://{}/api/conflicts/{}/resolve", addr, conflict_id))
+        .bearer_auth(TEST_TOKEN)
+        .json(&serde_json::json!({ "resolution": "accept_svn" }))
+        .send()
+        .await
+        .expect("resolve request");
+    let status = resp.status();
+    let body: serde_json::Value = resp.json().await.expect("resolve response");
+    assert_eq!(status, 200, "{}", body);
+
+    // The SVN side won, so its content was pushed to the second origin only.
+    let second_origin = tmp.path().join("second.git");
+    assert_eq!(git(&second_origin, &["show", "main:a.txt"]), "second svn\n");
+    let first_origin = tmp.path().join("first.git");
+    assert_eq!(git(&first_origin, &["show", "main:a.txt"]), "first git\n");
+
+    let resolved = state.db.get_conflict(&conflict_id).unwrap().unwrap();
+    assert_eq!(resolved.status, "resolved");
+    assert_eq!(resolved.repo_id.as_deref(), Some("second"));
+}
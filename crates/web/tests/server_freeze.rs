@@ -13,6 +13,10 @@
 //!   9. Concurrent requests while web DB mutex is held.
 //!  10. No file-descriptor or memory leaks after 1 000 requests.
 
+// Several tests deliberately hold a std mutex across an await to simulate
+// a long-running sync holding the database lock.
+#![allow(clippy::await_holding_lock)]
+
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::path::Path;
@@ -611,7 +615,6 @@ async fn test_sustained_load_under_sync_cycles() {
     // --- Sync simulation: lock the sync engine DB for 2 s every 5 s ----------
     let sync_engine = state.sync_engine.clone();
     let sync_task = {
-        let start = start;
         tokio::spawn(async move {
             while start.elapsed() < test_duration {
                 let se = sync_engine.clone();
@@ -643,7 +646,6 @@ async fn test_sustained_load_under_sync_cycles() {
             .default_headers(headers)
             .build()
             .unwrap();
-        let start = start;
         tokio::spawn(async move {
             while start.elapsed() < test_duration {
                 let req_start = Instant::now();
@@ -455,6 +455,9 @@ export default function RepoDetail() {
                   options={[
                     { value: 'github', label: 'GitHub' },
                     { value: 'gitea', label: 'Gitea' },
+                    { value: 'gitlab', label: 'GitLab' },
+                    { value: 'bitbucket-server', label: 'Bitbucket Server' },
+                    { value: 'git', label: 'Plain Git remote' },
                   ]}
                   onChange={(v) => setField('git_provider', v)}
                 />
@@ -493,6 +493,9 @@ export default function Repositories() {
                     >
                       <option value="github">GitHub</option>
                       <option value="gitea">Gitea</option>
+                      <option value="gitlab">GitLab</option>
+                      <option value="bitbucket-server">Bitbucket Server</option>
+                      <option value="git">Plain Git remote</option>
                     </select>
                   </div>
                   <div>
@@ -20,11 +20,12 @@ interface WizardData {
   svn_tags_path: string;
 
   // Git
-  git_provider: 'github' | 'gitea';
+  git_provider: 'github' | 'gitea' | 'gitlab' | 'bitbucket-server' | 'git';
   git_api_url: string;
   git_repo: string;
   git_token: string;
   git_token_env: string;
+  git_ssh_passphrase: string;
   git_default_branch: string;
 
   // Sync
@@ -82,6 +83,7 @@ const DEFAULT_DATA: WizardData = {
   git_repo: '',
   git_token: '',
   git_token_env: '',
+  git_ssh_passphrase: '',
   git_default_branch: 'main',
 
   sync_mode: 'direct',
@@ -147,11 +149,15 @@ function validateStep(step: number, data: WizardData): Errors {
   }
 
   if (step === 2) {
-    if (!data.git_api_url.trim()) errors.git_api_url = 'API URL is required';
-    else if (!/^https?:\/\/.+/.test(data.git_api_url.trim()))
-      errors.git_api_url = 'Must be a valid HTTP(S) URL';
+    // A plain remote has no API; the repository field is the remote itself.
+    const plainGit = data.git_provider === 'git';
+    if (!plainGit) {
+      if (!data.git_api_url.trim()) errors.git_api_url = 'API URL is required';
+      else if (!/^https?:\/\/.+/.test(data.git_api_url.trim()))
+        errors.git_api_url = 'Must be a valid HTTP(S) URL';
+    }
     if (!data.git_repo.trim()) errors.git_repo = 'Repository is required';
-    else if (!/^[^/]+\/[^/]+$/.test(data.git_repo.trim()))
+    else if (!plainGit && !/^[^/]+\/[^/]+$/.test(data.git_repo.trim()))
       errors.git_repo = 'Must be in owner/repo format';
   }
 
@@ -295,6 +301,7 @@ export default function SetupWizard() {
         git_repo: data.git_repo,
         git_token: data.git_token || undefined,
         git_token_env: data.git_token_env || undefined,
+        git_ssh_passphrase: data.git_ssh_passphrase || undefined,
         git_default_branch: data.git_default_branch,
         sync_mode: data.sync_mode,
         sync_auto_merge: data.sync_auto_merge,
@@ -684,7 +691,7 @@ function WelcomeStep() {
       <div className="mt-8 grid grid-cols-1 sm:grid-cols-3 gap-4 max-w-xl mx-auto text-left">
         <div className="bg-gray-700/50 rounded-lg p-4 border border-gray-600/50">
           <div className="text-blue-400 font-semibold text-sm mb-1">SVN & Git</div>
-          <p className="text-gray-400 text-xs">Connect your SVN repo and Git provider (GitHub/Gitea)</p>
+          <p className="text-gray-400 text-xs">Connect your SVN repo and Git provider (GitHub/Gitea/GitLab/Bitbucket or any Git remote)</p>
         </div>
         <div className="bg-gray-700/50 rounded-lg p-4 border border-gray-600/50">
           <div className="text-purple-400 font-semibold text-sm mb-1">Identity</div>
@@ -831,8 +838,8 @@ function GitStep({
   onGitTokenChange: () => void;
 }) {
   const handleProviderChange = (provider: string) => {
-    const p = provider as 'github' | 'gitea';
-    const apiUrl = p === 'github' ? 'https://api.github.com' : data.git_api_url;
+    const p = provider as WizardData['git_provider'];
+    const apiUrl = p === 'github' ? 'https://api.github.com' : p === 'git' ? '' : data.git_api_url;
     update({ git_provider: p, git_api_url: apiUrl });
   };
 
@@ -840,13 +847,13 @@ function GitStep({
     <div className="space-y-6">
       <SectionHeading
         title="Git Provider"
-        description="Configure the connection to GitHub or Gitea."
+        description="Configure the connection to GitHub, Gitea, GitLab, Bitbucket Server or a plain Git remote."
         color="purple"
       />
 
       {/* Provider selection */}
       <div className="flex space-x-3">
-        {(['github', 'gitea'] as const).map(p => (
+        {(['github', 'gitea', 'gitlab', 'bitbucket-server', 'git'] as const).map(p => (
           <button
             key={p}
             onClick={() => handleProviderChange(p)}
@@ -856,33 +863,60 @@ function GitStep({
                 : 'border-gray-600 bg-gray-700/50 text-gray-400 hover:bg-gray-700'
             }`}
           >
-            {p === 'github' ? 'GitHub / GitHub Enterprise' : 'Gitea'}
+            {{
+              github: 'GitHub / GitHub Enterprise',
+              gitea: 'Gitea',
+              gitlab: 'GitLab',
+              'bitbucket-server': 'Bitbucket Server',
+              git: 'Plain Git remote',
+            }[p]}
           </button>
         ))}
       </div>
 
+      {data.git_provider === 'git' && (
+        <p className="text-xs text-gray-400">
+          A plain Git remote has no API: PR mode, commit statuses and repository auto-create are
+          unavailable, and Git changes are picked up by polling instead of webhooks.
+        </p>
+      )}
+
       <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
-        <div className="md:col-span-2">
+        {data.git_provider !== 'git' && <div className="md:col-span-2">
           <FormField
             label="API URL"
             name="git_api_url"
             value={data.git_api_url}
             onChange={(v) => update({ git_api_url: v })}
             error={errors.git_api_url}
-            placeholder={data.git_provider === 'github' ? 'https://api.github.com' : 'http://gitea.example.com:3000/api/v1'}
-            help={data.git_provider === 'github' ? 'Use https://api.github.com for GitHub.com, or your GHE API URL' : 'Your Gitea server API URL (e.g., http://host:3000/api/v1)'}
+            placeholder={{
+              github: 'https://api.github.com',
+              gitea: 'http://gitea.example.com:3000/api/v1',
+              gitlab: 'https://gitlab.example.com/api/v4',
+              'bitbucket-server': 'https://bitbucket.example.com/rest/api/1.0',
+              git: '',
+            }[data.git_provider]}
+            help={{
+              github: 'Use https://api.github.com for GitHub.com, or your GHE API URL',
+              gitea: 'Your Gitea server API URL (e.g., http://host:3000/api/v1)',
+              gitlab: 'Your GitLab API URL (e.g., https://gitlab.example.com/api/v4)',
+              'bitbucket-server': 'Your Bitbucket REST API URL (e.g., https://host/rest/api/1.0)',
+              git: '',
+            }[data.git_provider]}
             mono
             required
           />
-        </div>
+        </div>}
         <FormField
           label="Repository"
           name="git_repo"
           value={data.git_repo}
           onChange={(v) => update({ git_repo: v })}
           error={errors.git_repo}
-          placeholder="owner/repo-name"
-          help="In owner/repo format"
+          placeholder={data.git_provider === 'git' ? 'git@host:path/repo.git' : 'owner/repo-name'}
+          help={data.git_provider === 'git'
+            ? 'Remote URL (SSH, HTTPS or file path)'
+            : 'In owner/repo format'}
           mono
           required
         />
@@ -907,6 +941,21 @@ function GitStep({
             <p className="text-xs text-gray-500 mt-1">Needs <code className="text-gray-400">repo</code> scope. Stored securely on the server.</p>
           </div>
         </div>
+        {data.git_provider === 'git' && !/^((https?|file):\/\/|[./])/.test(data.git_repo.trim()) && <div className="md:col-span-2">
+          <label className="block text-sm font-medium text-gray-300 mb-1">SSH Key Passphrase</label>
+          <input
+            type="password"
+            value={data.git_ssh_passphrase}
+            onChange={e => update({ git_ssh_passphrase: e.target.value })}
+            className="w-full px-3 py-2 bg-gray-700 border border-gray-600 rounded-lg text-gray-100 text-sm focus:ring-2 focus:ring-blue-500 focus:border-transparent"
+            placeholder="Leave empty for an unencrypted key"
+          />
+          <p className="text-xs text-gray-500 mt-1">
+            For the private key set as <code className="text-gray-400">github.ssh_key_path</code> in the
+            config file. Host keys are checked against <code className="text-gray-400">github.ssh_known_hosts</code>
+            (default <code className="text-gray-400">~/.ssh/known_hosts</code>). Stored securely on the server.
+          </p>
+        </div>}
       </div>
 
       <div className="border-t border-gray-700 pt-5">